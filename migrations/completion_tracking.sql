@@ -0,0 +1,101 @@
+-- Adds DNF tracking, the per-book snapshot of who read along, and the club setting that limits
+-- rating polls to those members. Books finished before this runs have no participants recorded.
+--
+-- Run once against a database created from a schema.sql without completion tracking (after
+-- migrations/multiple_clubs.sql if that applies):
+--     psql -d fumiko -f migrations/completion_tracking.sql
+
+BEGIN;
+
+ALTER TABLE server_clubs
+    ADD COLUMN IF NOT EXISTS ratings_participants_only BOOLEAN NOT NULL DEFAULT FALSE;
+
+ALTER TABLE user_reading_progress
+    ADD COLUMN IF NOT EXISTS dnf_at TIMESTAMP WITH TIME ZONE;
+
+CREATE TABLE IF NOT EXISTS public.completed_book_participants (
+    completed_id INTEGER NOT NULL REFERENCES server_completed_books(completed_id) ON DELETE CASCADE,
+    user_id BIGINT NOT NULL REFERENCES discord_users(user_id) ON DELETE CASCADE,
+    status TEXT NOT NULL CHECK (status IN ('finished', 'dnf', 'not_started')),
+    last_progress_text TEXT,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (completed_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_completed_book_participants_user_id
+    ON completed_book_participants(user_id);
+
+CREATE OR REPLACE FUNCTION finish_current_book_tx(p_club_id INTEGER)
+RETURNS TABLE (
+    completed_id INTEGER,
+    volume_id TEXT,
+    started_at TIMESTAMP WITH TIME ZONE,
+    success BOOLEAN,
+    error_message TEXT
+) AS $$
+DECLARE
+    v_current_book RECORD;
+    v_completed_id INTEGER;
+BEGIN
+    -- Get current book with lock (UPDATED to include suggested_by_user_id)
+    SELECT scb.volume_id, scb.started_at, scb.suggested_by_user_id
+    INTO v_current_book
+    FROM server_current_book scb
+    WHERE scb.club_id = p_club_id
+    FOR UPDATE;
+    
+    IF v_current_book.volume_id IS NULL THEN
+        RETURN QUERY SELECT 
+            NULL::INTEGER, NULL::TEXT, NULL::TIMESTAMP WITH TIME ZONE,
+            FALSE, 'No current book to finish'::TEXT;
+        RETURN;
+    END IF;
+    
+    -- Move to completed books (UPDATED to include suggested_by_user_id)
+    INSERT INTO server_completed_books (club_id, volume_id, suggested_by_user_id, started_at)
+    VALUES (p_club_id, v_current_book.volume_id, v_current_book.suggested_by_user_id, v_current_book.started_at)
+    RETURNING server_completed_books.completed_id INTO v_completed_id;
+
+    -- Keep a record of who read along before their progress is cleared; any progress that
+    -- wasn't marked with /dnf counts as finished
+    INSERT INTO completed_book_participants (completed_id, user_id, status, last_progress_text)
+    SELECT
+        v_completed_id,
+        urp.user_id,
+        CASE
+            WHEN urp.dnf_at IS NOT NULL THEN 'dnf'
+            WHEN urp.progress_text IS NULL THEN 'not_started'
+            ELSE 'finished'
+        END,
+        urp.progress_text
+    FROM user_reading_progress urp
+    WHERE urp.club_id = p_club_id
+      AND urp.volume_id = v_current_book.volume_id;
+
+    -- Clear reading progress for all users in this club
+    DELETE FROM user_reading_progress
+    WHERE club_id = p_club_id;
+    
+    -- Remove current book
+    DELETE FROM server_current_book
+    WHERE club_id = p_club_id;
+    
+    -- Return success with completed book info
+    RETURN QUERY
+    SELECT 
+        v_completed_id,
+        scb.volume_id,
+        scb.started_at,
+        TRUE,
+        NULL::TEXT
+    FROM server_completed_books scb
+    WHERE scb.completed_id = v_completed_id;
+    
+EXCEPTION WHEN OTHERS THEN
+    RETURN QUERY SELECT 
+        NULL::INTEGER, NULL::TEXT, NULL::TIMESTAMP WITH TIME ZONE,
+        FALSE, SQLERRM::TEXT;
+END;
+$$ LANGUAGE plpgsql;
+
+COMMIT;
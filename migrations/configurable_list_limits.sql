@@ -0,0 +1,22 @@
+-- Replaces the hardcoded 5-entry limits on favorites and reading lists with per-server settings
+-- that the bot checks itself (see `/config lists`).
+--
+-- Run once against a database created from a schema.sql that still has the limit triggers:
+--     psql -d fumiko -f migrations/configurable_list_limits.sql
+
+BEGIN;
+
+ALTER TABLE server_bot_config
+    ADD COLUMN IF NOT EXISTS favorite_books_limit INTEGER NOT NULL DEFAULT 5 CHECK (favorite_books_limit > 0),
+    ADD COLUMN IF NOT EXISTS favorite_authors_limit INTEGER NOT NULL DEFAULT 5 CHECK (favorite_authors_limit > 0),
+    ADD COLUMN IF NOT EXISTS reading_list_limit INTEGER NOT NULL DEFAULT 5 CHECK (reading_list_limit > 0);
+
+DROP TRIGGER IF EXISTS enforce_reading_list_limit ON user_reading_list;
+DROP TRIGGER IF EXISTS enforce_favorite_books_limit ON user_favorite_books;
+DROP TRIGGER IF EXISTS enforce_favorite_authors_limit ON user_favorite_authors;
+
+DROP FUNCTION IF EXISTS check_reading_list_limit();
+DROP FUNCTION IF EXISTS check_favorite_books_limit();
+DROP FUNCTION IF EXISTS check_favorite_authors_limit();
+
+COMMIT;
@@ -0,0 +1,632 @@
+-- Migrates a single-club database to multiple book clubs per server.
+--
+-- Every server gets a primary club named "Main" that inherits its existing queue, current
+-- book, completed books, reading progress, reminders and polls, along with the announcement
+-- channel, poll pinning and deadline auto-complete settings from server_bot_config.
+--
+-- Run once against a database created from the schema.sql that predates clubs:
+--     psql -d fumiko -f migrations/multiple_clubs.sql
+
+BEGIN;
+
+CREATE TABLE public.server_clubs (
+    club_id SERIAL PRIMARY KEY,
+    server_id BIGINT NOT NULL REFERENCES discord_servers(server_id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    is_primary BOOLEAN NOT NULL DEFAULT FALSE,
+    announcement_channel_id BIGINT,
+    pin_polls BOOLEAN NOT NULL DEFAULT TRUE,
+    auto_complete_on_deadline BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_server_clubs_server_id ON server_clubs(server_id);
+
+CREATE UNIQUE INDEX uidx_server_club_name
+    ON public.server_clubs (server_id, LOWER(name));
+
+CREATE UNIQUE INDEX uidx_server_one_primary_club
+    ON public.server_clubs (server_id)
+    WHERE is_primary;
+
+INSERT INTO server_clubs (server_id, name, is_primary, announcement_channel_id, pin_polls, auto_complete_on_deadline)
+SELECT
+    ds.server_id,
+    'Main',
+    TRUE,
+    sbc.announcement_channel_id,
+    COALESCE(sbc.pin_polls, TRUE),
+    COALESCE(sbc.auto_complete_on_deadline, FALSE)
+FROM discord_servers ds
+LEFT JOIN server_bot_config sbc ON sbc.server_id = ds.server_id;
+
+-- The view and the old server-keyed functions reference the columns being dropped
+DROP VIEW IF EXISTS server_book_ratings_view;
+
+DO $$
+DECLARE
+    f regprocedure;
+BEGIN
+    FOR f IN
+        SELECT p.oid::regprocedure
+        FROM pg_proc p
+        WHERE p.pronamespace = 'public'::regnamespace
+          AND p.proname IN (
+              'select_book_from_queue_tx',
+              'finish_current_book_tx',
+              'get_random_queue_candidates',
+              'get_queue_books_for_poll',
+              'move_queue_book_tx',
+              'swap_queue_books_tx',
+              'shuffle_queue_tx',
+              'clear_queue_tx',
+              'remove_current_book_tx',
+              'get_server_book_rankings'
+          )
+    LOOP
+        EXECUTE 'DROP FUNCTION ' || f;
+    END LOOP;
+END;
+$$;
+
+-- Re-key every club-scoped table on the server's primary club. Dropping server_id also drops
+-- the keys and indexes built on it, which are recreated below.
+DO $$
+DECLARE
+    t TEXT;
+BEGIN
+    FOREACH t IN ARRAY ARRAY[
+        'user_reading_progress',
+        'user_progress_reminders',
+        'server_book_queue',
+        'server_current_book',
+        'server_completed_books',
+        'selection_polls',
+        'scheduled_selection_polls'
+    ]
+    LOOP
+        EXECUTE format(
+            'ALTER TABLE %I ADD COLUMN club_id INTEGER REFERENCES server_clubs(club_id) ON DELETE CASCADE',
+            t
+        );
+        EXECUTE format(
+            'UPDATE %I x SET club_id = sc.club_id FROM server_clubs sc
+             WHERE sc.server_id = x.server_id AND sc.is_primary',
+            t
+        );
+        EXECUTE format('ALTER TABLE %I ALTER COLUMN club_id SET NOT NULL', t);
+        EXECUTE format('ALTER TABLE %I DROP COLUMN server_id', t);
+    END LOOP;
+END;
+$$;
+
+ALTER TABLE user_reading_progress ADD PRIMARY KEY (user_id, club_id);
+ALTER TABLE user_progress_reminders ADD PRIMARY KEY (user_id, club_id);
+ALTER TABLE server_current_book ADD PRIMARY KEY (club_id);
+ALTER TABLE scheduled_selection_polls ADD PRIMARY KEY (club_id);
+ALTER TABLE server_book_queue ADD UNIQUE (club_id, volume_id);
+ALTER TABLE server_completed_books ADD UNIQUE (club_id, volume_id, completed_at);
+
+CREATE INDEX idx_user_reading_progress_club_id ON user_reading_progress(club_id);
+CREATE INDEX idx_server_book_queue_club_id ON server_book_queue(club_id);
+CREATE INDEX idx_server_book_queue_position ON server_book_queue(club_id, position);
+CREATE INDEX idx_server_completed_books_club_id ON server_completed_books(club_id);
+CREATE INDEX idx_user_progress_reminders_club_id ON user_progress_reminders(club_id);
+
+CREATE UNIQUE INDEX uidx_one_active_selection_poll
+    ON selection_polls(club_id)
+    WHERE NOT processed;
+
+ALTER TABLE server_bot_config
+    DROP COLUMN announcement_channel_id,
+    DROP COLUMN pin_polls,
+    DROP COLUMN auto_complete_on_deadline;
+
+-- View for server book ratings (updated to not include title)
+CREATE OR REPLACE VIEW server_book_ratings_view AS
+SELECT 
+    sc.server_id,
+    scb.club_id,
+    scb.volume_id,
+    ubr.user_id,
+    ubr.rating,
+    ubr.rated_at
+FROM server_completed_books scb
+JOIN server_clubs sc ON sc.club_id = scb.club_id
+JOIN user_book_ratings ubr ON ubr.completed_id = scb.completed_id;
+
+-- Trigger for updating average ratings
+CREATE OR REPLACE FUNCTION update_average_rating()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_completed_id INTEGER := COALESCE(NEW.completed_id, OLD.completed_id);
+BEGIN
+    UPDATE server_completed_books
+    SET average_rating = (
+        SELECT AVG(rating)::DECIMAL(3,2)
+        FROM user_book_ratings
+        WHERE completed_id = v_completed_id
+    ),
+    total_ratings = (
+        SELECT COUNT(*)
+        FROM user_book_ratings
+        WHERE completed_id = v_completed_id
+    )
+    WHERE completed_id = v_completed_id;
+
+    RETURN COALESCE(NEW, OLD);
+END;
+$$ LANGUAGE plpgsql;
+
+-- Function to maintain queue positions
+CREATE OR REPLACE FUNCTION reorder_queue_positions()
+RETURNS TRIGGER AS $$
+BEGIN
+    WITH numbered_queue AS (
+        SELECT queue_id, 
+               ROW_NUMBER() OVER (ORDER BY position, added_at) as new_position
+        FROM server_book_queue
+        WHERE club_id = OLD.club_id
+    )
+    UPDATE server_book_queue sq
+    SET position = nq.new_position
+    FROM numbered_queue nq
+    WHERE sq.queue_id = nq.queue_id;
+    
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Function to move a book from queue to current (UPDATED)
+CREATE OR REPLACE FUNCTION select_book_from_queue_tx(
+    p_club_id INTEGER,
+    p_volume_id TEXT,
+    p_announcement_channel_id BIGINT DEFAULT NULL,
+    p_deadline TIMESTAMP WITH TIME ZONE DEFAULT NULL
+)
+RETURNS TABLE (
+    volume_id TEXT,
+    suggested_by_user_id BIGINT,
+    suggested_by_username TEXT,
+    pitch TEXT,
+    success BOOLEAN,
+    error_message TEXT
+) AS $$
+DECLARE
+    v_current_book TEXT;
+    v_book_exists BOOLEAN;
+BEGIN
+    -- Check if book exists in queue
+    SELECT EXISTS(
+        SELECT 1
+        FROM server_book_queue sbq
+        WHERE sbq.club_id = p_club_id
+        AND sbq.volume_id = p_volume_id
+    ) INTO v_book_exists;
+    
+    IF NOT v_book_exists THEN
+        RETURN QUERY SELECT 
+            NULL::TEXT, NULL::BIGINT, NULL::TEXT, NULL::TEXT,
+            FALSE, 'Book not found in queue'::TEXT;
+        RETURN;
+    END IF;
+    
+    -- Check if there's already a current book
+    SELECT scb.volume_id INTO v_current_book
+    FROM server_current_book scb
+    WHERE scb.club_id = p_club_id;
+    
+    IF v_current_book IS NOT NULL THEN
+        RETURN QUERY SELECT 
+            NULL::TEXT, NULL::BIGINT, NULL::TEXT, NULL::TEXT,
+            FALSE, 'Server already has a current book'::TEXT;
+        RETURN;
+    END IF;
+    
+    -- Get book details BEFORE removing from queue
+    CREATE TEMP TABLE book_details ON COMMIT DROP AS
+    SELECT 
+        sbq.volume_id,
+        sbq.suggested_by_user_id,
+        du.username as suggested_by_username,
+        sbq.pitch
+    FROM server_book_queue sbq
+    JOIN discord_users du ON du.user_id = sbq.suggested_by_user_id
+    WHERE sbq.club_id = p_club_id 
+    AND sbq.volume_id = p_volume_id;
+    
+    -- Insert into current book (UPDATED to include suggested_by_user_id)
+    INSERT INTO server_current_book (
+        club_id,
+        volume_id,
+        suggested_by_user_id,
+        announcement_channel_id,
+        deadline
+    )
+    SELECT p_club_id, bd.volume_id, bd.suggested_by_user_id, p_announcement_channel_id, p_deadline
+    FROM book_details bd;
+    
+    -- Remove from queue
+    DELETE FROM server_book_queue sbq
+    WHERE sbq.club_id = p_club_id
+        AND sbq.volume_id = p_volume_id;
+    
+    -- Return success with book details
+    RETURN QUERY 
+    SELECT bd.*, TRUE, NULL::TEXT
+    FROM book_details bd;
+    
+EXCEPTION WHEN OTHERS THEN
+    RETURN QUERY SELECT 
+        NULL::TEXT, NULL::BIGINT, NULL::TEXT, NULL::TEXT,
+        FALSE, SQLERRM::TEXT;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Function to finish current book and move to completed (UPDATED)
+CREATE OR REPLACE FUNCTION finish_current_book_tx(p_club_id INTEGER)
+RETURNS TABLE (
+    completed_id INTEGER,
+    volume_id TEXT,
+    started_at TIMESTAMP WITH TIME ZONE,
+    success BOOLEAN,
+    error_message TEXT
+) AS $$
+DECLARE
+    v_current_book RECORD;
+    v_completed_id INTEGER;
+BEGIN
+    -- Get current book with lock (UPDATED to include suggested_by_user_id)
+    SELECT scb.volume_id, scb.started_at, scb.suggested_by_user_id
+    INTO v_current_book
+    FROM server_current_book scb
+    WHERE scb.club_id = p_club_id
+    FOR UPDATE;
+    
+    IF v_current_book.volume_id IS NULL THEN
+        RETURN QUERY SELECT 
+            NULL::INTEGER, NULL::TEXT, NULL::TIMESTAMP WITH TIME ZONE,
+            FALSE, 'No current book to finish'::TEXT;
+        RETURN;
+    END IF;
+    
+    -- Move to completed books (UPDATED to include suggested_by_user_id)
+    INSERT INTO server_completed_books (club_id, volume_id, suggested_by_user_id, started_at)
+    VALUES (p_club_id, v_current_book.volume_id, v_current_book.suggested_by_user_id, v_current_book.started_at)
+    RETURNING server_completed_books.completed_id INTO v_completed_id;
+
+    -- Clear reading progress for all users in this club
+    DELETE FROM user_reading_progress
+    WHERE club_id = p_club_id;
+    
+    -- Remove current book
+    DELETE FROM server_current_book
+    WHERE club_id = p_club_id;
+    
+    -- Return success with completed book info
+    RETURN QUERY
+    SELECT 
+        v_completed_id,
+        scb.volume_id,
+        scb.started_at,
+        TRUE,
+        NULL::TEXT
+    FROM server_completed_books scb
+    WHERE scb.completed_id = v_completed_id;
+    
+EXCEPTION WHEN OTHERS THEN
+    RETURN QUERY SELECT 
+        NULL::INTEGER, NULL::TEXT, NULL::TIMESTAMP WITH TIME ZONE,
+        FALSE, SQLERRM::TEXT;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Function to get the books a random pick chooses between, with the data used to weight them.
+-- books_since_pick is NULL when the suggester has never had a pick read.
+CREATE OR REPLACE FUNCTION get_random_queue_candidates(
+    p_club_id INTEGER,
+    p_excluded_volume_ids TEXT[] DEFAULT '{}'
+)
+RETURNS TABLE (
+    volume_id TEXT,
+    suggested_by_username TEXT,
+    added_at TIMESTAMP WITH TIME ZONE,
+    vote_count BIGINT,
+    books_since_pick BIGINT
+) AS $$
+BEGIN
+    RETURN QUERY
+    SELECT 
+        sbq.volume_id,
+        du.username as suggested_by_username,
+        sbq.added_at,
+        (SELECT COUNT(*) FROM server_queue_votes sqv WHERE sqv.queue_id = sbq.queue_id) as vote_count,
+        CASE WHEN last_pick.completed_at IS NULL THEN NULL ELSE (
+            SELECT COUNT(*)
+            FROM server_completed_books later
+            WHERE later.club_id = p_club_id
+                AND later.completed_at > last_pick.completed_at
+        ) END as books_since_pick
+    FROM server_book_queue sbq
+    JOIN discord_users du ON du.user_id = sbq.suggested_by_user_id
+    LEFT JOIN LATERAL (
+        SELECT MAX(scb.completed_at) AS completed_at
+        FROM server_completed_books scb
+        WHERE scb.club_id = p_club_id
+            AND scb.suggested_by_user_id = sbq.suggested_by_user_id
+    ) last_pick ON TRUE
+    WHERE sbq.club_id = p_club_id
+        AND NOT (sbq.volume_id = ANY(p_excluded_volume_ids))
+    ORDER BY sbq.position;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Function to get books for poll (by queue position, or by upvotes with position as tiebreak),
+-- skipping books excluded by selection rules
+CREATE OR REPLACE FUNCTION get_queue_books_for_poll(
+    p_club_id INTEGER,
+    p_poll_size INTEGER DEFAULT 5,
+    p_by_votes BOOLEAN DEFAULT FALSE,
+    p_excluded_volume_ids TEXT[] DEFAULT '{}'
+)
+RETURNS TABLE (
+    volume_id TEXT,
+    suggested_by_username TEXT,
+    "position" INTEGER,
+    pitch TEXT,
+    vote_count BIGINT
+) AS $$
+BEGIN
+    RETURN QUERY
+    SELECT 
+        sbq.volume_id,
+        du.username as suggested_by_username,
+        sbq.position,
+        sbq.pitch,
+        (SELECT COUNT(*) FROM server_queue_votes sqv WHERE sqv.queue_id = sbq.queue_id) as vote_count
+    FROM server_book_queue sbq
+    JOIN discord_users du ON du.user_id = sbq.suggested_by_user_id
+    WHERE sbq.club_id = p_club_id
+        AND NOT (sbq.volume_id = ANY(p_excluded_volume_ids))
+    ORDER BY
+        sbq.pinned DESC,
+        CASE WHEN p_by_votes THEN
+            (SELECT COUNT(*) FROM server_queue_votes sqv WHERE sqv.queue_id = sbq.queue_id)
+        END DESC NULLS LAST,
+        sbq.position
+    LIMIT p_poll_size;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Move a queued book to a new position, shifting the books in between
+CREATE OR REPLACE FUNCTION move_queue_book_tx(
+    p_club_id INTEGER,
+    p_from_position INTEGER,
+    p_to_position INTEGER
+)
+RETURNS TABLE (
+    success BOOLEAN,
+    error_message TEXT
+) AS $$
+DECLARE
+    v_queue_id INTEGER;
+    v_count INTEGER;
+    v_target INTEGER;
+BEGIN
+    -- Lock the club's queue so concurrent edits can't interleave
+    PERFORM 1 FROM server_book_queue sbq WHERE sbq.club_id = p_club_id FOR UPDATE;
+
+    SELECT sbq.queue_id INTO v_queue_id
+    FROM server_book_queue sbq
+    WHERE sbq.club_id = p_club_id
+    AND sbq.position = p_from_position;
+
+    IF v_queue_id IS NULL THEN
+        RETURN QUERY SELECT FALSE, 'No book at that position'::TEXT;
+        RETURN;
+    END IF;
+
+    SELECT COUNT(*) INTO v_count
+    FROM server_book_queue sbq
+    WHERE sbq.club_id = p_club_id;
+
+    v_target := LEAST(GREATEST(p_to_position, 1), v_count);
+
+    IF v_target < p_from_position THEN
+        UPDATE server_book_queue sbq
+        SET position = sbq.position + 1
+        WHERE sbq.club_id = p_club_id
+        AND sbq.position >= v_target
+        AND sbq.position < p_from_position;
+    ELSIF v_target > p_from_position THEN
+        UPDATE server_book_queue sbq
+        SET position = sbq.position - 1
+        WHERE sbq.club_id = p_club_id
+        AND sbq.position > p_from_position
+        AND sbq.position <= v_target;
+    END IF;
+
+    UPDATE server_book_queue sbq
+    SET position = v_target
+    WHERE sbq.queue_id = v_queue_id;
+
+    RETURN QUERY SELECT TRUE, NULL::TEXT;
+
+EXCEPTION WHEN OTHERS THEN
+    RETURN QUERY SELECT FALSE, SQLERRM::TEXT;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Swap the positions of two queued books
+CREATE OR REPLACE FUNCTION swap_queue_books_tx(
+    p_club_id INTEGER,
+    p_first_position INTEGER,
+    p_second_position INTEGER
+)
+RETURNS TABLE (
+    success BOOLEAN,
+    error_message TEXT
+) AS $$
+DECLARE
+    v_first_id INTEGER;
+    v_second_id INTEGER;
+BEGIN
+    IF p_first_position = p_second_position THEN
+        RETURN QUERY SELECT FALSE, 'Pick two different positions'::TEXT;
+        RETURN;
+    END IF;
+
+    PERFORM 1 FROM server_book_queue sbq WHERE sbq.club_id = p_club_id FOR UPDATE;
+
+    SELECT sbq.queue_id INTO v_first_id
+    FROM server_book_queue sbq
+    WHERE sbq.club_id = p_club_id
+    AND sbq.position = p_first_position;
+
+    SELECT sbq.queue_id INTO v_second_id
+    FROM server_book_queue sbq
+    WHERE sbq.club_id = p_club_id
+    AND sbq.position = p_second_position;
+
+    IF v_first_id IS NULL OR v_second_id IS NULL THEN
+        RETURN QUERY SELECT FALSE, 'No book at one of those positions'::TEXT;
+        RETURN;
+    END IF;
+
+    UPDATE server_book_queue sbq
+    SET position = CASE
+        WHEN sbq.queue_id = v_first_id THEN p_second_position
+        ELSE p_first_position
+    END
+    WHERE sbq.queue_id IN (v_first_id, v_second_id);
+
+    RETURN QUERY SELECT TRUE, NULL::TEXT;
+
+EXCEPTION WHEN OTHERS THEN
+    RETURN QUERY SELECT FALSE, SQLERRM::TEXT;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Shuffle the unpinned books into the positions they currently occupy; pinned books stay put
+CREATE OR REPLACE FUNCTION shuffle_queue_tx(p_club_id INTEGER)
+RETURNS INTEGER AS $$
+DECLARE
+    v_shuffled INTEGER;
+BEGIN
+    PERFORM 1 FROM server_book_queue sbq WHERE sbq.club_id = p_club_id FOR UPDATE;
+
+    WITH free_positions AS (
+        SELECT sbq.position,
+               ROW_NUMBER() OVER (ORDER BY sbq.position) AS slot
+        FROM server_book_queue sbq
+        WHERE sbq.club_id = p_club_id
+        AND NOT sbq.pinned
+    ),
+    shuffled AS (
+        SELECT sbq.queue_id,
+               ROW_NUMBER() OVER (ORDER BY RANDOM()) AS slot
+        FROM server_book_queue sbq
+        WHERE sbq.club_id = p_club_id
+        AND NOT sbq.pinned
+    )
+    UPDATE server_book_queue sq
+    SET position = fp.position
+    FROM shuffled s
+    JOIN free_positions fp ON fp.slot = s.slot
+    WHERE sq.queue_id = s.queue_id;
+
+    GET DIAGNOSTICS v_shuffled = ROW_COUNT;
+    RETURN v_shuffled;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Empty the queue, optionally keeping pinned books; returns how many books were removed
+CREATE OR REPLACE FUNCTION clear_queue_tx(
+    p_club_id INTEGER,
+    p_keep_pinned BOOLEAN DEFAULT FALSE
+)
+RETURNS INTEGER AS $$
+DECLARE
+    v_removed INTEGER;
+BEGIN
+    PERFORM 1 FROM server_book_queue sbq WHERE sbq.club_id = p_club_id FOR UPDATE;
+
+    DELETE FROM server_book_queue sbq
+    WHERE sbq.club_id = p_club_id
+    AND (NOT p_keep_pinned OR NOT sbq.pinned);
+
+    GET DIAGNOSTICS v_removed = ROW_COUNT;
+    RETURN v_removed;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Remove the current book without marking it completed (UPDATED)
+CREATE OR REPLACE FUNCTION remove_current_book_tx(p_club_id INTEGER)
+RETURNS TABLE (
+    volume_id TEXT,
+    success BOOLEAN,
+    error_message TEXT
+) AS $$
+DECLARE
+    v_volume_id TEXT;
+BEGIN
+    -- Lock current book row for this club
+    SELECT scb.volume_id
+    INTO v_volume_id
+    FROM server_current_book scb
+    WHERE scb.club_id = p_club_id
+    FOR UPDATE;
+
+    IF v_volume_id IS NULL THEN
+        RETURN QUERY SELECT
+            NULL::TEXT, FALSE, 'No current book to remove'::TEXT;
+        RETURN;
+    END IF;
+
+    -- Clear the club's reading progress
+    DELETE FROM user_reading_progress
+    WHERE club_id = p_club_id;
+
+    -- Remove the current book
+    DELETE FROM server_current_book
+    WHERE club_id = p_club_id;
+
+    -- Return the removed book info
+    RETURN QUERY SELECT
+        v_volume_id,
+        TRUE,
+        NULL::TEXT;
+
+EXCEPTION WHEN OTHERS THEN
+    RETURN QUERY SELECT
+        NULL::TEXT, FALSE, SQLERRM::TEXT;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Function to get club book rankings (UPDATED to include suggested_by) + (FIXED ranking logic)
+CREATE OR REPLACE FUNCTION get_club_book_rankings(p_club_id INTEGER)
+RETURNS TABLE (
+    rank INTEGER,
+    volume_id TEXT,
+    suggested_by_username TEXT,
+    average_rating DECIMAL(3,2),
+    total_ratings INTEGER,
+    completed_at TIMESTAMP WITH TIME ZONE
+) AS $$
+BEGIN
+    RETURN QUERY
+    SELECT 
+        DENSE_RANK() OVER (ORDER BY scb.average_rating DESC NULLS LAST)::INTEGER as rank,
+        scb.volume_id,
+        du.username as suggested_by_username,
+        scb.average_rating,
+        scb.total_ratings,
+        scb.completed_at
+    FROM server_completed_books scb
+    LEFT JOIN discord_users du ON du.user_id = scb.suggested_by_user_id
+    WHERE scb.club_id = p_club_id
+    ORDER BY scb.average_rating DESC NULLS LAST, scb.completed_at DESC;
+END;
+$$ LANGUAGE plpgsql;
+
+COMMIT;
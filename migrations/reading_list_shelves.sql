@@ -0,0 +1,16 @@
+-- Adds shelves and tags to reading lists. Every existing entry lands on the default "to-read"
+-- shelf with no tags.
+--
+-- Run once against a database created from a schema.sql without reading list shelves:
+--     psql -d fumiko -f migrations/reading_list_shelves.sql
+
+BEGIN;
+
+ALTER TABLE user_reading_list
+    ADD COLUMN IF NOT EXISTS shelf TEXT NOT NULL DEFAULT 'to-read',
+    ADD COLUMN IF NOT EXISTS tags TEXT[] NOT NULL DEFAULT '{}';
+
+CREATE INDEX IF NOT EXISTS idx_user_reading_list_shelf
+    ON user_reading_list(user_id, server_id, shelf);
+
+COMMIT;
@@ -0,0 +1,17 @@
+-- Adds the settings behind automatic /wrapped yearly recaps. The recap stays off until a club
+-- turns it on with `/config wrapped set`.
+--
+-- Run once against a database created from a schema.sql without /wrapped (after
+-- migrations/multiple_clubs.sql if that applies):
+--     psql -d fumiko -f migrations/wrapped_recaps.sql
+
+BEGIN;
+
+ALTER TABLE server_clubs
+    ADD COLUMN IF NOT EXISTS wrapped_posted_year INTEGER;
+
+ALTER TABLE server_bot_config
+    ADD COLUMN IF NOT EXISTS wrapped_post_month INTEGER CHECK (wrapped_post_month BETWEEN 1 AND 12),
+    ADD COLUMN IF NOT EXISTS wrapped_post_day INTEGER CHECK (wrapped_post_day BETWEEN 1 AND 28);
+
+COMMIT;
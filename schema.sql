@@ -14,6 +14,21 @@ CREATE TABLE public.discord_servers (
     updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
 );
 
+-- Book clubs within a server. Each club has its own queue, current book, history and polls;
+-- commands use the primary club when no club is named.
+CREATE TABLE public.server_clubs (
+    club_id SERIAL PRIMARY KEY,
+    server_id BIGINT NOT NULL REFERENCES discord_servers(server_id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    is_primary BOOLEAN NOT NULL DEFAULT FALSE,
+    announcement_channel_id BIGINT,
+    pin_polls BOOLEAN NOT NULL DEFAULT TRUE,
+    auto_complete_on_deadline BOOLEAN NOT NULL DEFAULT FALSE,
+    ratings_participants_only BOOLEAN NOT NULL DEFAULT FALSE, -- Only count ratings from members who read along
+    wrapped_posted_year INTEGER, -- Calendar year the automatic /wrapped recap was last posted
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
 -- User Favorite Books (stores only Google Books volume IDs)
 CREATE TABLE public.user_favorite_books (
     user_id BIGINT NOT NULL REFERENCES discord_users(user_id) ON DELETE CASCADE,
@@ -24,24 +39,25 @@ CREATE TABLE public.user_favorite_books (
     PRIMARY KEY (user_id, server_id, volume_id)
 );
 
--- User Favorite Authors (user-provided names, not from API)
--- CREATE TABLE public.user_favorite_authors (
---     user_id BIGINT NOT NULL REFERENCES discord_users(user_id) ON DELETE CASCADE,
---     server_id BIGINT NOT NULL REFERENCES discord_servers(server_id) ON DELETE CASCADE,
---     author_name TEXT NOT NULL, -- User-provided author name
---     added_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
---     is_number_one BOOLEAN NOT NULL DEFAULT FALSE,
---     PRIMARY KEY (user_id, server_id, author_name)
--- );
+-- User Favorite Authors (names checked against Google Books, stored as Google spells them)
+CREATE TABLE public.user_favorite_authors (
+    user_id BIGINT NOT NULL REFERENCES discord_users(user_id) ON DELETE CASCADE,
+    server_id BIGINT NOT NULL REFERENCES discord_servers(server_id) ON DELETE CASCADE,
+    author_name TEXT NOT NULL,
+    added_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    is_number_one BOOLEAN NOT NULL DEFAULT FALSE,
+    PRIMARY KEY (user_id, server_id, author_name)
+);
 
 -- User Reading Progress (current book progress)
 CREATE TABLE public.user_reading_progress (
     user_id BIGINT NOT NULL REFERENCES discord_users(user_id) ON DELETE CASCADE,
-    server_id BIGINT NOT NULL REFERENCES discord_servers(server_id) ON DELETE CASCADE,
+    club_id INTEGER NOT NULL REFERENCES server_clubs(club_id) ON DELETE CASCADE,
     volume_id TEXT NOT NULL, -- Google Books volume ID only
     progress_text TEXT,
     updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
-    PRIMARY KEY (user_id, server_id)
+    dnf_at TIMESTAMP WITH TIME ZONE, -- Set when the member gave up on the book with /dnf
+    PRIMARY KEY (user_id, club_id)
 );
 
 -- Progress command bans
@@ -53,30 +69,83 @@ CREATE TABLE public.progress_command_bans (
     PRIMARY KEY (server_id, user_id)
 );
 
+-- Progress reminder opt-ins (per user and club)
+CREATE TABLE public.user_progress_reminders (
+    user_id BIGINT NOT NULL REFERENCES discord_users(user_id) ON DELETE CASCADE,
+    club_id INTEGER NOT NULL REFERENCES server_clubs(club_id) ON DELETE CASCADE,
+    interval_days INTEGER NOT NULL CHECK (interval_days > 0),
+    last_reminded_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (user_id, club_id)
+);
+
+-- Reminder preferences shared across servers (global opt-out and quiet hours in UTC)
+CREATE TABLE public.user_reminder_settings (
+    user_id BIGINT PRIMARY KEY REFERENCES discord_users(user_id) ON DELETE CASCADE,
+    reminders_paused BOOLEAN NOT NULL DEFAULT FALSE,
+    quiet_hours_start SMALLINT CHECK (quiet_hours_start BETWEEN 0 AND 23),
+    quiet_hours_end SMALLINT CHECK (quiet_hours_end BETWEEN 0 AND 23),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
 -- User Reading List (books to read in the future)
 CREATE TABLE public.user_reading_list (
     user_id BIGINT NOT NULL REFERENCES discord_users(user_id) ON DELETE CASCADE,
     server_id BIGINT NOT NULL REFERENCES discord_servers(server_id) ON DELETE CASCADE,
     volume_id TEXT NOT NULL, -- Google Books volume ID only
+    shelf TEXT NOT NULL DEFAULT 'to-read', -- Lowercase shelf name chosen by the member
+    tags TEXT[] NOT NULL DEFAULT '{}', -- Lowercase free-form tags
     added_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
     PRIMARY KEY (user_id, server_id, volume_id)
 );
 
--- Server Book Queue
+-- Members who want to hear when a book on their reading list or favorites is queued, put in a
+-- selection poll or selected. No row means notifications are off for that server.
+CREATE TABLE public.user_list_notifications (
+    user_id BIGINT NOT NULL REFERENCES discord_users(user_id) ON DELETE CASCADE,
+    server_id BIGINT NOT NULL REFERENCES discord_servers(server_id) ON DELETE CASCADE,
+    delivery TEXT NOT NULL CHECK (delivery IN ('dm', 'mention')),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (user_id, server_id)
+);
+
+-- User Personal Ratings (books rated outside the club, e.g. imported from Goodreads or StoryGraph)
+CREATE TABLE public.user_personal_ratings (
+    user_id BIGINT NOT NULL REFERENCES discord_users(user_id) ON DELETE CASCADE,
+    server_id BIGINT NOT NULL REFERENCES discord_servers(server_id) ON DELETE CASCADE,
+    volume_id TEXT NOT NULL, -- Google Books volume ID only
+    rating INTEGER NOT NULL CHECK (rating >= 1 AND rating <= 5),
+    rated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (user_id, server_id, volume_id)
+);
+
+-- Club Book Queue
 CREATE TABLE public.server_book_queue (
     queue_id SERIAL PRIMARY KEY,
-    server_id BIGINT NOT NULL REFERENCES discord_servers(server_id) ON DELETE CASCADE,
+    club_id INTEGER NOT NULL REFERENCES server_clubs(club_id) ON DELETE CASCADE,
     volume_id TEXT NOT NULL, -- Google Books volume ID only
     suggested_by_user_id BIGINT NOT NULL REFERENCES discord_users(user_id) ON DELETE CASCADE,
     added_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
     position INTEGER NOT NULL,
-    UNIQUE (server_id, volume_id),
-    -- UNIQUE (server_id, suggested_by_user_id) -- One book per person in queue, not currently in use due to adminqueue not liking it for current impl
+    pitch TEXT, -- Optional reason the suggester gives for the pick
+    pinned BOOLEAN NOT NULL DEFAULT FALSE, -- Pinned books are always included in selection polls
+    renewed_at TIMESTAMP WITH TIME ZONE, -- Suggester renewed the entry; queue TTL counts from here
+    expiry_warned_at TIMESTAMP WITH TIME ZONE, -- Suggester was DMed that the entry is about to expire
+    UNIQUE (club_id, volume_id)
+    -- , UNIQUE (club_id, suggested_by_user_id) -- One book per person in queue, not currently in use due to adminqueue not liking it for current impl
 );
 
--- Server Current Book (UPDATED: Added suggested_by_user_id)
+-- Member upvotes on queued books (one per user per queue entry)
+CREATE TABLE public.server_queue_votes (
+    queue_id INTEGER NOT NULL REFERENCES server_book_queue(queue_id) ON DELETE CASCADE,
+    user_id BIGINT NOT NULL REFERENCES discord_users(user_id) ON DELETE CASCADE,
+    voted_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (queue_id, user_id)
+);
+
+-- Club Current Book (UPDATED: Added suggested_by_user_id)
 CREATE TABLE public.server_current_book (
-    server_id BIGINT PRIMARY KEY REFERENCES discord_servers(server_id) ON DELETE CASCADE,
+    club_id INTEGER PRIMARY KEY REFERENCES server_clubs(club_id) ON DELETE CASCADE,
     volume_id TEXT NOT NULL, -- Google Books volume ID only
     suggested_by_user_id BIGINT REFERENCES discord_users(user_id) ON DELETE SET NULL,
     started_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
@@ -85,17 +154,17 @@ CREATE TABLE public.server_current_book (
     discussion_thread_id BIGINT
 );
 
--- Server Completed Books (UPDATED: Added suggested_by_user_id)
+-- Club Completed Books (UPDATED: Added suggested_by_user_id)
 CREATE TABLE public.server_completed_books (
     completed_id SERIAL PRIMARY KEY,
-    server_id BIGINT NOT NULL REFERENCES discord_servers(server_id) ON DELETE CASCADE,
+    club_id INTEGER NOT NULL REFERENCES server_clubs(club_id) ON DELETE CASCADE,
     volume_id TEXT NOT NULL, -- Google Books volume ID only
     suggested_by_user_id BIGINT REFERENCES discord_users(user_id) ON DELETE SET NULL,
     started_at TIMESTAMP WITH TIME ZONE NOT NULL,
     completed_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
     average_rating DECIMAL(3,2),
     total_ratings INTEGER DEFAULT 0,
-    UNIQUE (server_id, volume_id, completed_at)
+    UNIQUE (club_id, volume_id, completed_at)
 );
 
 -- User Book Ratings (for completed books)
@@ -107,14 +176,38 @@ CREATE TABLE public.user_book_ratings (
     PRIMARY KEY (user_id, completed_id)
 );
 
+-- Who read along with a completed book, snapshotted from reading progress when it finished
+CREATE TABLE public.completed_book_participants (
+    completed_id INTEGER NOT NULL REFERENCES server_completed_books(completed_id) ON DELETE CASCADE,
+    user_id BIGINT NOT NULL REFERENCES discord_users(user_id) ON DELETE CASCADE,
+    status TEXT NOT NULL CHECK (status IN ('finished', 'dnf', 'not_started')),
+    last_progress_text TEXT,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (completed_id, user_id)
+);
+
 -- Bot Configuration per Server
 CREATE TABLE public.server_bot_config (
     server_id BIGINT PRIMARY KEY REFERENCES discord_servers(server_id) ON DELETE CASCADE,
-    announcement_channel_id BIGINT,
     discussion_channel_id BIGINT,
     queue_enabled BOOLEAN NOT NULL DEFAULT TRUE,
-    pin_polls BOOLEAN NOT NULL DEFAULT TRUE,
-    auto_complete_on_deadline BOOLEAN NOT NULL DEFAULT FALSE,
+    queue_max_per_user INTEGER NOT NULL DEFAULT 1 CHECK (queue_max_per_user > 0),
+    queue_cooldown_days INTEGER NOT NULL DEFAULT 0 CHECK (queue_cooldown_days >= 0), -- Wait after a member's pick is read
+    queue_block_completed BOOLEAN NOT NULL DEFAULT FALSE, -- Block suggesting books the club already finished
+    queue_ttl_days INTEGER CHECK (queue_ttl_days > 0), -- NULL keeps queue entries forever
+    auto_poll_enabled BOOLEAN NOT NULL DEFAULT FALSE, -- Open a selection poll automatically after a book finishes
+    auto_poll_delay_hours INTEGER NOT NULL DEFAULT 0 CHECK (auto_poll_delay_hours >= 0),
+    auto_poll_size INTEGER NOT NULL DEFAULT 5 CHECK (auto_poll_size BETWEEN 2 AND 10),
+    auto_poll_duration_hours INTEGER NOT NULL DEFAULT 24 CHECK (auto_poll_duration_hours BETWEEN 1 AND 167),
+    auto_poll_reading_days INTEGER CHECK (auto_poll_reading_days > 0), -- Deadline = poll close + this many days
+    selection_skip_recent_suggesters INTEGER NOT NULL DEFAULT 0 CHECK (selection_skip_recent_suggesters >= 0), -- Skip members whose pick was one of the last N books
+    selection_avoid_repeat_category BOOLEAN NOT NULL DEFAULT FALSE, -- Skip books sharing the last book's primary category
+    selection_max_pages INTEGER CHECK (selection_max_pages > 0), -- NULL allows any length
+    wrapped_post_month INTEGER CHECK (wrapped_post_month BETWEEN 1 AND 12), -- NULL turns the automatic yearly recap off
+    wrapped_post_day INTEGER CHECK (wrapped_post_day BETWEEN 1 AND 28),
+    favorite_books_limit INTEGER NOT NULL DEFAULT 5 CHECK (favorite_books_limit > 0), -- Per member; capped by the bot's MAX_LIST_LIMIT
+    favorite_authors_limit INTEGER NOT NULL DEFAULT 5 CHECK (favorite_authors_limit > 0),
+    reading_list_limit INTEGER NOT NULL DEFAULT 5 CHECK (reading_list_limit > 0),
     updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
 );
 
@@ -140,7 +233,7 @@ CREATE TABLE IF NOT EXISTS rating_polls (
 CREATE TABLE IF NOT EXISTS selection_polls (
     message_id BIGINT PRIMARY KEY,
     channel_id BIGINT NOT NULL,
-    server_id BIGINT NOT NULL REFERENCES discord_servers(server_id) ON DELETE CASCADE,
+    club_id INTEGER NOT NULL REFERENCES server_clubs(club_id) ON DELETE CASCADE,
     book_options TEXT[] NOT NULL, -- Array of volume_ids
     expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
     processed BOOLEAN DEFAULT FALSE,
@@ -149,50 +242,95 @@ CREATE TABLE IF NOT EXISTS selection_polls (
     created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
 );
 
+-- Buddy reads: small groups reading a book together outside the club's current pick
+CREATE TABLE public.buddy_reads (
+    buddy_read_id SERIAL PRIMARY KEY,
+    server_id BIGINT NOT NULL REFERENCES discord_servers(server_id) ON DELETE CASCADE,
+    volume_id TEXT NOT NULL, -- Google Books volume ID only
+    started_by_user_id BIGINT REFERENCES discord_users(user_id) ON DELETE SET NULL,
+    thread_id BIGINT, -- Private thread for the group, when one was created
+    started_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    finished_at TIMESTAMP WITH TIME ZONE -- NULL while the group is still reading
+);
+
+CREATE TABLE public.buddy_read_members (
+    buddy_read_id INTEGER NOT NULL REFERENCES buddy_reads(buddy_read_id) ON DELETE CASCADE,
+    user_id BIGINT NOT NULL REFERENCES discord_users(user_id) ON DELETE CASCADE,
+    progress_text TEXT,
+    updated_at TIMESTAMP WITH TIME ZONE,
+    joined_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (buddy_read_id, user_id)
+);
+
+-- Automatic selection polls waiting for their delay to pass (one per club)
+CREATE TABLE public.scheduled_selection_polls (
+    club_id INTEGER PRIMARY KEY REFERENCES server_clubs(club_id) ON DELETE CASCADE,
+    fallback_channel_id BIGINT, -- Used when no announcement channel is configured
+    run_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
 -- INDEXES
 
 CREATE INDEX idx_user_favorite_books_user_id ON user_favorite_books(user_id); -- is this still needed?
--- CREATE INDEX idx_user_favorite_authors_user_id ON user_favorite_authors(user_id);
-CREATE INDEX idx_user_reading_progress_server_id ON user_reading_progress(server_id);
+CREATE INDEX idx_user_favorite_authors_user_id ON user_favorite_authors(user_id);
+CREATE INDEX idx_user_reading_progress_club_id ON user_reading_progress(club_id);
 CREATE INDEX idx_user_reading_list_user_id ON user_reading_list(user_id);
-CREATE INDEX idx_server_book_queue_server_id ON server_book_queue(server_id);
-CREATE INDEX idx_server_book_queue_position ON server_book_queue(server_id, position);
-CREATE INDEX idx_server_completed_books_server_id ON server_completed_books(server_id);
+CREATE INDEX idx_server_clubs_server_id ON server_clubs(server_id);
+CREATE INDEX idx_server_book_queue_club_id ON server_book_queue(club_id);
+CREATE INDEX idx_server_book_queue_position ON server_book_queue(club_id, position);
+CREATE INDEX idx_server_queue_votes_user_id ON server_queue_votes(user_id);
+CREATE INDEX idx_server_completed_books_club_id ON server_completed_books(club_id);
 CREATE INDEX idx_user_book_ratings_completed_id ON user_book_ratings(completed_id);
+CREATE INDEX idx_completed_book_participants_user_id ON completed_book_participants(user_id);
 CREATE INDEX idx_rating_polls_expires_at ON rating_polls(expires_at) WHERE NOT processed;
 CREATE INDEX idx_selection_polls_expires_at ON selection_polls(expires_at) WHERE NOT processed;
 CREATE INDEX idx_server_maturity_enabled ON server_maturity_settings(server_id) WHERE mature_content_enabled;
 CREATE INDEX idx_user_favorite_books_user_server ON user_favorite_books(user_id, server_id);
--- CREATE INDEX idx_user_favorite_authors_user_server ON user_favorite_authors(user_id, server_id);
+CREATE INDEX idx_user_favorite_authors_user_server ON user_favorite_authors(user_id, server_id);
 CREATE INDEX idx_user_reading_list_user_server ON user_reading_list(user_id, server_id);
+CREATE INDEX idx_user_reading_list_shelf ON user_reading_list(user_id, server_id, shelf);
 CREATE INDEX idx_progress_command_bans_user_id ON progress_command_bans(user_id);
+CREATE INDEX idx_user_progress_reminders_club_id ON user_progress_reminders(club_id);
+CREATE INDEX idx_buddy_reads_active ON buddy_reads(server_id) WHERE finished_at IS NULL;
+CREATE INDEX idx_buddy_read_members_user_id ON buddy_read_members(user_id);
 
 
--- Prevent more than one unprocessed selection poll per server
+-- Prevent more than one unprocessed selection poll per club
 CREATE UNIQUE INDEX IF NOT EXISTS uidx_one_active_selection_poll
-    ON selection_polls(server_id)
+    ON selection_polls(club_id)
     WHERE NOT processed;
 
+-- Club names are unique within a server, and each server has at most one primary club
+CREATE UNIQUE INDEX uidx_server_club_name
+    ON public.server_clubs (server_id, LOWER(name));
+
+CREATE UNIQUE INDEX uidx_server_one_primary_club
+    ON public.server_clubs (server_id)
+    WHERE is_primary;
+
 -- Unique indexes for "number one" items updated to be per server
 CREATE UNIQUE INDEX uidx_user_one_fav_book_per_server
     ON public.user_favorite_books (user_id, server_id)
     WHERE is_number_one;
 
--- CREATE UNIQUE INDEX uidx_user_one_fav_author_per_server
---     ON public.user_favorite_authors (user_id, server_id)
---     WHERE is_number_one;
+CREATE UNIQUE INDEX uidx_user_one_fav_author_per_server
+    ON public.user_favorite_authors (user_id, server_id)
+    WHERE is_number_one;
 
 -- VIEWS
 
 -- View for server book ratings (updated to not include title)
 CREATE OR REPLACE VIEW server_book_ratings_view AS
 SELECT 
-    scb.server_id,
+    sc.server_id,
+    scb.club_id,
     scb.volume_id,
     ubr.user_id,
     ubr.rating,
     ubr.rated_at
 FROM server_completed_books scb
+JOIN server_clubs sc ON sc.club_id = scb.club_id
 JOIN user_book_ratings ubr ON ubr.completed_id = scb.completed_id;
 
 -- TRIGGER FUNCTIONS
@@ -228,7 +366,7 @@ BEGIN
         SELECT queue_id, 
                ROW_NUMBER() OVER (ORDER BY position, added_at) as new_position
         FROM server_book_queue
-        WHERE server_id = OLD.server_id
+        WHERE club_id = OLD.club_id
     )
     UPDATE server_book_queue sq
     SET position = nq.new_position
@@ -239,39 +377,6 @@ BEGIN
 END;
 $$ LANGUAGE plpgsql;
 
--- Function to check user reading list limit (reduced to 5)
-CREATE OR REPLACE FUNCTION check_reading_list_limit() 
-RETURNS TRIGGER AS $$
-BEGIN
-    IF (SELECT COUNT(*) FROM user_reading_list WHERE user_id = NEW.user_id AND server_id = NEW.server_id) >= 5 THEN
-        RAISE EXCEPTION 'User reading list cannot exceed 5 books per server';
-    END IF;
-    RETURN NEW;
-END;
-$$ LANGUAGE plpgsql;
-
--- Function to check user favorite books limit (reduced to 5)
-CREATE OR REPLACE FUNCTION check_favorite_books_limit() 
-RETURNS TRIGGER AS $$
-BEGIN
-    IF (SELECT COUNT(*) FROM user_favorite_books WHERE user_id = NEW.user_id AND server_id = NEW.server_id) >= 5 THEN
-        RAISE EXCEPTION 'User favorite books cannot exceed 5 books per server';
-    END IF;
-    RETURN NEW;
-END;
-$$ LANGUAGE plpgsql;
-
--- Function to check user favorite authors limit (reduced to 5)
--- CREATE OR REPLACE FUNCTION check_favorite_authors_limit() 
--- RETURNS TRIGGER AS $$
--- BEGIN
---     IF (SELECT COUNT(*) FROM user_favorite_authors WHERE user_id = NEW.user_id AND server_id = NEW.server_id) >= 5 THEN
---         RAISE EXCEPTION 'User favorite authors cannot exceed 5 authors per server';
---     END IF;
---     RETURN NEW;
--- END;
--- $$ LANGUAGE plpgsql;
-
 -- TRIGGERS
 
 CREATE TRIGGER trigger_update_average_rating
@@ -284,26 +389,11 @@ AFTER DELETE ON server_book_queue
 FOR EACH ROW
 EXECUTE FUNCTION reorder_queue_positions();
 
-CREATE TRIGGER enforce_reading_list_limit
-BEFORE INSERT ON user_reading_list
-FOR EACH ROW
-EXECUTE FUNCTION check_reading_list_limit();
-
-CREATE TRIGGER enforce_favorite_books_limit
-BEFORE INSERT ON user_favorite_books
-FOR EACH ROW
-EXECUTE FUNCTION check_favorite_books_limit();
-
--- CREATE TRIGGER enforce_favorite_authors_limit
--- BEFORE INSERT ON user_favorite_authors
--- FOR EACH ROW
--- EXECUTE FUNCTION check_favorite_authors_limit();
-
 -- BUSINESS LOGIC FUNCTIONS
 
 -- Function to move a book from queue to current (UPDATED)
 CREATE OR REPLACE FUNCTION select_book_from_queue_tx(
-    p_server_id BIGINT,
+    p_club_id INTEGER,
     p_volume_id TEXT,
     p_announcement_channel_id BIGINT DEFAULT NULL,
     p_deadline TIMESTAMP WITH TIME ZONE DEFAULT NULL
@@ -312,6 +402,7 @@ RETURNS TABLE (
     volume_id TEXT,
     suggested_by_user_id BIGINT,
     suggested_by_username TEXT,
+    pitch TEXT,
     success BOOLEAN,
     error_message TEXT
 ) AS $$
@@ -323,13 +414,13 @@ BEGIN
     SELECT EXISTS(
         SELECT 1
         FROM server_book_queue sbq
-        WHERE sbq.server_id = p_server_id
+        WHERE sbq.club_id = p_club_id
         AND sbq.volume_id = p_volume_id
     ) INTO v_book_exists;
     
     IF NOT v_book_exists THEN
         RETURN QUERY SELECT 
-            NULL::TEXT, NULL::BIGINT, NULL::TEXT, 
+            NULL::TEXT, NULL::BIGINT, NULL::TEXT, NULL::TEXT,
             FALSE, 'Book not found in queue'::TEXT;
         RETURN;
     END IF;
@@ -337,11 +428,11 @@ BEGIN
     -- Check if there's already a current book
     SELECT scb.volume_id INTO v_current_book
     FROM server_current_book scb
-    WHERE scb.server_id = p_server_id;
+    WHERE scb.club_id = p_club_id;
     
     IF v_current_book IS NOT NULL THEN
         RETURN QUERY SELECT 
-            NULL::TEXT, NULL::BIGINT, NULL::TEXT,
+            NULL::TEXT, NULL::BIGINT, NULL::TEXT, NULL::TEXT,
             FALSE, 'Server already has a current book'::TEXT;
         RETURN;
     END IF;
@@ -351,26 +442,27 @@ BEGIN
     SELECT 
         sbq.volume_id,
         sbq.suggested_by_user_id,
-        du.username as suggested_by_username
+        du.username as suggested_by_username,
+        sbq.pitch
     FROM server_book_queue sbq
     JOIN discord_users du ON du.user_id = sbq.suggested_by_user_id
-    WHERE sbq.server_id = p_server_id 
+    WHERE sbq.club_id = p_club_id 
     AND sbq.volume_id = p_volume_id;
     
     -- Insert into current book (UPDATED to include suggested_by_user_id)
     INSERT INTO server_current_book (
-        server_id,
+        club_id,
         volume_id,
         suggested_by_user_id,
         announcement_channel_id,
         deadline
     )
-    SELECT p_server_id, bd.volume_id, bd.suggested_by_user_id, p_announcement_channel_id, p_deadline
+    SELECT p_club_id, bd.volume_id, bd.suggested_by_user_id, p_announcement_channel_id, p_deadline
     FROM book_details bd;
     
     -- Remove from queue
     DELETE FROM server_book_queue sbq
-    WHERE sbq.server_id = p_server_id
+    WHERE sbq.club_id = p_club_id
         AND sbq.volume_id = p_volume_id;
     
     -- Return success with book details
@@ -380,13 +472,13 @@ BEGIN
     
 EXCEPTION WHEN OTHERS THEN
     RETURN QUERY SELECT 
-        NULL::TEXT, NULL::BIGINT, NULL::TEXT,
+        NULL::TEXT, NULL::BIGINT, NULL::TEXT, NULL::TEXT,
         FALSE, SQLERRM::TEXT;
 END;
 $$ LANGUAGE plpgsql;
 
 -- Function to finish current book and move to completed (UPDATED)
-CREATE OR REPLACE FUNCTION finish_current_book_tx(p_server_id BIGINT)
+CREATE OR REPLACE FUNCTION finish_current_book_tx(p_club_id INTEGER)
 RETURNS TABLE (
     completed_id INTEGER,
     volume_id TEXT,
@@ -402,7 +494,7 @@ BEGIN
     SELECT scb.volume_id, scb.started_at, scb.suggested_by_user_id
     INTO v_current_book
     FROM server_current_book scb
-    WHERE scb.server_id = p_server_id
+    WHERE scb.club_id = p_club_id
     FOR UPDATE;
     
     IF v_current_book.volume_id IS NULL THEN
@@ -413,17 +505,33 @@ BEGIN
     END IF;
     
     -- Move to completed books (UPDATED to include suggested_by_user_id)
-    INSERT INTO server_completed_books (server_id, volume_id, suggested_by_user_id, started_at)
-    VALUES (p_server_id, v_current_book.volume_id, v_current_book.suggested_by_user_id, v_current_book.started_at)
+    INSERT INTO server_completed_books (club_id, volume_id, suggested_by_user_id, started_at)
+    VALUES (p_club_id, v_current_book.volume_id, v_current_book.suggested_by_user_id, v_current_book.started_at)
     RETURNING server_completed_books.completed_id INTO v_completed_id;
 
-    -- Clear reading progress for all users in this server
+    -- Keep a record of who read along before their progress is cleared; any progress that
+    -- wasn't marked with /dnf counts as finished
+    INSERT INTO completed_book_participants (completed_id, user_id, status, last_progress_text)
+    SELECT
+        v_completed_id,
+        urp.user_id,
+        CASE
+            WHEN urp.dnf_at IS NOT NULL THEN 'dnf'
+            WHEN urp.progress_text IS NULL THEN 'not_started'
+            ELSE 'finished'
+        END,
+        urp.progress_text
+    FROM user_reading_progress urp
+    WHERE urp.club_id = p_club_id
+      AND urp.volume_id = v_current_book.volume_id;
+
+    -- Clear reading progress for all users in this club
     DELETE FROM user_reading_progress
-    WHERE server_id = p_server_id;
+    WHERE club_id = p_club_id;
     
     -- Remove current book
     DELETE FROM server_current_book
-    WHERE server_id = p_server_id;
+    WHERE club_id = p_club_id;
     
     -- Return success with completed book info
     RETURN QUERY
@@ -443,51 +551,247 @@ EXCEPTION WHEN OTHERS THEN
 END;
 $$ LANGUAGE plpgsql;
 
--- Function to get random book from queue
-CREATE OR REPLACE FUNCTION get_random_queue_book(p_server_id BIGINT)
+-- Function to get the books a random pick chooses between, with the data used to weight them.
+-- books_since_pick is NULL when the suggester has never had a pick read.
+CREATE OR REPLACE FUNCTION get_random_queue_candidates(
+    p_club_id INTEGER,
+    p_excluded_volume_ids TEXT[] DEFAULT '{}'
+)
 RETURNS TABLE (
     volume_id TEXT,
-    suggested_by_username TEXT
+    suggested_by_username TEXT,
+    added_at TIMESTAMP WITH TIME ZONE,
+    vote_count BIGINT,
+    books_since_pick BIGINT
 ) AS $$
 BEGIN
     RETURN QUERY
     SELECT 
         sbq.volume_id,
-        du.username as suggested_by_username
+        du.username as suggested_by_username,
+        sbq.added_at,
+        (SELECT COUNT(*) FROM server_queue_votes sqv WHERE sqv.queue_id = sbq.queue_id) as vote_count,
+        CASE WHEN last_pick.completed_at IS NULL THEN NULL ELSE (
+            SELECT COUNT(*)
+            FROM server_completed_books later
+            WHERE later.club_id = p_club_id
+                AND later.completed_at > last_pick.completed_at
+        ) END as books_since_pick
     FROM server_book_queue sbq
     JOIN discord_users du ON du.user_id = sbq.suggested_by_user_id
-    WHERE sbq.server_id = p_server_id
-    ORDER BY RANDOM()
-    LIMIT 1;
+    LEFT JOIN LATERAL (
+        SELECT MAX(scb.completed_at) AS completed_at
+        FROM server_completed_books scb
+        WHERE scb.club_id = p_club_id
+            AND scb.suggested_by_user_id = sbq.suggested_by_user_id
+    ) last_pick ON TRUE
+    WHERE sbq.club_id = p_club_id
+        AND NOT (sbq.volume_id = ANY(p_excluded_volume_ids))
+    ORDER BY sbq.position;
 END;
 $$ LANGUAGE plpgsql;
 
--- Function to get books for poll
+-- Function to get books for poll (by queue position, or by upvotes with position as tiebreak),
+-- skipping books excluded by selection rules
 CREATE OR REPLACE FUNCTION get_queue_books_for_poll(
-    p_server_id BIGINT,
-    p_poll_size INTEGER DEFAULT 5
+    p_club_id INTEGER,
+    p_poll_size INTEGER DEFAULT 5,
+    p_by_votes BOOLEAN DEFAULT FALSE,
+    p_excluded_volume_ids TEXT[] DEFAULT '{}'
 )
 RETURNS TABLE (
     volume_id TEXT,
     suggested_by_username TEXT,
-    "position" INTEGER
+    "position" INTEGER,
+    pitch TEXT,
+    vote_count BIGINT
 ) AS $$
 BEGIN
     RETURN QUERY
     SELECT 
         sbq.volume_id,
         du.username as suggested_by_username,
-        sbq.position
+        sbq.position,
+        sbq.pitch,
+        (SELECT COUNT(*) FROM server_queue_votes sqv WHERE sqv.queue_id = sbq.queue_id) as vote_count
     FROM server_book_queue sbq
     JOIN discord_users du ON du.user_id = sbq.suggested_by_user_id
-    WHERE sbq.server_id = p_server_id
-    ORDER BY sbq.position
+    WHERE sbq.club_id = p_club_id
+        AND NOT (sbq.volume_id = ANY(p_excluded_volume_ids))
+    ORDER BY
+        sbq.pinned DESC,
+        CASE WHEN p_by_votes THEN
+            (SELECT COUNT(*) FROM server_queue_votes sqv WHERE sqv.queue_id = sbq.queue_id)
+        END DESC NULLS LAST,
+        sbq.position
     LIMIT p_poll_size;
 END;
 $$ LANGUAGE plpgsql;
 
+-- Move a queued book to a new position, shifting the books in between
+CREATE OR REPLACE FUNCTION move_queue_book_tx(
+    p_club_id INTEGER,
+    p_from_position INTEGER,
+    p_to_position INTEGER
+)
+RETURNS TABLE (
+    success BOOLEAN,
+    error_message TEXT
+) AS $$
+DECLARE
+    v_queue_id INTEGER;
+    v_count INTEGER;
+    v_target INTEGER;
+BEGIN
+    -- Lock the club's queue so concurrent edits can't interleave
+    PERFORM 1 FROM server_book_queue sbq WHERE sbq.club_id = p_club_id FOR UPDATE;
+
+    SELECT sbq.queue_id INTO v_queue_id
+    FROM server_book_queue sbq
+    WHERE sbq.club_id = p_club_id
+    AND sbq.position = p_from_position;
+
+    IF v_queue_id IS NULL THEN
+        RETURN QUERY SELECT FALSE, 'No book at that position'::TEXT;
+        RETURN;
+    END IF;
+
+    SELECT COUNT(*) INTO v_count
+    FROM server_book_queue sbq
+    WHERE sbq.club_id = p_club_id;
+
+    v_target := LEAST(GREATEST(p_to_position, 1), v_count);
+
+    IF v_target < p_from_position THEN
+        UPDATE server_book_queue sbq
+        SET position = sbq.position + 1
+        WHERE sbq.club_id = p_club_id
+        AND sbq.position >= v_target
+        AND sbq.position < p_from_position;
+    ELSIF v_target > p_from_position THEN
+        UPDATE server_book_queue sbq
+        SET position = sbq.position - 1
+        WHERE sbq.club_id = p_club_id
+        AND sbq.position > p_from_position
+        AND sbq.position <= v_target;
+    END IF;
+
+    UPDATE server_book_queue sbq
+    SET position = v_target
+    WHERE sbq.queue_id = v_queue_id;
+
+    RETURN QUERY SELECT TRUE, NULL::TEXT;
+
+EXCEPTION WHEN OTHERS THEN
+    RETURN QUERY SELECT FALSE, SQLERRM::TEXT;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Swap the positions of two queued books
+CREATE OR REPLACE FUNCTION swap_queue_books_tx(
+    p_club_id INTEGER,
+    p_first_position INTEGER,
+    p_second_position INTEGER
+)
+RETURNS TABLE (
+    success BOOLEAN,
+    error_message TEXT
+) AS $$
+DECLARE
+    v_first_id INTEGER;
+    v_second_id INTEGER;
+BEGIN
+    IF p_first_position = p_second_position THEN
+        RETURN QUERY SELECT FALSE, 'Pick two different positions'::TEXT;
+        RETURN;
+    END IF;
+
+    PERFORM 1 FROM server_book_queue sbq WHERE sbq.club_id = p_club_id FOR UPDATE;
+
+    SELECT sbq.queue_id INTO v_first_id
+    FROM server_book_queue sbq
+    WHERE sbq.club_id = p_club_id
+    AND sbq.position = p_first_position;
+
+    SELECT sbq.queue_id INTO v_second_id
+    FROM server_book_queue sbq
+    WHERE sbq.club_id = p_club_id
+    AND sbq.position = p_second_position;
+
+    IF v_first_id IS NULL OR v_second_id IS NULL THEN
+        RETURN QUERY SELECT FALSE, 'No book at one of those positions'::TEXT;
+        RETURN;
+    END IF;
+
+    UPDATE server_book_queue sbq
+    SET position = CASE
+        WHEN sbq.queue_id = v_first_id THEN p_second_position
+        ELSE p_first_position
+    END
+    WHERE sbq.queue_id IN (v_first_id, v_second_id);
+
+    RETURN QUERY SELECT TRUE, NULL::TEXT;
+
+EXCEPTION WHEN OTHERS THEN
+    RETURN QUERY SELECT FALSE, SQLERRM::TEXT;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Shuffle the unpinned books into the positions they currently occupy; pinned books stay put
+CREATE OR REPLACE FUNCTION shuffle_queue_tx(p_club_id INTEGER)
+RETURNS INTEGER AS $$
+DECLARE
+    v_shuffled INTEGER;
+BEGIN
+    PERFORM 1 FROM server_book_queue sbq WHERE sbq.club_id = p_club_id FOR UPDATE;
+
+    WITH free_positions AS (
+        SELECT sbq.position,
+               ROW_NUMBER() OVER (ORDER BY sbq.position) AS slot
+        FROM server_book_queue sbq
+        WHERE sbq.club_id = p_club_id
+        AND NOT sbq.pinned
+    ),
+    shuffled AS (
+        SELECT sbq.queue_id,
+               ROW_NUMBER() OVER (ORDER BY RANDOM()) AS slot
+        FROM server_book_queue sbq
+        WHERE sbq.club_id = p_club_id
+        AND NOT sbq.pinned
+    )
+    UPDATE server_book_queue sq
+    SET position = fp.position
+    FROM shuffled s
+    JOIN free_positions fp ON fp.slot = s.slot
+    WHERE sq.queue_id = s.queue_id;
+
+    GET DIAGNOSTICS v_shuffled = ROW_COUNT;
+    RETURN v_shuffled;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Empty the queue, optionally keeping pinned books; returns how many books were removed
+CREATE OR REPLACE FUNCTION clear_queue_tx(
+    p_club_id INTEGER,
+    p_keep_pinned BOOLEAN DEFAULT FALSE
+)
+RETURNS INTEGER AS $$
+DECLARE
+    v_removed INTEGER;
+BEGIN
+    PERFORM 1 FROM server_book_queue sbq WHERE sbq.club_id = p_club_id FOR UPDATE;
+
+    DELETE FROM server_book_queue sbq
+    WHERE sbq.club_id = p_club_id
+    AND (NOT p_keep_pinned OR NOT sbq.pinned);
+
+    GET DIAGNOSTICS v_removed = ROW_COUNT;
+    RETURN v_removed;
+END;
+$$ LANGUAGE plpgsql;
+
 -- Remove the current book without marking it completed (UPDATED)
-CREATE OR REPLACE FUNCTION remove_current_book_tx(p_server_id BIGINT)
+CREATE OR REPLACE FUNCTION remove_current_book_tx(p_club_id INTEGER)
 RETURNS TABLE (
     volume_id TEXT,
     success BOOLEAN,
@@ -496,11 +800,11 @@ RETURNS TABLE (
 DECLARE
     v_volume_id TEXT;
 BEGIN
-    -- Lock current book row for this server
+    -- Lock current book row for this club
     SELECT scb.volume_id
     INTO v_volume_id
     FROM server_current_book scb
-    WHERE scb.server_id = p_server_id
+    WHERE scb.club_id = p_club_id
     FOR UPDATE;
 
     IF v_volume_id IS NULL THEN
@@ -509,13 +813,13 @@ BEGIN
         RETURN;
     END IF;
 
-    -- Clear per-server reading progress
+    -- Clear the club's reading progress
     DELETE FROM user_reading_progress
-    WHERE server_id = p_server_id;
+    WHERE club_id = p_club_id;
 
     -- Remove the current book
     DELETE FROM server_current_book
-    WHERE server_id = p_server_id;
+    WHERE club_id = p_club_id;
 
     -- Return the removed book info
     RETURN QUERY SELECT
@@ -529,8 +833,8 @@ EXCEPTION WHEN OTHERS THEN
 END;
 $$ LANGUAGE plpgsql;
 
--- Function to get server book rankings (UPDATED to include suggested_by) + (FIXED ranking logic)
-CREATE OR REPLACE FUNCTION get_server_book_rankings(p_server_id BIGINT)
+-- Function to get club book rankings (UPDATED to include suggested_by) + (FIXED ranking logic)
+CREATE OR REPLACE FUNCTION get_club_book_rankings(p_club_id INTEGER)
 RETURNS TABLE (
     rank INTEGER,
     volume_id TEXT,
@@ -550,7 +854,7 @@ BEGIN
         scb.completed_at
     FROM server_completed_books scb
     LEFT JOIN discord_users du ON du.user_id = scb.suggested_by_user_id
-    WHERE scb.server_id = p_server_id
+    WHERE scb.club_id = p_club_id
     ORDER BY scb.average_rating DESC NULLS LAST, scb.completed_at DESC;
 END;
 $$ LANGUAGE plpgsql;
@@ -19,13 +19,13 @@ pub async fn warm_cache(pool: &PgPool, google_books: &CachedGoogleBooksClient) {
         }
     }
 
-    // Get recently queued books (top 5 from each server - reduced from 20)
+    // Get recently queued books (top 5 from each club - reduced from 20)
     if let Ok(queued_books) = sqlx::query!(
         r#"
         SELECT DISTINCT volume_id 
         FROM (
             SELECT volume_id, 
-                   ROW_NUMBER() OVER (PARTITION BY server_id ORDER BY position) as rn
+                   ROW_NUMBER() OVER (PARTITION BY club_id ORDER BY position) as rn
             FROM server_book_queue
         ) ranked
         WHERE rn <= 5
@@ -39,13 +39,13 @@ pub async fn warm_cache(pool: &PgPool, google_books: &CachedGoogleBooksClient) {
         }
     }
 
-    // Get recently completed books (last 3 per server - reduced from 10)
+    // Get recently completed books (last 3 per club - reduced from 10)
     if let Ok(completed_books) = sqlx::query!(
         r#"
         SELECT DISTINCT volume_id 
         FROM (
             SELECT volume_id,
-                   ROW_NUMBER() OVER (PARTITION BY server_id ORDER BY completed_at DESC) as rn
+                   ROW_NUMBER() OVER (PARTITION BY club_id ORDER BY completed_at DESC) as rn
             FROM server_completed_books
         ) ranked
         WHERE rn <= 3
@@ -0,0 +1,322 @@
+use crate::types::Error;
+use crate::util::{log_error, log_error_with_source};
+use chrono::{Datelike, Duration, NaiveDate};
+use image::{ImageBuffer, ImageOutputFormat, Rgb};
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use plotters::style::{FontStyle, register_font};
+use poise::serenity_prelude::CreateAttachment;
+use std::io::Cursor;
+use std::sync::Once;
+
+const WIDTH: u32 = 800;
+const HEIGHT: u32 = 400;
+const FONT: &str = "sans-serif";
+const ACCENT: RGBColor = RGBColor(0xB7, 0x6E, 0x79);
+// Pie slice colours, starting from the embed accent
+const PALETTE: [RGBColor; 6] = [
+    ACCENT,
+    RGBColor(0x6E, 0x8B, 0xB7),
+    RGBColor(0x8F, 0xB7, 0x6E),
+    RGBColor(0xE0, 0xB0, 0x5A),
+    RGBColor(0x9A, 0x7B, 0xC4),
+    RGBColor(0xA0, 0xA0, 0xA0),
+];
+/// Longest stretch `/stats` charts month by month, so the bars stay readable.
+const MAX_MONTHS: usize = 24;
+
+// Bundled so rendering doesn't depend on fonts installed on the host
+static FONT_BYTES: &[u8] = include_bytes!("../assets/fonts/DejaVuSans.ttf");
+static REGISTER_FONT: Once = Once::new();
+
+/// A rendered PNG chart, ready to attach to a reply and show as an embed image.
+pub struct Chart {
+    filename: &'static str,
+    png: Vec<u8>,
+}
+
+impl Chart {
+    /// The value for `CreateEmbed::image` once the chart is attached to the same message.
+    pub fn url(&self) -> String {
+        format!("attachment://{}", self.filename)
+    }
+
+    pub fn attachment(&self) -> CreateAttachment {
+        CreateAttachment::bytes(self.png.clone(), self.filename)
+    }
+}
+
+// Charts are decoration, so a rendering failure is logged and the embed goes out without one
+fn render(
+    filename: &'static str,
+    draw: impl FnOnce(&DrawingArea<BitMapBackend<'_>, Shift>) -> Result<(), Error>,
+) -> Option<Chart> {
+    REGISTER_FONT.call_once(|| {
+        if register_font(FONT, FontStyle::Normal, FONT_BYTES).is_err() {
+            log_error("Couldn't load the bundled chart font");
+        }
+    });
+
+    let result = (|| -> Result<Vec<u8>, Error> {
+        let mut buffer = vec![0u8; (WIDTH * HEIGHT * 3) as usize];
+        {
+            let root = BitMapBackend::with_buffer(&mut buffer, (WIDTH, HEIGHT)).into_drawing_area();
+            root.fill(&WHITE)?;
+            draw(&root)?;
+            root.present()?;
+        }
+        let image = ImageBuffer::<Rgb<u8>, _>::from_raw(WIDTH, HEIGHT, buffer)
+            .ok_or("chart buffer doesn't match its dimensions")?;
+        let mut png = Vec::new();
+        image.write_to(&mut Cursor::new(&mut png), ImageOutputFormat::Png)?;
+        Ok(png)
+    })();
+
+    match result {
+        Ok(png) => Some(Chart { filename, png }),
+        Err(err) => {
+            log_error_with_source(&format!("Couldn't render {}", filename), &err);
+            None
+        }
+    }
+}
+
+/// How many members gave each score, indexed by rating - 1.
+pub fn rating_counts(ratings: impl IntoIterator<Item = i32>) -> [u32; 5] {
+    let mut counts = [0; 5];
+    for rating in ratings {
+        if let Some(count) = usize::try_from(rating - 1)
+            .ok()
+            .and_then(|index| counts.get_mut(index))
+        {
+            *count += 1;
+        }
+    }
+    counts
+}
+
+/// Bar chart of how many members gave a book each score.
+pub fn rating_histogram(counts: [u32; 5]) -> Option<Chart> {
+    let max = counts.iter().copied().max().unwrap_or(0);
+    if max == 0 {
+        return None;
+    }
+
+    render("ratings.png", |root| {
+        let mut chart = ChartBuilder::on(root)
+            .caption("Rating Distribution", (FONT, 26))
+            .margin(16)
+            .x_label_area_size(40)
+            .y_label_area_size(50)
+            .build_cartesian_2d((1u32..5u32).into_segmented(), 0u32..max + 1)?;
+
+        chart
+            .configure_mesh()
+            .disable_x_mesh()
+            .x_desc("Rating")
+            .y_desc("Members")
+            .x_label_formatter(&|value| match value {
+                SegmentValue::CenterOf(rating) => format!("{}/5", rating),
+                _ => String::new(),
+            })
+            .label_style((FONT, 16))
+            .axis_desc_style((FONT, 18))
+            .draw()?;
+
+        chart.draw_series(
+            Histogram::vertical(&chart)
+                .style(ACCENT.filled())
+                .margin(24)
+                .data(
+                    counts
+                        .iter()
+                        .enumerate()
+                        .map(|(index, count)| (index as u32 + 1, *count)),
+                ),
+        )?;
+        Ok(())
+    })
+}
+
+/// Line chart of each finished book's average rating, by completion date. Needs at least two
+/// rated books to draw a trend.
+pub fn rating_timeline(points: &[(NaiveDate, f64)]) -> Option<Chart> {
+    let (first, last) = (points.first()?.0, points.last()?.0);
+    if points.len() < 2 {
+        return None;
+    }
+    // Books finished on the same day would otherwise give an empty date range
+    let (start, end) = if first == last {
+        (first - Duration::days(15), last + Duration::days(15))
+    } else {
+        (first, last)
+    };
+
+    render("rating-trend.png", |root| {
+        let mut chart = ChartBuilder::on(root)
+            .caption("Average Rating Over Time", (FONT, 26))
+            .margin(16)
+            .x_label_area_size(40)
+            .y_label_area_size(50)
+            .build_cartesian_2d(start..end, 1f64..5f64)?;
+
+        chart
+            .configure_mesh()
+            .x_labels(6)
+            .y_labels(5)
+            .light_line_style(WHITE)
+            .x_label_formatter(&|date| date.format("%b %Y").to_string())
+            .y_label_formatter(&|rating| format!("{:.0}", rating))
+            .y_desc("Average rating")
+            .label_style((FONT, 16))
+            .axis_desc_style((FONT, 18))
+            .draw()?;
+
+        chart.draw_series(LineSeries::new(
+            points.iter().copied(),
+            ACCENT.stroke_width(2),
+        ))?;
+        chart.draw_series(
+            points
+                .iter()
+                .map(|point| Circle::new(*point, 4, ACCENT.filled())),
+        )?;
+        Ok(())
+    })
+}
+
+/// Books completed per calendar month, from the first completion to the last with empty
+/// months included, keeping only the most recent `MAX_MONTHS`.
+pub fn monthly_counts(dates: &[NaiveDate]) -> Vec<(NaiveDate, u32)> {
+    let month_of = |date: &NaiveDate| date.with_day(1);
+    let (Some(first), Some(last)) = (
+        dates.iter().filter_map(month_of).min(),
+        dates.iter().filter_map(month_of).max(),
+    ) else {
+        return Vec::new();
+    };
+
+    let mut months = Vec::new();
+    let mut month = first;
+    while month <= last {
+        let count = dates
+            .iter()
+            .filter(|date| month_of(date) == Some(month))
+            .count() as u32;
+        months.push((month, count));
+        let Some(next) = month.checked_add_months(chrono::Months::new(1)) else {
+            break;
+        };
+        month = next;
+    }
+
+    let skip = months.len().saturating_sub(MAX_MONTHS);
+    months.split_off(skip)
+}
+
+/// Bar chart of books completed each month.
+pub fn books_per_month(months: &[(NaiveDate, u32)]) -> Option<Chart> {
+    let max = months.iter().map(|(_, count)| *count).max()?;
+    let bars = months.len() as u32;
+
+    render("books-per-month.png", |root| {
+        let mut chart = ChartBuilder::on(root)
+            .caption("Books Completed per Month", (FONT, 26))
+            .margin(16)
+            .x_label_area_size(40)
+            .y_label_area_size(50)
+            .build_cartesian_2d((0u32..bars - 1).into_segmented(), 0u32..max + 1)?;
+
+        chart
+            .configure_mesh()
+            .disable_x_mesh()
+            .x_labels(months.len().min(12))
+            .x_label_formatter(&|value| match value {
+                SegmentValue::CenterOf(index) => months
+                    .get(*index as usize)
+                    .map(|(month, _)| month.format("%b %y").to_string())
+                    .unwrap_or_default(),
+                _ => String::new(),
+            })
+            .y_desc("Books")
+            .label_style((FONT, 14))
+            .axis_desc_style((FONT, 18))
+            .draw()?;
+
+        chart.draw_series(
+            Histogram::vertical(&chart)
+                .style(ACCENT.filled())
+                .margin(4)
+                .data(
+                    months
+                        .iter()
+                        .enumerate()
+                        .map(|(index, (_, count))| (index as u32, *count)),
+                ),
+        )?;
+        Ok(())
+    })
+}
+
+/// Pie chart of the club's genres, as produced by `genre_breakdown`.
+pub fn genre_pie(slices: &[(String, usize)]) -> Option<Chart> {
+    if slices.is_empty() {
+        return None;
+    }
+    let sizes: Vec<f64> = slices.iter().map(|(_, count)| *count as f64).collect();
+    let labels: Vec<String> = slices
+        .iter()
+        .map(|(genre, count)| format!("{} ({})", genre, count))
+        .collect();
+    let colors: Vec<RGBColor> = PALETTE.iter().cycle().take(slices.len()).copied().collect();
+
+    render("genres.png", |root| {
+        let area = root.titled("Genres", (FONT, 26))?;
+        let (width, height) = area.dim_in_pixel();
+        let center = (width as i32 / 2, height as i32 / 2);
+        let radius = f64::from(height.min(width)) * 0.36;
+
+        let mut pie = Pie::new(&center, &radius, &sizes, &colors, &labels);
+        pie.start_angle(-90.0);
+        pie.label_style((FONT, 16).into_font().color(&BLACK));
+        pie.label_offset(12.0);
+        area.draw(&pie)?;
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{monthly_counts, rating_counts};
+    use chrono::NaiveDate;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn rating_counts_ignores_out_of_range_scores() {
+        assert_eq!(rating_counts([5, 4, 5, 1, 0, 6]), [1, 0, 0, 1, 2]);
+    }
+
+    #[test]
+    fn monthly_counts_fills_empty_months() {
+        let dates = [date(2025, 11, 3), date(2026, 1, 20), date(2025, 11, 28)];
+        assert_eq!(
+            monthly_counts(&dates),
+            vec![
+                (date(2025, 11, 1), 2),
+                (date(2025, 12, 1), 0),
+                (date(2026, 1, 1), 1)
+            ]
+        );
+        assert!(monthly_counts(&[]).is_empty());
+    }
+
+    #[test]
+    fn monthly_counts_keeps_recent_months() {
+        let months = monthly_counts(&[date(2020, 1, 1), date(2026, 6, 1)]);
+        assert_eq!(months.len(), 24);
+        assert_eq!(months.last(), Some(&(date(2026, 6, 1), 1)));
+    }
+}
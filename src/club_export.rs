@@ -0,0 +1,276 @@
+use chrono::{DateTime, Utc};
+use serde_json::json;
+
+/// A completed club book with everything an archive needs, already resolved from Google Books.
+#[derive(Clone, Debug)]
+pub(crate) struct ExportedBook {
+    pub volume_id: String,
+    pub title: String,
+    pub authors: Vec<String>,
+    pub isbn: Option<String>,
+    pub suggested_by: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub average_rating: Option<f64>,
+    pub total_ratings: i32,
+    /// Each member's rating, highest first; `None` when member ratings weren't requested.
+    pub member_ratings: Option<Vec<(String, i32)>>,
+}
+
+fn date(at: DateTime<Utc>) -> String {
+    at.format("%Y-%m-%d").to_string()
+}
+
+/// Quotes a CSV field when it contains a delimiter, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// One row per book. Member ratings, when included, share a column as "name: 5; name: 4".
+pub(crate) fn to_csv(books: &[ExportedBook]) -> String {
+    let with_members = books.iter().any(|book| book.member_ratings.is_some());
+    let mut header = vec![
+        "title",
+        "authors",
+        "isbn",
+        "google_books_id",
+        "suggested_by",
+        "started_at",
+        "completed_at",
+        "average_rating",
+        "total_ratings",
+    ];
+    if with_members {
+        header.push("member_ratings");
+    }
+
+    let mut csv = header.join(",");
+    csv.push('\n');
+    for book in books {
+        let mut fields = vec![
+            book.title.clone(),
+            book.authors.join(", "),
+            book.isbn.clone().unwrap_or_default(),
+            book.volume_id.clone(),
+            book.suggested_by.clone().unwrap_or_default(),
+            date(book.started_at),
+            book.completed_at.map(date).unwrap_or_default(),
+            book.average_rating
+                .map(|rating| format!("{:.2}", rating))
+                .unwrap_or_default(),
+            book.total_ratings.to_string(),
+        ];
+        if with_members {
+            fields.push(
+                book.member_ratings
+                    .iter()
+                    .flatten()
+                    .map(|(member, rating)| format!("{}: {}", member, rating))
+                    .collect::<Vec<_>>()
+                    .join("; "),
+            );
+        }
+        csv.push_str(
+            &fields
+                .iter()
+                .map(|field| csv_field(field))
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+        csv.push('\n');
+    }
+    csv
+}
+
+pub(crate) fn to_json(
+    club_name: &str,
+    exported_at: DateTime<Utc>,
+    books: &[ExportedBook],
+) -> String {
+    let books: Vec<_> = books
+        .iter()
+        .map(|book| {
+            let mut entry = json!({
+                "title": book.title,
+                "authors": book.authors,
+                "isbn": book.isbn,
+                "google_books_id": book.volume_id,
+                "suggested_by": book.suggested_by,
+                "started_at": book.started_at.to_rfc3339(),
+                "completed_at": book.completed_at.map(|at| at.to_rfc3339()),
+                "average_rating": book.average_rating,
+                "total_ratings": book.total_ratings,
+            });
+            if let Some(ratings) = &book.member_ratings {
+                entry["member_ratings"] = ratings
+                    .iter()
+                    .map(|(member, rating)| json!({ "member": member, "rating": rating }))
+                    .collect();
+            }
+            entry
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&json!({
+        "club": club_name,
+        "exported_at": exported_at.to_rfc3339(),
+        "books": books,
+    }))
+    .unwrap_or_default()
+}
+
+/// A numbered reading log in Discord-flavoured Markdown, oldest book first.
+pub(crate) fn to_markdown(
+    club_name: &str,
+    exported_at: DateTime<Utc>,
+    books: &[ExportedBook],
+) -> String {
+    let mut log = format!(
+        "# 📚 {} Reading Log\n_{} book{} • exported {}_\n",
+        club_name,
+        books.len(),
+        if books.len() == 1 { "" } else { "s" },
+        exported_at.format("%B %d, %Y")
+    );
+
+    for (index, book) in books.iter().enumerate() {
+        let authors = if book.authors.is_empty() {
+            "Unknown Author".to_string()
+        } else {
+            book.authors.join(", ")
+        };
+        log.push_str(&format!(
+            "\n### {}. *{}* by {}\n",
+            index + 1,
+            book.title,
+            authors
+        ));
+
+        let mut details = Vec::new();
+        if let Some(suggester) = &book.suggested_by {
+            details.push(format!("Suggested by {}", suggester));
+        }
+        let finished = book
+            .completed_at
+            .map(|at| at.format("%B %d, %Y").to_string())
+            .unwrap_or_else(|| "?".to_string());
+        details.push(format!(
+            "{} – {}",
+            book.started_at.format("%B %d, %Y"),
+            finished
+        ));
+        details.push(match book.average_rating {
+            Some(rating) if book.total_ratings > 0 => format!(
+                "⭐ {:.2}/5 ({} rating{})",
+                rating,
+                book.total_ratings,
+                if book.total_ratings == 1 { "" } else { "s" }
+            ),
+            _ => "No ratings".to_string(),
+        });
+        log.push_str(&details.join(" • "));
+        log.push('\n');
+
+        if let Some(ratings) = book.member_ratings.as_ref().filter(|r| !r.is_empty()) {
+            log.push_str(&format!(
+                "> {}\n",
+                ratings
+                    .iter()
+                    .map(|(member, rating)| format!("{} {}★", member, rating))
+                    .collect::<Vec<_>>()
+                    .join(" • ")
+            ));
+        }
+    }
+    log
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExportedBook, to_csv, to_json, to_markdown};
+    use chrono::{TimeZone, Utc};
+
+    fn book(member_ratings: Option<Vec<(String, i32)>>) -> ExportedBook {
+        ExportedBook {
+            volume_id: "abc123".to_string(),
+            title: "Piranesi, Illustrated \"Edition\"".to_string(),
+            authors: vec!["Susanna Clarke".to_string()],
+            isbn: Some("9781635575637".to_string()),
+            suggested_by: Some("mira".to_string()),
+            started_at: Utc.with_ymd_and_hms(2025, 1, 3, 12, 0, 0).unwrap(),
+            completed_at: Some(Utc.with_ymd_and_hms(2025, 2, 10, 12, 0, 0).unwrap()),
+            average_rating: Some(4.5),
+            total_ratings: 2,
+            member_ratings,
+        }
+    }
+
+    #[test]
+    fn csv_quotes_fields_and_adds_member_column_on_request() {
+        let plain = to_csv(&[book(None)]);
+        assert_eq!(
+            plain,
+            "title,authors,isbn,google_books_id,suggested_by,started_at,completed_at,average_rating,total_ratings\n\
+             \"Piranesi, Illustrated \"\"Edition\"\"\",Susanna Clarke,9781635575637,abc123,mira,2025-01-03,2025-02-10,4.50,2\n"
+        );
+
+        let with_members = to_csv(&[book(Some(vec![
+            ("mira".to_string(), 5),
+            ("jo".to_string(), 4),
+        ]))]);
+        assert!(
+            with_members
+                .lines()
+                .next()
+                .unwrap()
+                .ends_with(",member_ratings")
+        );
+        assert!(
+            with_members
+                .lines()
+                .nth(1)
+                .unwrap()
+                .ends_with(",2,mira: 5; jo: 4")
+        );
+    }
+
+    #[test]
+    fn json_includes_member_ratings_only_when_requested() {
+        let exported_at = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let plain: serde_json::Value =
+            serde_json::from_str(&to_json("Main", exported_at, &[book(None)])).unwrap();
+        assert_eq!(plain["club"], "Main");
+        assert_eq!(plain["books"][0]["isbn"], "9781635575637");
+        assert!(plain["books"][0].get("member_ratings").is_none());
+
+        let with_members: serde_json::Value = serde_json::from_str(&to_json(
+            "Main",
+            exported_at,
+            &[book(Some(vec![("jo".to_string(), 4)]))],
+        ))
+        .unwrap();
+        assert_eq!(with_members["books"][0]["member_ratings"][0]["rating"], 4);
+    }
+
+    #[test]
+    fn markdown_log_numbers_books_and_lists_member_ratings() {
+        let exported_at = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let log = to_markdown(
+            "Cozy Club",
+            exported_at,
+            &[book(Some(vec![("mira".to_string(), 5)]))],
+        );
+        assert!(
+            log.starts_with("# 📚 Cozy Club Reading Log\n_1 book • exported January 01, 2026_\n")
+        );
+        assert!(log.contains("### 1. *Piranesi, Illustrated \"Edition\"* by Susanna Clarke\n"));
+        assert!(log.contains(
+            "Suggested by mira • January 03, 2025 – February 10, 2025 • ⭐ 4.50/5 (2 ratings)\n"
+        ));
+        assert!(log.ends_with("> mira 5★\n"));
+    }
+}
@@ -0,0 +1,103 @@
+use crate::commands::server_user::queue::queue_volume;
+use crate::commands::user::favorite::add_volume_to_favorites;
+use crate::commands::user::numberone::set_number_one_volume;
+use crate::commands::user::readinglist::{DEFAULT_SHELF, add_volume_to_reading_list};
+use crate::google_books::Volume;
+use crate::types::{Context, Error};
+use crate::util::resolve_club;
+use poise::serenity_prelude::{ButtonStyle, CreateActionRow, CreateButton};
+
+/// Shortcuts offered under a book in `/info` and `/explore`, so members don't have to retype
+/// it into another command. Each runs the same checks as its slash command.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum BookAction {
+    Queue,
+    ReadingList,
+    Favorite,
+    NumberOne,
+}
+
+const BOOK_ACTIONS: [BookAction; 4] = [
+    BookAction::Queue,
+    BookAction::ReadingList,
+    BookAction::Favorite,
+    BookAction::NumberOne,
+];
+
+impl BookAction {
+    fn custom_id(self) -> &'static str {
+        match self {
+            BookAction::Queue => "book_action_queue",
+            BookAction::ReadingList => "book_action_reading_list",
+            BookAction::Favorite => "book_action_favorite",
+            BookAction::NumberOne => "book_action_number_one",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            BookAction::Queue => "📥 Add to queue",
+            BookAction::ReadingList => "📚 Add to reading list",
+            BookAction::Favorite => "⭐ Favorite",
+            BookAction::NumberOne => "🥇 Set #1",
+        }
+    }
+
+    pub(crate) fn from_custom_id(custom_id: &str) -> Option<Self> {
+        BOOK_ACTIONS
+            .into_iter()
+            .find(|action| action.custom_id() == custom_id)
+    }
+}
+
+/// One button per action. `disabled` greys them all out, e.g. until a result is picked or
+/// once the message stops listening.
+pub(crate) fn book_action_row(disabled: bool) -> CreateActionRow {
+    CreateActionRow::Buttons(
+        BOOK_ACTIONS
+            .into_iter()
+            .map(|action| {
+                CreateButton::new(action.custom_id())
+                    .label(action.label())
+                    .style(ButtonStyle::Primary)
+                    .disabled(disabled)
+            })
+            .collect(),
+    )
+}
+
+/// Runs an action for the invoking member. The outcome is posted as a follow-up to the
+/// original command, exactly as the matching slash command would reply. Queue additions go
+/// to the server's main club.
+pub(crate) async fn run_book_action(
+    ctx: Context<'_>,
+    action: BookAction,
+    book: &Volume,
+) -> Result<(), Error> {
+    match action {
+        BookAction::Queue => {
+            let Some(club) = resolve_club(&ctx, None).await? else {
+                return Ok(());
+            };
+            queue_volume(ctx, &club, book, None, false).await
+        }
+        BookAction::ReadingList => {
+            add_volume_to_reading_list(ctx, book, DEFAULT_SHELF, &[], false).await
+        }
+        BookAction::Favorite => add_volume_to_favorites(ctx, book, false).await,
+        BookAction::NumberOne => set_number_one_volume(ctx, &book.id, Some(book.get_title())).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BOOK_ACTIONS, BookAction};
+
+    #[test]
+    fn custom_ids_round_trip() {
+        for action in BOOK_ACTIONS {
+            assert_eq!(BookAction::from_custom_id(action.custom_id()), Some(action));
+        }
+        assert_eq!(BookAction::from_custom_id("next"), None);
+    }
+}
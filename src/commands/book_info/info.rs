@@ -1,12 +1,16 @@
+use crate::commands::book_actions::{BookAction, book_action_row, run_book_action};
 use crate::google_books::Volume;
 use crate::maturity_check::{
     check_volume_maturity, create_mature_content_warning, current_channel_is_nsfw,
     server_maturity_enabled,
 };
 use crate::types::{Context, Error, QueryMode};
-use crate::util::{detect_query_mode, normalize_isbn, truncate_on_char_boundary};
+use crate::util::{
+    detect_query_mode, log_error_with_source, normalize_isbn, truncate_on_char_boundary,
+};
 use poise::serenity_prelude as serenity;
 use poise::serenity_prelude::CreateEmbed;
+use std::time::Duration;
 
 fn build_book_embed(
     book: &Volume,
@@ -117,6 +121,54 @@ fn build_book_embed(
     embed
 }
 
+/// Sends the book card with the add-to-list buttons and handles presses from the invoking
+/// member for two minutes. Outside servers the card is sent on its own.
+async fn send_with_book_actions(
+    ctx: Context<'_>,
+    book: &Volume,
+    embed: CreateEmbed,
+) -> Result<(), Error> {
+    if ctx.guild_id().is_none() {
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let mut msg = ctx
+        .send(
+            poise::CreateReply::default()
+                .embed(embed)
+                .components(vec![book_action_row(false)]),
+        )
+        .await?
+        .into_message()
+        .await?;
+
+    while let Some(mci) = msg
+        .await_component_interaction(ctx.serenity_context())
+        .author_id(ctx.author().id)
+        .timeout(Duration::from_secs(120))
+        .await
+    {
+        let Some(action) = BookAction::from_custom_id(&mci.data.custom_id) else {
+            continue;
+        };
+        mci.defer(ctx.http()).await.ok();
+        // Keep listening so the buttons are still disabled when the collector ends
+        if let Err(err) = run_book_action(ctx, action, book).await {
+            log_error_with_source("Book action failed", &err);
+        }
+    }
+
+    msg.edit(
+        ctx.serenity_context(),
+        serenity::EditMessage::default().components(vec![book_action_row(true)]),
+    )
+    .await
+    .ok();
+
+    Ok(())
+}
+
 #[poise::command(
     slash_command,
     description_localized("en-US", "Get book info by title or ISBN"),
@@ -169,24 +221,24 @@ pub async fn book(
                                     WHEN EXISTS (
                                         SELECT 1
                                         FROM server_completed_books scb
-                                        WHERE scb.volume_id = v.volume_id AND scb.server_id = $2
+                                        WHERE scb.volume_id = v.volume_id AND scb.club_id IN (SELECT club_id FROM server_clubs WHERE server_id = $2)
                                     ) THEN 'Completed'
                                     WHEN EXISTS (
                                         SELECT 1
                                         FROM server_current_book sc
-                                        WHERE sc.volume_id = v.volume_id AND sc.server_id = $2
+                                        WHERE sc.volume_id = v.volume_id AND sc.club_id IN (SELECT club_id FROM server_clubs WHERE server_id = $2)
                                     ) THEN 'Currently Reading'
                                     WHEN EXISTS (
                                         SELECT 1
                                         FROM server_book_queue sq
-                                        WHERE sq.volume_id = v.volume_id AND sq.server_id = $2
+                                        WHERE sq.volume_id = v.volume_id AND sq.club_id IN (SELECT club_id FROM server_clubs WHERE server_id = $2)
                                     ) THEN 'In Queue'
                                     ELSE 'Not Read'
                                 END as status,
                                 (
                                     SELECT scb.average_rating::DOUBLE PRECISION
                                     FROM server_completed_books scb
-                                    WHERE scb.volume_id = v.volume_id AND scb.server_id = $2
+                                    WHERE scb.volume_id = v.volume_id AND scb.club_id IN (SELECT club_id FROM server_clubs WHERE server_id = $2)
                                     ORDER BY scb.completed_at DESC
                                     LIMIT 1
                                 ) as "average_rating?"
@@ -226,7 +278,7 @@ pub async fn book(
                         favorites_count,
                         Some(QueryMode::Isbn),
                     );
-                    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+                    send_with_book_actions(ctx, &book, embed).await?;
                 }
                 None => {
                     ctx.say(format!("No book found with ISBN: {}", title_or_isbn))
@@ -266,24 +318,24 @@ pub async fn book(
                             WHEN EXISTS (
                                 SELECT 1
                                 FROM server_completed_books scb
-                                WHERE scb.volume_id = v.volume_id AND scb.server_id = $2
+                                WHERE scb.volume_id = v.volume_id AND scb.club_id IN (SELECT club_id FROM server_clubs WHERE server_id = $2)
                             ) THEN 'Completed'
                             WHEN EXISTS (
                                 SELECT 1
                                 FROM server_current_book sc
-                                WHERE sc.volume_id = v.volume_id AND sc.server_id = $2
+                                WHERE sc.volume_id = v.volume_id AND sc.club_id IN (SELECT club_id FROM server_clubs WHERE server_id = $2)
                             ) THEN 'Currently Reading'
                             WHEN EXISTS (
                                 SELECT 1
                                 FROM server_book_queue sq
-                                WHERE sq.volume_id = v.volume_id AND sq.server_id = $2
+                                WHERE sq.volume_id = v.volume_id AND sq.club_id IN (SELECT club_id FROM server_clubs WHERE server_id = $2)
                             ) THEN 'In Queue'
                             ELSE 'Not Read'
                         END as status,
                         (
                             SELECT scb.average_rating::DOUBLE PRECISION
                             FROM server_completed_books scb
-                            WHERE scb.volume_id = v.volume_id AND scb.server_id = $2
+                            WHERE scb.volume_id = v.volume_id AND scb.club_id IN (SELECT club_id FROM server_clubs WHERE server_id = $2)
                             ORDER BY scb.completed_at DESC
                             LIMIT 1
                         ) as "average_rating?"
@@ -330,7 +382,7 @@ pub async fn book(
                 ));
             }
 
-            ctx.send(poise::CreateReply::default().embed(embed)).await?;
+            send_with_book_actions(ctx, book, embed).await?;
         }
     }
 
@@ -1,10 +1,14 @@
+use crate::commands::book_actions::{BookAction, book_action_row, run_book_action};
 use crate::types::{Context, Data, Error};
+use crate::util::log_error_with_source;
 use poise::serenity_prelude as serenity;
 use poise::serenity_prelude::{CreateActionRow, CreateButton, CreateEmbed};
 use std::time::Duration;
 
 type CommandVec = Vec<poise::Command<Data, Error>>;
 
+const PICK_PREFIX: &str = "explore_pick:";
+
 pub fn explore_commands() -> CommandVec {
     vec![explore()]
 }
@@ -158,44 +162,70 @@ pub async fn explore(
         e
     };
 
-    let make_components = |page: usize| {
+    // Outside servers there is no queue or list to add to, so only pagination is offered
+    let show_actions = ctx.guild_id().is_some();
+    let mut selected: Option<usize> = None;
+
+    let make_components = |page: usize, selected: Option<usize>, disabled: bool| {
         let at_start = page == 0;
         let at_end = page + 1 >= total_pages;
+        let mut rows = Vec::new();
+
+        if total_pages > 1 {
+            rows.push(CreateActionRow::Buttons(vec![
+                CreateButton::new("first")
+                    .label("⮎ First")
+                    .style(serenity::ButtonStyle::Secondary)
+                    .disabled(disabled || at_start),
+                CreateButton::new("prev")
+                    .label("◀ Prev")
+                    .style(serenity::ButtonStyle::Secondary)
+                    .disabled(disabled || at_start),
+                CreateButton::new("page")
+                    .label(format!("Page {}/{}", page + 1, total_pages))
+                    .disabled(true),
+                CreateButton::new("next")
+                    .label("Next ▶")
+                    .style(serenity::ButtonStyle::Secondary)
+                    .disabled(disabled || at_end),
+                CreateButton::new("last")
+                    .label("Last ⮏")
+                    .style(serenity::ButtonStyle::Secondary)
+                    .disabled(disabled || at_end),
+            ]));
+        }
 
-        vec![CreateActionRow::Buttons(vec![
-            CreateButton::new("first")
-                .label("⮎ First")
-                .style(serenity::ButtonStyle::Secondary)
-                .disabled(at_start),
-            CreateButton::new("prev")
-                .label("◀ Prev")
-                .style(serenity::ButtonStyle::Secondary)
-                .disabled(at_start),
-            CreateButton::new("page")
-                .label(format!("Page {}/{}", page + 1, total_pages))
-                .disabled(true),
-            CreateButton::new("next")
-                .label("Next ▶")
-                .style(serenity::ButtonStyle::Secondary)
-                .disabled(at_end),
-            CreateButton::new("last")
-                .label("Last ⮏")
-                .style(serenity::ButtonStyle::Secondary)
-                .disabled(at_end),
-        ])]
+        if show_actions {
+            // Number buttons pick which result on the page the actions apply to
+            let start = page * page_size;
+            let end = (start + page_size).min(total);
+            rows.push(CreateActionRow::Buttons(
+                (start..end)
+                    .map(|index| {
+                        CreateButton::new(format!("{}{}", PICK_PREFIX, index))
+                            .label(format!("{}", index + 1))
+                            .style(if selected == Some(index) {
+                                serenity::ButtonStyle::Success
+                            } else {
+                                serenity::ButtonStyle::Secondary
+                            })
+                            .disabled(disabled)
+                    })
+                    .collect(),
+            ));
+            rows.push(book_action_row(disabled || selected.is_none()));
+        }
+
+        rows
     };
 
     let reply = poise::CreateReply::default()
         .embed(make_embed(page))
-        .components(if total_pages > 1 {
-            make_components(page)
-        } else {
-            vec![]
-        });
+        .components(make_components(page, selected, false));
 
     let mut msg = ctx.send(reply).await?.into_message().await?;
 
-    if total_pages == 1 {
+    if total_pages == 1 && !show_actions {
         return Ok(());
     }
 
@@ -207,7 +237,20 @@ pub async fn explore(
 
         match collector.next().await {
             Some(mci) => {
-                match mci.data.custom_id.as_str() {
+                let custom_id = mci.data.custom_id.as_str();
+
+                if let Some(action) = BookAction::from_custom_id(custom_id) {
+                    mci.defer(ctx.http()).await.ok();
+                    if let Some(book) = selected.and_then(|index| books.get(index))
+                        && let Err(err) = run_book_action(ctx, action, book).await
+                    {
+                        // Keep listening so the buttons are still disabled when the collector ends
+                        log_error_with_source("Book action failed", &err);
+                    }
+                    continue;
+                }
+
+                match custom_id {
                     "first" => page = 0,
                     "prev" => {
                         if page > 0 {
@@ -220,7 +263,19 @@ pub async fn explore(
                         }
                     }
                     "last" => page = total_pages.saturating_sub(1),
-                    _ => {}
+                    _ => {
+                        if let Some(index) = custom_id
+                            .strip_prefix(PICK_PREFIX)
+                            .and_then(|index| index.parse::<usize>().ok())
+                        {
+                            selected = Some(index);
+                        }
+                    }
+                }
+
+                // A pick only applies to the page it was made on
+                if selected.is_some_and(|index| index / page_size != page) {
+                    selected = None;
                 }
 
                 mci.create_response(
@@ -228,7 +283,7 @@ pub async fn explore(
                     serenity::CreateInteractionResponse::UpdateMessage(
                         serenity::CreateInteractionResponseMessage::default()
                             .embed(make_embed(page))
-                            .components(make_components(page)),
+                            .components(make_components(page, selected, false)),
                     ),
                 )
                 .await
@@ -239,19 +294,7 @@ pub async fn explore(
                     ctx.serenity_context(),
                     serenity::EditMessage::default()
                         .embed(make_embed(page))
-                        .components(
-                            make_components(page)
-                                .into_iter()
-                                .map(|mut row| {
-                                    if let CreateActionRow::Buttons(ref mut buttons) = row {
-                                        for button in buttons {
-                                            *button = button.clone().disabled(true);
-                                        }
-                                    }
-                                    row
-                                })
-                                .collect(),
-                        ),
+                        .components(make_components(page, selected, true)),
                 )
                 .await
                 .ok();
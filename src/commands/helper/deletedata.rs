@@ -29,7 +29,7 @@ async fn myself(ctx: Context<'_>) -> Result<(), Error> {
             "This will permanently delete ALL your data including:\n\
             • Favorite books and authors\n\
             • Reading list\n\
-            • All ratings and progress\n\n\
+            • All ratings (including imported ones) and progress\n\n\
             **This action cannot be undone!**",
         )
         .color(0xFF0000);
@@ -1,3 +1,4 @@
+pub mod book_actions;
 pub mod book_info;
 pub mod explore;
 pub mod helper;
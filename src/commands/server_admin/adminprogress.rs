@@ -1,4 +1,4 @@
-use crate::util::get_guild_name;
+use crate::util::{autocomplete_club, get_guild_name, resolve_club};
 use crate::*;
 use crate::{types::Context, types::Error};
 use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter, User};
@@ -28,6 +28,9 @@ pub async fn adminprogress(_ctx: Context<'_>) -> Result<(), Error> {
 async fn remove(
     ctx: Context<'_>,
     #[description = "User to clear progress for"] user: User,
+    #[description = "Club to clear progress in (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
 ) -> Result<(), Error> {
     ctx.defer().await?;
 
@@ -41,6 +44,9 @@ async fn remove(
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
     };
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
 
     let guild_name = get_guild_name(&ctx).await;
 
@@ -53,9 +59,9 @@ async fn remove(
         SELECT
             volume_id
         FROM server_current_book
-        WHERE server_id = $1
+        WHERE club_id = $1
         "#,
-        guild_id.get() as i64
+        club.club_id
     )
     .fetch_optional(pool)
     .await?;
@@ -64,7 +70,7 @@ async fn remove(
         let embed = CreateEmbed::default()
             .title("No Current Book")
             .description(
-                "There's no current book being read in this server, so there isn't any progress to remove.",
+                "There's no current book being read in this club, so there isn't any progress to remove.",
             )
             .color(0xB76E79)
             .footer(CreateEmbedFooter::new("Powered by Google Books API"));
@@ -79,9 +85,9 @@ async fn remove(
     };
 
     let removed = sqlx::query!(
-        "DELETE FROM user_reading_progress WHERE user_id = $1 AND server_id = $2 AND volume_id = $3 RETURNING progress_text",
+        "DELETE FROM user_reading_progress WHERE user_id = $1 AND club_id = $2 AND volume_id = $3 RETURNING progress_text",
         user.id.get() as i64,
-        guild_id.get() as i64,
+        club.club_id,
         volume_id
     )
     .fetch_optional(pool)
@@ -145,12 +151,16 @@ async fn ban(
     ensure_user_exists(pool, &user).await?;
     ensure_server_exists(pool, guild_id, &guild_name).await?;
 
+    // Bans cover the whole server, so progress is cleared in every club
     let cleared_progress = sqlx::query!(
-        "DELETE FROM user_reading_progress WHERE user_id = $1 AND server_id = $2 RETURNING volume_id",
+        "DELETE FROM user_reading_progress
+         WHERE user_id = $1
+           AND club_id IN (SELECT club_id FROM server_clubs WHERE server_id = $2)
+         RETURNING volume_id",
         user.id.get() as i64,
         guild_id.get() as i64
     )
-    .fetch_optional(pool)
+    .fetch_all(pool)
     .await?;
 
     sqlx::query!(
@@ -167,18 +177,20 @@ async fn ban(
         user.name
     );
 
-    if let Some(record) = cleared_progress {
-        let volume_id = record.volume_id;
-        let book_title = match google_books.get_volume(&volume_id).await {
-            Ok(volume) => volume.get_title(),
-            Err(_) => format!("Book ({})", volume_id),
-        };
+    if cleared_progress.is_empty() {
+        description.push_str(" They didn't have any saved progress to clear.");
+    } else {
+        let mut book_titles = Vec::with_capacity(cleared_progress.len());
+        for record in cleared_progress {
+            book_titles.push(match google_books.get_volume(&record.volume_id).await {
+                Ok(volume) => format!("'{}'", volume.get_title()),
+                Err(_) => format!("'Book ({})'", record.volume_id),
+            });
+        }
         description.push_str(&format!(
-            " Their saved progress for '{}' was cleared.",
-            book_title
+            " Their saved progress for {} was cleared.",
+            book_titles.join(", ")
         ));
-    } else {
-        description.push_str(" They didn't have any saved progress to clear.");
     }
 
     let embed = CreateEmbed::default()
@@ -1,9 +1,15 @@
+use crate::commands::server_user::queue::{build_queue_embed, validate_pitch_text};
+use crate::database_helpers::{move_queue_book_transactional, swap_queue_books_transactional};
+use crate::list_notifications::{ListEvent, ListNotice, notify_list_watchers};
 use crate::maturity_check::{
-    check_volume_maturity, create_mature_content_warning, current_channel_is_nsfw,
-    server_maturity_enabled,
+    can_display_mature_content, check_volume_maturity, create_mature_content_warning,
+    current_channel_is_nsfw, server_maturity_enabled,
 };
 use crate::types::QueryMode;
-use crate::util::{detect_query_mode, get_guild_name, normalize_isbn};
+use crate::util::{
+    Club, autocomplete_club, club_announcement_channel, detect_query_mode, get_guild_name,
+    normalize_isbn, resolve_club,
+};
 use crate::*;
 use crate::{types::Context, types::Error};
 use poise::futures_util::StreamExt;
@@ -23,7 +29,7 @@ enum QueueInsertion {
 
 #[poise::command(
     slash_command,
-    subcommands("add", "remove"),
+    subcommands("add", "remove", "move_book", "swap", "pin", "shuffle", "clear"),
     guild_only,
     required_permissions = "MANAGE_MESSAGES",
     description_localized("en-US", "Admin queue management (requires Manage Messages)"),
@@ -47,6 +53,12 @@ async fn add(
     #[description = "Place the book at the front or back of the queue"] placement: Option<
         QueueInsertion,
     >,
+    #[description = "Why the club should read it (optional, max 300 characters)"] pitch: Option<
+        String,
+    >,
+    #[description = "Club whose queue to change (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
 ) -> Result<(), Error> {
     ctx.defer().await?;
 
@@ -58,11 +70,33 @@ async fn add(
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
     };
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
     let guild_name = get_guild_name(&ctx).await;
 
     let pool = &ctx.data().database;
     let google_books = &ctx.data().google_books;
 
+    let pitch = match pitch.as_deref() {
+        Some(text) => {
+            let allow_unrestricted_sexual = can_display_mature_content(&ctx, pool).await?;
+            match validate_pitch_text(text, allow_unrestricted_sexual) {
+                Ok(sanitized) => Some(sanitized),
+                Err(error_msg) => {
+                    let embed = CreateEmbed::default()
+                        .title("❌ Invalid Pitch")
+                        .description(error_msg)
+                        .color(0xB76E79)
+                        .footer(CreateEmbedFooter::new("Powered by Google Books API"));
+                    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+                    return Ok(());
+                }
+            }
+        }
+        None => None,
+    };
+
     // Use the specified user or default to the command invoker
     let suggesting_user = suggested_by.as_ref().unwrap_or_else(|| ctx.author());
 
@@ -146,8 +180,8 @@ async fn add(
     let already_queued = sqlx::query!(
         "SELECT du.username FROM server_book_queue sbq
          JOIN discord_users du ON du.user_id = sbq.suggested_by_user_id
-         WHERE sbq.server_id = $1 AND sbq.volume_id = $2",
-        guild_id.get() as i64,
+         WHERE sbq.club_id = $1 AND sbq.volume_id = $2",
+        club.club_id,
         volume_id
     )
     .fetch_optional(pool)
@@ -172,16 +206,17 @@ async fn add(
     let new_position = match placement {
         QueueInsertion::Back => {
             let record = sqlx::query!(
-                "INSERT INTO server_book_queue (server_id, volume_id, suggested_by_user_id, position)
+                "INSERT INTO server_book_queue (club_id, volume_id, suggested_by_user_id, position, pitch)
                 VALUES ($1, $2, $3, (
                     SELECT COALESCE(MAX(position), 0) + 1
                     FROM server_book_queue
-                    WHERE server_id = $1
-                ))
+                    WHERE club_id = $1
+                ), $4)
                 RETURNING position",
-                guild_id.get() as i64,
+                club.club_id,
                 volume_id,
-                suggesting_user.id.get() as i64
+                suggesting_user.id.get() as i64,
+                pitch
             )
             .fetch_one(pool)
             .await?;
@@ -192,19 +227,20 @@ async fn add(
             let mut tx = pool.begin().await?;
 
             sqlx::query!(
-                "UPDATE server_book_queue SET position = position + 1 WHERE server_id = $1",
-                guild_id.get() as i64
+                "UPDATE server_book_queue SET position = position + 1 WHERE club_id = $1",
+                club.club_id
             )
             .execute(&mut *tx)
             .await?;
 
             let inserted = sqlx::query!(
-                "INSERT INTO server_book_queue (server_id, volume_id, suggested_by_user_id, position)
-                VALUES ($1, $2, $3, 1)
+                "INSERT INTO server_book_queue (club_id, volume_id, suggested_by_user_id, position, pitch)
+                VALUES ($1, $2, $3, 1, $4)
                 RETURNING position",
-                guild_id.get() as i64,
+                club.club_id,
                 volume_id,
-                suggesting_user.id.get() as i64
+                suggesting_user.id.get() as i64,
+                pitch
             )
             .fetch_one(&mut *tx)
             .await?;
@@ -223,6 +259,10 @@ async fn add(
         .field("Position", format!("#{}", new_position), true)
         .color(0xB76E79);
 
+    if let Some(pitch) = pitch.as_ref() {
+        embed = embed.field("Pitch", pitch, false);
+    }
+
     if let Some(thumbnail_url) = book.get_thumbnail_url() {
         embed = embed.thumbnail(thumbnail_url);
     }
@@ -236,6 +276,24 @@ async fn add(
     embed = embed.footer(CreateEmbedFooter::new(footer_text));
 
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    let channel_id = club_announcement_channel(pool, club.club_id)
+        .await?
+        .unwrap_or(ctx.channel_id());
+    notify_list_watchers(
+        ctx.http(),
+        pool,
+        &ctx.data().google_books,
+        ListNotice {
+            club: &club,
+            volume_ids: &[volume_id.to_string()],
+            event: ListEvent::Queued,
+            skip_user: Some(suggesting_user.id.get() as i64),
+            channel_id,
+        },
+    )
+    .await;
+
     Ok(())
 }
 
@@ -260,18 +318,24 @@ async fn book(
     ctx: Context<'_>,
     #[description = "Title or ISBN-10/13"] title_or_isbn: String,
     #[description = "Author name (optional; used when searching by title)"] author: Option<String>,
+    #[description = "Club whose queue to change (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
 ) -> Result<(), Error> {
     ctx.defer().await?;
 
     let pool = &ctx.data().database;
     let google_books = &ctx.data().google_books;
-    let Some(guild_id) = ctx.guild_id() else {
+    if ctx.guild_id().is_none() {
         let embed = CreateEmbed::default()
             .title("❌ Error")
             .description("This command must be used in a server.")
             .color(0xB76E79);
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
     };
 
     // Search for the book
@@ -326,7 +390,7 @@ async fn book(
 
             let query_str = format!(
                 "SELECT volume_id FROM server_book_queue 
-                 WHERE server_id = $1 AND volume_id IN ($2{})",
+                 WHERE club_id = $1 AND volume_id IN ($2{})",
                 if !placeholders.is_empty() {
                     format!(", {}", placeholders.join(", "))
                 } else {
@@ -334,7 +398,7 @@ async fn book(
                 }
             );
 
-            let mut query = sqlx::query(&query_str).bind(guild_id.get() as i64);
+            let mut query = sqlx::query(&query_str).bind(club.club_id);
             for vid in &volume_ids {
                 query = query.bind(vid);
             }
@@ -375,9 +439,9 @@ async fn book(
             du.username as suggested_by
         FROM server_book_queue sbq
         JOIN discord_users du ON du.user_id = sbq.suggested_by_user_id
-        WHERE sbq.server_id = $1 AND sbq.volume_id = $2
+        WHERE sbq.club_id = $1 AND sbq.volume_id = $2
         "#,
-        guild_id.get() as i64,
+        club.club_id,
         volume_id
     )
     .fetch_optional(pool)
@@ -425,8 +489,8 @@ async fn book(
                     // Remove from queue
                     sqlx::query!(
                         "DELETE FROM server_book_queue 
-                         WHERE server_id = $1 AND volume_id = $2",
-                        guild_id.get() as i64,
+                         WHERE club_id = $1 AND volume_id = $2",
+                        club.club_id,
                         volume_id
                     )
                     .execute(pool)
@@ -511,18 +575,24 @@ async fn book(
 async fn user(
     ctx: Context<'_>,
     #[description = "User whose books to remove"] user: User,
+    #[description = "Club whose queue to change (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
 ) -> Result<(), Error> {
     ctx.defer().await?;
 
     let pool = &ctx.data().database;
     let google_books = &ctx.data().google_books;
-    let Some(guild_id) = ctx.guild_id() else {
+    if ctx.guild_id().is_none() {
         let embed = CreateEmbed::default()
             .title("❌ Error")
             .description("This command must be used in a server.")
             .color(0xB76E79);
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
     };
 
     // Get ALL books in the queue suggested by this user (for this server)
@@ -530,10 +600,10 @@ async fn user(
         r#"
         SELECT sbq.volume_id, sbq.position
         FROM server_book_queue sbq
-        WHERE sbq.server_id = $1 AND sbq.suggested_by_user_id = $2
+        WHERE sbq.club_id = $1 AND sbq.suggested_by_user_id = $2
         ORDER BY sbq.position
         "#,
-        guild_id.get() as i64,
+        club.club_id,
         user.id.get() as i64
     )
     .fetch_all(pool)
@@ -624,8 +694,8 @@ async fn user(
                 // Delete ALL books suggested by that user for this server
                 sqlx::query!(
                     "DELETE FROM server_book_queue 
-                     WHERE server_id = $1 AND suggested_by_user_id = $2",
-                    guild_id.get() as i64,
+                     WHERE club_id = $1 AND suggested_by_user_id = $2",
+                    club.club_id,
                     user.id.get() as i64
                 )
                 .execute(pool)
@@ -670,3 +740,387 @@ async fn user(
 
     Ok(())
 }
+
+/// Sends the outcome of a queue edit followed by the refreshed queue.
+async fn send_with_queue(ctx: Context<'_>, club: &Club, result: CreateEmbed) -> Result<(), Error> {
+    let queue_embed = build_queue_embed(&ctx, club).await?;
+    ctx.send(
+        poise::CreateReply::default()
+            .embed(result)
+            .embed(queue_embed),
+    )
+    .await?;
+    Ok(())
+}
+
+async fn queue_title_at(ctx: Context<'_>, club_id: i32, position: i32) -> Option<String> {
+    let volume_id = sqlx::query_scalar!(
+        "SELECT volume_id FROM server_book_queue WHERE club_id = $1 AND position = $2",
+        club_id,
+        position
+    )
+    .fetch_optional(&ctx.data().database)
+    .await
+    .ok()??;
+
+    Some(match ctx.data().google_books.get_volume(&volume_id).await {
+        Ok(volume) => volume.get_title(),
+        Err(_) => format!("Book ({})", volume_id),
+    })
+}
+
+#[poise::command(
+    slash_command,
+    rename = "move",
+    description_localized("en-US", "Move a queued book to a new position"),
+    user_cooldown = 10
+)]
+async fn move_book(
+    ctx: Context<'_>,
+    #[description = "Current position of the book"]
+    #[min = 1]
+    from: i32,
+    #[description = "Position to move it to"]
+    #[min = 1]
+    to: i32,
+    #[description = "Club whose queue to change (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    if ctx.guild_id().is_none() {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+    let book_title = queue_title_at(ctx, club.club_id, from).await;
+
+    let result = match move_queue_book_transactional(pool, club.club_id, from, to).await {
+        Ok(()) => CreateEmbed::default()
+            .title("✅ Book Moved")
+            .description(format!(
+                "Moved **{}** from #{} toward #{}.",
+                book_title.unwrap_or_else(|| "the book".to_string()),
+                from,
+                to
+            ))
+            .color(0xB76E79),
+        Err(err) => {
+            let embed = CreateEmbed::default()
+                .title("❌ Couldn't Move Book")
+                .description(err.to_string())
+                .color(0xB76E79);
+            ctx.send(poise::CreateReply::default().embed(embed)).await?;
+            return Ok(());
+        }
+    };
+
+    send_with_queue(ctx, &club, result).await
+}
+
+#[poise::command(
+    slash_command,
+    description_localized("en-US", "Swap the positions of two queued books"),
+    user_cooldown = 10
+)]
+async fn swap(
+    ctx: Context<'_>,
+    #[description = "Position of the first book"]
+    #[min = 1]
+    first: i32,
+    #[description = "Position of the second book"]
+    #[min = 1]
+    second: i32,
+    #[description = "Club whose queue to change (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    if ctx.guild_id().is_none() {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+
+    let result = match swap_queue_books_transactional(pool, club.club_id, first, second).await {
+        Ok(()) => CreateEmbed::default()
+            .title("✅ Books Swapped")
+            .description(format!("Swapped the books at #{} and #{}.", first, second))
+            .color(0xB76E79),
+        Err(err) => {
+            let embed = CreateEmbed::default()
+                .title("❌ Couldn't Swap Books")
+                .description(err.to_string())
+                .color(0xB76E79);
+            ctx.send(poise::CreateReply::default().embed(embed)).await?;
+            return Ok(());
+        }
+    };
+
+    send_with_queue(ctx, &club, result).await
+}
+
+#[poise::command(
+    slash_command,
+    description_localized(
+        "en-US",
+        "Pin or unpin a queued book so it's always included in selection polls"
+    ),
+    user_cooldown = 10
+)]
+async fn pin(
+    ctx: Context<'_>,
+    #[description = "Position of the book to pin or unpin"]
+    #[min = 1]
+    position: i32,
+    #[description = "Club whose queue to change (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    if ctx.guild_id().is_none() {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+    let book_title = queue_title_at(ctx, club.club_id, position).await;
+
+    let toggled = sqlx::query_scalar!(
+        "UPDATE server_book_queue SET pinned = NOT pinned
+         WHERE club_id = $1 AND position = $2
+         RETURNING pinned",
+        club.club_id,
+        position
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let Some(pinned) = toggled else {
+        let embed = CreateEmbed::default()
+            .title("❌ Not in Queue")
+            .description(format!("There's no book at position #{}.", position))
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+
+    let book_title = book_title.unwrap_or_else(|| "The book".to_string());
+    let result = if pinned {
+        CreateEmbed::default()
+            .title("📌 Book Pinned")
+            .description(format!(
+                "**{}** will be included in every selection poll until it's unpinned.",
+                book_title
+            ))
+            .color(0xB76E79)
+    } else {
+        CreateEmbed::default()
+            .title("Book Unpinned")
+            .description(format!(
+                "**{}** will be picked for polls by its queue position again.",
+                book_title
+            ))
+            .color(0xB76E79)
+    };
+
+    send_with_queue(ctx, &club, result).await
+}
+
+#[poise::command(
+    slash_command,
+    description_localized("en-US", "Shuffle the queue (pinned books keep their spots)"),
+    user_cooldown = 10
+)]
+async fn shuffle(
+    ctx: Context<'_>,
+    #[description = "Club whose queue to change (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    if ctx.guild_id().is_none() {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+
+    let shuffled = sqlx::query_scalar!(
+        r#"SELECT shuffle_queue_tx($1) as "shuffled!""#,
+        club.club_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let result = CreateEmbed::default()
+        .title("🔀 Queue Shuffled")
+        .description(format!("Shuffled **{}** book(s).", shuffled))
+        .color(0xB76E79);
+
+    send_with_queue(ctx, &club, result).await
+}
+
+#[poise::command(
+    slash_command,
+    description_localized("en-US", "Remove every book from the queue"),
+    user_cooldown = 10
+)]
+async fn clear(
+    ctx: Context<'_>,
+    #[description = "Keep pinned books in the queue (default: no)"] keep_pinned: Option<bool>,
+    #[description = "Club whose queue to change (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    if ctx.guild_id().is_none() {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+    let keep_pinned = keep_pinned.unwrap_or(false);
+
+    let to_remove = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM server_book_queue
+         WHERE club_id = $1 AND (NOT $2 OR NOT pinned)"#,
+        club.club_id,
+        keep_pinned
+    )
+    .fetch_one(pool)
+    .await?;
+
+    if to_remove == 0 {
+        let embed = CreateEmbed::default()
+            .title("Nothing to Clear")
+            .description("There are no books to remove from the queue.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let embed = CreateEmbed::default()
+        .title("🗑️ Clear the queue?")
+        .description(format!(
+            "This will remove **{}** book(s) from the queue{}. This can't be undone.",
+            to_remove,
+            if keep_pinned {
+                " and keep pinned books"
+            } else {
+                ""
+            }
+        ))
+        .color(0xB76E79);
+
+    let components = vec![CreateActionRow::Buttons(vec![
+        CreateButton::new("confirm_clear")
+            .label("Confirm")
+            .style(ButtonStyle::Danger),
+        CreateButton::new("cancel")
+            .label("Cancel")
+            .style(ButtonStyle::Secondary),
+    ])];
+
+    let msg = ctx
+        .send(
+            poise::CreateReply::default()
+                .embed(embed)
+                .components(components),
+        )
+        .await?
+        .into_message()
+        .await?;
+
+    let mut stream = msg
+        .await_component_interactions(ctx)
+        .timeout(Duration::from_secs(60))
+        .author_id(ctx.author().id)
+        .stream();
+
+    if let Some(mci) = stream.next().await {
+        if mci.data.custom_id == "confirm_clear" {
+            let removed = sqlx::query_scalar!(
+                r#"SELECT clear_queue_tx($1, $2) as "removed!""#,
+                club.club_id,
+                keep_pinned
+            )
+            .fetch_one(pool)
+            .await?;
+
+            let result = CreateEmbed::default()
+                .title("✅ Queue Cleared")
+                .description(format!("Removed **{}** book(s) from the queue.", removed))
+                .color(0xB76E79);
+            let queue_embed = build_queue_embed(&ctx, &club).await?;
+
+            mci.create_response(
+                ctx.serenity_context(),
+                poise::serenity_prelude::CreateInteractionResponse::UpdateMessage(
+                    poise::serenity_prelude::CreateInteractionResponseMessage::default()
+                        .content("")
+                        .components(vec![])
+                        .embeds(vec![result, queue_embed]),
+                ),
+            )
+            .await
+            .ok();
+        } else {
+            mci.create_response(
+                ctx.serenity_context(),
+                poise::serenity_prelude::CreateInteractionResponse::UpdateMessage(
+                    poise::serenity_prelude::CreateInteractionResponseMessage::default()
+                        .content("❎ Cancelled.")
+                        .components(vec![])
+                        .embeds(vec![]),
+                ),
+            )
+            .await
+            .ok();
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,429 @@
+use crate::util::{autocomplete_club, get_guild_name, list_clubs, resolve_club};
+use crate::{types::Context, types::Error};
+use poise::CreateReply;
+use poise::serenity_prelude::{
+    ButtonStyle, CreateActionRow, CreateButton, CreateEmbed, CreateEmbedFooter,
+    EditInteractionResponse,
+};
+
+const MAX_CLUB_NAME_LENGTH: usize = 50;
+
+#[poise::command(
+    slash_command,
+    subcommands("create", "rename", "delete", "list", "primary"),
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized("en-US", "Manage this server's book clubs"),
+    user_cooldown = 5
+)]
+pub async fn club(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Trims a proposed club name, replying with an error and returning `None` when it can't be used.
+async fn validate_club_name(ctx: Context<'_>, name: &str) -> Result<Option<String>, Error> {
+    let name = name.trim();
+    let problem = if name.is_empty() {
+        Some("Club names can't be empty.".to_string())
+    } else if name.chars().count() > MAX_CLUB_NAME_LENGTH {
+        Some(format!(
+            "Club names can be at most {} characters long.",
+            MAX_CLUB_NAME_LENGTH
+        ))
+    } else {
+        None
+    };
+
+    if let Some(problem) = problem {
+        let embed = CreateEmbed::default()
+            .title("❌ Invalid Club Name")
+            .description(problem)
+            .color(0xB76E79);
+        ctx.send(CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(None);
+    }
+
+    Ok(Some(name.to_string()))
+}
+
+async fn send_name_taken(ctx: Context<'_>, name: &str) -> Result<(), Error> {
+    let embed = CreateEmbed::default()
+        .title("❌ Club Already Exists")
+        .description(format!(
+            "This server already has a club called **{}**.",
+            name
+        ))
+        .color(0xB76E79);
+    ctx.send(CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized("en-US", "Create a new book club (requires Manage Server)"),
+    user_cooldown = 5
+)]
+async fn create(
+    ctx: Context<'_>,
+    #[description = "Name of the new club"] name: String,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+    let Some(name) = validate_club_name(ctx, &name).await? else {
+        return Ok(());
+    };
+
+    // Make sure the primary club exists first so the new one never becomes the default
+    if resolve_club(&ctx, None).await?.is_none() {
+        return Ok(());
+    }
+
+    let pool = &ctx.data().database;
+    let created = sqlx::query!(
+        "INSERT INTO server_clubs (server_id, name)
+         VALUES ($1, $2)
+         ON CONFLICT DO NOTHING
+         RETURNING club_id",
+        guild_id.get() as i64,
+        name
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    if created.is_none() {
+        return send_name_taken(ctx, &name).await;
+    }
+
+    let embed = CreateEmbed::default()
+        .title("📚 Club Created")
+        .description(format!(
+            "**{}** now has its own queue, current book and history.\n\n\
+            Pass `club:{}` to `/queue`, `/select`, `/finishbook` and other club commands to use it, \
+            and `/config announcement set club:{}` to give it an announcement channel.",
+            name, name, name
+        ))
+        .color(0xB76E79);
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized("en-US", "Rename a book club (requires Manage Server)"),
+    user_cooldown = 5
+)]
+async fn rename(
+    ctx: Context<'_>,
+    #[description = "Club to rename"]
+    #[autocomplete = "autocomplete_club"]
+    club: String,
+    #[description = "New name for the club"] new_name: String,
+) -> Result<(), Error> {
+    if ctx.guild_id().is_none() {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, Some(&club)).await? else {
+        return Ok(());
+    };
+    let Some(new_name) = validate_club_name(ctx, &new_name).await? else {
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+    let renamed = sqlx::query!(
+        "UPDATE server_clubs SET name = $2
+         WHERE club_id = $1
+           AND NOT EXISTS (
+               SELECT 1 FROM server_clubs other
+               WHERE other.server_id = server_clubs.server_id
+                 AND other.club_id <> $1
+                 AND LOWER(other.name) = LOWER($2)
+           )",
+        club.club_id,
+        new_name
+    )
+    .execute(pool)
+    .await?;
+
+    if renamed.rows_affected() == 0 {
+        return send_name_taken(ctx, &new_name).await;
+    }
+
+    let embed = CreateEmbed::default()
+        .title("✏️ Club Renamed")
+        .description(format!("**{}** is now called **{}**.", club.name, new_name))
+        .color(0xB76E79);
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized(
+        "en-US",
+        "Delete a book club and all of its history (requires Manage Server)"
+    ),
+    user_cooldown = 10
+)]
+async fn delete(
+    ctx: Context<'_>,
+    #[description = "Club to delete"]
+    #[autocomplete = "autocomplete_club"]
+    club: String,
+) -> Result<(), Error> {
+    if ctx.guild_id().is_none() {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, Some(&club)).await? else {
+        return Ok(());
+    };
+
+    if club.is_primary {
+        let embed = CreateEmbed::default()
+            .title("❌ Can't Delete the Primary Club")
+            .description(format!(
+                "**{}** is this server's primary club. Make another club primary with `/club primary` first.",
+                club.name
+            ))
+            .color(0xB76E79);
+        ctx.send(CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    }
+
+    let embed = CreateEmbed::default()
+        .title("⚠️ Delete Club")
+        .description(format!(
+            "This will permanently delete **{}** including:\n\
+            • Its book queue\n\
+            • Its current and completed books\n\
+            • Member ratings, progress and reminders for the club\n\
+            • Its announcement, deadline and pinning settings\n\n\
+            **This action cannot be undone!**",
+            club.name
+        ))
+        .color(0xFF0000);
+
+    let action_row = CreateActionRow::Buttons(vec![
+        CreateButton::new("confirm_delete_club")
+            .label("Yes, delete this club")
+            .style(ButtonStyle::Danger),
+        CreateButton::new("cancel_delete_club")
+            .label("Cancel")
+            .style(ButtonStyle::Secondary),
+    ]);
+
+    let response = ctx
+        .send(
+            CreateReply::default()
+                .embed(embed)
+                .components(vec![action_row]),
+        )
+        .await?;
+
+    let interaction = response
+        .message()
+        .await?
+        .await_component_interaction(ctx.serenity_context())
+        .author_id(ctx.author().id)
+        .timeout(std::time::Duration::from_secs(60))
+        .await;
+
+    let Some(interaction) = interaction else {
+        response
+            .edit(
+                ctx,
+                CreateReply::default()
+                    .content("Club deletion cancelled (timed out).")
+                    .components(vec![]),
+            )
+            .await?;
+        return Ok(());
+    };
+    interaction.defer(&ctx.http()).await?;
+
+    let content = if interaction.data.custom_id == "confirm_delete_club" {
+        sqlx::query!(
+            "DELETE FROM server_clubs WHERE club_id = $1 AND NOT is_primary",
+            club.club_id
+        )
+        .execute(&ctx.data().database)
+        .await?;
+        format!("✅ **{}** has been deleted.", club.name)
+    } else {
+        "Club deletion cancelled.".to_string()
+    };
+
+    interaction
+        .edit_response(
+            &ctx.http(),
+            EditInteractionResponse::new()
+                .content(content)
+                .embeds(vec![])
+                .components(vec![]),
+        )
+        .await?;
+
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized("en-US", "List this server's book clubs (requires Manage Server)"),
+    user_cooldown = 5
+)]
+async fn list(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+    // Creates the primary club for servers that haven't used a club command yet
+    if resolve_club(&ctx, None).await?.is_none() {
+        return Ok(());
+    }
+
+    let pool = &ctx.data().database;
+    let google_books = &ctx.data().google_books;
+    let guild_name = get_guild_name(&ctx).await;
+
+    let mut lines = Vec::new();
+    for club in list_clubs(pool, guild_id.get() as i64).await? {
+        let details = sqlx::query!(
+            r#"
+            SELECT
+                sc.announcement_channel_id,
+                (SELECT COUNT(*) FROM server_book_queue q WHERE q.club_id = sc.club_id) AS "queued!",
+                (SELECT COUNT(*) FROM server_completed_books c WHERE c.club_id = sc.club_id) AS "completed!",
+                (SELECT volume_id FROM server_current_book cb WHERE cb.club_id = sc.club_id) AS current_volume_id
+            FROM server_clubs sc
+            WHERE sc.club_id = $1
+            "#,
+            club.club_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        let reading = match details.current_volume_id {
+            Some(volume_id) => match google_books.get_volume(&volume_id).await {
+                Ok(volume) => volume.get_title(),
+                Err(_) => format!("Book ({})", volume_id),
+            },
+            None => "nothing right now".to_string(),
+        };
+        let channel = details
+            .announcement_channel_id
+            .map(|id| format!("<#{}>", id))
+            .unwrap_or_else(|| "no announcement channel".to_string());
+
+        lines.push(format!(
+            "**{}**{}\nReading {} • {} queued • {} completed • {}",
+            club.name,
+            if club.is_primary { " (primary)" } else { "" },
+            reading,
+            details.queued,
+            details.completed,
+            channel
+        ));
+    }
+
+    let embed = CreateEmbed::default()
+        .title(format!("📚 Book Clubs in {}", guild_name))
+        .description(lines.join("\n\n"))
+        .color(0xB76E79)
+        .footer(CreateEmbedFooter::new(
+            "Commands use the primary club unless you pass club:<name>",
+        ));
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized(
+        "en-US",
+        "Make a club the default for commands without club: (requires Manage Server)"
+    ),
+    user_cooldown = 5
+)]
+async fn primary(
+    ctx: Context<'_>,
+    #[description = "Club to make primary"]
+    #[autocomplete = "autocomplete_club"]
+    club: String,
+) -> Result<(), Error> {
+    if ctx.guild_id().is_none() {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, Some(&club)).await? else {
+        return Ok(());
+    };
+
+    if !club.is_primary {
+        let mut tx = ctx.data().database.begin().await?;
+        // Clear the old primary first; only one club per server may be primary at a time
+        sqlx::query!(
+            "UPDATE server_clubs SET is_primary = FALSE WHERE server_id = $1 AND is_primary",
+            club.server_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            "UPDATE server_clubs SET is_primary = TRUE WHERE club_id = $1",
+            club.club_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+    }
+
+    let embed = CreateEmbed::default()
+        .title("⭐ Primary Club Updated")
+        .description(format!(
+            "**{}** is now the primary club. Commands use it whenever `club:` is left out.",
+            club.name
+        ))
+        .color(0xB76E79);
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
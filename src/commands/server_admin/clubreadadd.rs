@@ -4,13 +4,15 @@ use crate::maturity_check::{
 };
 use crate::types::QueryMode;
 use crate::util::{
-    detect_query_mode, get_guild_name, is_valid_isbn10, is_valid_isbn13, normalize_isbn,
+    autocomplete_club, detect_query_mode, get_guild_name, is_valid_isbn10, is_valid_isbn13,
+    normalize_isbn, resolve_club,
 };
 use crate::*;
 use crate::{types::Context, types::Error};
 use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter, User};
 use sqlx::types::BigDecimal;
-use sqlx::types::chrono::{NaiveDate, TimeZone, Utc};
+use sqlx::types::chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
 use std::str::FromStr;
 
 #[poise::command(
@@ -30,6 +32,9 @@ pub async fn clubreadadd(
     #[description = "Date completed (YYYY-MM-DD format)"] completion_date: Option<String>,
     #[description = "Average rating (1-5)"] rating: Option<f32>,
     #[description = "User who originally suggested this book"] suggested_by: Option<User>,
+    #[description = "Club to add the book to (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
 ) -> Result<(), Error> {
     ctx.defer().await?;
 
@@ -41,6 +46,9 @@ pub async fn clubreadadd(
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
     };
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
     let guild_name = get_guild_name(&ctx).await;
     let pool = &ctx.data().database;
     let google_books = &ctx.data().google_books;
@@ -49,7 +57,7 @@ pub async fn clubreadadd(
 
     // Validate rating if provided
     if let Some(r) = rating {
-        if r < 1.0 || r > 5.0 {
+        if !rating_in_range(r) {
             let embed = CreateEmbed::default()
                 .title("❌ Invalid Rating")
                 .description("Rating must be between 1 and 5 (inclusive).")
@@ -61,37 +69,25 @@ pub async fn clubreadadd(
     }
 
     // Parse completion date if provided
-    let completed_at = if let Some(date_str) = completion_date.clone() {
-        match NaiveDate::parse_from_str(&date_str, "%Y-%m-%d") {
-            Ok(date) => {
-                // Reject future dates beyond tomorrow (compare by calendar date)
-                let today = Utc::now().date_naive();
-                let tomorrow = today.succ_opt().unwrap(); // safe for all valid dates
-
-                if date > tomorrow {
-                    let embed = CreateEmbed::default()
-                        .title("❌ Invalid Completion Date")
-                        .description("Completion date cannot be in the future beyond tomorrow.")
-                        .color(0xB76E79);
-                    ctx.send(poise::CreateReply::default().embed(embed)).await?;
-                    return Ok(());
-                }
-
-                // Noon UTC to avoid timezone edge cases
-                let datetime = date.and_hms_opt(12, 0, 0).unwrap();
-                Some(Utc.from_utc_datetime(&datetime))
-            }
-            Err(_) => {
-                let embed = CreateEmbed::default()
-                    .title("❌ Invalid Date Format")
-                    .description("Please use YYYY-MM-DD format (e.g., 2024-03-15).")
-                    .color(0xB76E79);
-                ctx.send(poise::CreateReply::default().embed(embed)).await?;
-                return Ok(());
-            }
+    let completed_at = match completion_date.as_deref().map(parse_completion_date) {
+        None => None,
+        Some(Ok(date)) => Some(date),
+        Some(Err(CompletionDateError::Future)) => {
+            let embed = CreateEmbed::default()
+                .title("❌ Invalid Completion Date")
+                .description("Completion date cannot be in the future beyond tomorrow.")
+                .color(0xB76E79);
+            ctx.send(poise::CreateReply::default().embed(embed)).await?;
+            return Ok(());
+        }
+        Some(Err(CompletionDateError::Format)) => {
+            let embed = CreateEmbed::default()
+                .title("❌ Invalid Date Format")
+                .description("Please use YYYY-MM-DD format (e.g., 2024-03-15).")
+                .color(0xB76E79);
+            ctx.send(poise::CreateReply::default().embed(embed)).await?;
+            return Ok(());
         }
-    } else {
-        None
     };
 
     // Ensure suggested_by user exists in database if provided
@@ -106,7 +102,7 @@ pub async fn clubreadadd(
     let book = match chosen {
         QueryMode::Isbn => {
             let isbn = normalize_isbn(&title_or_isbn);
-            if !is_plausible_isbn(&isbn) || !(is_valid_isbn10(&isbn) || is_valid_isbn13(&isbn)) {
+            if !is_valid_club_isbn(&isbn) {
                 let embed = CreateEmbed::default()
                     .title("❌ Invalid ISBN")
                     .description(
@@ -189,94 +185,34 @@ pub async fn clubreadadd(
     let book_authors = book.get_authors_string();
 
     // Only block exact same-day duplicates if a completion_date was provided.
-    if completed_at.is_some() {
-        let same_day_exists = sqlx::query_scalar!(
-            r#"
-            SELECT 1
-            FROM server_completed_books
-            WHERE server_id = $1
-              AND volume_id = $2
-              AND DATE(completed_at) = DATE($3)
-            LIMIT 1
-            "#,
-            guild_id.get() as i64,
-            volume_id,
-            completed_at
-        )
-        .fetch_optional(pool)
-        .await?;
-
-        if same_day_exists.is_some() {
-            let embed = CreateEmbed::default()
+    if let Some(completed) = completed_at
+        && completed_same_day(pool, club.club_id, volume_id, completed).await?
+    {
+        let embed = CreateEmbed::default()
                 .title("⚠️ Already Completed That Day")
                 .description(format!(
                     "'{}' is already recorded as completed on {}.\n\nIf you intended a re-read, use a different completion date.",
                     book_title,
-                    completed_at.unwrap().format("%B %d, %Y")
+                    completed.format("%B %d, %Y")
                 ))
                 .color(0xB76E79)
                 .footer(CreateEmbedFooter::new("Powered by Google Books API"));
-            ctx.send(poise::CreateReply::default().embed(embed)).await?;
-            return Ok(());
-        }
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
     }
 
-    // Calculate started_at (if completed_at is provided, assume it started 30 days before; otherwise use 30 days ago)
-    let started_at = if let Some(completed) = completed_at {
-        completed - chrono::Duration::days(30)
-    } else {
-        Utc::now() - chrono::Duration::days(30)
-    };
-
     // Begin a transaction so the history insert and optional rating insert are atomic.
     let mut tx = pool.begin().await?;
 
-    let rating_bd: Option<BigDecimal> = match rating {
-        Some(r) => {
-            let s = format!("{:.2}", r);
-            BigDecimal::from_str(&s).ok()
-        }
-        None => None,
-    };
-
-    // Insert into server_completed_books
-    // If completed_at is provided, write it; otherwise let it remain NULL
-    let completed_id = if let Some(completed) = completed_at {
-        sqlx::query_scalar!(
-            r#"
-            INSERT INTO server_completed_books 
-                (server_id, volume_id, suggested_by_user_id, started_at, completed_at, average_rating, total_ratings)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
-            RETURNING completed_id
-            "#,
-            guild_id.get() as i64,
-            volume_id,
-            suggested_by.as_ref().map(|u| u.id.get() as i64),
-            started_at,
-            completed,
-            rating_bd,
-            if rating.is_some() { 1 } else { 0 }
-        )
-        .fetch_one(&mut *tx)
-        .await?
-    } else {
-        sqlx::query_scalar!(
-            r#"
-            INSERT INTO server_completed_books 
-                (server_id, volume_id, suggested_by_user_id, started_at, average_rating, total_ratings)
-            VALUES ($1, $2, $3, $4, $5, $6)
-            RETURNING completed_id
-            "#,
-            guild_id.get() as i64,
-            volume_id,
-            suggested_by.as_ref().map(|u| u.id.get() as i64),
-            started_at,
-            rating_bd,
-            if rating.is_some() { 1 } else { 0 }
-        )
-        .fetch_one(&mut *tx)
-        .await?
-    };
+    let completed_id = insert_completed_book(
+        &mut tx,
+        club.club_id,
+        volume_id,
+        suggested_by.as_ref().map(|u| u.id.get() as i64),
+        completed_at,
+        rating,
+    )
+    .await?;
 
     // If a rating was provided and the admin wants to record it as their personal rating
     if let Some(r) = rating {
@@ -341,6 +277,124 @@ pub async fn clubreadadd(
 }
 
 /* ----------------------------- Helpers ----------------------------- */
+// Shared with /clubreadimport so bulk imports follow the same rules as single adds.
+
+pub(crate) enum CompletionDateError {
+    Format,
+    Future,
+}
+
+/// Parses a YYYY-MM-DD completion date, rejecting anything after tomorrow.
+pub(crate) fn parse_completion_date(input: &str) -> Result<DateTime<Utc>, CompletionDateError> {
+    let date = NaiveDate::parse_from_str(input.trim(), "%Y-%m-%d")
+        .map_err(|_| CompletionDateError::Format)?;
+
+    // Reject future dates beyond tomorrow (compare by calendar date)
+    let today = Utc::now().date_naive();
+    let tomorrow = today.succ_opt().unwrap(); // safe for all valid dates
+    if date > tomorrow {
+        return Err(CompletionDateError::Future);
+    }
+
+    // Noon UTC to avoid timezone edge cases
+    let datetime = date.and_hms_opt(12, 0, 0).unwrap();
+    Ok(Utc.from_utc_datetime(&datetime))
+}
+
+pub(crate) fn rating_in_range(rating: f32) -> bool {
+    (1.0..=5.0).contains(&rating)
+}
+
+/// A normalized ISBN with the right shape and a correct checksum.
+pub(crate) fn is_valid_club_isbn(isbn: &str) -> bool {
+    is_plausible_isbn(isbn) && (is_valid_isbn10(isbn) || is_valid_isbn13(isbn))
+}
+
+pub(crate) async fn completed_same_day(
+    pool: &PgPool,
+    club_id: i32,
+    volume_id: &str,
+    completed_at: DateTime<Utc>,
+) -> Result<bool, Error> {
+    let same_day_exists = sqlx::query_scalar!(
+        r#"
+        SELECT 1
+        FROM server_completed_books
+        WHERE club_id = $1
+          AND volume_id = $2
+          AND DATE(completed_at) = DATE($3)
+        LIMIT 1
+        "#,
+        club_id,
+        volume_id,
+        completed_at
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(same_day_exists.is_some())
+}
+
+/// Records a finished book. Without a completion date it counts as finished today; the reading
+/// period is assumed to be the 30 days before. Returns the new `completed_id`.
+pub(crate) async fn insert_completed_book(
+    tx: &mut Transaction<'_, Postgres>,
+    club_id: i32,
+    volume_id: &str,
+    suggested_by: Option<i64>,
+    completed_at: Option<DateTime<Utc>>,
+    rating: Option<f32>,
+) -> Result<i32, Error> {
+    let started_at = completed_at.unwrap_or_else(Utc::now) - chrono::Duration::days(30);
+
+    let rating_bd: Option<BigDecimal> = match rating {
+        Some(r) => {
+            let s = format!("{:.2}", r);
+            BigDecimal::from_str(&s).ok()
+        }
+        None => None,
+    };
+
+    // If completed_at is provided, write it; otherwise the column default (now) applies
+    let completed_id = if let Some(completed) = completed_at {
+        sqlx::query_scalar!(
+            r#"
+            INSERT INTO server_completed_books 
+                (club_id, volume_id, suggested_by_user_id, started_at, completed_at, average_rating, total_ratings)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING completed_id
+            "#,
+            club_id,
+            volume_id,
+            suggested_by,
+            started_at,
+            completed,
+            rating_bd,
+            if rating.is_some() { 1 } else { 0 }
+        )
+        .fetch_one(&mut **tx)
+        .await?
+    } else {
+        sqlx::query_scalar!(
+            r#"
+            INSERT INTO server_completed_books 
+                (club_id, volume_id, suggested_by_user_id, started_at, average_rating, total_ratings)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING completed_id
+            "#,
+            club_id,
+            volume_id,
+            suggested_by,
+            started_at,
+            rating_bd,
+            if rating.is_some() { 1 } else { 0 }
+        )
+        .fetch_one(&mut **tx)
+        .await?
+    };
+
+    Ok(completed_id)
+}
 
 fn is_plausible_isbn(isbn: &str) -> bool {
     // ISBN-10: digits + optional final 'X'
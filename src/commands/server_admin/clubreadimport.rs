@@ -0,0 +1,701 @@
+use super::clubreadadd::{
+    CompletionDateError, completed_same_day, insert_completed_book, is_valid_club_isbn,
+    parse_completion_date, rating_in_range,
+};
+use crate::google_books::Volume;
+use crate::maturity_check::can_display_mature_content;
+use crate::reading_import::parse_csv;
+use crate::types::QueryMode;
+use crate::util::{
+    Club, EMBED_DESCRIPTION_LIMIT, autocomplete_club, detect_query_mode, ensure_server_exists,
+    ensure_user_exists, get_guild_name, join_lines_capped, log_error_with_source, normalize_isbn,
+    resolve_club, truncate_with_ellipsis,
+};
+use crate::{types::Context, types::Error};
+use futures::future::join_all;
+use poise::serenity_prelude::{
+    Attachment, ButtonStyle, CreateActionRow, CreateAttachment, CreateButton, CreateEmbed,
+    CreateEmbedFooter, CreateInteractionResponse, CreateInteractionResponseMessage, EditMessage,
+    User, UserId,
+};
+use sqlx::PgPool;
+use sqlx::types::chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+/// Spreadsheets of past reads are small; anything bigger is the wrong file.
+const MAX_IMPORT_BYTES: u32 = 1024 * 1024;
+const MAX_IMPORT_ROWS: usize = 100;
+// Same pacing as `get_volumes_batch`, so a big import doesn't exhaust the API quota
+const LOOKUP_BATCH_SIZE: usize = 10;
+const LOOKUP_BATCH_DELAY: Duration = Duration::from_secs(1);
+/// Rows listed in the preview embed; the full preview is attached as a file.
+const PREVIEW_LINES: usize = 15;
+/// Spreadsheet cells and titles are shortened to this in the preview.
+const PREVIEW_CELL_LENGTH: usize = 100;
+const ATTACHED_NOTE: &str = " (see the attached file)";
+
+/// Accepted header names for each column, compared lowercase with spaces as underscores.
+const BOOK_COLUMNS: [&str; 4] = ["title_or_isbn", "title", "isbn", "book"];
+const AUTHOR_COLUMNS: [&str; 2] = ["author", "authors"];
+const DATE_COLUMNS: [&str; 4] = ["completion_date", "completed", "completed_at", "date"];
+const RATING_COLUMNS: [&str; 2] = ["rating", "average_rating"];
+const SUGGESTER_COLUMNS: [&str; 2] = ["suggested_by", "suggester"];
+
+/// One line of the uploaded spreadsheet before anything is looked up.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct ClubImportRow {
+    line: usize,
+    book: String,
+    author: Option<String>,
+    completion_date: Option<String>,
+    rating: Option<String>,
+    suggested_by: Option<String>,
+}
+
+/// How sure we are that the Google Books result is the book the spreadsheet meant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MatchConfidence {
+    High,
+    Medium,
+    Low,
+}
+
+impl MatchConfidence {
+    fn label(self) -> &'static str {
+        match self {
+            MatchConfidence::High => "🟢 high",
+            MatchConfidence::Medium => "🟡 medium",
+            MatchConfidence::Low => "🔴 low",
+        }
+    }
+}
+
+/// A row that passed validation and is ready to be written on confirmation.
+struct PlannedBook {
+    volume: Volume,
+    confidence: MatchConfidence,
+    completed_at: Option<DateTime<Utc>>,
+    rating: Option<f32>,
+    suggested_by: Option<User>,
+}
+
+/// Validated fields of a row, waiting for its Google Books lookup.
+struct CheckedRow {
+    completed_at: Option<DateTime<Utc>>,
+    rating: Option<f32>,
+    suggested_by: Option<User>,
+}
+
+fn column_key(name: &str) -> String {
+    name.trim().to_lowercase().replace([' ', '-'], "_")
+}
+
+/// Reads the spreadsheet by its header row. Only the book column is required; returns `None`
+/// when it can't be found.
+fn parse_rows(text: &str) -> Option<Vec<ClubImportRow>> {
+    let mut records = parse_csv(text).into_iter();
+    let header: Vec<String> = records
+        .next()?
+        .fields
+        .iter()
+        .map(|name| column_key(name))
+        .collect();
+    let find = |names: &[&str]| {
+        header
+            .iter()
+            .position(|name| names.contains(&name.as_str()))
+    };
+    let book_column = find(&BOOK_COLUMNS)?;
+    let author_column = find(&AUTHOR_COLUMNS);
+    let date_column = find(&DATE_COLUMNS);
+    let rating_column = find(&RATING_COLUMNS);
+    let suggester_column = find(&SUGGESTER_COLUMNS);
+
+    let rows = records
+        .map(|record| {
+            let cell = |column: Option<usize>| {
+                column
+                    .and_then(|column| record.fields.get(column))
+                    .map(|value| value.trim().to_string())
+                    .filter(|value| !value.is_empty())
+            };
+            ClubImportRow {
+                line: record.line,
+                book: cell(Some(book_column)).unwrap_or_default(),
+                author: cell(author_column),
+                completion_date: cell(date_column),
+                rating: cell(rating_column),
+                suggested_by: cell(suggester_column),
+            }
+        })
+        .collect();
+    Some(rows)
+}
+
+/// Accepts a mention (`<@123>`, `<@!123>`) or a bare user ID.
+fn parse_user_id(input: &str) -> Option<u64> {
+    let id = input
+        .trim()
+        .trim_start_matches("<@")
+        .trim_start_matches('!')
+        .trim_end_matches('>');
+    id.parse().ok().filter(|id| *id > 0)
+}
+
+fn comparable(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Compares a title search with the result it returned. Exact titles with a matching author are
+/// a sure thing; a subtitle or edition difference, or no author to check, lowers confidence; an
+/// author mismatch or unrelated title makes it a likely wrong pick.
+fn title_match_confidence(
+    title: &str,
+    author: Option<&str>,
+    found_title: &str,
+    found_authors: &[String],
+) -> MatchConfidence {
+    let wanted = comparable(title);
+    let found = comparable(found_title);
+    let exact_title = wanted == found;
+    let similar_title = !wanted.is_empty()
+        && !found.is_empty()
+        && (found.contains(&wanted) || wanted.contains(&found));
+    let author_matches = author.map(|author| {
+        let author = comparable(author);
+        found_authors
+            .iter()
+            .map(|name| comparable(name))
+            .any(|name| {
+                !name.is_empty()
+                    && !author.is_empty()
+                    && (name.contains(&author) || author.contains(&name))
+            })
+    });
+
+    match (exact_title || similar_title, author_matches) {
+        (false, _) | (_, Some(false)) => MatchConfidence::Low,
+        (true, Some(true)) => MatchConfidence::High,
+        (true, None) if exact_title => MatchConfidence::Medium,
+        (true, None) => MatchConfidence::Low,
+    }
+}
+
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized(
+        "en-US",
+        "Bulk add past club reads from a CSV file (requires Manage Server)",
+    ),
+    user_cooldown = 10
+)]
+pub async fn clubreadimport(
+    ctx: Context<'_>,
+    #[description = "CSV with title_or_isbn, author, completion_date, rating, suggested_by"]
+    file: Attachment,
+    #[description = "Club to add the books to (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
+    let guild_name = get_guild_name(&ctx).await;
+    let pool = &ctx.data().database;
+    let google_books = &ctx.data().google_books;
+
+    ensure_server_exists(pool, guild_id, &guild_name).await?;
+
+    if !file.filename.to_lowercase().ends_with(".csv") || file.size > MAX_IMPORT_BYTES {
+        let embed = CreateEmbed::default()
+            .title("❌ Invalid File")
+            .description("Please attach a CSV file of up to 1 MB.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+    let bytes = file.download().await?;
+    let Some(rows) = parse_rows(&String::from_utf8_lossy(&bytes)) else {
+        let embed = CreateEmbed::default()
+            .title("❌ Invalid File")
+            .description(
+                "The first row must name the columns. A `title_or_isbn` (or `title`/`isbn`) column is required; \
+                `author`, `completion_date` (YYYY-MM-DD), `rating` (1-5) and `suggested_by` (a mention or user ID) are optional.",
+            )
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+    if rows.is_empty() || rows.len() > MAX_IMPORT_ROWS {
+        let embed = CreateEmbed::default()
+            .title("❌ Invalid File")
+            .description(format!(
+                "The file has {} book(s); imports take between 1 and {} at a time.",
+                rows.len(),
+                MAX_IMPORT_ROWS
+            ))
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    // Check each row's own fields first so only usable rows cost a Google Books lookup
+    let mut suggesters: HashMap<u64, Option<User>> = HashMap::new();
+    let mut checked: Vec<(ClubImportRow, Result<CheckedRow, String>)> = Vec::new();
+    for row in rows {
+        let result = check_row(&ctx, &row, &mut suggesters).await;
+        checked.push((row, result));
+    }
+
+    let mut plan: Vec<(ClubImportRow, Result<PlannedBook, String>)> = Vec::new();
+    let batch_count = checked.len().div_ceil(LOOKUP_BATCH_SIZE);
+    let mut checked = checked.into_iter();
+    for batch_index in 0..batch_count {
+        let batch: Vec<_> = checked.by_ref().take(LOOKUP_BATCH_SIZE).collect();
+        let lookups = join_all(batch.iter().map(|(row, result)| async move {
+            match result {
+                Ok(_) => resolve_book(google_books, row).await,
+                Err(problem) => Err(problem.clone()),
+            }
+        }))
+        .await;
+        for ((row, result), lookup) in batch.into_iter().zip(lookups) {
+            let planned = result.and_then(|fields| {
+                lookup.map(|(volume, confidence)| PlannedBook {
+                    volume,
+                    confidence,
+                    completed_at: fields.completed_at,
+                    rating: fields.rating,
+                    suggested_by: fields.suggested_by,
+                })
+            });
+            plan.push((row, planned));
+        }
+        if batch_index + 1 < batch_count {
+            tokio::time::sleep(LOOKUP_BATCH_DELAY).await;
+        }
+    }
+
+    // Rules that depend on the matched book: maturity and same-day duplicates, in the
+    // club's history or earlier in the same file
+    let allow_mature = can_display_mature_content(&ctx, pool).await?;
+    let mut seen: HashSet<(String, Option<chrono::NaiveDate>)> = HashSet::new();
+    for (_, planned) in plan.iter_mut() {
+        let Ok(book) = planned else {
+            continue;
+        };
+        let problem = if book.volume.is_mature() && !allow_mature {
+            Some("mature content is hidden here".to_string())
+        } else if let Some(completed) = book.completed_at
+            && completed_same_day(pool, club.club_id, &book.volume.id, completed).await?
+        {
+            Some(format!(
+                "already recorded as completed on {}",
+                completed.format("%B %d, %Y")
+            ))
+        } else if !seen.insert((
+            book.volume.id.clone(),
+            book.completed_at.map(|at| at.date_naive()),
+        )) {
+            Some("same book and date as an earlier row".to_string())
+        } else {
+            None
+        };
+        if let Some(problem) = problem {
+            *planned = Err(problem);
+        }
+    }
+
+    let ready = plan.iter().filter(|(_, planned)| planned.is_ok()).count();
+    let preview_lines: Vec<String> = plan
+        .iter()
+        .map(|(row, planned)| preview_line(row, planned))
+        .collect();
+    let mut description = format!(
+        "**{}** of {} row(s) are ready to add to {}{}. Nothing is saved until you confirm.\n\n",
+        ready,
+        plan.len(),
+        guild_name,
+        club.title_suffix(),
+    );
+    let (lines, hidden) = join_lines_capped(
+        &preview_lines[..preview_lines.len().min(PREVIEW_LINES)],
+        preview_lines.len(),
+        EMBED_DESCRIPTION_LIMIT.saturating_sub(description.len() + ATTACHED_NOTE.len()),
+    );
+    description.push_str(&lines);
+    if hidden > 0 {
+        description.push_str(ATTACHED_NOTE);
+    }
+    let embed = CreateEmbed::default()
+        .title("📋 Club History Import Preview")
+        .description(description)
+        .color(0xB76E79)
+        .footer(CreateEmbedFooter::new(
+            "Match confidence compares each title and author with the Google Books result • Powered by Google Books API",
+        ));
+    let buttons = vec![CreateActionRow::Buttons(vec![
+        CreateButton::new("confirm_club_import")
+            .label(format!("Add {} book(s)", ready))
+            .style(ButtonStyle::Success)
+            .disabled(ready == 0),
+        CreateButton::new("cancel_club_import")
+            .label("Cancel")
+            .style(ButtonStyle::Secondary),
+    ])];
+    let mut reply = poise::CreateReply::default()
+        .embed(embed)
+        .components(buttons);
+    if hidden > 0 {
+        reply = reply.attachment(CreateAttachment::bytes(
+            preview_lines.join("\n").replace('*', ""),
+            "import-preview.txt",
+        ));
+    }
+
+    let mut message = ctx.send(reply).await?.into_message().await?;
+    let interaction = message
+        .await_component_interaction(ctx.serenity_context())
+        .author_id(ctx.author().id)
+        .timeout(Duration::from_secs(120))
+        .await;
+
+    match interaction {
+        Some(mci) if mci.data.custom_id == "confirm_club_import" => {
+            // Acknowledge before writing so a large import can't miss the interaction deadline
+            let importing = CreateEmbed::default()
+                .title("⏳ Importing…")
+                .description(format!("Adding {} book(s) to the club history.", ready))
+                .color(0xB76E79);
+            mci.create_response(
+                ctx.serenity_context(),
+                CreateInteractionResponse::UpdateMessage(
+                    CreateInteractionResponseMessage::new()
+                        .embed(importing)
+                        .components(vec![]),
+                ),
+            )
+            .await?;
+
+            let result_embed = match import_planned(pool, &club, &plan).await {
+                Ok(()) => CreateEmbed::default()
+                    .title("✅ Club History Imported")
+                    .description(format!(
+                        "Added {} book(s) to {}{}. {} row(s) were skipped.",
+                        ready,
+                        guild_name,
+                        club.title_suffix(),
+                        plan.len() - ready
+                    ))
+                    .color(0xB76E79)
+                    .footer(CreateEmbedFooter::new(
+                        "Books manually added to history • Powered by Google Books API",
+                    )),
+                Err(err) => {
+                    log_error_with_source("Club history import failed", &err);
+                    CreateEmbed::default()
+                        .title("❌ Import Failed")
+                        .description("Something went wrong while saving. No books were added.")
+                        .color(0xB76E79)
+                }
+            };
+            message
+                .edit(
+                    ctx.serenity_context(),
+                    EditMessage::default().embed(result_embed),
+                )
+                .await?;
+        }
+        Some(mci) => {
+            let embed = CreateEmbed::default()
+                .title("Import Cancelled")
+                .description("No books were added.")
+                .color(0xB76E79);
+            mci.create_response(
+                ctx.serenity_context(),
+                CreateInteractionResponse::UpdateMessage(
+                    CreateInteractionResponseMessage::new()
+                        .embed(embed)
+                        .components(vec![]),
+                ),
+            )
+            .await?;
+        }
+        None => {
+            let embed = CreateEmbed::default()
+                .title("Import Cancelled")
+                .description("No books were added (timed out).")
+                .color(0xB76E79);
+            message
+                .edit(
+                    ctx.serenity_context(),
+                    EditMessage::default().embed(embed).components(vec![]),
+                )
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes every ready row in one transaction, so a failure leaves the history untouched.
+async fn import_planned(
+    pool: &PgPool,
+    club: &Club,
+    plan: &[(ClubImportRow, Result<PlannedBook, String>)],
+) -> Result<(), Error> {
+    let mut tx = pool.begin().await?;
+    for (_, planned) in plan {
+        let Ok(book) = planned else {
+            continue;
+        };
+        if let Some(user) = &book.suggested_by {
+            ensure_user_exists(pool, user).await?;
+        }
+        insert_completed_book(
+            &mut tx,
+            club.club_id,
+            &book.volume.id,
+            book.suggested_by.as_ref().map(|u| u.id.get() as i64),
+            book.completed_at,
+            book.rating,
+        )
+        .await?;
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Applies `/clubreadadd`'s rules to a row's own fields and resolves its suggester.
+async fn check_row(
+    ctx: &Context<'_>,
+    row: &ClubImportRow,
+    suggesters: &mut HashMap<u64, Option<User>>,
+) -> Result<CheckedRow, String> {
+    if row.book.is_empty() {
+        return Err("no title or ISBN".to_string());
+    }
+    if matches!(detect_query_mode(&row.book), QueryMode::Isbn)
+        && !is_valid_club_isbn(&normalize_isbn(&row.book))
+    {
+        return Err("invalid ISBN".to_string());
+    }
+
+    let rating = match row.rating.as_deref() {
+        None => None,
+        Some(text) => match text.parse::<f32>() {
+            Ok(rating) if rating_in_range(rating) => Some(rating),
+            _ => {
+                return Err(format!(
+                    "rating \"{}\" isn't between 1 and 5",
+                    truncate_with_ellipsis(text, PREVIEW_CELL_LENGTH)
+                ));
+            }
+        },
+    };
+
+    let completed_at = match row.completion_date.as_deref().map(parse_completion_date) {
+        None => None,
+        Some(Ok(date)) => Some(date),
+        Some(Err(CompletionDateError::Format)) => {
+            return Err("completion date must be YYYY-MM-DD".to_string());
+        }
+        Some(Err(CompletionDateError::Future)) => {
+            return Err("completion date is in the future".to_string());
+        }
+    };
+
+    let suggested_by = match row.suggested_by.as_deref() {
+        None => None,
+        Some(text) => {
+            let Some(id) = parse_user_id(text) else {
+                return Err(format!(
+                    "suggester \"{}\" isn't a mention or user ID",
+                    truncate_with_ellipsis(text, PREVIEW_CELL_LENGTH)
+                ));
+            };
+            let user = match suggesters.get(&id) {
+                Some(user) => user.clone(),
+                None => {
+                    let user = UserId::new(id).to_user(ctx.http()).await.ok();
+                    suggesters.insert(id, user.clone());
+                    user
+                }
+            };
+            match user {
+                Some(user) => Some(user),
+                None => return Err(format!("no Discord user with ID {}", id)),
+            }
+        }
+    };
+
+    Ok(CheckedRow {
+        completed_at,
+        rating,
+        suggested_by,
+    })
+}
+
+/// Looks a row up the same way `/clubreadadd` does: by ISBN when it is one, otherwise the best
+/// title and author match.
+async fn resolve_book(
+    google_books: &crate::google_books_cache::CachedGoogleBooksClient,
+    row: &ClubImportRow,
+) -> Result<(Volume, MatchConfidence), String> {
+    let lookup_failed = |e: anyhow::Error| {
+        log_error_with_source(
+            &format!("Club import lookup failed for line {}", row.line),
+            &e,
+        );
+        "Google Books lookup failed".to_string()
+    };
+
+    if matches!(detect_query_mode(&row.book), QueryMode::Isbn) {
+        return match google_books
+            .search_by_isbn(&normalize_isbn(&row.book))
+            .await
+            .map_err(lookup_failed)?
+        {
+            Some(volume) => Ok((volume, MatchConfidence::High)),
+            None => Err("no book with that ISBN".to_string()),
+        };
+    }
+
+    let volume = google_books
+        .search_books(&row.book, row.author.as_deref(), Some(5))
+        .await
+        .map_err(lookup_failed)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| "not found on Google Books".to_string())?;
+    let confidence = title_match_confidence(
+        &row.book,
+        row.author.as_deref(),
+        &volume.get_title(),
+        volume.volume_info.authors.as_deref().unwrap_or_default(),
+    );
+    Ok((volume, confidence))
+}
+
+fn preview_line(row: &ClubImportRow, planned: &Result<PlannedBook, String>) -> String {
+    match planned {
+        Ok(book) => {
+            let mut details = vec![
+                book.completed_at
+                    .map(|at| at.format("%Y-%m-%d").to_string())
+                    .unwrap_or_else(|| "today".to_string()),
+            ];
+            if let Some(rating) = book.rating {
+                details.push(format!("{:.1}/5", rating));
+            }
+            if let Some(user) = &book.suggested_by {
+                details.push(format!("by {}", user.name));
+            }
+            format!(
+                "✅ Line {}: *{}* — {} • {} match ({})",
+                row.line,
+                truncate_with_ellipsis(&book.volume.get_title(), PREVIEW_CELL_LENGTH),
+                truncate_with_ellipsis(&book.volume.get_authors_string(), PREVIEW_CELL_LENGTH),
+                book.confidence.label(),
+                details.join(", ")
+            )
+        }
+        Err(problem) => format!(
+            "❌ Line {}: {} — {}",
+            row.line,
+            if row.book.is_empty() {
+                "(empty)".to_string()
+            } else {
+                truncate_with_ellipsis(&row.book, PREVIEW_CELL_LENGTH)
+            },
+            problem
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ClubImportRow, MatchConfidence, parse_rows, parse_user_id, title_match_confidence,
+    };
+
+    #[test]
+    fn rows_are_read_by_header_name() {
+        let text = "Title, Author ,Completed,Rating,Suggested By\n\
+            Piranesi,Susanna Clarke,2024-03-15,4.5,<@!123456789>\n\
+            9780316556347,,,,\n";
+        let rows = parse_rows(text).unwrap();
+        assert_eq!(
+            rows[0],
+            ClubImportRow {
+                line: 2,
+                book: "Piranesi".to_string(),
+                author: Some("Susanna Clarke".to_string()),
+                completion_date: Some("2024-03-15".to_string()),
+                rating: Some("4.5".to_string()),
+                suggested_by: Some("<@!123456789>".to_string()),
+            }
+        );
+        assert_eq!(rows[1].book, "9780316556347");
+        assert_eq!(rows[1].author, None);
+        assert!(parse_rows("author,rating\nSomeone,4\n").is_none());
+    }
+
+    #[test]
+    fn suggesters_can_be_mentions_or_ids() {
+        assert_eq!(parse_user_id("<@123>"), Some(123));
+        assert_eq!(parse_user_id("<@!123>"), Some(123));
+        assert_eq!(parse_user_id(" 123 "), Some(123));
+        assert_eq!(parse_user_id("@someone"), None);
+    }
+
+    #[test]
+    fn confidence_reflects_title_and_author_agreement() {
+        let authors = vec!["Frank Herbert".to_string()];
+        assert_eq!(
+            title_match_confidence("dune", Some("Herbert"), "Dune", &authors),
+            MatchConfidence::High
+        );
+        assert_eq!(
+            title_match_confidence(
+                "Dune",
+                Some("Frank Herbert"),
+                "Dune: Deluxe Edition",
+                &authors
+            ),
+            MatchConfidence::High
+        );
+        assert_eq!(
+            title_match_confidence("Dune", None, "Dune", &authors),
+            MatchConfidence::Medium
+        );
+        assert_eq!(
+            title_match_confidence("Dune", None, "Dune Messiah", &authors),
+            MatchConfidence::Low
+        );
+        assert_eq!(
+            title_match_confidence("Dune", Some("Brian Herbert"), "Dune", &authors),
+            MatchConfidence::Low
+        );
+        assert_eq!(
+            title_match_confidence("Piranesi", Some("Susanna Clarke"), "Dune", &authors),
+            MatchConfidence::Low
+        );
+    }
+}
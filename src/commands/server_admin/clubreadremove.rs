@@ -1,4 +1,4 @@
-use crate::util::{detect_query_mode, normalize_isbn};
+use crate::util::{autocomplete_club, detect_query_mode, normalize_isbn, resolve_club};
 use crate::{types::Context, types::Error, types::QueryMode};
 use poise::futures_util::StreamExt;
 use poise::serenity_prelude::{
@@ -21,18 +21,24 @@ pub async fn clubreadremove(
     ctx: Context<'_>,
     #[description = "Title, ISBN, or exact Google Books volume ID"] book: String,
     #[description = "Author name (optional; used when searching by title)"] author: Option<String>,
+    #[description = "Club to remove the book from (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
 ) -> Result<(), Error> {
     ctx.defer().await?;
 
     let pool = &ctx.data().database;
     let google_books = &ctx.data().google_books;
-    let Some(guild_id) = ctx.guild_id() else {
+    if ctx.guild_id().is_none() {
         let embed = CreateEmbed::default()
             .title("❌ Error")
             .description("This command must be used in a server.")
             .color(0xB76E79);
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
     };
 
     // First, try direct volume_id lookup for backwards compatibility
@@ -42,10 +48,10 @@ pub async fn clubreadremove(
     let direct_results = sqlx::query!(
         r#"
         SELECT completed_id FROM server_completed_books
-        WHERE server_id = $1 AND volume_id = $2
+        WHERE club_id = $1 AND volume_id = $2
         LIMIT 1
         "#,
-        guild_id.get() as i64,
+        club.club_id,
         book
     )
     .fetch_optional(pool)
@@ -139,7 +145,7 @@ pub async fn clubreadremove(
                 volume_id,
                 completed_at
             FROM server_completed_books
-            WHERE server_id = $1
+            WHERE club_id = $1
               AND volume_id = $2
             ORDER BY completed_at DESC
             LIMIT 10
@@ -153,7 +159,7 @@ pub async fn clubreadremove(
                 volume_id,
                 completed_at
             FROM server_completed_books
-            WHERE server_id = $1
+            WHERE club_id = $1
               AND volume_id IN ($2{})
             ORDER BY completed_at DESC
             LIMIT 10
@@ -166,7 +172,7 @@ pub async fn clubreadremove(
         )
     };
 
-    let mut query = sqlx::query(&query_str).bind(guild_id.get() as i64);
+    let mut query = sqlx::query(&query_str).bind(club.club_id);
 
     for volume_id in &volume_ids_to_search {
         query = query.bind(volume_id);
@@ -269,10 +275,10 @@ pub async fn clubreadremove(
                 let deleted = sqlx::query!(
                     r#"
                     DELETE FROM server_completed_books
-                    WHERE server_id = $1 AND completed_id = $2
+                    WHERE club_id = $1 AND completed_id = $2
                     RETURNING volume_id
                     "#,
-                    guild_id.get() as i64,
+                    club.club_id,
                     b.completed_id
                 )
                 .fetch_optional(pool)
@@ -458,10 +464,10 @@ pub async fn clubreadremove(
                 let deleted = sqlx::query!(
                     r#"
                     DELETE FROM server_completed_books
-                    WHERE server_id = $1 AND completed_id = $2
+                    WHERE club_id = $1 AND completed_id = $2
                     RETURNING volume_id
                     "#,
-                    guild_id.get() as i64,
+                    club.club_id,
                     completed_id
                 )
                 .fetch_optional(pool)
@@ -1,15 +1,32 @@
+use crate::commands::server_user::wrapped::recap_year_for_post;
 use crate::ensure_server_exists;
 use crate::util::{
-    auto_complete_on_deadline_enabled, get_guild_name, pin_polls_enabled, queue_commands_enabled,
+    AutoPollConfig, ListLimits, QueueRules, SelectionRules, auto_complete_on_deadline_enabled,
+    auto_poll_config, autocomplete_club, club_announcement_channel, get_guild_name, list_limits,
+    pin_polls_enabled, queue_commands_enabled, queue_rules, queue_ttl_days,
+    ratings_participants_only, resolve_club, selection_rules, wrapped_post_date,
 };
 use crate::{types::Context, types::Error};
+use chrono::{Datelike, NaiveDate};
 use poise::serenity_prelude::{
     Channel, ChannelId, ChannelType, CreateEmbed, CreateEmbedFooter, Mentionable,
 };
+use sqlx::types::chrono::Utc;
 
 #[poise::command(
     slash_command,
-    subcommands("announcement", "queue", "pinning", "deadline", "mature"),
+    subcommands(
+        "announcement",
+        "queue",
+        "lists",
+        "pinning",
+        "ratings",
+        "deadline",
+        "autopoll",
+        "selection",
+        "wrapped",
+        "mature"
+    ),
     guild_only,
     required_permissions = "MANAGE_GUILD",
     description_localized(
@@ -50,6 +67,9 @@ async fn announcement(_ctx: Context<'_>) -> Result<(), Error> {
 async fn set(
     ctx: Context<'_>,
     #[description = "Channel where the bot should post announcements"] channel: Channel,
+    #[description = "Club whose announcements go to this channel (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
 ) -> Result<(), Error> {
     ctx.defer().await?;
 
@@ -61,6 +81,9 @@ async fn set(
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
     };
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
 
     let guild_channel = match channel {
         Channel::Guild(channel) => channel,
@@ -96,22 +119,17 @@ async fn set(
     };
 
     let pool = &ctx.data().database;
-    let guild_name = get_guild_name(&ctx).await;
-    ensure_server_exists(pool, guild_id, &guild_name).await?;
 
     sqlx::query!(
-        "INSERT INTO server_bot_config (server_id, announcement_channel_id)
-         VALUES ($1, $2)
-         ON CONFLICT (server_id)
-         DO UPDATE SET announcement_channel_id = $2, updated_at = CURRENT_TIMESTAMP",
-        guild_id.get() as i64,
+        "UPDATE server_clubs SET announcement_channel_id = $2 WHERE club_id = $1",
+        club.club_id,
         channel_id.get() as i64
     )
     .execute(pool)
     .await?;
 
     let embed = CreateEmbed::default()
-        .title("✅ Announcement Channel Set")
+        .title(format!("✅ Announcement Channel Set{}", club.title_suffix()))
         .description(format!(
             "I'll share book announcements in {} from now on.\n\nPlease make sure that Fumiko has the necessary permissions to view the channel you have set and send messages in it!",
             channel_id.mention()
@@ -134,51 +152,55 @@ async fn set(
     ),
     user_cooldown = 10
 )]
-async fn clear(ctx: Context<'_>) -> Result<(), Error> {
+async fn clear(
+    ctx: Context<'_>,
+    #[description = "Club to clear the announcement channel for (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
     ctx.defer().await?;
 
-    let Some(guild_id) = ctx.guild_id() else {
+    if ctx.guild_id().is_none() {
         let embed = CreateEmbed::default()
             .title("❌ Error")
             .description("This command must be used in a server.")
             .color(0xB76E79);
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
     };
 
     let pool = &ctx.data().database;
 
-    let existing = sqlx::query!(
-        "SELECT announcement_channel_id FROM server_bot_config WHERE server_id = $1",
-        guild_id.get() as i64
-    )
-    .fetch_optional(pool)
-    .await?;
-
-    if existing
-        .as_ref()
-        .and_then(|row| row.announcement_channel_id)
+    if club_announcement_channel(pool, club.club_id)
+        .await?
         .is_none()
     {
         let embed = CreateEmbed::default()
             .title("No Announcement Channel Set")
-            .description("Use `/config announcement set` to choose a channel for announcements.")
+            .description(format!(
+                "Use `/config announcement set{}` to choose a channel for announcements.",
+                club.command_option()
+            ))
             .color(0xB76E79);
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
     }
 
     sqlx::query!(
-        "UPDATE server_bot_config
-         SET announcement_channel_id = NULL, updated_at = CURRENT_TIMESTAMP
-         WHERE server_id = $1",
-        guild_id.get() as i64
+        "UPDATE server_clubs SET announcement_channel_id = NULL WHERE club_id = $1",
+        club.club_id
     )
     .execute(pool)
     .await?;
 
     let embed = CreateEmbed::default()
-        .title("Announcement Channel Cleared")
+        .title(format!(
+            "Announcement Channel Cleared{}",
+            club.title_suffix()
+        ))
         .description(
             "Announcements will no longer be posted automatically until a new channel is set.",
         )
@@ -200,41 +222,42 @@ async fn clear(ctx: Context<'_>) -> Result<(), Error> {
     ),
     user_cooldown = 10
 )]
-async fn status(ctx: Context<'_>) -> Result<(), Error> {
+async fn status(
+    ctx: Context<'_>,
+    #[description = "Club to check (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
     ctx.defer().await?;
 
-    let Some(guild_id) = ctx.guild_id() else {
+    if ctx.guild_id().is_none() {
         let embed = CreateEmbed::default()
             .title("❌ Error")
             .description("This command must be used in a server.")
             .color(0xB76E79);
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
     };
 
     let pool = &ctx.data().database;
 
-    let current = sqlx::query!(
-        "SELECT announcement_channel_id FROM server_bot_config WHERE server_id = $1",
-        guild_id.get() as i64
-    )
-    .fetch_optional(pool)
-    .await?;
-
-    let embed = if let Some(channel_id) = current.and_then(|row| row.announcement_channel_id) {
-        let channel_id = ChannelId::new(channel_id as u64);
+    let embed = if let Some(channel_id) = club_announcement_channel(pool, club.club_id).await? {
         CreateEmbed::default()
-            .title("Announcement Channel Configured")
+            .title(format!("Announcement Channel Configured{}", club.title_suffix()))
             .description(format!(
-                "Announcements are currently sent to {}. Use `/config announcement clear` to remove it. Make sure Fumiko has permissions to view this channel and send messages in it!",
-                channel_id.mention()
+                "Announcements are currently sent to {}. Use `/config announcement clear{}` to remove it. Make sure Fumiko has permissions to view this channel and send messages in it!",
+                channel_id.mention(),
+                club.command_option()
             ))
             .color(0xB76E79)
             .footer(CreateEmbedFooter::new("Fumiko Book Club Bot"))
     } else {
         CreateEmbed::default()
-            .title("Announcement Channel Not Set")
-            .description("No announcement channel is configured. Use `/config announcement set` to choose one.")
+            .title(format!("Announcement Channel Not Set{}", club.title_suffix()))
+            .description(format!("No announcement channel is configured. Use `/config announcement set{}` to choose one.", club.command_option()))
             .color(0xB76E79)
             .footer(CreateEmbedFooter::new("Fumiko Book Club Bot"))
     };
@@ -270,23 +293,29 @@ async fn deadline(_ctx: Context<'_>) -> Result<(), Error> {
     ),
     user_cooldown = 10
 )]
-async fn deadline_enable(ctx: Context<'_>) -> Result<(), Error> {
+async fn deadline_enable(
+    ctx: Context<'_>,
+    #[description = "Club to change (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
     ctx.defer().await?;
 
-    let Some(guild_id) = ctx.guild_id() else {
+    if ctx.guild_id().is_none() {
         let embed = CreateEmbed::default()
             .title("❌ Error")
             .description("This command must be used in a server.")
             .color(0xB76E79);
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
     };
 
     let pool = &ctx.data().database;
-    let guild_name = get_guild_name(&ctx).await;
-    ensure_server_exists(pool, guild_id, &guild_name).await?;
 
-    if auto_complete_on_deadline_enabled(pool, guild_id.get() as i64).await? {
+    if auto_complete_on_deadline_enabled(pool, club.club_id).await? {
         let embed = CreateEmbed::default()
             .title("Deadline Auto-Completion Already Enabled")
             .description(
@@ -298,18 +327,15 @@ async fn deadline_enable(ctx: Context<'_>) -> Result<(), Error> {
     }
 
     sqlx::query!(
-        "INSERT INTO server_bot_config (server_id, auto_complete_on_deadline)
-         VALUES ($1, $2)
-         ON CONFLICT (server_id)
-         DO UPDATE SET auto_complete_on_deadline = $2, updated_at = CURRENT_TIMESTAMP",
-        guild_id.get() as i64,
+        "UPDATE server_clubs SET auto_complete_on_deadline = $2 WHERE club_id = $1",
+        club.club_id,
         true
     )
     .execute(pool)
     .await?;
 
     let embed = CreateEmbed::default()
-        .title("Deadline Auto-Completion Enabled")
+        .title(format!("Deadline Auto-Completion Enabled{}", club.title_suffix()))
         .description(
             "When a selected book reaches its deadline, I'll mark it as finished and start the rating poll automatically.",
         )
@@ -333,21 +359,29 @@ async fn deadline_enable(ctx: Context<'_>) -> Result<(), Error> {
     ),
     user_cooldown = 10
 )]
-async fn deadline_disable(ctx: Context<'_>) -> Result<(), Error> {
+async fn deadline_disable(
+    ctx: Context<'_>,
+    #[description = "Club to change (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
     ctx.defer().await?;
 
-    let Some(guild_id) = ctx.guild_id() else {
+    if ctx.guild_id().is_none() {
         let embed = CreateEmbed::default()
             .title("❌ Error")
             .description("This command must be used in a server.")
             .color(0xB76E79);
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
     };
 
     let pool = &ctx.data().database;
 
-    if !auto_complete_on_deadline_enabled(pool, guild_id.get() as i64).await? {
+    if !auto_complete_on_deadline_enabled(pool, club.club_id).await? {
         let embed = CreateEmbed::default()
             .title("Deadline Auto-Completion Already Disabled")
             .description(
@@ -359,18 +393,15 @@ async fn deadline_disable(ctx: Context<'_>) -> Result<(), Error> {
     }
 
     sqlx::query!(
-        "INSERT INTO server_bot_config (server_id, auto_complete_on_deadline)
-         VALUES ($1, $2)
-         ON CONFLICT (server_id)
-         DO UPDATE SET auto_complete_on_deadline = $2, updated_at = CURRENT_TIMESTAMP",
-        guild_id.get() as i64,
+        "UPDATE server_clubs SET auto_complete_on_deadline = $2 WHERE club_id = $1",
+        club.club_id,
         false
     )
     .execute(pool)
     .await?;
 
     let embed = CreateEmbed::default()
-        .title("Deadline Auto-Completion Disabled")
+        .title(format!("Deadline Auto-Completion Disabled{}", club.title_suffix()))
         .description(
             "I'll leave the current book alone when its deadline passes. Run `/finishbook` whenever you're ready to wrap it up.",
         )
@@ -392,24 +423,32 @@ async fn deadline_disable(ctx: Context<'_>) -> Result<(), Error> {
     ),
     user_cooldown = 10
 )]
-async fn deadline_status(ctx: Context<'_>) -> Result<(), Error> {
+async fn deadline_status(
+    ctx: Context<'_>,
+    #[description = "Club to check (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
     ctx.defer().await?;
 
-    let Some(guild_id) = ctx.guild_id() else {
+    if ctx.guild_id().is_none() {
         let embed = CreateEmbed::default()
             .title("❌ Error")
             .description("This command must be used in a server.")
             .color(0xB76E79);
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
     };
 
     let pool = &ctx.data().database;
-    let enabled = auto_complete_on_deadline_enabled(pool, guild_id.get() as i64).await?;
+    let enabled = auto_complete_on_deadline_enabled(pool, club.club_id).await?;
 
     let embed = if enabled {
         CreateEmbed::default()
-            .title("✅ Deadline Auto-Completion Enabled")
+            .title(format!("✅ Deadline Auto-Completion Enabled{}", club.title_suffix()))
             .description(
                 "Books with deadlines will finish automatically when the date arrives and a rating poll will be created.",
             )
@@ -419,7 +458,7 @@ async fn deadline_status(ctx: Context<'_>) -> Result<(), Error> {
             ))
     } else {
         CreateEmbed::default()
-            .title("Deadline Auto-Completion Disabled")
+            .title(format!("Deadline Auto-Completion Disabled{}", club.title_suffix()))
             .description(
                 "Deadlines are informational only right now. Run `/config deadline enable` if you'd like me to finish books automatically.",
             )
@@ -433,7 +472,13 @@ async fn deadline_status(ctx: Context<'_>) -> Result<(), Error> {
 
 #[poise::command(
     slash_command,
-    subcommands("queue_disable", "queue_enable", "queue_status"),
+    subcommands(
+        "queue_disable",
+        "queue_enable",
+        "queue_limits",
+        "queue_expiry",
+        "queue_status"
+    ),
     guild_only,
     required_permissions = "MANAGE_GUILD",
     description_localized(
@@ -591,6 +636,7 @@ async fn queue_status(ctx: Context<'_>) -> Result<(), Error> {
 
     let pool = &ctx.data().database;
     let enabled = queue_commands_enabled(pool, guild_id.get() as i64).await?;
+    let rules = queue_rules(pool, guild_id.get() as i64).await?;
 
     let embed = if enabled {
         CreateEmbed::default()
@@ -609,6 +655,14 @@ async fn queue_status(ctx: Context<'_>) -> Result<(), Error> {
             .color(0xB76E79)
             .footer(CreateEmbedFooter::new("Configured via /config queue"))
     };
+    let embed = add_queue_rule_fields(embed, &rules).field(
+        "Entry Expiry",
+        match queue_ttl_days(pool, guild_id.get() as i64).await? {
+            Some(days) => format!("After {} day(s)", days),
+            None => "Never".to_string(),
+        },
+        true,
+    );
 
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
@@ -616,31 +670,118 @@ async fn queue_status(ctx: Context<'_>) -> Result<(), Error> {
 
 #[poise::command(
     slash_command,
-    subcommands("pinning_disable", "pinning_enable", "pinning_status"),
+    rename = "expiry",
     guild_only,
     required_permissions = "MANAGE_GUILD",
     description_localized(
         "en-US",
-        "Control whether /finishbook and /select messages are pinned (requires Manage Server)",
+        "Expire queue entries after N days; leave empty to keep them forever (requires Manage Server)",
     ),
     user_cooldown = 10
 )]
-async fn pinning(_ctx: Context<'_>) -> Result<(), Error> {
+async fn queue_expiry(
+    ctx: Context<'_>,
+    #[description = "Days a suggestion stays in the queue before it's removed (empty = never)"]
+    #[min = 1]
+    #[max = 730]
+    days: Option<i32>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+    let guild_name = get_guild_name(&ctx).await;
+    ensure_server_exists(pool, guild_id, &guild_name).await?;
+
+    let days = days.map(|d| d.clamp(1, 730));
+
+    sqlx::query!(
+        "INSERT INTO server_bot_config (server_id, queue_ttl_days)
+         VALUES ($1, $2)
+         ON CONFLICT (server_id)
+         DO UPDATE SET queue_ttl_days = $2, updated_at = CURRENT_TIMESTAMP",
+        guild_id.get() as i64,
+        days
+    )
+    .execute(pool)
+    .await?;
+
+    let embed = match days {
+        Some(days) => CreateEmbed::default()
+            .title("✅ Queue Expiry Enabled")
+            .description(format!(
+                "Suggestions are removed after **{}** day(s) in the queue, or when the member who suggested them leaves the server. Suggesters get a DM with a renew button before their book expires, and removals are logged in the announcement channel.",
+                days
+            ))
+            .color(0xB76E79)
+            .footer(CreateEmbedFooter::new("Fumiko Book Club Bot")),
+        None => CreateEmbed::default()
+            .title("Queue Expiry Disabled")
+            .description("Suggestions stay in the queue until they're selected or removed.")
+            .color(0xB76E79)
+            .footer(CreateEmbedFooter::new("Fumiko Book Club Bot")),
+    };
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
 }
 
+fn add_queue_rule_fields(embed: CreateEmbed, rules: &QueueRules) -> CreateEmbed {
+    embed
+        .field("Books per Member", rules.max_per_user.to_string(), true)
+        .field(
+            "Cooldown After a Pick Is Read",
+            if rules.cooldown_days == 0 {
+                "None".to_string()
+            } else {
+                format!("{} day(s)", rules.cooldown_days)
+            },
+            true,
+        )
+        .field(
+            "Re-suggesting Finished Books",
+            if rules.block_completed {
+                "Blocked"
+            } else {
+                "Allowed"
+            },
+            true,
+        )
+}
+
 #[poise::command(
     slash_command,
-    rename = "disable",
+    rename = "limits",
     guild_only,
     required_permissions = "MANAGE_GUILD",
     description_localized(
         "en-US",
-        "Stop pinning poll and announcement messages (requires Manage Server)",
+        "Set /queue add limits; /adminqueue add ignores them (requires Manage Server)",
     ),
     user_cooldown = 10
 )]
-async fn pinning_disable(ctx: Context<'_>) -> Result<(), Error> {
+async fn queue_limits(
+    ctx: Context<'_>,
+    #[description = "How many books each member can have in the queue (1–25)"]
+    #[min = 1]
+    #[max = 25]
+    max_per_member: Option<i32>,
+    #[description = "Days a member must wait to suggest again after their pick is read (0 = off)"]
+    #[min = 0]
+    #[max = 365]
+    cooldown_days: Option<i32>,
+    #[description = "Block suggesting books the club has already finished"] block_finished: Option<
+        bool,
+    >,
+) -> Result<(), Error> {
     ctx.defer().await?;
 
     let Some(guild_id) = ctx.guild_id() else {
@@ -656,36 +797,33 @@ async fn pinning_disable(ctx: Context<'_>) -> Result<(), Error> {
     let guild_name = get_guild_name(&ctx).await;
     ensure_server_exists(pool, guild_id, &guild_name).await?;
 
-    if !pin_polls_enabled(pool, guild_id.get() as i64).await? {
-        let embed = CreateEmbed::default()
-            .title("Pinning Already Disabled")
-            .description(
-                "Polls from `/finishbook` and `/select poll`, along with `/select` announcement messages, aren't being pinned right now.",
-            )
-            .color(0xB76E79)
-            .footer(CreateEmbedFooter::new("Fumiko Book Club Bot"));
-        ctx.send(poise::CreateReply::default().embed(embed)).await?;
-        return Ok(());
-    }
+    let current = queue_rules(pool, guild_id.get() as i64).await?;
+    let rules = QueueRules {
+        max_per_user: max_per_member.unwrap_or(current.max_per_user).clamp(1, 25),
+        cooldown_days: cooldown_days.unwrap_or(current.cooldown_days).clamp(0, 365),
+        block_completed: block_finished.unwrap_or(current.block_completed),
+    };
 
     sqlx::query!(
-        "INSERT INTO server_bot_config (server_id, pin_polls)
-         VALUES ($1, $2)
+        "INSERT INTO server_bot_config (server_id, queue_max_per_user, queue_cooldown_days, queue_block_completed)
+         VALUES ($1, $2, $3, $4)
          ON CONFLICT (server_id)
-         DO UPDATE SET pin_polls = $2, updated_at = CURRENT_TIMESTAMP",
+         DO UPDATE SET queue_max_per_user = $2, queue_cooldown_days = $3,
+             queue_block_completed = $4, updated_at = CURRENT_TIMESTAMP",
         guild_id.get() as i64,
-        false
+        rules.max_per_user,
+        rules.cooldown_days,
+        rules.block_completed
     )
     .execute(pool)
     .await?;
 
     let embed = CreateEmbed::default()
-        .title("Pinning Disabled")
-        .description(
-            "New polls and announcements from `/finishbook` and `/select` will no longer be pinned automatically.",
-        )
+        .title("✅ Queue Limits Updated")
+        .description("These limits apply to `/queue add`. Admins can still add anything with `/adminqueue add`.")
         .color(0xB76E79)
         .footer(CreateEmbedFooter::new("Fumiko Book Club Bot"));
+    let embed = add_queue_rule_fields(embed, &rules);
 
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
@@ -693,16 +831,53 @@ async fn pinning_disable(ctx: Context<'_>) -> Result<(), Error> {
 
 #[poise::command(
     slash_command,
-    rename = "enable",
+    subcommands("lists_limits", "lists_status"),
     guild_only,
     required_permissions = "MANAGE_GUILD",
     description_localized(
         "en-US",
-        "Let the bot pin poll and /select announcements (requires Manage Server)",
+        "Configure how many favorites and reading list books members can keep (requires Manage Server)",
+    ),
+    user_cooldown = 10
+)]
+async fn lists(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+fn add_list_limit_fields(embed: CreateEmbed, limits: &ListLimits) -> CreateEmbed {
+    embed
+        .field("Favorite Books", limits.favorite_books.to_string(), true)
+        .field(
+            "Favorite Authors",
+            limits.favorite_authors.to_string(),
+            true,
+        )
+        .field("Reading List", limits.reading_list.to_string(), true)
+}
+
+#[poise::command(
+    slash_command,
+    rename = "limits",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized(
+        "en-US",
+        "Set per-member limits for favorites and reading lists (requires Manage Server)",
     ),
     user_cooldown = 10
 )]
-async fn pinning_enable(ctx: Context<'_>) -> Result<(), Error> {
+async fn lists_limits(
+    ctx: Context<'_>,
+    #[description = "Favorite books each member can keep"]
+    #[min = 1]
+    favorite_books: Option<i32>,
+    #[description = "Favorite authors each member can keep"]
+    #[min = 1]
+    favorite_authors: Option<i32>,
+    #[description = "Books each member can keep on their reading list"]
+    #[min = 1]
+    reading_list: Option<i32>,
+) -> Result<(), Error> {
     ctx.defer().await?;
 
     let Some(guild_id) = ctx.guild_id() else {
@@ -715,39 +890,218 @@ async fn pinning_enable(ctx: Context<'_>) -> Result<(), Error> {
     };
 
     let pool = &ctx.data().database;
+    let cap = ctx.data().list_limit_cap;
     let guild_name = get_guild_name(&ctx).await;
     ensure_server_exists(pool, guild_id, &guild_name).await?;
 
-    if pin_polls_enabled(pool, guild_id.get() as i64).await? {
+    let requested = [favorite_books, favorite_authors, reading_list];
+    if requested.iter().flatten().any(|limit| *limit > cap) {
         let embed = CreateEmbed::default()
-            .title("Pinning Already Enabled")
-            .description(
-                "`/finishbook` and `/select` polls nad announcements are already being pinned automatically when created.",
-            )
-            .color(0xB76E79)
-            .footer(CreateEmbedFooter::new("Fumiko Book Club Bot"));
+            .title("❌ Limit Too High")
+            .description(format!(
+                "This bot allows at most {} entries per list. Pick a smaller limit.",
+                cap
+            ))
+            .color(0xB76E79);
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
     }
 
+    let current = list_limits(pool, guild_id.get() as i64, cap).await?;
+    let limits = ListLimits {
+        favorite_books: favorite_books.unwrap_or(current.favorite_books).max(1),
+        favorite_authors: favorite_authors.unwrap_or(current.favorite_authors).max(1),
+        reading_list: reading_list.unwrap_or(current.reading_list).max(1),
+    };
+
     sqlx::query!(
-        "INSERT INTO server_bot_config (server_id, pin_polls)
-         VALUES ($1, $2)
+        "INSERT INTO server_bot_config (server_id, favorite_books_limit, favorite_authors_limit, reading_list_limit)
+         VALUES ($1, $2, $3, $4)
          ON CONFLICT (server_id)
-         DO UPDATE SET pin_polls = $2, updated_at = CURRENT_TIMESTAMP",
+         DO UPDATE SET favorite_books_limit = $2, favorite_authors_limit = $3,
+             reading_list_limit = $4, updated_at = CURRENT_TIMESTAMP",
         guild_id.get() as i64,
-        true
+        limits.favorite_books,
+        limits.favorite_authors,
+        limits.reading_list
     )
     .execute(pool)
     .await?;
 
     let embed = CreateEmbed::default()
-        .title("Pinning Enabled")
-        .description(
-            "I'll pin new polls and announcements from `/finishbook` and `/select` automatically.",
+        .title("✅ List Limits Updated")
+        .description("Members who are already over a new limit keep their entries but can't add more until they're under it.")
+        .color(0xB76E79)
+        .footer(CreateEmbedFooter::new(format!(
+            "Highest allowed limit: {}",
+            cap
+        )));
+    let embed = add_list_limit_fields(embed, &limits);
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    rename = "status",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized(
+        "en-US",
+        "View the per-member limits for favorites and reading lists (requires Manage Server)",
+    ),
+    user_cooldown = 10
+)]
+async fn lists_status(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+    let cap = ctx.data().list_limit_cap;
+    let limits = list_limits(pool, guild_id.get() as i64, cap).await?;
+
+    let embed = CreateEmbed::default()
+        .title("List Limits")
+        .description("How many entries each member can keep in this server.")
+        .color(0xB76E79)
+        .footer(CreateEmbedFooter::new(format!(
+            "Highest allowed limit: {} • Change with /config lists limits",
+            cap
+        )));
+    let embed = add_list_limit_fields(embed, &limits);
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("selection_set_rules", "selection_status"),
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized("en-US", "Set rules that keep /select and selection polls fair",),
+    user_cooldown = 10
+)]
+async fn selection(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+fn add_selection_rule_fields(embed: CreateEmbed, rules: &SelectionRules) -> CreateEmbed {
+    embed
+        .field(
+            "Recent Suggesters",
+            if rules.skip_recent_suggesters == 0 {
+                "Allowed".to_string()
+            } else {
+                format!(
+                    "Skipped if their pick was one of the last {} book(s)",
+                    rules.skip_recent_suggesters
+                )
+            },
+            false,
+        )
+        .field(
+            "Repeat Categories",
+            if rules.avoid_repeat_category {
+                "Skipped if they match the last book's category"
+            } else {
+                "Allowed"
+            },
+            false,
         )
+        .field(
+            "Page Limit",
+            match rules.max_pages {
+                Some(pages) => format!("{} pages", pages),
+                None => "None".to_string(),
+            },
+            false,
+        )
+}
+
+#[poise::command(
+    slash_command,
+    rename = "rules",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized(
+        "en-US",
+        "Choose which queued books /select and selection polls skip (requires Manage Server)",
+    ),
+    user_cooldown = 10
+)]
+async fn selection_set_rules(
+    ctx: Context<'_>,
+    #[description = "Skip members whose pick was one of the last N books read (0 = off)"]
+    #[min = 0]
+    #[max = 20]
+    recent_suggesters: Option<i32>,
+    #[description = "Skip books in the same category as the last book"]
+    avoid_repeat_category: Option<bool>,
+    #[description = "Skip books longer than this many pages (0 = no limit)"]
+    #[min = 0]
+    #[max = 5000]
+    max_pages: Option<i32>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+    let guild_name = get_guild_name(&ctx).await;
+    ensure_server_exists(pool, guild_id, &guild_name).await?;
+
+    let current = selection_rules(pool, guild_id.get() as i64).await?;
+    let rules = SelectionRules {
+        skip_recent_suggesters: recent_suggesters
+            .unwrap_or(current.skip_recent_suggesters)
+            .clamp(0, 20),
+        avoid_repeat_category: avoid_repeat_category.unwrap_or(current.avoid_repeat_category),
+        max_pages: match max_pages {
+            Some(0) => None,
+            Some(pages) => Some(pages.clamp(1, 5000)),
+            None => current.max_pages,
+        },
+    };
+
+    sqlx::query!(
+        "INSERT INTO server_bot_config (server_id, selection_skip_recent_suggesters,
+             selection_avoid_repeat_category, selection_max_pages)
+         VALUES ($1, $2, $3, $4)
+         ON CONFLICT (server_id)
+         DO UPDATE SET selection_skip_recent_suggesters = $2,
+             selection_avoid_repeat_category = $3, selection_max_pages = $4,
+             updated_at = CURRENT_TIMESTAMP",
+        guild_id.get() as i64,
+        rules.skip_recent_suggesters,
+        rules.avoid_repeat_category,
+        rules.max_pages
+    )
+    .execute(pool)
+    .await?;
+
+    let embed = CreateEmbed::default()
+        .title("✅ Selection Rules Updated")
+        .description("These rules apply to `/select next`, `/select random`, `/select top` and selection polls. Pinned books and `/select manual` ignore them, and every pick lists the books that were skipped.")
         .color(0xB76E79)
         .footer(CreateEmbedFooter::new("Fumiko Book Club Bot"));
+    let embed = add_selection_rule_fields(embed, &rules);
 
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
@@ -760,11 +1114,11 @@ async fn pinning_enable(ctx: Context<'_>) -> Result<(), Error> {
     required_permissions = "MANAGE_GUILD",
     description_localized(
         "en-US",
-        "See whether polls and /select announcements are pinned (requires Manage Server)",
+        "View the rules /select and selection polls follow (requires Manage Server)",
     ),
     user_cooldown = 10
 )]
-async fn pinning_status(ctx: Context<'_>) -> Result<(), Error> {
+async fn selection_status(ctx: Context<'_>) -> Result<(), Error> {
     ctx.defer().await?;
 
     let Some(guild_id) = ctx.guild_id() else {
@@ -777,26 +1131,907 @@ async fn pinning_status(ctx: Context<'_>) -> Result<(), Error> {
     };
 
     let pool = &ctx.data().database;
-    let enabled = pin_polls_enabled(pool, guild_id.get() as i64).await?;
+    let rules = selection_rules(pool, guild_id.get() as i64).await?;
 
-    let embed = if enabled {
+    let embed = if rules.any_active() {
         CreateEmbed::default()
-            .title("Pinning Enabled")
-            .description(
-                "`/finishbook` and `/select` polls and announcements are currently pinned automatically.",
-            )
+            .title("⚖️ Selection Rules")
+            .description("Queued books that break these rules are skipped when a book is picked. Pinned books are always eligible.")
             .color(0xB76E79)
-            .footer(CreateEmbedFooter::new(
-                "Use `/config pinning disable` to stop pinning",
-            ))
+            .footer(CreateEmbedFooter::new("Configured via /config selection rules"))
     } else {
         CreateEmbed::default()
-            .title("Pinning Disabled")
-            .description(
-                "`/finishbook` and `/select` polls and announcements will stay unpinned until you re-enable it.",
-            )
+            .title("No Selection Rules")
+            .description("Every queued book is eligible. Use `/config selection rules` to skip recent suggesters, repeat categories, or long books.")
             .color(0xB76E79)
-            .footer(CreateEmbedFooter::new("Use `/config pinning enable` to resume"))
+            .footer(CreateEmbedFooter::new("Configured via /config selection rules"))
+    };
+    let embed = add_selection_rule_fields(embed, &rules);
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("autopoll_enable", "autopoll_disable", "autopoll_status"),
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized(
+        "en-US",
+        "Open a selection poll automatically after each book finishes",
+    ),
+    user_cooldown = 10
+)]
+async fn autopoll(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+fn add_auto_poll_fields(embed: CreateEmbed, config: &AutoPollConfig) -> CreateEmbed {
+    embed
+        .field(
+            "Opens",
+            if config.delay_hours == 0 {
+                "Right after a book finishes".to_string()
+            } else {
+                format!("{} hour(s) after a book finishes", config.delay_hours)
+            },
+            true,
+        )
+        .field("Candidates", config.poll_size.to_string(), true)
+        .field(
+            "Poll Length",
+            format!("{} hour(s)", config.duration_hours),
+            true,
+        )
+        .field(
+            "Reading Time",
+            match config.reading_days {
+                Some(days) => format!("{} day(s) after the poll closes", days),
+                None => "No deadline".to_string(),
+            },
+            true,
+        )
+}
+
+#[poise::command(
+    slash_command,
+    rename = "enable",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized(
+        "en-US",
+        "Open a selection poll from the queue after each book finishes (requires Manage Server)",
+    ),
+    user_cooldown = 10
+)]
+async fn autopoll_enable(
+    ctx: Context<'_>,
+    #[description = "Hours to wait after a book finishes before opening the poll (0–168)"]
+    #[min = 0]
+    #[max = 168]
+    delay_hours: Option<i32>,
+    #[description = "Number of books in the poll (2–10)"]
+    #[min = 2]
+    #[max = 10]
+    size: Option<i32>,
+    #[description = "How long the poll stays open, in hours (1–167)"]
+    #[min = 1]
+    #[max = 167]
+    duration_hours: Option<i32>,
+    #[description = "Days members get to read the winner after the poll closes (sets a deadline)"]
+    #[min = 1]
+    #[max = 365]
+    reading_days: Option<i32>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+    let guild_name = get_guild_name(&ctx).await;
+    ensure_server_exists(pool, guild_id, &guild_name).await?;
+
+    let current = auto_poll_config(pool, guild_id.get() as i64).await?;
+    let config = AutoPollConfig {
+        enabled: true,
+        delay_hours: delay_hours.unwrap_or(current.delay_hours).clamp(0, 168),
+        poll_size: size.unwrap_or(current.poll_size).clamp(2, 10),
+        duration_hours: duration_hours
+            .unwrap_or(current.duration_hours)
+            .clamp(1, 167),
+        reading_days: reading_days
+            .or(current.reading_days)
+            .map(|d| d.clamp(1, 365)),
+    };
+
+    sqlx::query!(
+        "INSERT INTO server_bot_config (server_id, auto_poll_enabled, auto_poll_delay_hours,
+             auto_poll_size, auto_poll_duration_hours, auto_poll_reading_days)
+         VALUES ($1, TRUE, $2, $3, $4, $5)
+         ON CONFLICT (server_id)
+         DO UPDATE SET auto_poll_enabled = TRUE, auto_poll_delay_hours = $2, auto_poll_size = $3,
+             auto_poll_duration_hours = $4, auto_poll_reading_days = $5,
+             updated_at = CURRENT_TIMESTAMP",
+        guild_id.get() as i64,
+        config.delay_hours,
+        config.poll_size,
+        config.duration_hours,
+        config.reading_days
+    )
+    .execute(pool)
+    .await?;
+
+    let embed = CreateEmbed::default()
+        .title("✅ Automatic Selection Polls Enabled")
+        .description(
+            "When a book finishes, through `/finishbook` or its deadline, I'll open a selection poll from the queue in the announcement channel. If a poll is already running or a book has been picked, I'll skip it.",
+        )
+        .color(0xB76E79)
+        .footer(CreateEmbedFooter::new("Fumiko Book Club Bot"));
+    let embed = add_auto_poll_fields(embed, &config);
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    rename = "disable",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized(
+        "en-US",
+        "Stop opening selection polls automatically (requires Manage Server)",
+    ),
+    user_cooldown = 10
+)]
+async fn autopoll_disable(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+    let guild_name = get_guild_name(&ctx).await;
+    ensure_server_exists(pool, guild_id, &guild_name).await?;
+
+    sqlx::query!(
+        "INSERT INTO server_bot_config (server_id, auto_poll_enabled)
+         VALUES ($1, FALSE)
+         ON CONFLICT (server_id)
+         DO UPDATE SET auto_poll_enabled = FALSE, updated_at = CURRENT_TIMESTAMP",
+        guild_id.get() as i64
+    )
+    .execute(pool)
+    .await?;
+
+    // Drop a poll that was already waiting to open
+    sqlx::query!(
+        "DELETE FROM scheduled_selection_polls
+         WHERE club_id IN (SELECT club_id FROM server_clubs WHERE server_id = $1)",
+        guild_id.get() as i64
+    )
+    .execute(pool)
+    .await?;
+
+    let embed = CreateEmbed::default()
+        .title("Automatic Selection Polls Disabled")
+        .description("Start selection polls yourself with `/select poll`.")
+        .color(0xB76E79)
+        .footer(CreateEmbedFooter::new("Fumiko Book Club Bot"));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    rename = "status",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized(
+        "en-US",
+        "View automatic selection poll settings (requires Manage Server)",
+    ),
+    user_cooldown = 10
+)]
+async fn autopoll_status(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+    let config = auto_poll_config(pool, guild_id.get() as i64).await?;
+
+    let scheduled = sqlx::query!(
+        "SELECT sc.name, sc.is_primary, ssp.run_at
+         FROM scheduled_selection_polls ssp
+         JOIN server_clubs sc ON sc.club_id = ssp.club_id
+         WHERE sc.server_id = $1
+         ORDER BY ssp.run_at",
+        guild_id.get() as i64
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let embed = if config.enabled {
+        let mut embed = CreateEmbed::default()
+            .title("✅ Automatic Selection Polls Enabled")
+            .description("A selection poll opens from the queue after each book finishes.")
+            .color(0xB76E79)
+            .footer(CreateEmbedFooter::new(
+                "Use `/config autopoll disable` to turn this off",
+            ));
+        embed = add_auto_poll_fields(embed, &config);
+        if !scheduled.is_empty() {
+            let upcoming = scheduled
+                .iter()
+                .map(|row| {
+                    if row.is_primary {
+                        format!("<t:{}:R>", row.run_at.timestamp())
+                    } else {
+                        format!("{}: <t:{}:R>", row.name, row.run_at.timestamp())
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            embed = embed.field("Next Poll", upcoming, true);
+        }
+        embed
+    } else {
+        CreateEmbed::default()
+            .title("Automatic Selection Polls Disabled")
+            .description(
+                "Run `/config autopoll enable` to open a poll automatically after each book finishes.",
+            )
+            .color(0xB76E79)
+            .footer(CreateEmbedFooter::new("Configure with /config autopoll"))
+    };
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("pinning_disable", "pinning_enable", "pinning_status"),
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized(
+        "en-US",
+        "Control whether /finishbook and /select messages are pinned (requires Manage Server)",
+    ),
+    user_cooldown = 10
+)]
+async fn pinning(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    rename = "disable",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized(
+        "en-US",
+        "Stop pinning poll and announcement messages (requires Manage Server)",
+    ),
+    user_cooldown = 10
+)]
+async fn pinning_disable(
+    ctx: Context<'_>,
+    #[description = "Club to change (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    if ctx.guild_id().is_none() {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+
+    if !pin_polls_enabled(pool, club.club_id).await? {
+        let embed = CreateEmbed::default()
+            .title("Pinning Already Disabled")
+            .description(
+                "Polls from `/finishbook` and `/select poll`, along with `/select` announcement messages, aren't being pinned right now.",
+            )
+            .color(0xB76E79)
+            .footer(CreateEmbedFooter::new("Fumiko Book Club Bot"));
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    sqlx::query!(
+        "UPDATE server_clubs SET pin_polls = $2 WHERE club_id = $1",
+        club.club_id,
+        false
+    )
+    .execute(pool)
+    .await?;
+
+    let embed = CreateEmbed::default()
+        .title(format!("Pinning Disabled{}", club.title_suffix()))
+        .description(
+            "New polls and announcements from `/finishbook` and `/select` will no longer be pinned automatically.",
+        )
+        .color(0xB76E79)
+        .footer(CreateEmbedFooter::new("Fumiko Book Club Bot"));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    rename = "enable",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized(
+        "en-US",
+        "Let the bot pin poll and /select announcements (requires Manage Server)",
+    ),
+    user_cooldown = 10
+)]
+async fn pinning_enable(
+    ctx: Context<'_>,
+    #[description = "Club to change (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    if ctx.guild_id().is_none() {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+
+    if pin_polls_enabled(pool, club.club_id).await? {
+        let embed = CreateEmbed::default()
+            .title("Pinning Already Enabled")
+            .description(
+                "`/finishbook` and `/select` polls nad announcements are already being pinned automatically when created.",
+            )
+            .color(0xB76E79)
+            .footer(CreateEmbedFooter::new("Fumiko Book Club Bot"));
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    sqlx::query!(
+        "UPDATE server_clubs SET pin_polls = $2 WHERE club_id = $1",
+        club.club_id,
+        true
+    )
+    .execute(pool)
+    .await?;
+
+    let embed = CreateEmbed::default()
+        .title(format!("Pinning Enabled{}", club.title_suffix()))
+        .description(
+            "I'll pin new polls and announcements from `/finishbook` and `/select` automatically.",
+        )
+        .color(0xB76E79)
+        .footer(CreateEmbedFooter::new("Fumiko Book Club Bot"));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    rename = "status",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized(
+        "en-US",
+        "See whether polls and /select announcements are pinned (requires Manage Server)",
+    ),
+    user_cooldown = 10
+)]
+async fn pinning_status(
+    ctx: Context<'_>,
+    #[description = "Club to check (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    if ctx.guild_id().is_none() {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+    let enabled = pin_polls_enabled(pool, club.club_id).await?;
+
+    let embed = if enabled {
+        CreateEmbed::default()
+            .title(format!("Pinning Enabled{}", club.title_suffix()))
+            .description(
+                "`/finishbook` and `/select` polls and announcements are currently pinned automatically.",
+            )
+            .color(0xB76E79)
+            .footer(CreateEmbedFooter::new(
+                "Use `/config pinning disable` to stop pinning",
+            ))
+    } else {
+        CreateEmbed::default()
+            .title(format!("Pinning Disabled{}", club.title_suffix()))
+            .description(
+                "`/finishbook` and `/select` polls and announcements will stay unpinned until you re-enable it.",
+            )
+            .color(0xB76E79)
+            .footer(CreateEmbedFooter::new("Use `/config pinning enable` to resume"))
+    };
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("ratings_restrict", "ratings_open", "ratings_status"),
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized(
+        "en-US",
+        "Control who can rate finished books (requires Manage Server)",
+    ),
+    user_cooldown = 10
+)]
+async fn ratings(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    rename = "restrict",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized(
+        "en-US",
+        "Only count ratings from members who read along (requires Manage Server)",
+    ),
+    user_cooldown = 10
+)]
+async fn ratings_restrict(
+    ctx: Context<'_>,
+    #[description = "Club to change (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    if ctx.guild_id().is_none() {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+
+    if ratings_participants_only(pool, club.club_id).await? {
+        let embed = CreateEmbed::default()
+            .title("Ratings Already Restricted")
+            .description("Rating polls already only count votes from members who read along.")
+            .color(0xB76E79)
+            .footer(CreateEmbedFooter::new("Fumiko Book Club Bot"));
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    sqlx::query!(
+        "UPDATE server_clubs SET ratings_participants_only = $2 WHERE club_id = $1",
+        club.club_id,
+        true
+    )
+    .execute(pool)
+    .await?;
+
+    let embed = CreateEmbed::default()
+        .title(format!("Ratings Restricted{}", club.title_suffix()))
+        .description(
+            "Rating polls will only count votes from members who tracked their progress with `/progress` (including anyone who reported a DNF). Other votes are ignored.",
+        )
+        .color(0xB76E79)
+        .footer(CreateEmbedFooter::new("Fumiko Book Club Bot"));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    rename = "open",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized("en-US", "Let any member rate finished books (requires Manage Server)",),
+    user_cooldown = 10
+)]
+async fn ratings_open(
+    ctx: Context<'_>,
+    #[description = "Club to change (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    if ctx.guild_id().is_none() {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+
+    if !ratings_participants_only(pool, club.club_id).await? {
+        let embed = CreateEmbed::default()
+            .title("Ratings Already Open")
+            .description("Any member's vote in a rating poll already counts.")
+            .color(0xB76E79)
+            .footer(CreateEmbedFooter::new("Fumiko Book Club Bot"));
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    sqlx::query!(
+        "UPDATE server_clubs SET ratings_participants_only = $2 WHERE club_id = $1",
+        club.club_id,
+        false
+    )
+    .execute(pool)
+    .await?;
+
+    let embed = CreateEmbed::default()
+        .title(format!("Ratings Open{}", club.title_suffix()))
+        .description("Every member's vote in a rating poll will count again.")
+        .color(0xB76E79)
+        .footer(CreateEmbedFooter::new("Fumiko Book Club Bot"));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    rename = "status",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized("en-US", "See who can rate finished books (requires Manage Server)",),
+    user_cooldown = 10
+)]
+async fn ratings_status(
+    ctx: Context<'_>,
+    #[description = "Club to check (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    if ctx.guild_id().is_none() {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+
+    let embed = if ratings_participants_only(pool, club.club_id).await? {
+        CreateEmbed::default()
+            .title(format!("Ratings Restricted{}", club.title_suffix()))
+            .description(
+                "Rating polls only count votes from members who tracked their progress on the book.",
+            )
+            .color(0xB76E79)
+            .footer(CreateEmbedFooter::new(
+                "Use `/config ratings open` to count every vote",
+            ))
+    } else {
+        CreateEmbed::default()
+            .title(format!("Ratings Open{}", club.title_suffix()))
+            .description("Rating polls count every member's vote.")
+            .color(0xB76E79)
+            .footer(CreateEmbedFooter::new(
+                "Use `/config ratings restrict` to only count members who read along",
+            ))
+    };
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("wrapped_set", "wrapped_disable", "wrapped_status"),
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized(
+        "en-US",
+        "Schedule the yearly /wrapped recap post (requires Manage Server)",
+    ),
+    user_cooldown = 10
+)]
+async fn wrapped(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// The next time the recap goes out on or after `today`, given the posting month and day.
+fn next_wrapped_post(today: NaiveDate, month: u32, day: u32) -> Option<NaiveDate> {
+    let this_year = NaiveDate::from_ymd_opt(today.year(), month, day)?;
+    if this_year >= today {
+        Some(this_year)
+    } else {
+        NaiveDate::from_ymd_opt(today.year() + 1, month, day)
+    }
+}
+
+#[poise::command(
+    slash_command,
+    rename = "set",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized(
+        "en-US",
+        "Post every club's yearly recap on this date (requires Manage Server)",
+    ),
+    user_cooldown = 10
+)]
+async fn wrapped_set(
+    ctx: Context<'_>,
+    #[description = "Month to post in (1-6 recaps the previous year, 7-12 the current one)"]
+    #[min = 1]
+    #[max = 12]
+    month: i32,
+    #[description = "Day of the month to post on (defaults to the 1st)"]
+    #[min = 1]
+    #[max = 28]
+    day: Option<i32>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+    let guild_name = get_guild_name(&ctx).await;
+    ensure_server_exists(pool, guild_id, &guild_name).await?;
+
+    let month = month.clamp(1, 12);
+    let day = day.unwrap_or(1).clamp(1, 28);
+    let today = Utc::now().date_naive();
+    let Some(next_post) = next_wrapped_post(today, month as u32, day as u32) else {
+        return Ok(());
+    };
+
+    let mut tx = pool.begin().await?;
+    sqlx::query!(
+        "INSERT INTO server_bot_config (server_id, wrapped_post_month, wrapped_post_day)
+         VALUES ($1, $2, $3)
+         ON CONFLICT (server_id)
+         DO UPDATE SET wrapped_post_month = $2, wrapped_post_day = $3, updated_at = CURRENT_TIMESTAMP",
+        guild_id.get() as i64,
+        month,
+        day
+    )
+    .execute(&mut *tx)
+    .await?;
+    // A date that already passed this year waits for next year instead of posting right away
+    if next_post.year() > today.year() {
+        sqlx::query!(
+            "UPDATE server_clubs SET wrapped_posted_year = $2
+             WHERE server_id = $1 AND COALESCE(wrapped_posted_year, 0) < $2",
+            guild_id.get() as i64,
+            today.year()
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+
+    let embed = CreateEmbed::default()
+        .title("✅ Yearly Recap Scheduled")
+        .description(format!(
+            "Each club's `/wrapped` recap will be posted in its announcement channel every **{}**. The next one goes out on **{}** and covers **{}**.",
+            next_post.format("%B %-d"),
+            next_post.format("%B %-d, %Y"),
+            recap_year_for_post(month as u32, next_post.year())
+        ))
+        .color(0xB76E79)
+        .footer(CreateEmbedFooter::new(
+            "Clubs without an announcement channel are skipped",
+        ));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    rename = "disable",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized(
+        "en-US",
+        "Stop posting the yearly recap automatically (requires Manage Server)",
+    ),
+    user_cooldown = 10
+)]
+async fn wrapped_disable(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+
+    if wrapped_post_date(pool, guild_id.get() as i64)
+        .await?
+        .is_none()
+    {
+        let embed = CreateEmbed::default()
+            .title("Yearly Recap Already Off")
+            .description("The `/wrapped` recap isn't being posted automatically.")
+            .color(0xB76E79)
+            .footer(CreateEmbedFooter::new("Fumiko Book Club Bot"));
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    sqlx::query!(
+        "UPDATE server_bot_config
+         SET wrapped_post_month = NULL, wrapped_post_day = NULL, updated_at = CURRENT_TIMESTAMP
+         WHERE server_id = $1",
+        guild_id.get() as i64
+    )
+    .execute(pool)
+    .await?;
+
+    let embed = CreateEmbed::default()
+        .title("Yearly Recap Disabled")
+        .description(
+            "I'll stop posting `/wrapped` recaps. Members can still run `/wrapped` themselves.",
+        )
+        .color(0xB76E79)
+        .footer(CreateEmbedFooter::new("Fumiko Book Club Bot"));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    rename = "status",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized(
+        "en-US",
+        "See when the yearly recap is posted (requires Manage Server)",
+    ),
+    user_cooldown = 10
+)]
+async fn wrapped_status(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+    let today = Utc::now().date_naive();
+
+    let embed = match wrapped_post_date(pool, guild_id.get() as i64)
+        .await?
+        .and_then(|(month, day)| Some((month, next_wrapped_post(today, month, day)?)))
+    {
+        Some((month, next_post)) => CreateEmbed::default()
+            .title("Yearly Recap Scheduled")
+            .description(format!(
+                "Each club's `/wrapped` recap is posted in its announcement channel every **{}**.",
+                next_post.format("%B %-d")
+            ))
+            .field(
+                "Next Post",
+                format!(
+                    "{} (covers {})",
+                    next_post.format("%B %-d, %Y"),
+                    recap_year_for_post(month, next_post.year())
+                ),
+                true,
+            )
+            .color(0xB76E79)
+            .footer(CreateEmbedFooter::new(
+                "Use `/config wrapped disable` to turn it off",
+            )),
+        None => CreateEmbed::default()
+            .title("Yearly Recap Off")
+            .description("The `/wrapped` recap isn't posted automatically.")
+            .color(0xB76E79)
+            .footer(CreateEmbedFooter::new(
+                "Use `/config wrapped set` to schedule it",
+            )),
     };
 
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
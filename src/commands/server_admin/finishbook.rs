@@ -2,8 +2,11 @@ use crate::database_helpers::finish_book_transactional;
 use crate::maturity_check::{
     check_volume_maturity, current_channel_is_nsfw, server_maturity_enabled,
 };
-use crate::util::{log_error, log_error_with_source, pin_polls_enabled};
-use crate::{poll_handler, types::Context, types::Error};
+use crate::util::{
+    autocomplete_club, club_announcement_channel, log_error, log_error_with_source,
+    pin_polls_enabled, resolve_club,
+};
+use crate::{poll_handler, selection_poll_handler, types::Context, types::Error};
 use poise::serenity_prelude::{
     CreateEmbed, CreateEmbedFooter, CreateMessage, CreatePoll, CreatePollAnswer,
 };
@@ -19,7 +22,12 @@ use sqlx::types::chrono::Utc;
     ),
     user_cooldown = 10
 )]
-pub async fn finishbook(ctx: Context<'_>) -> Result<(), Error> {
+pub async fn finishbook(
+    ctx: Context<'_>,
+    #[description = "Club to finish the book for (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
     ctx.defer().await?;
 
     let pool = &ctx.data().database;
@@ -32,14 +40,27 @@ pub async fn finishbook(ctx: Context<'_>) -> Result<(), Error> {
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
     };
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
 
     // Use the database function to finish the book
-    match finish_book_transactional(pool, guild_id.get() as i64).await {
+    match finish_book_transactional(pool, club.club_id).await {
         Ok(book_info) => {
             let volume_id = book_info.volume_id.unwrap_or("Unknown".to_string());
             let started_at = book_info.started_at.unwrap();
             let completed_id = book_info.completed_id.unwrap();
 
+            if let Err(err) = selection_poll_handler::schedule_auto_poll(
+                pool,
+                club.club_id,
+                Some(ctx.channel_id().get() as i64),
+            )
+            .await
+            {
+                log_error_with_source("Couldn't schedule automatic selection poll", &err);
+            }
+
             // Fetch book details from Google Books
             let volume = google_books.get_volume(&volume_id).await;
 
@@ -76,7 +97,7 @@ pub async fn finishbook(ctx: Context<'_>) -> Result<(), Error> {
 
             // Create completion announcement embed
             let mut embed = CreateEmbed::default()
-                .title("Book Completed!")
+                .title(format!("Book Completed!{}", club.title_suffix()))
                 .field("Title", &book_title, false)
                 .field("Reading Duration", format!("{} days", days), true)
                 .color(0xB76E79)
@@ -109,19 +130,11 @@ pub async fn finishbook(ctx: Context<'_>) -> Result<(), Error> {
                 );
 
             // Check if there's an announcement channel configured
-            let config = sqlx::query!(
-                "SELECT announcement_channel_id FROM server_bot_config WHERE server_id = $1",
-                guild_id.get() as i64
-            )
-            .fetch_optional(pool)
-            .await?;
-
-            let channel_id = config
-                .and_then(|c| c.announcement_channel_id)
-                .map(|id| poise::serenity_prelude::ChannelId::new(id as u64))
+            let channel_id = club_announcement_channel(pool, club.club_id)
+                .await?
                 .unwrap_or_else(|| ctx.channel_id());
 
-            let should_pin_poll = pin_polls_enabled(pool, guild_id.get() as i64).await?;
+            let should_pin_poll = pin_polls_enabled(pool, club.club_id).await?;
 
             // Send the message with embed and poll
             let message = channel_id
@@ -190,7 +203,7 @@ pub async fn finishbook(ctx: Context<'_>) -> Result<(), Error> {
             let embed = if error_msg.contains("No current book") {
                 CreateEmbed::default()
                     .title("❌ No Current Book")
-                    .description("No current book to finish. Use `/current` to check the current book. A mod/admin can use `/select` to select one.")
+                    .description(format!("No current book to finish. Use `/current{}` to check the current book. A mod/admin can use `/select` to select one.", club.command_option()))
                     .color(0xB76E79)
             } else {
                 CreateEmbed::default()
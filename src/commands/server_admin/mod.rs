@@ -1,6 +1,8 @@
 pub mod adminprogress;
 pub mod adminqueue;
+pub mod club;
 pub mod clubreadadd;
+pub mod clubreadimport;
 pub mod clubreadremove;
 pub mod config;
 pub mod finishbook;
@@ -21,6 +23,8 @@ pub fn server_admin_commands() -> CommandVec {
         clubreadremove::clubreadremove(),
         adminqueue::adminqueue(),
         clubreadadd::clubreadadd(),
+        clubreadimport::clubreadimport(),
         adminprogress::adminprogress(),
+        club::club(),
     ]
 }
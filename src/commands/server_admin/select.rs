@@ -1,12 +1,20 @@
+use crate::commands::server_user::queue::displayable_pitch;
 use crate::database_helpers::select_book_transactional;
+use crate::google_books_cache::CachedGoogleBooksClient;
+use crate::list_notifications::{ListEvent, ListNotice, notify_list_watchers};
 use crate::maturity_check::{
-    check_volume_maturity, create_mature_content_warning, current_channel_is_nsfw,
-    server_maturity_enabled,
+    can_display_mature_content, can_display_mature_content_event, check_volume_maturity,
+    create_mature_content_warning, current_channel_is_nsfw, server_maturity_enabled,
 };
+use crate::selection_rules::{self, RuleCheck};
 use crate::types::QueryMode;
 use crate::util::{
-    detect_query_mode, format_deadline, get_guild_name, log_error_with_source, normalize_isbn,
-    pin_polls_enabled,
+    Club, autocomplete_club, club_announcement_channel, detect_query_mode, format_deadline,
+    log_error_with_source, normalize_isbn, pin_polls_enabled, resolve_club,
+};
+use crate::weighted_selection::{
+    CandidateStats, RandomWeighting, VoteStanding, candidate_weight, format_probability,
+    most_upvoted, pick_weighted, probabilities,
 };
 use crate::*;
 use crate::{types::Context, types::Error};
@@ -22,6 +30,7 @@ use serenity::{
         CreateActionRow, CreateButton, CreateInteractionResponse, CreateInteractionResponseMessage,
     },
 };
+use sqlx::PgPool;
 use sqlx::types::chrono::{DateTime, NaiveDate, TimeZone, Utc};
 use std::time::Duration;
 
@@ -53,12 +62,11 @@ enum GuardOutcome {
     CancelledProceed, // user cancelled poll; proceed with the command
 }
 
-async fn active_selection_poll_row(ctx: &Context<'_>) -> Result<Option<(i64, i64)>, Error> {
+async fn active_selection_poll_row(
+    ctx: &Context<'_>,
+    club: &Club,
+) -> Result<Option<(i64, i64)>, Error> {
     let pool = &ctx.data().database;
-    let Some(guild_id) = ctx.guild_id() else {
-        return Ok(None);
-    };
-    let guild_id_i64 = guild_id.get() as i64;
 
     // Clean up stale polls that have already expired but were never processed. These
     // stale rows violate the partial unique index on `selection_polls` and prevent
@@ -67,11 +75,11 @@ async fn active_selection_poll_row(ctx: &Context<'_>) -> Result<Option<(i64, i64
         r#"
         UPDATE selection_polls
         SET processed = TRUE
-        WHERE server_id = $1
+        WHERE club_id = $1
           AND NOT processed
           AND expires_at <= NOW()
         "#,
-        guild_id_i64
+        club.club_id
     )
     .execute(pool)
     .await?;
@@ -80,11 +88,11 @@ async fn active_selection_poll_row(ctx: &Context<'_>) -> Result<Option<(i64, i64
         r#"
         SELECT message_id, channel_id
         FROM selection_polls
-        WHERE server_id = $1 AND NOT processed AND expires_at > NOW()
+        WHERE club_id = $1 AND NOT processed AND expires_at > NOW()
         ORDER BY expires_at DESC
         LIMIT 1
         "#,
-        guild_id_i64
+        club.club_id
     )
     .fetch_optional(pool)
     .await?;
@@ -92,8 +100,8 @@ async fn active_selection_poll_row(ctx: &Context<'_>) -> Result<Option<(i64, i64
     Ok(row.map(|r| (r.message_id, r.channel_id)))
 }
 
-async fn interactive_poll_guard(ctx: &Context<'_>) -> Result<GuardOutcome, Error> {
-    let Some((message_id, channel_id)) = active_selection_poll_row(ctx).await? else {
+async fn interactive_poll_guard(ctx: &Context<'_>, club: &Club) -> Result<GuardOutcome, Error> {
+    let Some((message_id, channel_id)) = active_selection_poll_row(ctx, club).await? else {
         return Ok(GuardOutcome::NoActivePoll);
     };
 
@@ -141,11 +149,11 @@ async fn interactive_poll_guard(ctx: &Context<'_>) -> Result<GuardOutcome, Error
     {
         match mci.data.custom_id.as_str() {
             "cancel_and_proceed" => {
-                // Mark all unprocessed selection polls for this server as processed.
+                // Mark all unprocessed selection polls for this club as processed.
                 let pool = &ctx.data().database;
                 sqlx::query!(
-                    "UPDATE selection_polls SET processed = TRUE WHERE server_id = $1 AND NOT processed",
-                    guild_id as i64
+                    "UPDATE selection_polls SET processed = TRUE WHERE club_id = $1 AND NOT processed",
+                    club.club_id
                 )
                 .execute(pool)
                 .await?;
@@ -221,9 +229,60 @@ struct PreloadedBookInfo {
     thumbnail_url: Option<String>,
 }
 
+#[derive(poise::ChoiceParameter, Clone, Copy, Debug, Eq, PartialEq)]
+enum PollFill {
+    #[name = "queue order"]
+    Position,
+    #[name = "most upvoted"]
+    Votes,
+}
+
+/// Tells the admin which queued books the selection rules held back. With `nothing_left`, the
+/// rules excluded every candidate and no book was picked.
+async fn send_rule_exclusions(
+    ctx: &Context<'_>,
+    club: &Club,
+    check: &RuleCheck,
+    nothing_left: bool,
+) -> Result<(), Error> {
+    let pool = &ctx.data().database;
+    let allow_mature = can_display_mature_content(ctx, pool).await?;
+    let Some(summary) = check.summary(allow_mature) else {
+        return Ok(());
+    };
+
+    let embed = if nothing_left {
+        CreateEmbed::default()
+            .title("No Eligible Books")
+            .description(format!(
+                "Every book in the queue is held back by this server's selection rules:\n\n{}\n\nAdjust them with `/config selection rules`, pin a book with `/adminqueue pin{}`, or pick one with `/select manual{}`.",
+                summary,
+                club.command_option(),
+                club.command_option()
+            ))
+    } else {
+        CreateEmbed::default()
+            .title("⚖️ Selection Rules Applied")
+            .description(format!(
+                "These books were skipped for this pick:\n\n{}",
+                summary
+            ))
+    };
+
+    ctx.send(
+        CreateReply::default().embed(
+            embed
+                .color(0xB76E79)
+                .footer(CreateEmbedFooter::new("Configured via /config selection")),
+        ),
+    )
+    .await?;
+    Ok(())
+}
+
 #[poise::command(
     slash_command,
-    subcommands("next", "poll", "random", "manual", "remove"),
+    subcommands("next", "poll", "random", "top", "manual", "remove"),
     guild_only,
     required_permissions = "MANAGE_MESSAGES",
     description_localized(
@@ -244,14 +303,20 @@ pub async fn select(_ctx: Context<'_>) -> Result<(), Error> {
 async fn next(
     ctx: Context<'_>,
     #[description = "Reading deadline (YYYY-MM-DD)"] deadline: Option<String>,
+    #[description = "Club to select for (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
 ) -> Result<(), Error> {
-    let Some(guild_id) = ctx.guild_id() else {
+    if ctx.guild_id().is_none() {
         let embed = CreateEmbed::default()
             .title("❌ Error")
             .description("This command must be used in a server.")
             .color(0xB76E79);
         ctx.send(CreateReply::default().embed(embed)).await?;
         return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
     };
 
     let deadline = match parse_deadline_input(deadline) {
@@ -268,9 +333,12 @@ async fn next(
 
     ctx.defer().await?;
 
-    match interactive_poll_guard(&ctx).await? {
+    match interactive_poll_guard(&ctx, &club).await? {
         GuardOutcome::NoActivePoll | GuardOutcome::CancelledProceed => {
             let pool = &ctx.data().database;
+            let google_books = &ctx.data().google_books;
+
+            let rule_check = selection_rules::check_queue(pool, google_books, &club).await?;
 
             let next_book = sqlx::query!(
                 r#"
@@ -278,23 +346,32 @@ async fn next(
                     volume_id,
                     position
                 FROM server_book_queue
-                WHERE server_id = $1
+                WHERE club_id = $1
+                    AND NOT (volume_id = ANY($2))
                 ORDER BY position
                 LIMIT 1
                 "#,
-                guild_id.get() as i64
+                club.club_id,
+                &rule_check.excluded_ids()
             )
             .fetch_optional(pool)
             .await?;
 
             match next_book {
                 Some(book) => {
-                    select_book(ctx, book.volume_id, deadline, None, None).await?;
+                    send_rule_exclusions(&ctx, &club, &rule_check, false).await?;
+                    select_book(ctx, &club, book.volume_id, deadline, None, None).await?;
+                }
+                None if !rule_check.excluded.is_empty() => {
+                    send_rule_exclusions(&ctx, &club, &rule_check, true).await?;
                 }
                 None => {
                     let embed = CreateEmbed::default()
                         .title("Queue Empty")
-                        .description("The queue is empty! Add books with `/queue add`.")
+                        .description(format!(
+                            "The queue is empty! Add books with `/queue add{}`.",
+                            club.command_option()
+                        ))
                         .color(0xB76E79)
                         .footer(CreateEmbedFooter::new("Powered by Google Books API"));
                     ctx.send(CreateReply::default().embed(embed)).await?;
@@ -306,6 +383,236 @@ async fn next(
     }
 }
 
+pub(crate) struct PollSettings {
+    pub poll_size: i32,
+    pub duration_hours: u64,
+    pub deadline: Option<DateTime<Utc>>,
+    pub by_votes: bool,
+}
+
+pub(crate) enum PollOpenOutcome {
+    Posted {
+        channel_id: serenity::ChannelId,
+        message_id: serenity::MessageId,
+    },
+    BookAlreadySelected,
+    InsufficientBooks,
+    PollAlreadyActive,
+    NoChannel,
+}
+
+/// Posts a selection poll for the club and records it in `selection_polls`. Shared by
+/// `/select poll` and the automatic poll scheduler. The poll goes to the announcement channel
+/// when one is configured, otherwise (or if posting there fails) to `fallback_channel_id`.
+pub(crate) async fn open_selection_poll(
+    http: &serenity::Http,
+    pool: &PgPool,
+    google_books: &CachedGoogleBooksClient,
+    club: &Club,
+    fallback_channel_id: Option<serenity::ChannelId>,
+    settings: &PollSettings,
+) -> Result<PollOpenOutcome, Error> {
+    let poll_duration = Duration::from_secs(settings.duration_hours * 60 * 60);
+
+    let current_book = sqlx::query!(
+        "SELECT volume_id FROM server_current_book WHERE club_id = $1",
+        club.club_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    if current_book.is_some() {
+        return Ok(PollOpenOutcome::BookAlreadySelected);
+    }
+
+    // Mirrors uidx_one_active_selection_poll so we don't post a poll we can't record
+    let active_poll = sqlx::query!(
+        "SELECT message_id FROM selection_polls WHERE club_id = $1 AND NOT processed",
+        club.club_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    if active_poll.is_some() {
+        return Ok(PollOpenOutcome::PollAlreadyActive);
+    }
+
+    let rule_check = selection_rules::check_queue(pool, google_books, club).await?;
+
+    // Get books for poll
+    let candidates = sqlx::query!(
+        "SELECT * FROM get_queue_books_for_poll($1, $2, $3, $4)",
+        club.club_id,
+        settings.poll_size,
+        settings.by_votes,
+        &rule_check.excluded_ids()
+    )
+    .fetch_all(pool)
+    .await?;
+
+    if candidates.len() < 2 {
+        return Ok(PollOpenOutcome::InsufficientBooks);
+    }
+
+    let book_ids: Vec<String> = candidates
+        .iter()
+        .map(|c| c.volume_id.clone().unwrap_or_default())
+        .collect();
+
+    // Fetch book details from Google Books
+    let volumes = google_books.get_volumes_batch(&book_ids).await;
+
+    let answer_labels: Vec<String> = candidates
+        .iter()
+        .enumerate()
+        .map(|(i, _book)| {
+            if let Some(Ok(volume)) = volumes.get(i) {
+                format!(
+                    "{}. {} — {}",
+                    i + 1,
+                    volume.get_title(),
+                    volume.get_authors_string()
+                )
+            } else {
+                format!("{}. [Book data unavailable]", i + 1)
+            }
+        })
+        .collect();
+
+    let pitches: Vec<(String, Option<String>, Option<String>)> = candidates
+        .iter()
+        .zip(answer_labels.iter())
+        .map(|(c, label)| {
+            (
+                label.clone(),
+                c.suggested_by_username.clone(),
+                c.pitch.clone(),
+            )
+        })
+        .collect();
+    let has_pitches = pitches.iter().any(|(_, _, pitch)| pitch.is_some());
+
+    let make_poll = || {
+        let answers: Vec<CreatePollAnswer> = answer_labels
+            .iter()
+            .map(|label| CreatePollAnswer::new().text(label.clone()))
+            .collect();
+        CreatePoll::new()
+            .question(format!("Pick the club's next book{}", club.title_suffix()))
+            .answers(answers)
+            .duration(poll_duration)
+    };
+
+    let announcement_channel_id = club_announcement_channel(pool, club.club_id).await?;
+    let Some(mut poll_channel_id) = announcement_channel_id.or(fallback_channel_id) else {
+        return Ok(PollOpenOutcome::NoChannel);
+    };
+
+    let should_pin_poll = pin_polls_enabled(pool, club.club_id).await?;
+
+    let poll_content = "Cast your vote below! (Book data from Google Books API)";
+
+    // Pitches are filtered per channel, so the embed is rebuilt if we fall back
+    let make_message = |allow_unrestricted_sexual: bool| {
+        let mut message = CreateMessage::new().content(poll_content).poll(make_poll());
+        if has_pitches {
+            let mut embed = CreateEmbed::default()
+                .title("📣 The Candidates")
+                .color(0xB76E79)
+                .footer(CreateEmbedFooter::new(
+                    "Pitches from the members who suggested each book",
+                ));
+            for (label, suggested_by, pitch) in &pitches {
+                let mut value = format!(
+                    "Suggested by: {}",
+                    suggested_by.as_deref().unwrap_or("Unknown")
+                );
+                if let Some(pitch) = displayable_pitch(pitch.as_deref(), allow_unrestricted_sexual)
+                {
+                    value.push_str(&format!("\n> {}", pitch));
+                }
+                embed = embed.field(label, value, false);
+            }
+            message = message.embed(embed);
+        }
+        if let Some(summary) = rule_check.summary(allow_unrestricted_sexual) {
+            message = message.embed(
+                CreateEmbed::default()
+                    .title("⚖️ Skipped by Selection Rules")
+                    .description(summary)
+                    .color(0xB76E79)
+                    .footer(CreateEmbedFooter::new("Configured via /config selection")),
+            );
+        }
+        message
+    };
+
+    let allow_unrestricted_sexual =
+        can_display_mature_content_event(http, pool, club.server_id, poll_channel_id).await?;
+
+    let message = match poll_channel_id
+        .send_message(http, make_message(allow_unrestricted_sexual))
+        .await
+    {
+        Ok(msg) => msg,
+        Err(err) => match fallback_channel_id {
+            Some(fallback) if announcement_channel_id.is_some() && poll_channel_id != fallback => {
+                log_error_with_source("Couldn't send poll to announcement channel", &err);
+                poll_channel_id = fallback;
+                let allow_unrestricted_sexual =
+                    can_display_mature_content_event(http, pool, club.server_id, poll_channel_id)
+                        .await?;
+                poll_channel_id
+                    .send_message(http, make_message(allow_unrestricted_sexual))
+                    .await?
+            }
+            _ => return Err(Box::new(err)),
+        },
+    };
+
+    if should_pin_poll {
+        if let Err(err) = message.pin(http).await {
+            log_error_with_source("Couldn't pin selection poll message", &err);
+        }
+    }
+
+    // Store poll information
+    let expires_at = Utc::now() + chrono::Duration::seconds(settings.duration_hours as i64 * 3600);
+
+    sqlx::query!(
+        "INSERT INTO selection_polls (message_id, channel_id, club_id, book_options, expires_at, deadline)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        ON CONFLICT (message_id) DO NOTHING",
+        message.id.get() as i64,
+        poll_channel_id.get() as i64,
+        club.club_id,
+        &book_ids,
+        expires_at,
+        settings.deadline
+    )
+    .execute(pool)
+    .await?;
+
+    notify_list_watchers(
+        http,
+        pool,
+        google_books,
+        ListNotice {
+            club,
+            volume_ids: &book_ids,
+            event: ListEvent::InPoll,
+            skip_user: None,
+            channel_id: poll_channel_id,
+        },
+    )
+    .await;
+
+    Ok(PollOpenOutcome::Posted {
+        channel_id: poll_channel_id,
+        message_id: message.id,
+    })
+}
+
 #[poise::command(
     slash_command,
     description_localized("en-US", "Create a poll to select from the queue"),
@@ -325,7 +632,26 @@ pub async fn poll(
     #[description = "Reading deadline applied to the winning book (YYYY-MM-DD)"] deadline: Option<
         String,
     >,
+    #[description = "Fill the poll by queue order (default) or by member upvotes"] fill_by: Option<
+        PollFill,
+    >,
+    #[description = "Club to select for (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
 ) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
+
+    let by_votes = fill_by == Some(PollFill::Votes);
     let deadline = match parse_deadline_input(deadline) {
         Ok(value) => value,
         Err(reason) => {
@@ -339,161 +665,68 @@ pub async fn poll(
     };
 
     ctx.defer().await?;
-    match interactive_poll_guard(&ctx).await? {
+    match interactive_poll_guard(&ctx, &club).await? {
         GuardOutcome::NoActivePoll | GuardOutcome::CancelledProceed => {
-            let Some(guild_id) = ctx.guild_id() else {
-                let embed = CreateEmbed::default()
-                    .title("❌ Error")
-                    .description("This command must be used in a server.")
-                    .color(0xB76E79);
-                ctx.send(CreateReply::default().embed(embed)).await?;
-                return Ok(());
+            let settings = PollSettings {
+                poll_size: size.unwrap_or(5).clamp(2, 10) as i32,
+                duration_hours: duration_hours.unwrap_or(24).min(167) as u64,
+                deadline,
+                by_votes,
             };
-            let poll_size = size.unwrap_or(5).clamp(2, 10) as i32;
-            let hours = duration_hours.unwrap_or(24).min(167) as u64;
-            let poll_duration = Duration::from_secs(hours * 60 * 60);
 
             let pool = &ctx.data().database;
             let google_books = &ctx.data().google_books;
 
-            let current_book = sqlx::query!(
-                "SELECT volume_id FROM server_current_book WHERE server_id = $1",
-                guild_id.get() as i64
-            )
-            .fetch_optional(pool)
-            .await?;
-
-            if current_book.is_some() {
-                let embed = CreateEmbed::default()
-                    .title("❌ Book Already Selected")
-                    .description("There's already a current book! Use `/finishbook` first to complete it, or `/select remove` to remove it.")
-                    .color(0xB76E79)
-                    .footer(CreateEmbedFooter::new("Powered by Google Books API"));
-                ctx.send(CreateReply::default().embed(embed)).await?;
-                return Ok(());
-            }
-
-            // Get books for poll
-            let candidates = sqlx::query!(
-                "SELECT * FROM get_queue_books_for_poll($1, $2)",
-                guild_id.get() as i64,
-                poll_size
-            )
-            .fetch_all(pool)
-            .await?;
-
-            if candidates.len() < 2 {
-                let embed = CreateEmbed::default()
-                    .title("❌ Insufficient Books")
-                    .description("Need at least 2 books in the queue to create a poll.")
-                    .color(0xB76E79)
-                    .footer(CreateEmbedFooter::new("Powered by Google Books API"));
-                ctx.send(CreateReply::default().embed(embed)).await?;
-                return Ok(());
-            }
-
-            let book_ids: Vec<String> = candidates
-                .iter()
-                .map(|c| c.volume_id.clone().unwrap_or_default())
-                .collect();
-
-            // Fetch book details from Google Books
-            let volumes = google_books.get_volumes_batch(&book_ids).await;
-
-            let answer_labels: Vec<String> = candidates
-                .iter()
-                .enumerate()
-                .map(|(i, _book)| {
-                    if let Some(Ok(volume)) = volumes.get(i) {
-                        format!(
-                            "{}. {} — {}",
-                            i + 1,
-                            volume.get_title(),
-                            volume.get_authors_string()
-                        )
-                    } else {
-                        format!("{}. [Book data unavailable]", i + 1)
-                    }
-                })
-                .collect();
-
-            let make_poll = || {
-                let answers: Vec<CreatePollAnswer> = answer_labels
-                    .iter()
-                    .map(|label| CreatePollAnswer::new().text(label.clone()))
-                    .collect();
-                CreatePoll::new()
-                    .question("Pick the club's next book")
-                    .answers(answers)
-                    .duration(poll_duration)
-            };
-
-            let config = sqlx::query!(
-                "SELECT announcement_channel_id FROM server_bot_config WHERE server_id = $1",
-                guild_id.get() as i64
+            let outcome = open_selection_poll(
+                ctx.http(),
+                pool,
+                google_books,
+                &club,
+                Some(ctx.channel_id()),
+                &settings,
             )
-            .fetch_optional(pool)
             .await?;
 
-            let announcement_channel_id = config.and_then(|row| row.announcement_channel_id);
-            let mut poll_channel_id = announcement_channel_id
-                .map(|id| poise::serenity_prelude::ChannelId::new(id as u64))
-                .unwrap_or_else(|| ctx.channel_id());
-
-            let should_pin_poll = pin_polls_enabled(pool, guild_id.get() as i64).await?;
-
-            let poll_content = "Cast your vote below! (Book data from Google Books API)";
-
-            let message = match poll_channel_id
-                .send_message(
-                    &ctx.http(),
-                    CreateMessage::new().content(poll_content).poll(make_poll()),
-                )
-                .await
-            {
-                Ok(msg) => msg,
-                Err(err) => {
-                    if announcement_channel_id.is_some() && poll_channel_id != ctx.channel_id() {
-                        log_error_with_source("Couldn't send poll to announcement channel", &err);
-                        poll_channel_id = ctx.channel_id();
-                        match poll_channel_id
-                            .send_message(
-                                &ctx.http(),
-                                CreateMessage::new().content(poll_content).poll(make_poll()),
-                            )
-                            .await
-                        {
-                            Ok(fallback_msg) => fallback_msg,
-                            Err(fallback_err) => return Err(Box::new(fallback_err)),
-                        }
-                    } else {
-                        return Err(Box::new(err));
-                    }
+            let (poll_channel_id, message_id) = match outcome {
+                PollOpenOutcome::Posted {
+                    channel_id,
+                    message_id,
+                } => (channel_id, message_id),
+                PollOpenOutcome::BookAlreadySelected => {
+                    let embed = CreateEmbed::default()
+                        .title("❌ Book Already Selected")
+                        .description(format!("There's already a current book! Use `/finishbook{0}` first to complete it, or `/select remove{0}` to remove it.", club.command_option()))
+                        .color(0xB76E79)
+                        .footer(CreateEmbedFooter::new("Powered by Google Books API"));
+                    ctx.send(CreateReply::default().embed(embed)).await?;
+                    return Ok(());
                 }
-            };
-
-            if should_pin_poll {
-                if let Err(err) = message.pin(&ctx.http()).await {
-                    log_error_with_source("Couldn't pin selection poll message", &err);
+                PollOpenOutcome::InsufficientBooks => {
+                    let embed = CreateEmbed::default()
+                        .title("❌ Insufficient Books")
+                        .description("Need at least 2 eligible books in the queue to create a poll. Books held back by `/config selection` rules don't count.")
+                        .color(0xB76E79)
+                        .footer(CreateEmbedFooter::new("Powered by Google Books API"));
+                    ctx.send(CreateReply::default().embed(embed)).await?;
+                    return Ok(());
                 }
-            }
-
-            // Store poll information
-            let expires_at = Utc::now() + chrono::Duration::seconds(hours as i64 * 3600);
-
-            sqlx::query!(
-                "INSERT INTO selection_polls (message_id, channel_id, server_id, book_options, expires_at, deadline)
-                VALUES ($1, $2, $3, $4, $5, $6)
-                ON CONFLICT (message_id) DO NOTHING",
-                message.id.get() as i64,
-                poll_channel_id.get() as i64,
-                guild_id.get() as i64,
-                &book_ids,
-                expires_at,
-                deadline
-            )
-            .execute(pool)
-            .await?;
+                PollOpenOutcome::PollAlreadyActive => {
+                    let embed = CreateEmbed::default()
+                        .title("❌ Poll Already Running")
+                        .description("A selection poll is already open for this club.")
+                        .color(0xB76E79);
+                    ctx.send(CreateReply::default().embed(embed)).await?;
+                    return Ok(());
+                }
+                PollOpenOutcome::NoChannel => {
+                    let embed = CreateEmbed::default()
+                        .title("❌ No Poll Channel")
+                        .description(format!("There's no channel to post the poll in. Use `/config announcement set{}` to choose one.", club.command_option()))
+                        .color(0xB76E79);
+                    ctx.send(CreateReply::default().embed(embed)).await?;
+                    return Ok(());
+                }
+            };
 
             let same_channel = poll_channel_id == ctx.channel_id();
 
@@ -507,7 +740,7 @@ pub async fn poll(
                     "https://discord.com/channels/{}/{}/{}",
                     guild_id.get(),
                     poll_channel_id.get(),
-                    message.id.get()
+                    message_id.get()
                 );
 
                 confirmation_embed = confirmation_embed.description(format!(
@@ -532,6 +765,46 @@ pub async fn poll(
     }
 }
 
+/// Lists each candidate's chance of winning a random pick, most likely first.
+fn format_odds_table(
+    volumes: &[anyhow::Result<crate::google_books::Volume>],
+    odds: &[f64],
+    winner: usize,
+    allow_mature: bool,
+) -> String {
+    const SHOWN: usize = 10;
+
+    let mut order: Vec<usize> = (0..odds.len()).collect();
+    order.sort_by(|a, b| odds[*b].total_cmp(&odds[*a]));
+
+    let mut lines: Vec<String> = order
+        .iter()
+        .take(SHOWN)
+        .map(|&i| {
+            let title = match volumes.get(i) {
+                Some(Ok(volume)) if volume.is_mature() && !allow_mature => {
+                    "A mature-rated book".to_string()
+                }
+                Some(Ok(volume)) => volume.get_title(),
+                _ => "[Book data unavailable]".to_string(),
+            };
+            let marker = if i == winner { "🎲 " } else { "" };
+            format!("{}**{}** — {}", marker, title, format_probability(odds[i]))
+        })
+        .collect();
+
+    if order.len() > SHOWN {
+        let rest: f64 = order[SHOWN..].iter().map(|&i| odds[i]).sum();
+        lines.push(format!(
+            "…and {} more ({} combined)",
+            order.len() - SHOWN,
+            format_probability(rest)
+        ));
+    }
+
+    lines.join("\n")
+}
+
 #[poise::command(
     slash_command,
     description_localized("en-US", "Select a random book from the queue"),
@@ -540,14 +813,23 @@ pub async fn poll(
 async fn random(
     ctx: Context<'_>,
     #[description = "Reading deadline (YYYY-MM-DD)"] deadline: Option<String>,
+    #[description = "How to weight each book's chance (default: uniform)"] weighting: Option<
+        RandomWeighting,
+    >,
+    #[description = "Club to select for (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
 ) -> Result<(), Error> {
-    let Some(guild_id) = ctx.guild_id() else {
+    if ctx.guild_id().is_none() {
         let embed = CreateEmbed::default()
             .title("❌ Error")
             .description("This command must be used in a server.")
             .color(0xB76E79);
         ctx.send(CreateReply::default().embed(embed)).await?;
         return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
     };
 
     let deadline = match parse_deadline_input(deadline) {
@@ -563,70 +845,246 @@ async fn random(
     };
 
     ctx.defer().await?;
-    match interactive_poll_guard(&ctx).await? {
+    match interactive_poll_guard(&ctx, &club).await? {
         GuardOutcome::NoActivePoll | GuardOutcome::CancelledProceed => {
             let pool = &ctx.data().database;
             let google_books = &ctx.data().google_books;
 
-            let random_book = sqlx::query!(
-                "SELECT * FROM get_random_queue_book($1)",
-                guild_id.get() as i64
+            let rule_check = selection_rules::check_queue(pool, google_books, &club).await?;
+
+            let candidates = sqlx::query!(
+                "SELECT * FROM get_random_queue_candidates($1, $2)",
+                club.club_id,
+                &rule_check.excluded_ids()
             )
-            .fetch_optional(pool)
+            .fetch_all(pool)
             .await?;
 
-            match random_book {
-                Some(book) => {
-                    let volume_id = book.volume_id.unwrap();
-
-                    // Fetch book details
-                    let volume = google_books.get_volume(&volume_id).await?;
-
-                    if !check_volume_maturity(&ctx, pool, &volume).await? {
-                        let is_nsfw = current_channel_is_nsfw(&ctx).await?;
-                        let maturity_enabled = server_maturity_enabled(&ctx, pool).await?;
-                        let embed = create_mature_content_warning(
-                            Some(&volume.get_title()),
-                            is_nsfw,
-                            maturity_enabled,
-                        );
-                        ctx.send(poise::CreateReply::default().embed(embed)).await?;
-                        return Ok(());
-                    }
+            if candidates.is_empty() {
+                if rule_check.excluded.is_empty() {
+                    let embed = CreateEmbed::default()
+                        .title("Queue Empty")
+                        .description(format!(
+                            "The queue is empty! Add books with `/queue add{}`.",
+                            club.command_option()
+                        ))
+                        .color(0xFFA500)
+                        .footer(CreateEmbedFooter::new("Powered by Google Books API"));
+                    ctx.send(CreateReply::default().embed(embed)).await?;
+                } else {
+                    send_rule_exclusions(&ctx, &club, &rule_check, true).await?;
+                }
+                return Ok(());
+            }
 
-                    let pre: PreloadedBookInfo = PreloadedBookInfo {
-                        title: volume.get_title(),
-                        authors: volume.get_authors_string(),
-                        thumbnail_url: volume.get_thumbnail_url(),
+            let weighting = weighting.unwrap_or(RandomWeighting::Uniform);
+            let books_read = if weighting == RandomWeighting::SuggesterDrought {
+                sqlx::query_scalar!(
+                    "SELECT COUNT(*) FROM server_completed_books WHERE club_id = $1",
+                    club.club_id
+                )
+                .fetch_one(pool)
+                .await?
+                .unwrap_or(0)
+            } else {
+                0
+            };
+
+            let now = Utc::now();
+            let weights: Vec<f64> = candidates
+                .iter()
+                .map(|c| {
+                    let stats = CandidateStats {
+                        added_at: c.added_at,
+                        vote_count: c.vote_count.unwrap_or(0),
+                        books_since_pick: c.books_since_pick,
                     };
-                    let book_title = volume.get_title();
+                    candidate_weight(weighting, &stats, books_read, now)
+                })
+                .collect();
+            let odds = probabilities(&weights);
+            let winner = pick_weighted(&weights, &mut rand::thread_rng()).unwrap_or(0);
+
+            let book = &candidates[winner];
+            let volume_id = book.volume_id.clone().unwrap_or_default();
+
+            // Fetch book details
+            let volume = google_books.get_volume(&volume_id).await?;
+
+            if !check_volume_maturity(&ctx, pool, &volume).await? {
+                let is_nsfw = current_channel_is_nsfw(&ctx).await?;
+                let maturity_enabled = server_maturity_enabled(&ctx, pool).await?;
+                let embed = create_mature_content_warning(
+                    Some(&volume.get_title()),
+                    is_nsfw,
+                    maturity_enabled,
+                );
+                ctx.send(poise::CreateReply::default().embed(embed)).await?;
+                return Ok(());
+            }
+
+            let pre: PreloadedBookInfo = PreloadedBookInfo {
+                title: volume.get_title(),
+                authors: volume.get_authors_string(),
+                thumbnail_url: volume.get_thumbnail_url(),
+            };
+            let book_title = volume.get_title();
+
+            let mut embed = CreateEmbed::default()
+                .title("Random Selection")
+                .description(format!(
+                    "Randomly selected: **{}**\nSuggested by: {}",
+                    book_title,
+                    book.suggested_by_username.as_deref().unwrap_or("Unknown")
+                ))
+                .color(0xB76E79)
+                .footer(CreateEmbedFooter::new("Powered by Google Books API"));
+
+            if candidates.len() > 1 {
+                let book_ids: Vec<String> = candidates
+                    .iter()
+                    .map(|c| c.volume_id.clone().unwrap_or_default())
+                    .collect();
+                let volumes = google_books.get_volumes_batch(&book_ids).await;
+                let allow_mature = can_display_mature_content(&ctx, pool).await?;
+                embed = embed.field(
+                    format!("Odds ({})", weighting.describe()),
+                    format_odds_table(&volumes, &odds, winner, allow_mature),
+                    false,
+                );
+            }
+
+            ctx.send(CreateReply::default().embed(embed)).await?;
+            send_rule_exclusions(&ctx, &club, &rule_check, false).await?;
+
+            let disclaimer = format!(
+                "Picked at random with a {} chance ({})",
+                format_probability(odds[winner]),
+                weighting.describe()
+            );
+            select_book(ctx, &club, volume_id, deadline, Some(disclaimer), Some(pre)).await?;
+
+            Ok(())
+        }
+        GuardOutcome::KeepPoll => return Ok(()),
+    }
+}
+
+#[poise::command(
+    slash_command,
+    description_localized("en-US", "Select the most upvoted book in the queue"),
+    user_cooldown = 10
+)]
+async fn top(
+    ctx: Context<'_>,
+    #[description = "Reading deadline (YYYY-MM-DD)"] deadline: Option<String>,
+    #[description = "Club to select for (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
+    if ctx.guild_id().is_none() {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
+
+    let deadline = match parse_deadline_input(deadline) {
+        Ok(value) => value,
+        Err(reason) => {
+            let embed = CreateEmbed::default()
+                .title("❌ Invalid Deadline")
+                .description(reason)
+                .color(0xB76E79);
+            ctx.send(CreateReply::default().embed(embed)).await?;
+            return Ok(());
+        }
+    };
+
+    ctx.defer().await?;
+    match interactive_poll_guard(&ctx, &club).await? {
+        GuardOutcome::NoActivePoll | GuardOutcome::CancelledProceed => {
+            let pool = &ctx.data().database;
+            let google_books = &ctx.data().google_books;
+
+            let rule_check = selection_rules::check_queue(pool, google_books, &club).await?;
+
+            let queued = sqlx::query!(
+                r#"
+                SELECT
+                    sbq.volume_id,
+                    sbq.position,
+                    (SELECT COUNT(*) FROM server_queue_votes sqv WHERE sqv.queue_id = sbq.queue_id) as "vote_count!"
+                FROM server_book_queue sbq
+                WHERE sbq.club_id = $1
+                    AND NOT (sbq.volume_id = ANY($2))
+                ORDER BY sbq.position
+                "#,
+                club.club_id,
+                &rule_check.excluded_ids()
+            )
+            .fetch_all(pool)
+            .await?;
 
+            let standings: Vec<VoteStanding> = queued
+                .iter()
+                .map(|book| VoteStanding {
+                    position: book.position,
+                    vote_count: book.vote_count,
+                })
+                .collect();
+            let top_book = most_upvoted(&standings).map(|index| &queued[index]);
+
+            match top_book {
+                Some(book) if book.vote_count > 0 => {
+                    let votes = book.vote_count;
+                    let disclaimer = format!(
+                        "Most upvoted with {} vote{}",
+                        votes,
+                        if votes == 1 { "" } else { "s" }
+                    );
+                    send_rule_exclusions(&ctx, &club, &rule_check, false).await?;
+                    select_book(
+                        ctx,
+                        &club,
+                        book.volume_id.clone(),
+                        deadline,
+                        Some(disclaimer),
+                        None,
+                    )
+                    .await?;
+                }
+                Some(_) => {
                     let embed = CreateEmbed::default()
-                        .title("Random Selection")
-                        .description(format!(
-                            "Randomly selected: **{}**\nSuggested by: {}",
-                            book_title,
-                            book.suggested_by_username.unwrap_or("Unknown".to_string())
-                        ))
+                        .title("No Votes Yet")
+                        .description(format!("Nobody has upvoted a queued book yet. Members can vote with `/queue vote{0}`, or use `/select next{0}` to take the next book in line.", club.command_option()))
                         .color(0xB76E79)
                         .footer(CreateEmbedFooter::new("Powered by Google Books API"));
                     ctx.send(CreateReply::default().embed(embed)).await?;
-
-                    select_book(ctx, volume_id, deadline, None, Some(pre)).await?;
+                }
+                None if !rule_check.excluded.is_empty() => {
+                    send_rule_exclusions(&ctx, &club, &rule_check, true).await?;
                 }
                 None => {
                     let embed = CreateEmbed::default()
                         .title("Queue Empty")
-                        .description("The queue is empty! Add books with `/queue add`.")
-                        .color(0xFFA500)
+                        .description(format!(
+                            "The queue is empty! Add books with `/queue add{}`.",
+                            club.command_option()
+                        ))
+                        .color(0xB76E79)
                         .footer(CreateEmbedFooter::new("Powered by Google Books API"));
                     ctx.send(CreateReply::default().embed(embed)).await?;
                 }
             }
-
             Ok(())
         }
-        GuardOutcome::KeepPoll => return Ok(()),
+        GuardOutcome::KeepPoll => Ok(()),
     }
 }
 
@@ -643,16 +1101,21 @@ async fn manual(
         poise::serenity_prelude::User,
     >,
     #[description = "Reading deadline (YYYY-MM-DD)"] deadline: Option<String>,
+    #[description = "Club to select for (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
 ) -> Result<(), Error> {
-    let Some(guild_id) = ctx.guild_id() else {
+    if ctx.guild_id().is_none() {
         let embed = CreateEmbed::default()
             .title("❌ Error")
             .description("This command must be used in a server.")
             .color(0xB76E79);
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
     };
-    let guild_name = get_guild_name(&ctx).await;
 
     let deadline = match parse_deadline_input(deadline) {
         Ok(value) => value,
@@ -668,13 +1131,11 @@ async fn manual(
 
     ctx.defer().await?;
 
-    match interactive_poll_guard(&ctx).await? {
+    match interactive_poll_guard(&ctx, &club).await? {
         GuardOutcome::NoActivePoll | GuardOutcome::CancelledProceed => {
             let pool = &ctx.data().database;
             let google_books = &ctx.data().google_books;
 
-            ensure_server_exists(pool, guild_id, &guild_name).await?;
-
             // Collect footer disclaimer notes here
             let mut footer_notes: Vec<String> = Vec::new();
 
@@ -754,8 +1215,8 @@ async fn manual(
 
             // Check if book is in queue and get the original suggester
             let queue_info = sqlx::query!(
-                "SELECT suggested_by_user_id FROM server_book_queue WHERE server_id = $1 AND volume_id = $2",
-                guild_id.get() as i64,
+                "SELECT suggested_by_user_id FROM server_book_queue WHERE club_id = $1 AND volume_id = $2",
+                club.club_id,
                 volume_id
             )
             .fetch_optional(pool)
@@ -787,13 +1248,13 @@ async fn manual(
             if queue_info.is_none() {
                 footer_notes.push("Book was not in queue; added and selected".to_string());
                 sqlx::query!(
-                    "INSERT INTO server_book_queue (server_id, volume_id, suggested_by_user_id, position)
+                    "INSERT INTO server_book_queue (club_id, volume_id, suggested_by_user_id, position)
                     VALUES ($1, $2, $3, (
                         SELECT COALESCE(MAX(position), 0) + 1
                         FROM server_book_queue
-                        WHERE server_id = $1
+                        WHERE club_id = $1
                     ))",
-                    guild_id.get() as i64,
+                    club.club_id,
                     volume_id,
                     suggesting_user.id.get() as i64
                 )
@@ -809,6 +1270,7 @@ async fn manual(
 
             select_book(
                 ctx,
+                &club,
                 volume_id.clone(),
                 deadline,
                 footer_disclaimer,
@@ -825,6 +1287,7 @@ async fn manual(
 // Helper function to select a book
 async fn select_book(
     ctx: Context<'_>,
+    club: &Club,
     volume_id: String,
     deadline: Option<DateTime<Utc>>,
     footer_disclaimer: Option<String>,
@@ -838,30 +1301,22 @@ async fn select_book(
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
     };
-    let guild_name = get_guild_name(&ctx).await;
 
     let pool = &ctx.data().database;
     let invocation_channel_id = ctx.channel_id();
     // let google_books = &ctx.data().google_books;
 
-    ensure_server_exists(pool, guild_id, &guild_name).await?;
-
     // Get announcement channel if configured
-    let config = sqlx::query!(
-        "SELECT announcement_channel_id FROM server_bot_config WHERE server_id = $1",
-        guild_id.get() as i64
-    )
-    .fetch_optional(pool)
-    .await?;
-
-    let announcement_channel_id = config.and_then(|c| c.announcement_channel_id);
+    let announcement_channel_id = club_announcement_channel(pool, club.club_id)
+        .await?
+        .map(|channel| channel.get() as i64);
 
     // Use the transactional function
     let deadline_for_embed = deadline.clone();
 
     match select_book_transactional(
         pool,
-        guild_id.get() as i64,
+        club.club_id,
         &volume_id,
         announcement_channel_id,
         deadline,
@@ -902,6 +1357,18 @@ async fn select_book(
                 .suggested_by_username
                 .unwrap_or("Unknown".to_string());
 
+            let pitch_channel = announcement_channel_id
+                .map(|id| serenity::ChannelId::new(id as u64))
+                .unwrap_or(invocation_channel_id);
+            let allow_unrestricted_sexual = can_display_mature_content_event(
+                ctx.http(),
+                pool,
+                guild_id.get() as i64,
+                pitch_channel,
+            )
+            .await?;
+            let pitch = displayable_pitch(book_info.pitch.as_deref(), allow_unrestricted_sexual);
+
             // Build footer
             let mut footer_text = String::from("Book data from Google Books API");
             if let Some(extra) = footer_disclaimer.filter(|s| !s.trim().is_empty()) {
@@ -911,14 +1378,21 @@ async fn select_book(
 
             // Create announcement embed
             let mut embed = CreateEmbed::default()
-                .title("New Book Selected!")
+                .title(format!("New Book Selected!{}", club.title_suffix()))
                 .field("Title", &title, false)
                 .field("Authors", &authors, false)
                 .field("Suggested by", &suggested_by, false)
-                .description("Happy reading! Track progress with `/progress`.")
+                .description(format!(
+                    "Happy reading! Track progress with `/progress{}`.",
+                    club.command_option()
+                ))
                 .color(0xB76E79)
                 .footer(CreateEmbedFooter::new(footer_text));
 
+            if let Some(pitch) = pitch {
+                embed = embed.field("Pitch", pitch, false);
+            }
+
             if let Some(due) = deadline_for_embed {
                 embed = embed.field("Deadline", format_deadline(due), true);
             }
@@ -927,7 +1401,7 @@ async fn select_book(
                 embed = embed.image(thumbnail_url);
             }
 
-            let should_pin_announcements = pin_polls_enabled(pool, guild_id.get() as i64).await?;
+            let should_pin_announcements = pin_polls_enabled(pool, club.club_id).await?;
 
             let mut announcement_message: Option<serenity::Message> = None;
             if let Some(channel_id) = announcement_channel_id {
@@ -1078,13 +1552,27 @@ async fn select_book(
                     .await?;
                 }
             }
+
+            notify_list_watchers(
+                ctx.http(),
+                pool,
+                &ctx.data().google_books,
+                ListNotice {
+                    club,
+                    volume_ids: &[volume_id],
+                    event: ListEvent::Selected,
+                    skip_user: None,
+                    channel_id: pitch_channel,
+                },
+            )
+            .await;
         }
         Err(e) => {
             let error_msg = e.to_string();
             let embed = if error_msg.contains("already has a current book") {
                 CreateEmbed::default()
                     .title("❌ Book Already Selected")
-                    .description("There's already a current book! Use `/finishbook` or `/select remove` first.")
+                    .description(format!("There's already a current book! Use `/finishbook{0}` or `/select remove{0}` first.", club.command_option()))
                     .color(0xB76E79)
             } else if error_msg.contains("not found in queue") {
                 CreateEmbed::default()
@@ -1115,21 +1603,28 @@ async fn select_book(
     required_permissions = "MANAGE_MESSAGES",
     user_cooldown = 10
 )]
-pub async fn remove(ctx: Context<'_>) -> Result<(), Error> {
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "Club to remove the current book from (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
     ctx.defer().await?;
 
     let pool = &ctx.data().database;
     let google_books = &ctx.data().google_books;
-    let guild_id = ctx.guild_id().ok_or("No guild")?;
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
 
     let current_book = sqlx::query!(
         r#"
         SELECT scb.volume_id, du.username AS "username?"
         FROM server_current_book scb
         LEFT JOIN discord_users du ON du.user_id = scb.suggested_by_user_id
-        WHERE scb.server_id = $1
+        WHERE scb.club_id = $1
         "#,
-        guild_id.get() as i64
+        club.club_id
     )
     .fetch_optional(pool)
     .await?;
@@ -1197,7 +1692,7 @@ pub async fn remove(ctx: Context<'_>) -> Result<(), Error> {
                 SELECT volume_id, success, error_message
                 FROM remove_current_book_tx($1)
                 "#,
-                guild_id.get() as i64
+                club.club_id
             )
             .fetch_one(pool)
             .await?;
@@ -0,0 +1,749 @@
+use crate::commands::user::progress::{
+    PROGRESS_HIDDEN_MESSAGE, is_progress_banned, progress_text_is_allowed_in_channel,
+    validate_progress_text,
+};
+use crate::maturity_check::{
+    can_display_mature_content, check_volume_maturity, create_mature_content_warning,
+    current_channel_is_nsfw, server_maturity_enabled,
+};
+use crate::types::QueryMode;
+use crate::util::{detect_query_mode, get_guild_name, log_error_with_source, normalize_isbn};
+use crate::*;
+use crate::{types::Context, types::Error};
+use poise::serenity_prelude::{
+    self as serenity, AutocompleteChoice, ChannelType, CreateEmbed, CreateEmbedFooter,
+    CreateThread, User,
+};
+use sqlx::PgPool;
+
+/// Buddy reads are meant for pairs and small groups, not a second club.
+const MAX_BUDDY_READ_MEMBERS: i64 = 10;
+
+struct BuddyRead {
+    buddy_read_id: i32,
+    volume_id: String,
+    started_by_user_id: Option<i64>,
+    thread_id: Option<i64>,
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("start", "join", "progress", "finish"),
+    guild_only,
+    description_localized("en-US", "Read a book with a small group outside the club pick"),
+    user_cooldown = 10
+)]
+pub async fn buddyread(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+async fn book_title(ctx: Context<'_>, volume_id: &str) -> String {
+    match ctx.data().google_books.get_volume(volume_id).await {
+        Ok(volume) => volume.get_title(),
+        Err(_) => format!("Book ({})", volume_id),
+    }
+}
+
+/// Active buddy reads in the server, optionally limited to the ones `member_id` belongs to.
+async fn active_buddy_reads(
+    pool: &PgPool,
+    server_id: i64,
+    member_id: Option<i64>,
+) -> Result<Vec<BuddyRead>, Error> {
+    let reads = sqlx::query_as!(
+        BuddyRead,
+        r#"
+        SELECT br.buddy_read_id, br.volume_id, br.started_by_user_id, br.thread_id
+        FROM buddy_reads br
+        WHERE br.server_id = $1
+          AND br.finished_at IS NULL
+          AND ($2::BIGINT IS NULL OR EXISTS (
+              SELECT 1 FROM buddy_read_members brm
+              WHERE brm.buddy_read_id = br.buddy_read_id AND brm.user_id = $2
+          ))
+        ORDER BY br.started_at DESC
+        "#,
+        server_id,
+        member_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(reads)
+}
+
+async fn autocomplete_buddy_reads(
+    ctx: Context<'_>,
+    partial: &str,
+    member_id: Option<i64>,
+) -> Vec<AutocompleteChoice> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Vec::new();
+    };
+    let reads = active_buddy_reads(&ctx.data().database, guild_id.get() as i64, member_id)
+        .await
+        .unwrap_or_default();
+    let partial = partial.to_lowercase();
+
+    let mut choices = Vec::new();
+    for read in reads.into_iter().take(25) {
+        let title = book_title(ctx, &read.volume_id).await;
+        if title.to_lowercase().contains(&partial) {
+            choices.push(AutocompleteChoice::new(title, read.buddy_read_id));
+        }
+    }
+    choices
+}
+
+/// Autocompletes any active buddy read in the server (for `/buddyread join`).
+async fn autocomplete_joinable_buddy_read(
+    ctx: Context<'_>,
+    partial: &str,
+) -> Vec<AutocompleteChoice> {
+    autocomplete_buddy_reads(ctx, partial, None).await
+}
+
+/// Autocompletes the active buddy reads the caller belongs to.
+async fn autocomplete_my_buddy_read(ctx: Context<'_>, partial: &str) -> Vec<AutocompleteChoice> {
+    let member_id = ctx.author().id.get() as i64;
+    autocomplete_buddy_reads(ctx, partial, Some(member_id)).await
+}
+
+/// Whether the caller has Manage Server, which lets them finish any buddy read.
+async fn author_can_manage(ctx: Context<'_>) -> bool {
+    ctx.author_member()
+        .await
+        .and_then(|member| member.permissions)
+        .is_some_and(|permissions| permissions.manage_guild())
+}
+
+/// Autocompletes the buddy reads the caller can finish: their own, or every active one for
+/// members with Manage Server.
+async fn autocomplete_finishable_buddy_read(
+    ctx: Context<'_>,
+    partial: &str,
+) -> Vec<AutocompleteChoice> {
+    if author_can_manage(ctx).await {
+        autocomplete_buddy_reads(ctx, partial, None).await
+    } else {
+        autocomplete_my_buddy_read(ctx, partial).await
+    }
+}
+
+/// Resolves the buddy read a member is acting on. Without an explicit choice this is their
+/// only active buddy read; otherwise they're asked to pick one. Replies and returns `None`
+/// when nothing matches.
+async fn resolve_member_buddy_read(
+    ctx: Context<'_>,
+    buddy_read: Option<i32>,
+) -> Result<Option<BuddyRead>, Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(None);
+    };
+    let pool = &ctx.data().database;
+    let mut reads = active_buddy_reads(
+        pool,
+        guild_id.get() as i64,
+        Some(ctx.author().id.get() as i64),
+    )
+    .await?;
+
+    let description = match buddy_read {
+        Some(id) => match reads.iter().position(|read| read.buddy_read_id == id) {
+            Some(index) => return Ok(Some(reads.swap_remove(index))),
+            None => "You're not part of that buddy read, or it has already finished.".to_string(),
+        },
+        None if reads.len() == 1 => return Ok(reads.pop()),
+        None if reads.is_empty() => {
+            "You're not part of any buddy reads right now. Start one with `/buddyread start` or join one with `/buddyread join`.".to_string()
+        }
+        None => {
+            let mut titles = Vec::new();
+            for read in &reads {
+                titles.push(format!("• {}", book_title(ctx, &read.volume_id).await));
+            }
+            format!(
+                "You're in {} buddy reads. Pick one with the `buddy_read` option:\n\n{}",
+                reads.len(),
+                titles.join("\n")
+            )
+        }
+    };
+
+    let embed = CreateEmbed::default()
+        .title("❌ Buddy Read Not Found")
+        .description(description)
+        .color(0xB76E79);
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(None)
+}
+
+/// Builds the group's progress embed, hiding progress the current channel can't show.
+async fn buddy_read_embed(
+    ctx: Context<'_>,
+    read: &BuddyRead,
+    title: String,
+) -> Result<CreateEmbed, Error> {
+    let pool = &ctx.data().database;
+    let allow_unrestricted_sexual = can_display_mature_content(&ctx, pool).await?;
+    let book_title = book_title(ctx, &read.volume_id).await;
+
+    let members = sqlx::query!(
+        r#"
+        SELECT brm.user_id, du.username, brm.progress_text, brm.updated_at
+        FROM buddy_read_members brm
+        JOIN discord_users du ON du.user_id = brm.user_id
+        WHERE brm.buddy_read_id = $1
+        ORDER BY brm.joined_at, brm.user_id
+        "#,
+        read.buddy_read_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut description = format!("**{}**\n\n", book_title);
+    for member in &members {
+        let progress = match member.progress_text.as_deref() {
+            Some(text) if progress_text_is_allowed_in_channel(text, allow_unrestricted_sexual) => {
+                text
+            }
+            Some(_) => PROGRESS_HIDDEN_MESSAGE,
+            None => "No progress tracked yet",
+        };
+        let updated = member
+            .updated_at
+            .map(|dt| format!("\n_Last updated: <t:{}:R>_", dt.timestamp()))
+            .unwrap_or_default();
+        description.push_str(&format!(
+            "**{}** (<@{}>)\n{}{}\n\n",
+            member.username, member.user_id, progress, updated
+        ));
+    }
+
+    let mut embed = CreateEmbed::default()
+        .title(title)
+        .description(description.trim().to_string())
+        .color(0xB76E79)
+        .footer(CreateEmbedFooter::new(format!(
+            "{}/{} members • Powered by Google Books API",
+            members.len(),
+            MAX_BUDDY_READ_MEMBERS
+        )));
+    if let Some(thread_id) = read.thread_id {
+        embed = embed.field("Thread", format!("<#{}>", thread_id), false);
+    }
+
+    Ok(embed)
+}
+
+/// Opens a private thread for the group in the current channel. Failures (missing
+/// permissions, unsupported channel types) are logged and the buddy read goes ahead without one.
+async fn create_buddy_thread(
+    ctx: Context<'_>,
+    book_title: &str,
+    members: &[serenity::UserId],
+) -> Option<serenity::ChannelId> {
+    let name: String = format!("Buddy read: {}", book_title)
+        .chars()
+        .take(100)
+        .collect();
+    let thread = match ctx
+        .channel_id()
+        .create_thread(
+            ctx.http(),
+            CreateThread::new(name)
+                .kind(ChannelType::PrivateThread)
+                .invitable(false),
+        )
+        .await
+    {
+        Ok(thread) => thread,
+        Err(err) => {
+            log_error_with_source("Couldn't create buddy read thread", &err);
+            return None;
+        }
+    };
+
+    for member in members {
+        if let Err(err) = thread.id.add_thread_member(ctx.http(), *member).await {
+            log_error_with_source("Couldn't add member to buddy read thread", &err);
+        }
+    }
+
+    Some(thread.id)
+}
+
+#[poise::command(
+    slash_command,
+    description_localized("en-US", "Start a buddy read with up to three other members"),
+    user_cooldown = 10
+)]
+async fn start(
+    ctx: Context<'_>,
+    #[description = "Title or ISBN-10/13 of the book"] title_or_isbn: String,
+    #[description = "Author name (optional; used when title)"] author: Option<String>,
+    #[description = "Member to read with"] buddy: Option<User>,
+    #[description = "Another member to read with"] buddy2: Option<User>,
+    #[description = "Another member to read with"] buddy3: Option<User>,
+    #[description = "Open a private thread for the group (default: no)"] thread: Option<bool>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+    let pool = &ctx.data().database;
+    let google_books = &ctx.data().google_books;
+
+    let book = match detect_query_mode(&title_or_isbn) {
+        QueryMode::Isbn => {
+            let isbn = normalize_isbn(&title_or_isbn);
+            if isbn.len() != 10 && isbn.len() != 13 {
+                let embed = CreateEmbed::default()
+                    .title("❌ Invalid ISBN")
+                    .description(format!(
+                        "ISBN must be 10 or 13 characters long. You provided {} characters.",
+                        isbn.len()
+                    ))
+                    .color(0xB76E79)
+                    .footer(CreateEmbedFooter::new("Powered by Google Books API"));
+                ctx.send(poise::CreateReply::default().embed(embed)).await?;
+                return Ok(());
+            }
+            match google_books.search_by_isbn(&isbn).await? {
+                Some(book) => book,
+                None => {
+                    let embed = CreateEmbed::default()
+                        .title("❌ Book Not Found")
+                        .description(format!("No book found with ISBN: {}", title_or_isbn))
+                        .color(0xB76E79)
+                        .footer(CreateEmbedFooter::new("Searched via Google Books API"));
+                    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+                    return Ok(());
+                }
+            }
+        }
+        QueryMode::Title => {
+            let results = google_books
+                .search_books(&title_or_isbn, author.as_deref(), Some(1))
+                .await?;
+            let Some(book) = results.into_iter().next() else {
+                let embed = CreateEmbed::default()
+                    .title("❌ Book Not Found")
+                    .description("No books found with that title.")
+                    .color(0xB76E79)
+                    .footer(CreateEmbedFooter::new("Searched via Google Books API"));
+                ctx.send(poise::CreateReply::default().embed(embed)).await?;
+                return Ok(());
+            };
+            book
+        }
+    };
+
+    if !check_volume_maturity(&ctx, pool, &book).await? {
+        let is_nsfw = current_channel_is_nsfw(&ctx).await?;
+        let maturity_enabled = server_maturity_enabled(&ctx, pool).await?;
+        let embed =
+            create_mature_content_warning(Some(&book.get_title()), is_nsfw, maturity_enabled);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let mut members: Vec<User> = vec![ctx.author().clone()];
+    for buddy in [buddy, buddy2, buddy3].into_iter().flatten() {
+        if buddy.bot {
+            let embed = CreateEmbed::default()
+                .title("❌ Invalid Buddy")
+                .description("Bots can't join buddy reads.")
+                .color(0xB76E79);
+            ctx.send(poise::CreateReply::default().embed(embed)).await?;
+            return Ok(());
+        }
+        if !members.iter().any(|member| member.id == buddy.id) {
+            members.push(buddy);
+        }
+    }
+
+    let server_id = guild_id.get() as i64;
+    let member_ids: Vec<i64> = members
+        .iter()
+        .map(|member| member.id.get() as i64)
+        .collect();
+    let already_reading = sqlx::query_scalar!(
+        r#"
+        SELECT DISTINCT brm.user_id
+        FROM buddy_reads br
+        JOIN buddy_read_members brm ON brm.buddy_read_id = br.buddy_read_id
+        WHERE br.server_id = $1 AND br.volume_id = $2
+          AND br.finished_at IS NULL AND brm.user_id = ANY($3)
+        "#,
+        server_id,
+        book.id,
+        &member_ids
+    )
+    .fetch_all(pool)
+    .await?;
+
+    if !already_reading.is_empty() {
+        // Keep the order members were given in
+        let conflicting: Vec<String> = members
+            .iter()
+            .filter(|member| already_reading.contains(&(member.id.get() as i64)))
+            .map(|member| format!("<@{}>", member.id))
+            .collect();
+        let description = if conflicting.len() == 1 && already_reading[0] == member_ids[0] {
+            format!(
+                "You're already in a buddy read for **{}**. Use `/buddyread progress` to post an update.",
+                book.get_title()
+            )
+        } else {
+            format!(
+                "Already in a buddy read for **{}**: {}. Each member can only be in one active buddy read per book.",
+                book.get_title(),
+                conflicting.join(", ")
+            )
+        };
+        let embed = CreateEmbed::default()
+            .title("Already Buddy Reading")
+            .description(description)
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let guild_name = get_guild_name(&ctx).await;
+    ensure_server_exists(pool, guild_id, &guild_name).await?;
+    for member in &members {
+        ensure_user_exists(pool, member).await?;
+    }
+
+    let thread_id = if thread.unwrap_or(false) {
+        let member_ids: Vec<serenity::UserId> = members.iter().map(|member| member.id).collect();
+        create_buddy_thread(ctx, &book.get_title(), &member_ids).await
+    } else {
+        None
+    };
+
+    let mut tx = pool.begin().await?;
+    let buddy_read_id = sqlx::query_scalar!(
+        "INSERT INTO buddy_reads (server_id, volume_id, started_by_user_id, thread_id)
+         VALUES ($1, $2, $3, $4)
+         RETURNING buddy_read_id",
+        server_id,
+        book.id,
+        ctx.author().id.get() as i64,
+        thread_id.map(|id| id.get() as i64)
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "INSERT INTO buddy_read_members (buddy_read_id, user_id)
+         SELECT $1, UNNEST($2::BIGINT[])",
+        buddy_read_id,
+        &member_ids
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    let read = BuddyRead {
+        buddy_read_id,
+        volume_id: book.id.clone(),
+        started_by_user_id: Some(ctx.author().id.get() as i64),
+        thread_id: thread_id.map(|id| id.get() as i64),
+    };
+    let mut embed = buddy_read_embed(ctx, &read, "📖 Buddy Read Started".to_string()).await?;
+    if thread.unwrap_or(false) && thread_id.is_none() {
+        embed = embed.field(
+            "Thread",
+            "I couldn't open a private thread here, so the group will have to chat elsewhere.",
+            false,
+        );
+    }
+    if let Some(url) = book.get_thumbnail_url() {
+        embed = embed.thumbnail(url);
+    }
+    ctx.send(
+        poise::CreateReply::default()
+            .content("Others can hop in with `/buddyread join`.")
+            .embed(embed),
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    description_localized("en-US", "Join an active buddy read"),
+    user_cooldown = 10
+)]
+async fn join(
+    ctx: Context<'_>,
+    #[description = "Buddy read to join"]
+    #[autocomplete = "autocomplete_joinable_buddy_read"]
+    buddy_read: i32,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+    let pool = &ctx.data().database;
+
+    let read = active_buddy_reads(pool, guild_id.get() as i64, None)
+        .await?
+        .into_iter()
+        .find(|read| read.buddy_read_id == buddy_read);
+    let Some(read) = read else {
+        let embed = CreateEmbed::default()
+            .title("❌ Buddy Read Not Found")
+            .description("That buddy read doesn't exist in this server or has already finished.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+
+    let volume = ctx.data().google_books.get_volume(&read.volume_id).await;
+    if let Ok(volume) = &volume
+        && !check_volume_maturity(&ctx, pool, volume).await?
+    {
+        let is_nsfw = current_channel_is_nsfw(&ctx).await?;
+        let maturity_enabled = server_maturity_enabled(&ctx, pool).await?;
+        let embed =
+            create_mature_content_warning(Some(&volume.get_title()), is_nsfw, maturity_enabled);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    ensure_user_exists(pool, ctx.author()).await?;
+
+    // The insert only happens while the group has room, so concurrent joins can't overfill it
+    let joined = sqlx::query!(
+        "INSERT INTO buddy_read_members (buddy_read_id, user_id)
+         SELECT $1, $2
+         WHERE (SELECT COUNT(*) FROM buddy_read_members WHERE buddy_read_id = $1) < $3
+         ON CONFLICT DO NOTHING",
+        read.buddy_read_id,
+        ctx.author().id.get() as i64,
+        MAX_BUDDY_READ_MEMBERS
+    )
+    .execute(pool)
+    .await?;
+
+    if joined.rows_affected() == 0 {
+        let already_member = sqlx::query_scalar!(
+            r#"SELECT EXISTS(
+                SELECT 1 FROM buddy_read_members WHERE buddy_read_id = $1 AND user_id = $2
+            ) AS "exists!""#,
+            read.buddy_read_id,
+            ctx.author().id.get() as i64
+        )
+        .fetch_one(pool)
+        .await?;
+        let description = if already_member {
+            "You're already part of this buddy read.".to_string()
+        } else {
+            format!(
+                "This buddy read already has {} members. Start your own with `/buddyread start`.",
+                MAX_BUDDY_READ_MEMBERS
+            )
+        };
+        let embed = CreateEmbed::default()
+            .title("Couldn't Join")
+            .description(description)
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    if let Some(thread_id) = read.thread_id
+        && let Err(err) = serenity::ChannelId::new(thread_id as u64)
+            .add_thread_member(ctx.http(), ctx.author().id)
+            .await
+    {
+        log_error_with_source("Couldn't add member to buddy read thread", &err);
+    }
+
+    let embed = buddy_read_embed(ctx, &read, "👋 Joined Buddy Read".to_string()).await?;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    description_localized(
+        "en-US",
+        "Update or view your buddy read progress (280 character limit)"
+    ),
+    user_cooldown = 10
+)]
+async fn progress(
+    ctx: Context<'_>,
+    #[description = "Your progress update (leave empty to view the group's progress)"]
+    progress_text: Option<String>,
+    #[description = "Buddy read to update (only needed if you're in more than one)"]
+    #[autocomplete = "autocomplete_my_buddy_read"]
+    buddy_read: Option<i32>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+    let pool = &ctx.data().database;
+
+    if is_progress_banned(pool, guild_id.get() as i64, ctx.author().id.get() as i64).await? {
+        let embed = CreateEmbed::default()
+            .title("🚫 Progress Command Disabled")
+            .description("You are banned from using /progress commands in this server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let sanitized_progress = match progress_text.as_deref() {
+        Some(text) => {
+            let allow_unrestricted_sexual = can_display_mature_content(&ctx, pool).await?;
+            match validate_progress_text(text, allow_unrestricted_sexual) {
+                Ok(text) => Some(text),
+                Err(error_msg) => {
+                    let embed = CreateEmbed::default()
+                        .title("❌ Invalid Progress Update")
+                        .description(error_msg)
+                        .color(0xB76E79)
+                        .footer(CreateEmbedFooter::new("Powered by Google Books API"));
+                    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+                    return Ok(());
+                }
+            }
+        }
+        None => None,
+    };
+
+    let Some(read) = resolve_member_buddy_read(ctx, buddy_read).await? else {
+        return Ok(());
+    };
+
+    let title = match sanitized_progress {
+        Some(text) => {
+            sqlx::query!(
+                "UPDATE buddy_read_members
+                 SET progress_text = $3, updated_at = CURRENT_TIMESTAMP
+                 WHERE buddy_read_id = $1 AND user_id = $2",
+                read.buddy_read_id,
+                ctx.author().id.get() as i64,
+                text
+            )
+            .execute(pool)
+            .await?;
+            "✅ Buddy Read Progress Updated"
+        }
+        None => "📖 Buddy Read Progress",
+    };
+
+    let embed = buddy_read_embed(ctx, &read, title.to_string()).await?;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    description_localized("en-US", "Wrap up a buddy read you started"),
+    user_cooldown = 10
+)]
+async fn finish(
+    ctx: Context<'_>,
+    #[description = "Buddy read to finish (needed if you're in several, or to finish someone else's as an admin)"]
+    #[autocomplete = "autocomplete_finishable_buddy_read"]
+    buddy_read: Option<i32>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    if ctx.guild_id().is_none() {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+    let can_manage = author_can_manage(ctx).await;
+
+    // Members with Manage Server can finish a read they aren't part of by picking it
+    let read = match buddy_read {
+        Some(id) if can_manage => {
+            let Some(guild_id) = ctx.guild_id() else {
+                return Ok(());
+            };
+            let reads =
+                active_buddy_reads(&ctx.data().database, guild_id.get() as i64, None).await?;
+            reads.into_iter().find(|read| read.buddy_read_id == id)
+        }
+        _ => match resolve_member_buddy_read(ctx, buddy_read).await? {
+            Some(read) => Some(read),
+            None => return Ok(()),
+        },
+    };
+    let Some(read) = read else {
+        let embed = CreateEmbed::default()
+            .title("❌ Buddy Read Not Found")
+            .description("That buddy read doesn't exist or has already finished.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    };
+
+    let is_starter = read.started_by_user_id == Some(ctx.author().id.get() as i64);
+    if !is_starter && !can_manage {
+        let embed = CreateEmbed::default()
+            .title("🚫 Can't Finish This Buddy Read")
+            .description("Only the member who started it (or someone with Manage Server) can finish a buddy read.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    sqlx::query!(
+        "UPDATE buddy_reads SET finished_at = CURRENT_TIMESTAMP WHERE buddy_read_id = $1",
+        read.buddy_read_id
+    )
+    .execute(&ctx.data().database)
+    .await?;
+
+    let embed = buddy_read_embed(ctx, &read, "🎉 Buddy Read Finished".to_string()).await?;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    if let Some(thread_id) = read.thread_id {
+        let archive = serenity::EditThread::new().archived(true);
+        if let Err(err) = serenity::ChannelId::new(thread_id as u64)
+            .edit_thread(ctx.http(), archive)
+            .await
+        {
+            log_error_with_source("Couldn't archive buddy read thread", &err);
+        }
+    }
+
+    Ok(())
+}
@@ -1,10 +1,12 @@
+use crate::charts::{rating_counts, rating_histogram};
 use crate::maturity_check::{
     check_volume_maturity, create_mature_content_warning, current_channel_is_nsfw,
     server_maturity_enabled,
 };
 use crate::types::QueryMode;
 use crate::util::{
-    detect_query_mode, embed_author_with_icon, get_guild_icon_url, get_guild_name, normalize_isbn,
+    autocomplete_club, book_participation, detect_query_mode, embed_author_with_icon,
+    get_guild_icon_url, get_guild_name, normalize_isbn, resolve_club,
 };
 use crate::{types::Context, types::Error};
 use poise::serenity_prelude::{
@@ -22,20 +24,26 @@ pub async fn clubrating(
     ctx: Context<'_>,
     #[description = "Title or ISBN-10/13 to check ratings for"] title_or_isbn: String,
     #[description = "Author name (optional; used when title)"] author: Option<String>,
+    #[description = "Club whose ratings to show (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
 ) -> Result<(), Error> {
     ctx.defer().await?;
 
     let pool = &ctx.data().database;
     let google_books = &ctx.data().google_books;
-    let Some(guild_id) = ctx.guild_id() else {
+    if ctx.guild_id().is_none() {
         let embed = CreateEmbed::default()
             .title("❌ Error")
             .description("This command must be used in a server.")
             .color(0xB76E79);
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
     };
-    let guild_name = get_guild_name(&ctx).await;
+    let guild_name = format!("{}{}", get_guild_name(&ctx).await, club.title_suffix());
     let guild_icon = get_guild_icon_url(&ctx).await;
 
     // Search for the book using Google Books API
@@ -120,16 +128,16 @@ pub async fn clubrating(
         return Ok(());
     }
 
-    // Check if book was completed by this server
+    // Check if book was completed by this club
     let completed_book = sqlx::query!(
         r#"
         SELECT completed_id, average_rating, total_ratings
         FROM server_completed_books
-        WHERE server_id = $1 AND volume_id = $2
+        WHERE club_id = $1 AND volume_id = $2
         ORDER BY completed_at DESC
         LIMIT 1
         "#,
-        guild_id.get() as i64,
+        club.club_id,
         volume_id
     )
     .fetch_optional(pool)
@@ -181,6 +189,11 @@ pub async fn clubrating(
                 )
             });
 
+            let participation = book_participation(pool, book_info.completed_id)
+                .await?
+                .summary();
+            let chart = rating_histogram(rating_counts(ratings.iter().map(|r| r.rating)));
+
             let make_embed = |page: usize| {
                 let mut e = CreateEmbed::default()
                     .author(embed_author_with_icon(
@@ -193,6 +206,7 @@ pub async fn clubrating(
                 if let Some(avg) = avg_display.as_ref() {
                     e = e.field("Average Rating", avg.clone(), false);
                 }
+                e = e.field("Participation", participation.clone(), false);
 
                 if ratings.is_empty() {
                     e = e.description("No ratings yet!");
@@ -215,6 +229,9 @@ pub async fn clubrating(
                         .push_str(&format!("**{}**: {}/5\n", rating.username, rating.rating));
                 }
                 e = e.field("Individual Ratings", ratings_text, false);
+                if let Some(chart) = chart.as_ref() {
+                    e = e.image(chart.url());
+                }
 
                 let footer_text = if total_pages > 1 {
                     format!(
@@ -263,9 +280,12 @@ pub async fn clubrating(
                 ])]
             };
 
-            let reply = poise::CreateReply::default()
+            let mut reply = poise::CreateReply::default()
                 .embed(make_embed(page))
                 .components(make_components(page));
+            if let Some(chart) = chart.as_ref() {
+                reply = reply.attachment(chart.attachment());
+            }
             let mut msg = ctx.send(reply).await?.into_message().await?;
 
             if total_pages > 1 {
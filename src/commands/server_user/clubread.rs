@@ -1,11 +1,18 @@
+use crate::charts::{Chart, rating_timeline};
+use crate::club_export::{ExportedBook, to_csv, to_json, to_markdown};
 use crate::maturity_check::{
     check_volume_maturity, current_channel_is_nsfw, server_maturity_enabled,
 };
-use crate::util::{embed_author_with_icon, get_guild_icon_url, get_guild_name};
+use crate::util::{
+    autocomplete_club, embed_author_with_icon, get_guild_icon_url, get_guild_name, resolve_club,
+};
 use crate::{types::Context, types::Error};
+use chrono::Utc;
 use poise::serenity_prelude::{
-    ButtonStyle, CreateActionRow, CreateButton, CreateEmbed, CreateEmbedFooter,
+    ButtonStyle, CreateActionRow, CreateAttachment, CreateButton, CreateEmbed, CreateEmbedFooter,
 };
+use sqlx::PgPool;
+use std::collections::HashMap;
 use std::time::Duration;
 
 #[derive(poise::ChoiceParameter, Clone, Copy, Debug)]
@@ -16,31 +23,83 @@ pub enum ClubReadSort {
     Date,
 }
 
+// Titles aren't drawn, so mature books can stay in the trend
+async fn rating_trend_chart(pool: &PgPool, club_id: i32) -> Result<Option<Chart>, Error> {
+    let points = sqlx::query!(
+        r#"
+        SELECT completed_at AS "completed_at!", average_rating::FLOAT8 AS "average_rating!"
+        FROM server_completed_books
+        WHERE club_id = $1
+          AND completed_at IS NOT NULL
+          AND average_rating IS NOT NULL
+          AND total_ratings > 0
+        ORDER BY completed_at
+        "#,
+        club_id
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| (row.completed_at.date_naive(), row.average_rating))
+    .collect::<Vec<_>>();
+
+    Ok(rating_timeline(&points))
+}
+
+#[derive(poise::ChoiceParameter, Clone, Copy, Debug)]
+pub enum ClubExportFormat {
+    #[name = "csv"]
+    Csv,
+    #[name = "json"]
+    Json,
+    #[name = "md"]
+    Markdown,
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("view", "export"),
+    guild_only,
+    description_localized("en-US", "Browse or export the books your club has completed"),
+    user_cooldown = 10
+)]
+pub async fn clubread(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
 #[poise::command(
     slash_command,
+    rename = "view",
     guild_only,
     description_localized("en-US", "List all books completed by the server with rankings"),
     user_cooldown = 10
 )]
-pub async fn clubread(
+async fn view(
     ctx: Context<'_>,
     #[description = "Sort by 'rating' or 'date' (default: rating)"] sort: Option<ClubReadSort>,
+    #[description = "Club whose history to show (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
 ) -> Result<(), Error> {
     ctx.defer().await?;
 
     let pool = &ctx.data().database;
     let google_books = &ctx.data().google_books;
-    let Some(guild_id) = ctx.guild_id() else {
+    if ctx.guild_id().is_none() {
         let embed = CreateEmbed::default()
             .title("❌ Error")
             .description("This command must be used in a server.")
             .color(0xB76E79);
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
     };
-    let guild_name = get_guild_name(&ctx).await;
+    let guild_name = format!("{}{}", get_guild_name(&ctx).await, club.title_suffix());
     let guild_icon = get_guild_icon_url(&ctx).await;
     let sort = sort.unwrap_or(ClubReadSort::Rating);
+    let chart = rating_trend_chart(pool, club.club_id).await?;
 
     match sort {
         ClubReadSort::Rating => {
@@ -54,9 +113,9 @@ pub async fn clubread(
                 average_rating,
                 total_ratings,
                 completed_at
-                FROM get_server_book_rankings($1)
+                FROM get_club_book_rankings($1)
                 "#,
-                guild_id.get() as i64
+                club.club_id
             )
             .fetch_all(pool)
             .await?;
@@ -210,6 +269,10 @@ pub async fn clubread(
                     );
                 }
 
+                if let Some(chart) = chart.as_ref() {
+                    embed = embed.image(chart.url());
+                }
+
                 if total_pages > 1 {
                     embed = embed.footer(CreateEmbedFooter::new(format!(
                         "Page {} / {} • showing {}–{} of {} • Powered by Google Books API",
@@ -254,13 +317,16 @@ pub async fn clubread(
                 ])]
             };
 
-            let reply = poise::CreateReply::default()
+            let mut reply = poise::CreateReply::default()
                 .embed(make_embed(page))
                 .components(if total_pages > 1 {
                     make_components(page)
                 } else {
                     vec![]
                 });
+            if let Some(chart) = chart.as_ref() {
+                reply = reply.attachment(chart.attachment());
+            }
 
             let mut msg = ctx.send(reply).await?.into_message().await?;
 
@@ -346,10 +412,10 @@ pub async fn clubread(
                     scb.completed_at
                 FROM server_completed_books scb
                 LEFT JOIN discord_users du ON du.user_id = scb.suggested_by_user_id
-                WHERE scb.server_id = $1
+                WHERE scb.club_id = $1
                 ORDER BY scb.completed_at ASC 
                 "#,
-                guild_id.get() as i64
+                club.club_id
             )
             .fetch_all(pool)
             .await?;
@@ -490,6 +556,10 @@ pub async fn clubread(
                     );
                 }
 
+                if let Some(chart) = chart.as_ref() {
+                    embed = embed.image(chart.url());
+                }
+
                 if total_pages > 1 {
                     embed = embed.footer(CreateEmbedFooter::new(format!(
                         "Page {} / {} • showing {}–{} of {} • Powered by Google Books API",
@@ -534,13 +604,16 @@ pub async fn clubread(
                 ])]
             };
 
-            let reply = poise::CreateReply::default()
+            let mut reply = poise::CreateReply::default()
                 .embed(make_embed(page))
                 .components(if total_pages > 1 {
                     make_components(page)
                 } else {
                     vec![]
                 });
+            if let Some(chart) = chart.as_ref() {
+                reply = reply.attachment(chart.attachment());
+            }
 
             let mut msg = ctx.send(reply).await?.into_message().await?;
 
@@ -616,3 +689,164 @@ pub async fn clubread(
 
     Ok(())
 }
+
+#[poise::command(
+    slash_command,
+    rename = "export",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized("en-US", "Export the club's reading history as a file"),
+    user_cooldown = 10
+)]
+async fn export(
+    ctx: Context<'_>,
+    #[description = "File format: csv, json, or md (a ready-to-post reading log)"]
+    format: ClubExportFormat,
+    #[description = "Include each member's rating (default: no)"] member_ratings: Option<bool>,
+    #[description = "Club whose history to export (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let pool = &ctx.data().database;
+    let google_books = &ctx.data().google_books;
+    if ctx.guild_id().is_none() {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
+    let guild_name = format!("{}{}", get_guild_name(&ctx).await, club.title_suffix());
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            scb.completed_id,
+            scb.volume_id,
+            du.username AS "suggested_by_username?",
+            scb.started_at,
+            scb.completed_at,
+            scb.average_rating::FLOAT8 AS average_rating,
+            COALESCE(scb.total_ratings, 0) AS "total_ratings!"
+        FROM server_completed_books scb
+        LEFT JOIN discord_users du ON du.user_id = scb.suggested_by_user_id
+        WHERE scb.club_id = $1
+        ORDER BY scb.completed_at ASC
+        "#,
+        club.club_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    if rows.is_empty() {
+        let embed = CreateEmbed::default()
+            .title("No Books Completed")
+            .description("This server hasn't completed any books yet!")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let mut ratings_by_book: HashMap<i32, Vec<(String, i32)>> = HashMap::new();
+    if member_ratings.unwrap_or(false) {
+        for rating in sqlx::query!(
+            r#"
+            SELECT ubr.completed_id, du.username, ubr.rating
+            FROM user_book_ratings ubr
+            JOIN server_completed_books scb ON scb.completed_id = ubr.completed_id
+            JOIN discord_users du ON du.user_id = ubr.user_id
+            WHERE scb.club_id = $1
+            ORDER BY ubr.rating DESC, du.username
+            "#,
+            club.club_id
+        )
+        .fetch_all(pool)
+        .await?
+        {
+            ratings_by_book
+                .entry(rating.completed_id)
+                .or_default()
+                .push((rating.username, rating.rating));
+        }
+    }
+
+    let volume_ids: Vec<String> = rows.iter().map(|row| row.volume_id.clone()).collect();
+    let volumes = google_books.get_volumes_batch(&volume_ids).await;
+
+    let mut books = Vec::with_capacity(rows.len());
+    let mut mature_count = 0;
+    for (row, volume) in rows.into_iter().zip(volumes) {
+        let (title, authors, isbn) = match volume {
+            Ok(volume) => {
+                if !check_volume_maturity(&ctx, pool, &volume).await? {
+                    mature_count += 1;
+                    continue;
+                }
+                (
+                    volume.get_title(),
+                    volume.volume_info.authors.clone().unwrap_or_default(),
+                    volume.get_isbn(),
+                )
+            }
+            // Keep books that fail to fetch so the archive stays complete
+            Err(_) => (format!("Book ({})", row.volume_id), Vec::new(), None),
+        };
+        books.push(ExportedBook {
+            title,
+            authors,
+            isbn,
+            suggested_by: row.suggested_by_username,
+            started_at: row.started_at,
+            completed_at: row.completed_at,
+            average_rating: row.average_rating,
+            total_ratings: row.total_ratings,
+            member_ratings: member_ratings.unwrap_or(false).then(|| {
+                ratings_by_book
+                    .remove(&row.completed_id)
+                    .unwrap_or_default()
+            }),
+            volume_id: row.volume_id,
+        });
+    }
+
+    let exported_at = Utc::now();
+    let (contents, extension) = match format {
+        ClubExportFormat::Csv => (to_csv(&books), "csv"),
+        ClubExportFormat::Json => (to_json(&guild_name, exported_at, &books), "json"),
+        ClubExportFormat::Markdown => (to_markdown(&guild_name, exported_at, &books), "md"),
+    };
+
+    let mut description = format!("Exported {} completed book(s).", books.len());
+    if mature_count > 0 {
+        description.push_str(&format!(
+            " {} mature book(s) were left out; run the export in an NSFW channel with mature content enabled to include them.",
+            mature_count
+        ));
+    }
+    let embed = CreateEmbed::default()
+        .author(embed_author_with_icon(
+            format!("{} Reading History", guild_name),
+            get_guild_icon_url(&ctx).await,
+        ))
+        .title("📦 Club History Export")
+        .description(description)
+        .color(0xB76E79)
+        .footer(CreateEmbedFooter::new("Powered by Google Books API"));
+    ctx.send(
+        poise::CreateReply::default()
+            .embed(embed)
+            .attachment(CreateAttachment::bytes(
+                contents,
+                format!("club-history.{}", extension),
+            )),
+    )
+    .await?;
+
+    Ok(())
+}
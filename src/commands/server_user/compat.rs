@@ -0,0 +1,330 @@
+use crate::maturity_check::check_volume_maturity;
+use crate::util::{embed_author_with_icon, get_guild_name, member_left_guild};
+use crate::{types::Context, types::Error};
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter, User, UserId};
+use std::collections::HashMap;
+
+/// Books both members must have rated before a score means anything.
+const MIN_SHARED_RATINGS: usize = 3;
+/// Matches listed by `/compat top`.
+const TOP_MATCHES: usize = 5;
+/// Books listed under "agreed" and "disagreed".
+const BOOKS_PER_LIST: usize = 3;
+
+/// Taste similarity from -1 (opposite) to 1 (identical) over pairs of ratings for the same
+/// books. Uses the Pearson correlation; when either member gave every shared book the same
+/// score the correlation is undefined, so it falls back to how close their scores were.
+/// Returns `None` below `MIN_SHARED_RATINGS`.
+pub(crate) fn taste_similarity(pairs: &[(i32, i32)]) -> Option<f64> {
+    if pairs.len() < MIN_SHARED_RATINGS {
+        return None;
+    }
+    let n = pairs.len() as f64;
+    let mean_a = pairs.iter().map(|(a, _)| *a as f64).sum::<f64>() / n;
+    let mean_b = pairs.iter().map(|(_, b)| *b as f64).sum::<f64>() / n;
+
+    let mut covariance = 0.0;
+    let mut variance_a = 0.0;
+    let mut variance_b = 0.0;
+    for (a, b) in pairs {
+        let (da, db) = (*a as f64 - mean_a, *b as f64 - mean_b);
+        covariance += da * db;
+        variance_a += da * da;
+        variance_b += db * db;
+    }
+
+    if variance_a > 0.0 && variance_b > 0.0 {
+        return Some(covariance / (variance_a * variance_b).sqrt());
+    }
+    // Ratings run 1-5, so the largest possible gap is 4
+    let mean_gap = pairs.iter().map(|(a, b)| (a - b).abs() as f64).sum::<f64>() / n;
+    Some(1.0 - mean_gap / 2.0)
+}
+
+/// The similarity as a 0-100% compatibility score.
+pub(crate) fn compatibility_percent(similarity: f64) -> f64 {
+    ((similarity + 1.0) / 2.0 * 100.0).clamp(0.0, 100.0)
+}
+
+fn describe_score(percent: f64) -> &'static str {
+    match percent {
+        p if p >= 85.0 => "Reading twins 📚",
+        p if p >= 65.0 => "Kindred spirits",
+        p if p >= 45.0 => "Some common ground",
+        p if p >= 25.0 => "Different shelves",
+        _ => "Opposite tastes",
+    }
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("member", "top"),
+    guild_only,
+    description_localized("en-US", "Compare reading taste with other members"),
+    user_cooldown = 10
+)]
+pub async fn compat(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    guild_only,
+    description_localized("en-US", "See how closely your book ratings match another member's"),
+    user_cooldown = 10
+)]
+async fn member(
+    ctx: Context<'_>,
+    #[description = "Member to compare with"] user: User,
+    #[description = "Compare them with this member instead of you"] with: Option<User>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+    let google_books = &ctx.data().google_books;
+    let base_user = with.as_ref().unwrap_or_else(|| ctx.author());
+
+    if base_user.id == user.id {
+        let embed = CreateEmbed::default()
+            .title("❌ Same Member")
+            .description("Pick two different members to compare.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let shared = sqlx::query!(
+        r#"
+        SELECT scb.volume_id, mine.rating AS mine, theirs.rating AS theirs
+        FROM user_book_ratings mine
+        JOIN user_book_ratings theirs ON theirs.completed_id = mine.completed_id
+        JOIN server_completed_books scb ON scb.completed_id = mine.completed_id
+        JOIN server_clubs sc ON sc.club_id = scb.club_id
+        WHERE sc.server_id = $1 AND mine.user_id = $2 AND theirs.user_id = $3
+        ORDER BY scb.completed_at DESC
+        "#,
+        guild_id.get() as i64,
+        base_user.id.get() as i64,
+        user.id.get() as i64
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let pairs: Vec<(i32, i32)> = shared.iter().map(|row| (row.mine, row.theirs)).collect();
+    let Some(similarity) = taste_similarity(&pairs) else {
+        let embed = CreateEmbed::default()
+            .author(embed_author_with_icon(
+                format!("{} & {}", base_user.name, user.name),
+                Some(user.face()),
+            ))
+            .title("Not Enough Shared Ratings")
+            .description(format!(
+                "They've rated {} of the same club book{} in this server. At least {} are needed for a compatibility score.",
+                pairs.len(),
+                if pairs.len() == 1 { "" } else { "s" },
+                MIN_SHARED_RATINGS
+            ))
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+    let percent = compatibility_percent(similarity);
+
+    // Mature books still count toward the score but are left out of the lists
+    let volume_ids: Vec<String> = shared.iter().map(|row| row.volume_id.clone()).collect();
+    let mut titles: HashMap<&str, String> = HashMap::new();
+    for (row, result) in shared
+        .iter()
+        .zip(google_books.get_volumes_batch(&volume_ids).await)
+    {
+        match result {
+            Ok(volume) if check_volume_maturity(&ctx, pool, &volume).await? => {
+                titles.insert(row.volume_id.as_str(), volume.get_title());
+            }
+            Ok(_) => {}
+            Err(_) => {
+                titles.insert(row.volume_id.as_str(), format!("Book ({})", row.volume_id));
+            }
+        }
+    }
+    let listable: Vec<_> = shared
+        .iter()
+        .filter_map(|row| Some((titles.get(row.volume_id.as_str())?, row.mine, row.theirs)))
+        .collect();
+
+    let mut agreed = listable.clone();
+    // Closest scores first, favoring books they both loved
+    agreed.sort_by_key(|(_, mine, theirs)| ((mine - theirs).abs(), -(mine + theirs)));
+    let mut disagreed: Vec<_> = listable
+        .iter()
+        .filter(|(_, mine, theirs)| mine != theirs)
+        .copied()
+        .collect();
+    disagreed.sort_by_key(|(_, mine, theirs)| -(mine - theirs).abs());
+
+    let format_books = |books: &[(&String, i32, i32)]| {
+        if books.is_empty() {
+            return "Nothing to show.".to_string();
+        }
+        books
+            .iter()
+            .take(BOOKS_PER_LIST)
+            .map(|(title, mine, theirs)| {
+                format!(
+                    "**{}** — {}: {}/5 • {}: {}/5",
+                    title, base_user.name, mine, user.name, theirs
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let embed = CreateEmbed::default()
+        .author(embed_author_with_icon(
+            format!("{} & {}", base_user.name, user.name),
+            Some(user.face()),
+        ))
+        .title(format!("{:.0}% Compatible", percent))
+        .description(describe_score(percent))
+        .color(0xB76E79)
+        .field("Books Both Rated", pairs.len().to_string(), true)
+        .field("Most Agreed On", format_books(&agreed), false)
+        .field("Most Disagreed On", format_books(&disagreed), false)
+        .footer(CreateEmbedFooter::new(format!(
+            "{} • Based on ratings of club books in this server",
+            get_guild_name(&ctx).await
+        )));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    guild_only,
+    description_localized("en-US", "Find the members whose taste is closest to yours"),
+    user_cooldown = 10
+)]
+async fn top(
+    ctx: Context<'_>,
+    #[description = "Member to find matches for (defaults to you)"] user: Option<User>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+    let target_user = user.as_ref().unwrap_or_else(|| ctx.author());
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT theirs.user_id, mine.rating AS mine, theirs.rating AS theirs
+        FROM user_book_ratings mine
+        JOIN user_book_ratings theirs
+            ON theirs.completed_id = mine.completed_id AND theirs.user_id <> mine.user_id
+        JOIN server_completed_books scb ON scb.completed_id = mine.completed_id
+        JOIN server_clubs sc ON sc.club_id = scb.club_id
+        WHERE sc.server_id = $1 AND mine.user_id = $2
+        "#,
+        guild_id.get() as i64,
+        target_user.id.get() as i64
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut pairs_by_user: HashMap<i64, Vec<(i32, i32)>> = HashMap::new();
+    for row in rows {
+        pairs_by_user
+            .entry(row.user_id)
+            .or_default()
+            .push((row.mine, row.theirs));
+    }
+    let mut candidates: Vec<(i64, f64, usize)> = pairs_by_user
+        .iter()
+        .filter_map(|(user_id, pairs)| Some((*user_id, taste_similarity(pairs)?, pairs.len())))
+        .collect();
+    candidates.sort_by(|a, b| b.1.total_cmp(&a.1).then(b.2.cmp(&a.2)));
+
+    // Ratings outlive membership, so skip anyone who left or deleted their account
+    let mut matches: Vec<String> = Vec::new();
+    for (user_id, similarity, shared) in candidates {
+        if matches.len() >= TOP_MATCHES {
+            break;
+        }
+        if member_left_guild(ctx.http(), guild_id, UserId::new(user_id as u64)).await? {
+            continue;
+        }
+        let percent = compatibility_percent(similarity);
+        matches.push(format!(
+            "**{}.** <@{}> — {:.0}% ({}, {} shared ratings)",
+            matches.len() + 1,
+            user_id,
+            percent,
+            describe_score(percent),
+            shared
+        ));
+    }
+
+    let embed = CreateEmbed::default()
+        .author(embed_author_with_icon(
+            format!("{}'s Closest Reading Matches", target_user.name),
+            Some(target_user.face()),
+        ))
+        .description(if matches.is_empty() {
+            format!(
+                "No one in this server has rated at least {} of the same club books yet.",
+                MIN_SHARED_RATINGS
+            )
+        } else {
+            matches.join("\n")
+        })
+        .color(0xB76E79)
+        .footer(CreateEmbedFooter::new(format!(
+            "Needs at least {} shared ratings • Use /compat member to see the details",
+            MIN_SHARED_RATINGS
+        )));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compatibility_percent, taste_similarity};
+
+    #[test]
+    fn similarity_needs_enough_shared_ratings() {
+        assert_eq!(taste_similarity(&[(5, 5), (1, 1)]), None);
+    }
+
+    #[test]
+    fn similarity_uses_correlation() {
+        let aligned = taste_similarity(&[(1, 2), (3, 4), (5, 5)]).unwrap();
+        assert!(aligned > 0.9);
+        let opposite = taste_similarity(&[(1, 5), (3, 3), (5, 1)]).unwrap();
+        assert!((opposite + 1.0).abs() < 1e-9);
+        assert_eq!(compatibility_percent(opposite), 0.0);
+    }
+
+    #[test]
+    fn similarity_falls_back_to_score_gaps_without_variance() {
+        assert_eq!(taste_similarity(&[(4, 4), (4, 4), (4, 4)]), Some(1.0));
+        assert_eq!(taste_similarity(&[(5, 1), (5, 1), (5, 1)]), Some(-1.0));
+    }
+}
@@ -5,7 +5,10 @@ use crate::maturity_check::{
     can_display_mature_content, check_volume_maturity, create_mature_content_warning,
     current_channel_is_nsfw, server_maturity_enabled,
 };
-use crate::util::{embed_author_with_icon, format_deadline, get_guild_icon_url, get_guild_name};
+use crate::util::{
+    autocomplete_club, embed_author_with_icon, format_deadline, get_guild_icon_url, get_guild_name,
+    resolve_club,
+};
 use crate::{types::Context, types::Error};
 use chrono::Utc;
 use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
@@ -16,20 +19,28 @@ use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
     description_localized("en-US", "Show the book currently being read by the club"),
     user_cooldown = 10
 )]
-pub async fn current(ctx: Context<'_>) -> Result<(), Error> {
+pub async fn current(
+    ctx: Context<'_>,
+    #[description = "Club to show (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
     ctx.defer().await?;
 
     let pool = &ctx.data().database;
     let google_books = &ctx.data().google_books;
-    let Some(guild_id) = ctx.guild_id() else {
+    if ctx.guild_id().is_none() {
         let embed = CreateEmbed::default()
             .title("❌ Error")
             .description("This command must be used in a server.")
             .color(0xB76E79);
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
     };
-    let guild_name = get_guild_name(&ctx).await;
+    let guild_name = format!("{}{}", get_guild_name(&ctx).await, club.title_suffix());
     let guild_icon = get_guild_icon_url(&ctx).await;
 
     // Get current book and member progress (UPDATED to include suggested_by)
@@ -44,11 +55,11 @@ pub async fn current(ctx: Context<'_>) -> Result<(), Error> {
             MAX(urp.updated_at) as last_progress_update
         FROM server_current_book scb
         LEFT JOIN discord_users du ON du.user_id = scb.suggested_by_user_id
-        LEFT JOIN user_reading_progress urp ON urp.server_id = scb.server_id AND urp.volume_id = scb.volume_id
-        WHERE scb.server_id = $1
+        LEFT JOIN user_reading_progress urp ON urp.club_id = scb.club_id AND urp.volume_id = scb.volume_id
+        WHERE scb.club_id = $1
         GROUP BY scb.volume_id, scb.started_at, scb.deadline, du.username
         "#,
-        guild_id.get() as i64
+        club.club_id
     )
     .fetch_optional(pool)
     .await?;
@@ -132,11 +143,11 @@ pub async fn current(ctx: Context<'_>) -> Result<(), Error> {
                     urp.updated_at
                 FROM user_reading_progress urp
                 JOIN discord_users du ON du.user_id = urp.user_id
-                WHERE urp.server_id = $1 AND urp.volume_id = $2
+                WHERE urp.club_id = $1 AND urp.volume_id = $2
                 ORDER BY urp.updated_at DESC
                 LIMIT 3
                 "#,
-                guild_id.get() as i64,
+                club.club_id,
                 book.volume_id
             )
             .fetch_all(pool)
@@ -0,0 +1,279 @@
+use crate::commands::user::progress::is_progress_banned;
+use crate::maturity_check::{
+    check_volume_maturity, create_mature_content_warning, current_channel_is_nsfw,
+    server_maturity_enabled,
+};
+use crate::types::QueryMode;
+use crate::util::{autocomplete_club, detect_query_mode, normalize_isbn, resolve_club};
+use crate::*;
+use crate::{types::Context, types::Error};
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
+
+#[poise::command(
+    slash_command,
+    guild_only,
+    description_localized(
+        "en-US",
+        "Report that you didn't finish a club book (defaults to the current book)"
+    ),
+    user_cooldown = 10
+)]
+pub async fn dnf(
+    ctx: Context<'_>,
+    #[description = "Title or ISBN-10/13 of a book the club already finished (leave empty for the current book)"]
+    title_or_isbn: Option<String>,
+    #[description = "Author name (optional; used when title)"] author: Option<String>,
+    #[description = "Take back a DNF you reported earlier"] undo: Option<bool>,
+    #[description = "Club the book belongs to (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+    let google_books = &ctx.data().google_books;
+    let user_id = ctx.author().id.get() as i64;
+    let undo = undo.unwrap_or(false);
+
+    if is_progress_banned(pool, guild_id.get() as i64, user_id).await? {
+        let embed = CreateEmbed::default()
+            .title("🚫 Progress Command Disabled")
+            .description("You are banned from using /progress commands in this server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    ensure_user_exists(pool, ctx.author()).await?;
+
+    let Some(title_or_isbn) = title_or_isbn else {
+        let current_book = sqlx::query!(
+            "SELECT volume_id FROM server_current_book WHERE club_id = $1",
+            club.club_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        let Some(book) = current_book else {
+            let embed = CreateEmbed::default()
+                .title("No Current Book")
+                .description(format!(
+                    "There's no current book being read in this club. To report a DNF for a book the club already finished, use `/dnf title_or_isbn:<book>{}`.",
+                    club.command_option()
+                ))
+                .color(0xB76E79);
+            ctx.send(poise::CreateReply::default().embed(embed)).await?;
+            return Ok(());
+        };
+
+        let book_title = match google_books.get_volume(&book.volume_id).await {
+            Ok(volume) if check_volume_maturity(&ctx, pool, &volume).await? => volume.get_title(),
+            Ok(_) => "the current book".to_string(),
+            Err(_) => format!("Book ({})", book.volume_id),
+        };
+
+        let embed = if undo {
+            let result = sqlx::query!(
+                "UPDATE user_reading_progress SET dnf_at = NULL
+                 WHERE user_id = $1 AND club_id = $2 AND volume_id = $3 AND dnf_at IS NOT NULL",
+                user_id,
+                club.club_id,
+                book.volume_id
+            )
+            .execute(pool)
+            .await?;
+
+            if result.rows_affected() == 0 {
+                CreateEmbed::default()
+                    .title("Nothing to Undo")
+                    .description(format!("You haven't marked **{}** as DNF.", book_title))
+                    .color(0xB76E79)
+            } else {
+                CreateEmbed::default()
+                    .title(format!("📖 Back to Reading{}", club.title_suffix()))
+                    .description(format!(
+                        "You're reading **{}** again. Use `/progress update` to share where you are.",
+                        book_title
+                    ))
+                    .color(0xB76E79)
+            }
+        } else {
+            // Keep earlier progress so the snapshot shows where the member stopped
+            sqlx::query!(
+                "INSERT INTO user_reading_progress (user_id, club_id, volume_id, dnf_at)
+                 VALUES ($1, $2, $3, CURRENT_TIMESTAMP)
+                 ON CONFLICT (user_id, club_id)
+                 DO UPDATE SET
+                     progress_text = CASE
+                         WHEN user_reading_progress.volume_id = $3 THEN user_reading_progress.progress_text
+                     END,
+                     volume_id = $3,
+                     dnf_at = CURRENT_TIMESTAMP",
+                user_id,
+                club.club_id,
+                book.volume_id
+            )
+            .execute(pool)
+            .await?;
+
+            CreateEmbed::default()
+                .title(format!("🚫 Marked as Did Not Finish{}", club.title_suffix()))
+                .description(format!(
+                    "**{}** will count as a DNF for you when the club finishes it, and I won't send you progress reminders for it.\n\nChanged your mind? Use `/progress update` or `/dnf undo:True`.",
+                    book_title
+                ))
+                .color(0xB76E79)
+        };
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+
+    let book = match detect_query_mode(&title_or_isbn) {
+        QueryMode::Isbn => {
+            let isbn = normalize_isbn(&title_or_isbn);
+            if isbn.len() != 10 && isbn.len() != 13 {
+                let embed = CreateEmbed::default()
+                    .title("❌ Invalid ISBN")
+                    .description(format!(
+                        "ISBN must be 10 or 13 characters long. You provided {} characters.",
+                        isbn.len()
+                    ))
+                    .color(0xB76E79)
+                    .footer(CreateEmbedFooter::new("Powered by Google Books API"));
+                ctx.send(poise::CreateReply::default().embed(embed)).await?;
+                return Ok(());
+            }
+            match google_books.search_by_isbn(&isbn).await? {
+                Some(book) => book,
+                None => {
+                    let embed = CreateEmbed::default()
+                        .title("❌ Book Not Found")
+                        .description(format!("No book found with ISBN: {}", title_or_isbn))
+                        .color(0xB76E79)
+                        .footer(CreateEmbedFooter::new("Searched via Google Books API"));
+                    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+                    return Ok(());
+                }
+            }
+        }
+        QueryMode::Title => {
+            let results = google_books
+                .search_books(&title_or_isbn, author.as_deref(), Some(1))
+                .await?;
+            let Some(book) = results.into_iter().next() else {
+                let embed = CreateEmbed::default()
+                    .title("❌ Book Not Found")
+                    .description("No books found with that title.")
+                    .color(0xB76E79)
+                    .footer(CreateEmbedFooter::new("Searched via Google Books API"));
+                ctx.send(poise::CreateReply::default().embed(embed)).await?;
+                return Ok(());
+            };
+            book
+        }
+    };
+
+    if !check_volume_maturity(&ctx, pool, &book).await? {
+        let is_nsfw = current_channel_is_nsfw(&ctx).await?;
+        let maturity_enabled = server_maturity_enabled(&ctx, pool).await?;
+        let embed =
+            create_mature_content_warning(Some(&book.get_title()), is_nsfw, maturity_enabled);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let completed = sqlx::query!(
+        "SELECT completed_id FROM server_completed_books
+         WHERE club_id = $1 AND volume_id = $2
+         ORDER BY completed_at DESC
+         LIMIT 1",
+        club.club_id,
+        book.id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let Some(completed) = completed else {
+        let embed = CreateEmbed::default()
+            .title("❌ Not a Finished Club Book")
+            .description(format!(
+                "The club hasn't finished **{}**. Leave the book out to report a DNF for the current book.",
+                book.get_title()
+            ))
+            .color(0xB76E79)
+            .footer(CreateEmbedFooter::new("Powered by Google Books API"));
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+
+    let embed = if undo {
+        let result = sqlx::query!(
+            "UPDATE completed_book_participants
+             SET status = 'finished', updated_at = CURRENT_TIMESTAMP
+             WHERE completed_id = $1 AND user_id = $2 AND status = 'dnf'",
+            completed.completed_id,
+            user_id
+        )
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            CreateEmbed::default()
+                .title("Nothing to Undo")
+                .description(format!(
+                    "You haven't marked **{}** as DNF.",
+                    book.get_title()
+                ))
+                .color(0xB76E79)
+        } else {
+            CreateEmbed::default()
+                .title(format!("✅ Marked as Finished{}", club.title_suffix()))
+                .description(format!(
+                    "**{}** now counts as finished for you.",
+                    book.get_title()
+                ))
+                .color(0xB76E79)
+        }
+    } else {
+        sqlx::query!(
+            "INSERT INTO completed_book_participants (completed_id, user_id, status)
+             VALUES ($1, $2, 'dnf')
+             ON CONFLICT (completed_id, user_id)
+             DO UPDATE SET status = 'dnf', updated_at = CURRENT_TIMESTAMP",
+            completed.completed_id,
+            user_id
+        )
+        .execute(pool)
+        .await?;
+
+        CreateEmbed::default()
+            .title(format!(
+                "🚫 Marked as Did Not Finish{}",
+                club.title_suffix()
+            ))
+            .description(format!(
+                "**{}** now counts as a DNF for you in the club's completion stats.",
+                book.get_title()
+            ))
+            .color(0xB76E79)
+    };
+    ctx.send(
+        poise::CreateReply::default()
+            .embed(embed.footer(CreateEmbedFooter::new("Powered by Google Books API"))),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -1,9 +1,14 @@
+pub mod buddyread;
 pub mod clubrating;
 pub mod clubread;
+pub mod compat;
 pub mod current;
+pub mod dnf;
 pub mod queue;
+pub mod recommend;
 pub mod stats;
 pub mod userrating;
+pub mod wrapped;
 
 use crate::{types::Data, types::Error};
 
@@ -11,11 +16,16 @@ type CommandVec = Vec<poise::Command<Data, Error>>;
 
 pub fn server_user_commands() -> CommandVec {
     vec![
+        buddyread::buddyread(),
         clubrating::clubrating(),
         clubread::clubread(),
+        compat::compat(),
         current::current(),
+        dnf::dnf(),
         queue::queue(),
+        recommend::recommend(),
         stats::stats(),
         userrating::userrating(),
+        wrapped::wrapped(),
     ]
 }
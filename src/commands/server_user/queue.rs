@@ -1,11 +1,15 @@
+use crate::commands::user::progress::{progress_text_is_allowed_in_channel, validate_member_text};
+use crate::google_books::Volume;
+use crate::list_notifications::{ListEvent, ListNotice, notify_list_watchers};
 use crate::maturity_check::{
-    check_volume_maturity, create_mature_content_warning, current_channel_is_nsfw,
-    server_maturity_enabled,
+    can_display_mature_content, check_volume_maturity, create_mature_content_warning,
+    current_channel_is_nsfw, server_maturity_enabled,
 };
 use crate::types::QueryMode;
 use crate::util::{
-    detect_query_mode, embed_author_with_icon, get_guild_icon_url, get_guild_name, normalize_isbn,
-    queue_commands_enabled,
+    Club, autocomplete_club, club_announcement_channel, detect_query_mode, embed_author_with_icon,
+    get_guild_icon_url, get_guild_name, normalize_isbn, queue_commands_enabled, queue_rules,
+    resolve_club, suggestion_cooldown_ends,
 };
 use crate::*;
 use crate::{types::Context, types::Error};
@@ -14,8 +18,40 @@ use poise::serenity_prelude::{
     ButtonStyle, CreateActionRow, CreateButton, CreateEmbed, CreateEmbedFooter,
     CreateInteractionResponse, CreateInteractionResponseMessage,
 };
+use sqlx::types::chrono::Utc;
 use std::time::Duration;
 
+pub(crate) const PITCH_MAX_LENGTH: usize = 300;
+pub(crate) const PITCH_HIDDEN_MESSAGE: &str =
+    "_Pitch hidden in this channel because it contains sexual content that's not allowed here._";
+
+pub(crate) fn validate_pitch_text(
+    text: &str,
+    allow_unrestricted_sexual: bool,
+) -> Result<String, String> {
+    validate_member_text(
+        text,
+        "pitch",
+        "pitches",
+        PITCH_MAX_LENGTH,
+        allow_unrestricted_sexual,
+    )
+}
+
+/// Returns the pitch as it should be shown in the current channel, if there is one.
+pub(crate) fn displayable_pitch(
+    pitch: Option<&str>,
+    allow_unrestricted_sexual: bool,
+) -> Option<String> {
+    pitch.map(|text| {
+        if progress_text_is_allowed_in_channel(text, allow_unrestricted_sexual) {
+            text.to_string()
+        } else {
+            PITCH_HIDDEN_MESSAGE.to_string()
+        }
+    })
+}
+
 fn queue_disabled_embed() -> CreateEmbed {
     CreateEmbed::default()
         .title("🚫 Queue Command Disabled")
@@ -28,7 +64,7 @@ fn queue_disabled_embed() -> CreateEmbed {
 
 #[poise::command(
     slash_command,
-    subcommands("view", "add", "remove"),
+    subcommands("view", "add", "remove", "vote"),
     guild_only,
     description_localized("en-US", "Manage the book queue for this server"),
     user_cooldown = 10
@@ -37,27 +73,55 @@ pub async fn queue(_ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
+fn format_vote_count(votes: i64) -> String {
+    match votes {
+        0 => String::new(),
+        1 => " • ▲ 1 vote".to_string(),
+        n => format!(" • ▲ {} votes", n),
+    }
+}
+
 #[poise::command(
     slash_command,
     rename = "view",
     description_localized("en-US", "View the current book queue"),
     user_cooldown = 10
 )]
-async fn view(ctx: Context<'_>) -> Result<(), Error> {
+async fn view(
+    ctx: Context<'_>,
+    #[description = "Club whose queue to show (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
     ctx.defer().await?;
 
-    let pool = &ctx.data().database;
-    let google_books = &ctx.data().google_books;
-    let Some(guild_id) = ctx.guild_id() else {
+    if ctx.guild_id().is_none() {
         let embed = CreateEmbed::default()
             .title("❌ Error")
             .description("This command must be used in a server.")
             .color(0xB76E79);
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
     };
-    let guild_name = get_guild_name(&ctx).await;
-    let guild_icon = get_guild_icon_url(&ctx).await;
+
+    let embed = build_queue_embed(&ctx, &club).await?;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Builds the `/queue view` embed: the first 10 books with maturity filtering applied for
+/// the current channel. Also used to show the result of `/adminqueue` edits.
+pub(crate) async fn build_queue_embed(
+    ctx: &Context<'_>,
+    club: &Club,
+) -> Result<CreateEmbed, Error> {
+    let pool = &ctx.data().database;
+    let google_books = &ctx.data().google_books;
+    let guild_name = format!("{}{}", get_guild_name(ctx).await, club.title_suffix());
+    let guild_icon = get_guild_icon_url(ctx).await;
 
     let queue = sqlx::query!(
         r#"
@@ -65,13 +129,16 @@ async fn view(ctx: Context<'_>) -> Result<(), Error> {
             sbq.position,
             sbq.volume_id,
             du.username as suggested_by,
-            sbq.added_at
+            sbq.added_at,
+            sbq.pitch,
+            sbq.pinned,
+            (SELECT COUNT(*) FROM server_queue_votes sqv WHERE sqv.queue_id = sbq.queue_id) as "vote_count!"
         FROM server_book_queue sbq
         JOIN discord_users du ON du.user_id = sbq.suggested_by_user_id
-        WHERE sbq.server_id = $1
+        WHERE sbq.club_id = $1
         ORDER BY sbq.position
         "#,
-        guild_id.get() as i64
+        club.club_id
     )
     .fetch_all(pool)
     .await?;
@@ -82,24 +149,27 @@ async fn view(ctx: Context<'_>) -> Result<(), Error> {
                 format!("{} Book Queue", guild_name),
                 guild_icon.clone(),
             ))
-            .description("The queue is empty! Add books with `/queue add`.")
+            .description(format!(
+                "The queue is empty! Add books with `/queue add{}`.",
+                club.command_option()
+            ))
             .color(0xB76E79)
             .footer(CreateEmbedFooter::new("Powered by Google Books API"));
 
-        ctx.send(poise::CreateReply::default().embed(embed)).await?;
-        return Ok(());
+        return Ok(embed);
     }
 
     // Fetch book details from Google Books API and check maturity
     let mut filtered_queue = Vec::new();
     let mut mature_count = 0;
-    let is_nsfw = current_channel_is_nsfw(&ctx).await?;
-    let maturity_enabled = server_maturity_enabled(&ctx, pool).await?;
+    let is_nsfw = current_channel_is_nsfw(ctx).await?;
+    let maturity_enabled = server_maturity_enabled(ctx, pool).await?;
+    let allow_unrestricted_sexual = can_display_mature_content(ctx, pool).await?;
 
     for book in queue.iter().take(10) {
         match google_books.get_volume(&book.volume_id).await {
             Ok(volume) => {
-                if check_volume_maturity(&ctx, pool, &volume).await? {
+                if check_volume_maturity(ctx, pool, &volume).await? {
                     filtered_queue.push((book, Some(volume)));
                 } else {
                     mature_count += 1;
@@ -126,8 +196,7 @@ async fn view(ctx: Context<'_>) -> Result<(), Error> {
             ))
             .color(0xB76E79)
             .footer(CreateEmbedFooter::new("Content rating from Google Books API"));
-        ctx.send(poise::CreateReply::default().embed(embed)).await?;
-        return Ok(());
+        return Ok(embed);
     }
 
     let mut embed = CreateEmbed::default()
@@ -162,23 +231,31 @@ async fn view(ctx: Context<'_>) -> Result<(), Error> {
         .color(0xB76E79);
 
     for (book, volume_opt) in filtered_queue {
+        let pitch_line = displayable_pitch(book.pitch.as_deref(), allow_unrestricted_sexual)
+            .map(|pitch| format!("\n> {}", pitch))
+            .unwrap_or_default();
+        let votes_line = format_vote_count(book.vote_count);
+        let pin_marker = if book.pinned { " 📌" } else { "" };
         match volume_opt {
             Some(volume) => {
                 let title = volume.get_title();
                 let authors = volume.get_authors_string();
                 embed = embed.field(
-                    format!("{}. {}", book.position, title),
-                    format!("by {}\nSuggested by: {}", authors, book.suggested_by),
+                    format!("{}. {}{}", book.position, title, pin_marker),
+                    format!(
+                        "by {}\nSuggested by: {}{}{}",
+                        authors, book.suggested_by, votes_line, pitch_line
+                    ),
                     false,
                 );
             }
             None => {
                 // Fallback if API fails
                 embed = embed.field(
-                    format!("{}. [Book data unavailable]", book.position),
+                    format!("{}. [Book data unavailable]{}", book.position, pin_marker),
                     format!(
-                        "Volume ID: {}\nSuggested by: {}",
-                        book.volume_id, book.suggested_by
+                        "Volume ID: {}\nSuggested by: {}{}{}",
+                        book.volume_id, book.suggested_by, votes_line, pitch_line
                     ),
                     false,
                 );
@@ -195,13 +272,12 @@ async fn view(ctx: Context<'_>) -> Result<(), Error> {
         embed = embed.footer(CreateEmbedFooter::new("Powered by Google Books API"));
     }
 
-    ctx.send(poise::CreateReply::default().embed(embed)).await?;
-    Ok(())
+    Ok(embed)
 }
 
 #[poise::command(
     slash_command,
-    description_localized("en-US", "Add a book to the queue. One per user."),
+    description_localized("en-US", "Add a book to the queue"),
     user_cooldown = 10,
     guild_only
 )]
@@ -209,43 +285,49 @@ async fn add(
     ctx: Context<'_>,
     #[description = "Title or ISBN-10/13"] title_or_isbn: String,
     #[description = "Author name (optional; used when title)"] author: Option<String>,
+    #[description = "Why the club should read it (optional, max 300 characters)"] pitch: Option<
+        String,
+    >,
+    #[description = "Club whose queue to add to (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
 ) -> Result<(), Error> {
     ctx.defer().await?;
 
-    let Some(guild_id) = ctx.guild_id() else {
+    if ctx.guild_id().is_none() {
         let embed = CreateEmbed::default()
             .title("❌ Error")
             .description("This command must be used in a server.")
             .color(0xB76E79);
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
     };
 
     let pool = &ctx.data().database;
+    let google_books = &ctx.data().google_books;
 
-    if !queue_commands_enabled(pool, guild_id.get() as i64).await? {
-        ctx.send(poise::CreateReply::default().embed(queue_disabled_embed()))
-            .await?;
-        return Ok(());
-    }
-
-    let guild_name = if let Some(g) = ctx.guild() {
-        g.name.clone()
-    } else {
-        match guild_id
-            .to_partial_guild(ctx.serenity_context().http.clone())
-            .await
-        {
-            Ok(pg) => pg.name,
-            Err(_) => format!("Server {}", guild_id.get()),
+    let pitch = match pitch.as_deref() {
+        Some(text) => {
+            let allow_unrestricted_sexual = can_display_mature_content(&ctx, pool).await?;
+            match validate_pitch_text(text, allow_unrestricted_sexual) {
+                Ok(sanitized) => Some(sanitized),
+                Err(error_msg) => {
+                    let embed = CreateEmbed::default()
+                        .title("❌ Invalid Pitch")
+                        .description(error_msg)
+                        .color(0xB76E79)
+                        .footer(CreateEmbedFooter::new("Powered by Google Books API"));
+                    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+                    return Ok(());
+                }
+            }
         }
+        None => None,
     };
 
-    let google_books = &ctx.data().google_books;
-
-    ensure_user_exists(pool, ctx.author()).await?;
-    ensure_server_exists(pool, guild_id, &guild_name).await?;
-
     // Search for the book using unified logic
     let chosen = detect_query_mode(&title_or_isbn);
 
@@ -303,8 +385,35 @@ async fn add(
         }
     };
 
+    queue_volume(ctx, &club, &book, pitch, result_bool).await
+}
+
+/// Adds a book the member already picked to the club's queue, applying the maturity check and
+/// the server's queue rules. Shared by `/queue add` and the "Add to queue" book action.
+pub(crate) async fn queue_volume(
+    ctx: Context<'_>,
+    club: &Club,
+    book: &Volume,
+    pitch: Option<String>,
+    multiple_found: bool,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+    let pool = &ctx.data().database;
+
+    if !queue_commands_enabled(pool, guild_id.get() as i64).await? {
+        ctx.send(poise::CreateReply::default().embed(queue_disabled_embed()))
+            .await?;
+        return Ok(());
+    }
+
+    let guild_name = get_guild_name(&ctx).await;
+    ensure_user_exists(pool, ctx.author()).await?;
+    ensure_server_exists(pool, guild_id, &guild_name).await?;
+
     // Check maturity
-    if !check_volume_maturity(&ctx, pool, &book).await? {
+    if !check_volume_maturity(&ctx, pool, book).await? {
         let is_nsfw = current_channel_is_nsfw(&ctx).await?;
         let maturity_enabled = server_maturity_enabled(&ctx, pool).await?;
         let embed =
@@ -313,35 +422,103 @@ async fn add(
         return Ok(());
     }
 
-    let user_book = sqlx::query!(
-        "SELECT volume_id FROM server_book_queue 
-         WHERE server_id = $1 AND suggested_by_user_id = $2",
-        guild_id.get() as i64,
+    let rules = queue_rules(pool, guild_id.get() as i64).await?;
+
+    let active_suggestions = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM server_book_queue
+         WHERE club_id = $1 AND suggested_by_user_id = $2"#,
+        club.club_id,
         ctx.author().id.get() as i64
     )
-    .fetch_optional(pool)
+    .fetch_one(pool)
     .await?;
 
-    // check if user already has a book in the queue
-    if user_book.is_some() {
+    // check if user already has as many books in the queue as the server allows
+    if active_suggestions >= rules.max_per_user as i64 {
+        let description = if rules.max_per_user == 1 {
+            format!(
+                "You already have a book in the queue! Use `/queue remove{}` first to change your suggestion.",
+                club.command_option()
+            )
+        } else {
+            format!(
+                "You already have {} books in the queue, which is this server's limit. Use `/queue remove{}` first to change your suggestions.",
+                active_suggestions,
+                club.command_option()
+            )
+        };
         let embed = CreateEmbed::default()
             .title("❌ Queue Limit")
-            .description("You already have a book in the queue! Use `/queue remove` first to change your suggestion.")
+            .description(description)
             .color(0xB76E79)
-            .footer(CreateEmbedFooter::new("Powered by Google Books API"));
+            .footer(CreateEmbedFooter::new(
+                "Configured via /config queue limits",
+            ));
 
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
     }
 
+    if rules.cooldown_days > 0 {
+        let last_read = sqlx::query_scalar!(
+            "SELECT MAX(completed_at) FROM server_completed_books
+             WHERE club_id = $1 AND suggested_by_user_id = $2",
+            club.club_id,
+            ctx.author().id.get() as i64
+        )
+        .fetch_one(pool)
+        .await?;
+
+        if let Some(ends) = suggestion_cooldown_ends(last_read, rules.cooldown_days, Utc::now()) {
+            let embed = CreateEmbed::default()
+                .title("⏳ Suggestion Cooldown")
+                .description(format!(
+                    "The club recently read one of your picks. You can suggest another book <t:{}:R>.",
+                    ends.timestamp()
+                ))
+                .color(0xB76E79)
+                .footer(CreateEmbedFooter::new("Configured via /config queue limits"));
+
+            ctx.send(poise::CreateReply::default().embed(embed)).await?;
+            return Ok(());
+        }
+    }
+
     let volume_id = &book.id;
     let book_title = book.get_title();
     let book_authors = book.get_authors_string();
 
+    if rules.block_completed {
+        let finished_at = sqlx::query_scalar!(
+            "SELECT MAX(completed_at) FROM server_completed_books
+             WHERE club_id = $1 AND volume_id = $2",
+            club.club_id,
+            volume_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        if let Some(finished_at) = finished_at {
+            let embed = CreateEmbed::default()
+                .title("📚 Already Read")
+                .description(format!(
+                    "The club already finished this book on {}. This server doesn't allow suggesting books it has already read.",
+                    finished_at.format("%Y-%m-%d")
+                ))
+                .field("Title", &book_title, false)
+                .field("Authors", &book_authors, false)
+                .color(0xB76E79)
+                .footer(CreateEmbedFooter::new("Configured via /config queue limits"));
+
+            ctx.send(poise::CreateReply::default().embed(embed)).await?;
+            return Ok(());
+        }
+    }
+
     // Check if book is already in queue
     let already_queued = sqlx::query!(
-        "SELECT suggested_by_user_id FROM server_book_queue WHERE server_id = $1 AND volume_id = $2",
-        guild_id.get() as i64,
+        "SELECT suggested_by_user_id FROM server_book_queue WHERE club_id = $1 AND volume_id = $2",
+        club.club_id,
         volume_id
     )
     .fetch_optional(pool)
@@ -372,16 +549,17 @@ async fn add(
 
     // Add to queue
     let result = sqlx::query!(
-        "INSERT INTO server_book_queue (server_id, volume_id, suggested_by_user_id, position)
+        "INSERT INTO server_book_queue (club_id, volume_id, suggested_by_user_id, position, pitch)
         VALUES ($1, $2, $3, (
             SELECT COALESCE(MAX(position), 0) + 1
             FROM server_book_queue
-            WHERE server_id = $1
-        ))
+            WHERE club_id = $1
+        ), $4)
         RETURNING position",
-        guild_id.get() as i64,
+        club.club_id,
         volume_id,
-        ctx.author().id.get() as i64
+        ctx.author().id.get() as i64,
+        pitch
     )
     .fetch_optional(pool)
     .await?;
@@ -389,26 +567,30 @@ async fn add(
     match result {
         Some(record) => {
             let mut embed = CreateEmbed::default()
-                .title("✅ Book Added to Queue")
+                .title(format!("✅ Book Added to Queue{}", club.title_suffix()))
                 .field("Title", &book_title, false)
                 .field("Authors", &book_authors, false)
                 .field("Position", format!("#{}", record.position), true)
                 .color(0xB76E79);
 
+            if let Some(pitch) = pitch.as_ref() {
+                embed = embed.field("Pitch", pitch, false);
+            }
+
             if let Some(thumbnail_url) = book.get_thumbnail_url() {
                 embed = embed.thumbnail(thumbnail_url);
             }
 
-            // Check for prior ratings of this book in this server
+            // Check for prior ratings of this book in this club
             let completed_book = sqlx::query!(
                 r#"
                 SELECT completed_id, average_rating, total_ratings
                 FROM server_completed_books
-                WHERE server_id = $1 AND volume_id = $2
+                WHERE club_id = $1 AND volume_id = $2
                 ORDER BY completed_at DESC
                 LIMIT 1
                 "#,
-                guild_id.get() as i64,
+                club.club_id,
                 volume_id
             )
             .fetch_optional(pool)
@@ -428,7 +610,7 @@ async fn add(
                 }
             }
 
-            let footer_text = if result_bool {
+            let footer_text = if multiple_found {
                 "Multiple books found. • Powered by Google Books API"
             } else {
                 "Powered by Google Books API"
@@ -436,6 +618,23 @@ async fn add(
             embed = embed.footer(CreateEmbedFooter::new(footer_text));
 
             ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+            let channel_id = club_announcement_channel(pool, club.club_id)
+                .await?
+                .unwrap_or(ctx.channel_id());
+            notify_list_watchers(
+                ctx.http(),
+                pool,
+                &ctx.data().google_books,
+                ListNotice {
+                    club,
+                    volume_ids: &[volume_id.to_string()],
+                    event: ListEvent::Queued,
+                    skip_user: Some(ctx.author().id.get() as i64),
+                    channel_id,
+                },
+            )
+            .await;
         }
         None => {
             let embed = CreateEmbed::default()
@@ -456,7 +655,12 @@ async fn add(
     description_localized("en-US", "Remove your book from the queue"),
     user_cooldown = 10
 )]
-async fn remove(ctx: Context<'_>) -> Result<(), Error> {
+async fn remove(
+    ctx: Context<'_>,
+    #[description = "Club whose queue to remove from (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
     ctx.defer().await?;
 
     let pool = &ctx.data().database;
@@ -469,6 +673,9 @@ async fn remove(ctx: Context<'_>) -> Result<(), Error> {
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
     };
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
 
     if !queue_commands_enabled(pool, guild_id.get() as i64).await? {
         ctx.send(poise::CreateReply::default().embed(queue_disabled_embed()))
@@ -480,10 +687,10 @@ async fn remove(ctx: Context<'_>) -> Result<(), Error> {
         r#"
         SELECT sbq.volume_id, sbq.position
         FROM server_book_queue sbq
-        WHERE sbq.server_id = $1 AND sbq.suggested_by_user_id = $2
+        WHERE sbq.club_id = $1 AND sbq.suggested_by_user_id = $2
         ORDER BY sbq.position
         "#,
-        guild_id.get() as i64,
+        club.club_id,
         ctx.author().id.get() as i64
     )
     .fetch_all(pool)
@@ -574,8 +781,8 @@ async fn remove(ctx: Context<'_>) -> Result<(), Error> {
         if mci.data.custom_id == "confirm_remove" {
             sqlx::query!(
                 "DELETE FROM server_book_queue
-                 WHERE server_id = $1 AND suggested_by_user_id = $2",
-                guild_id.get() as i64,
+                 WHERE club_id = $1 AND suggested_by_user_id = $2",
+                club.club_id,
                 ctx.author().id.get() as i64
             )
             .execute(pool)
@@ -633,3 +840,142 @@ async fn remove(ctx: Context<'_>) -> Result<(), Error> {
 
     Ok(())
 }
+
+#[poise::command(
+    slash_command,
+    description_localized("en-US", "Upvote a book in the queue (run again to remove your vote)"),
+    user_cooldown = 10
+)]
+async fn vote(
+    ctx: Context<'_>,
+    #[description = "Queue position of the book, as shown in /queue view"]
+    #[min = 1]
+    position: i32,
+    #[description = "Club whose queue to vote in (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+    let google_books = &ctx.data().google_books;
+
+    if !queue_commands_enabled(pool, guild_id.get() as i64).await? {
+        ctx.send(poise::CreateReply::default().embed(queue_disabled_embed()))
+            .await?;
+        return Ok(());
+    }
+
+    let entry = sqlx::query!(
+        "SELECT queue_id, volume_id, suggested_by_user_id FROM server_book_queue
+         WHERE club_id = $1 AND position = $2",
+        club.club_id,
+        position
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let Some(entry) = entry else {
+        let embed = CreateEmbed::default()
+            .title("❌ Not in Queue")
+            .description(format!(
+                "There's no book at position #{} in the queue. Check `/queue view{}` for the current positions.",
+                position,
+                club.command_option()
+            ))
+            .color(0xB76E79)
+            .footer(CreateEmbedFooter::new("Powered by Google Books API"));
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+
+    let user_id = ctx.author().id.get() as i64;
+
+    if entry.suggested_by_user_id == user_id {
+        let embed = CreateEmbed::default()
+            .title("❌ Can't Vote")
+            .description("You can't upvote a book you suggested yourself.")
+            .color(0xB76E79)
+            .footer(CreateEmbedFooter::new("Powered by Google Books API"));
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let volume = google_books.get_volume(&entry.volume_id).await.ok();
+    if let Some(volume) = volume.as_ref()
+        && !check_volume_maturity(&ctx, pool, volume).await?
+    {
+        let is_nsfw = current_channel_is_nsfw(&ctx).await?;
+        let maturity_enabled = server_maturity_enabled(&ctx, pool).await?;
+        let embed =
+            create_mature_content_warning(Some(&volume.get_title()), is_nsfw, maturity_enabled);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+    let book_title = volume
+        .as_ref()
+        .map(|v| v.get_title())
+        .unwrap_or_else(|| format!("Book ({})", entry.volume_id));
+
+    ensure_user_exists(pool, ctx.author()).await?;
+
+    let removed = sqlx::query!(
+        "DELETE FROM server_queue_votes WHERE queue_id = $1 AND user_id = $2",
+        entry.queue_id,
+        user_id
+    )
+    .execute(pool)
+    .await?
+    .rows_affected()
+        > 0;
+
+    if !removed {
+        sqlx::query!(
+            "INSERT INTO server_queue_votes (queue_id, user_id) VALUES ($1, $2)
+             ON CONFLICT DO NOTHING",
+            entry.queue_id,
+            user_id
+        )
+        .execute(pool)
+        .await?;
+    }
+
+    let total_votes = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM server_queue_votes WHERE queue_id = $1"#,
+        entry.queue_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let (title, description) = if removed {
+        (
+            "Vote Removed",
+            format!("You removed your upvote for **{}**.", book_title),
+        )
+    } else {
+        ("▲ Vote Counted", format!("You upvoted **{}**.", book_title))
+    };
+
+    let embed = CreateEmbed::default()
+        .title(title)
+        .description(description)
+        .field("Position", format!("#{}", position), true)
+        .field("Total Votes", total_votes.to_string(), true)
+        .color(0xB76E79)
+        .footer(CreateEmbedFooter::new("Powered by Google Books API"));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
@@ -0,0 +1,404 @@
+use super::compat::taste_similarity;
+use super::wrapped::primary_genre;
+use crate::google_books::Volume;
+use crate::maturity_check::check_volume_maturity;
+use crate::recommendation::{
+    BookTraits, Candidate, CandidateSource, TasteProfile, TasteSignal, rank_candidates,
+};
+use crate::util::{embed_author_with_icon, get_guild_name};
+use crate::{types::Context, types::Error};
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter, User};
+use std::collections::{HashMap, HashSet};
+
+/// Recommendations shown per request.
+const RECOMMENDATION_LIMIT: usize = 8;
+/// Favorite authors and genres searched on Google Books when club data runs short.
+const FALLBACK_SEARCHES: usize = 2;
+const FALLBACK_RESULTS: u32 = 10;
+
+fn book_traits(volume: &Volume) -> BookTraits {
+    BookTraits {
+        authors: volume.volume_info.authors.clone().unwrap_or_default(),
+        genre: primary_genre(&volume.get_categories()),
+    }
+}
+
+fn add_search_results(
+    candidates: &mut Vec<Candidate>,
+    volumes: &mut HashMap<String, Volume>,
+    source: CandidateSource,
+    results: Vec<Volume>,
+) {
+    for volume in results {
+        candidates.push(Candidate {
+            volume_id: volume.id.clone(),
+            traits: book_traits(&volume),
+            source,
+            peer_support: 0.0,
+        });
+        volumes.entry(volume.id.clone()).or_insert(volume);
+    }
+}
+
+#[poise::command(
+    slash_command,
+    guild_only,
+    description_localized(
+        "en-US",
+        "Get book recommendations based on your favorites, ratings and similar members"
+    ),
+    user_cooldown = 10
+)]
+pub async fn recommend(
+    ctx: Context<'_>,
+    #[description = "Member to recommend books for (defaults to you)"] user: Option<User>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+    let google_books = &ctx.data().google_books;
+    let target_user = user.as_ref().unwrap_or_else(|| ctx.author());
+    let server_id = guild_id.get() as i64;
+    let user_id = target_user.id.get() as i64;
+
+    let mut seeds: Vec<(String, TasteSignal)> = Vec::new();
+    for row in sqlx::query!(
+        "SELECT volume_id, is_number_one FROM user_favorite_books
+         WHERE user_id = $1 AND server_id = $2",
+        user_id,
+        server_id
+    )
+    .fetch_all(pool)
+    .await?
+    {
+        let signal = if row.is_number_one {
+            TasteSignal::NumberOne
+        } else {
+            TasteSignal::Favorite
+        };
+        seeds.push((row.volume_id, signal));
+    }
+    for row in sqlx::query!(
+        "SELECT volume_id FROM user_reading_list WHERE user_id = $1 AND server_id = $2",
+        user_id,
+        server_id
+    )
+    .fetch_all(pool)
+    .await?
+    {
+        seeds.push((row.volume_id, TasteSignal::ReadingList));
+    }
+    for row in sqlx::query!(
+        r#"
+        SELECT scb.volume_id AS "volume_id!", ubr.rating AS "rating!"
+        FROM user_book_ratings ubr
+        JOIN server_completed_books scb ON scb.completed_id = ubr.completed_id
+        JOIN server_clubs sc ON sc.club_id = scb.club_id
+        WHERE ubr.user_id = $1 AND sc.server_id = $2
+        UNION ALL
+        SELECT volume_id, rating FROM user_personal_ratings
+        WHERE user_id = $1 AND server_id = $2
+        "#,
+        user_id,
+        server_id
+    )
+    .fetch_all(pool)
+    .await?
+    {
+        seeds.push((row.volume_id, TasteSignal::Rated(row.rating)));
+    }
+
+    // Anything the member already has an opinion on, plus what the club is reading now
+    let mut exclude: HashSet<String> = seeds.iter().map(|(id, _)| id.clone()).collect();
+    exclude.extend(
+        sqlx::query_scalar!(
+            r#"
+            SELECT scb.volume_id FROM server_current_book scb
+            JOIN server_clubs sc ON sc.club_id = scb.club_id
+            WHERE sc.server_id = $1
+            UNION
+            SELECT sbq.volume_id FROM server_book_queue sbq
+            JOIN server_clubs sc ON sc.club_id = sbq.club_id
+            WHERE sc.server_id = $1 AND sbq.suggested_by_user_id = $2
+            "#,
+            server_id,
+            user_id
+        )
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .flatten(),
+    );
+
+    let seed_ids: Vec<String> = seeds.iter().map(|(id, _)| id.clone()).collect();
+    let mut profile = TasteProfile::default();
+    for ((_, signal), result) in seeds
+        .iter()
+        .zip(google_books.get_volumes_batch(&seed_ids).await)
+    {
+        if let Ok(volume) = result {
+            profile.add(*signal, &book_traits(&volume));
+        }
+    }
+
+    for row in sqlx::query!(
+        "SELECT author_name, is_number_one FROM user_favorite_authors
+         WHERE user_id = $1 AND server_id = $2",
+        user_id,
+        server_id
+    )
+    .fetch_all(pool)
+    .await?
+    {
+        let signal = if row.is_number_one {
+            TasteSignal::NumberOne
+        } else {
+            TasteSignal::Favorite
+        };
+        profile.add(
+            signal,
+            &BookTraits {
+                authors: vec![row.author_name],
+                genre: None,
+            },
+        );
+    }
+
+    if profile.is_empty() {
+        let embed = CreateEmbed::default()
+            .author(embed_author_with_icon(
+                format!("Recommendations for {}", target_user.name),
+                Some(target_user.face()),
+            ))
+            .title("Not Enough to Go On")
+            .description(
+                "Add books with `/favorite add` or `/readinglist add`, authors with `/favorite author add`, or rate club books, and I'll have something to work with.",
+            )
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    // Members whose ratings line up with this member's, weighted by how closely
+    let co_ratings = sqlx::query!(
+        r#"
+        SELECT theirs.user_id, mine.rating AS mine, theirs.rating AS theirs
+        FROM user_book_ratings mine
+        JOIN user_book_ratings theirs
+            ON theirs.completed_id = mine.completed_id AND theirs.user_id <> mine.user_id
+        JOIN server_completed_books scb ON scb.completed_id = mine.completed_id
+        JOIN server_clubs sc ON sc.club_id = scb.club_id
+        WHERE sc.server_id = $1 AND mine.user_id = $2
+        "#,
+        server_id,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+    let mut pairs_by_user: HashMap<i64, Vec<(i32, i32)>> = HashMap::new();
+    for row in co_ratings {
+        pairs_by_user
+            .entry(row.user_id)
+            .or_default()
+            .push((row.mine, row.theirs));
+    }
+    let peers: HashMap<i64, f64> = pairs_by_user
+        .into_iter()
+        .filter_map(|(peer_id, pairs)| {
+            let similarity = taste_similarity(&pairs)?;
+            (similarity > 0.0).then_some((peer_id, similarity))
+        })
+        .collect();
+    let peer_ids: Vec<i64> = peers.keys().copied().collect();
+
+    let mut pending: Vec<(String, CandidateSource, f64)> = Vec::new();
+    for row in sqlx::query!(
+        r#"
+        SELECT sbq.volume_id FROM server_book_queue sbq
+        JOIN server_clubs sc ON sc.club_id = sbq.club_id
+        WHERE sc.server_id = $1 AND sbq.suggested_by_user_id <> $2
+        "#,
+        server_id,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?
+    {
+        pending.push((row.volume_id, CandidateSource::Queue, 0.0));
+    }
+    for row in sqlx::query!(
+        r#"
+        SELECT user_id, volume_id, is_number_one, NULL::INT AS rating
+        FROM user_favorite_books
+        WHERE server_id = $1 AND user_id = ANY($2)
+        UNION ALL
+        SELECT ubr.user_id, scb.volume_id, FALSE, ubr.rating
+        FROM user_book_ratings ubr
+        JOIN server_completed_books scb ON scb.completed_id = ubr.completed_id
+        JOIN server_clubs sc ON sc.club_id = scb.club_id
+        WHERE sc.server_id = $1 AND ubr.user_id = ANY($2) AND ubr.rating >= 4
+        "#,
+        server_id,
+        &peer_ids
+    )
+    .fetch_all(pool)
+    .await?
+    {
+        let (Some(peer_id), Some(volume_id)) = (row.user_id, row.volume_id) else {
+            continue;
+        };
+        let signal = match (row.rating, row.is_number_one) {
+            (Some(rating), _) => TasteSignal::Rated(rating),
+            (None, Some(true)) => TasteSignal::NumberOne,
+            (None, _) => TasteSignal::Favorite,
+        };
+        let support = peers.get(&peer_id).copied().unwrap_or(0.0) * signal.weight();
+        pending.push((volume_id, CandidateSource::Peers, support));
+    }
+
+    let pending_ids: Vec<String> = pending.iter().map(|(id, _, _)| id.clone()).collect();
+    let mut volumes: HashMap<String, Volume> = HashMap::new();
+    let mut candidates: Vec<Candidate> = Vec::new();
+    for ((volume_id, source, peer_support), result) in pending
+        .into_iter()
+        .zip(google_books.get_volumes_batch(&pending_ids).await)
+    {
+        let Ok(volume) = result else {
+            continue;
+        };
+        candidates.push(Candidate {
+            volume_id: volume_id.clone(),
+            traits: book_traits(&volume),
+            source,
+            peer_support,
+        });
+        volumes.insert(volume_id, volume);
+    }
+
+    // Only reach for Google Books when the club's own data doesn't fill the list. Ranking keeps
+    // a margin so books hidden by the maturity filter don't leave the list short.
+    let rank_limit = RECOMMENDATION_LIMIT * 2;
+    let from_club = rank_candidates(&profile, candidates.clone(), &exclude, rank_limit);
+    let ranked = if from_club.len() < rank_limit {
+        for author in profile.top_authors(FALLBACK_SEARCHES) {
+            if let Ok(results) = google_books
+                .search_by_author(&author, Some(FALLBACK_RESULTS))
+                .await
+            {
+                add_search_results(
+                    &mut candidates,
+                    &mut volumes,
+                    CandidateSource::Author,
+                    results,
+                );
+            }
+        }
+        for genre in profile.top_genres(FALLBACK_SEARCHES) {
+            if let Ok(results) = google_books
+                .search_by_genre(&genre, Some(FALLBACK_RESULTS))
+                .await
+            {
+                add_search_results(
+                    &mut candidates,
+                    &mut volumes,
+                    CandidateSource::Genre,
+                    results,
+                );
+            }
+        }
+        rank_candidates(&profile, candidates, &exclude, rank_limit)
+    } else {
+        from_club
+    };
+
+    let mut recommendations = Vec::new();
+    let mut hidden = 0;
+    for rec in ranked {
+        if recommendations.len() >= RECOMMENDATION_LIMIT {
+            break;
+        }
+        let Some(volume) = volumes.get(&rec.volume_id) else {
+            continue;
+        };
+        if !check_volume_maturity(&ctx, pool, volume).await? {
+            hidden += 1;
+            continue;
+        }
+        recommendations.push((rec, volume));
+    }
+
+    let top_authors = profile.top_authors(3);
+    let lines: Vec<String> = recommendations
+        .iter()
+        .enumerate()
+        .map(|(index, (rec, volume))| {
+            let traits = book_traits(volume);
+            let reason = match rec.source {
+                CandidateSource::Queue => "In the club queue".to_string(),
+                CandidateSource::Peers => "Loved by members with similar taste".to_string(),
+                CandidateSource::Author => {
+                    let author = traits
+                        .authors
+                        .iter()
+                        .find(|author| {
+                            top_authors
+                                .iter()
+                                .any(|liked| liked.eq_ignore_ascii_case(author))
+                        })
+                        .or(traits.authors.first())
+                        .cloned()
+                        .unwrap_or_else(|| "an author".to_string());
+                    format!("More from {}", author)
+                }
+                CandidateSource::Genre => format!(
+                    "Popular in {}",
+                    traits.genre.as_deref().unwrap_or("a genre you read")
+                ),
+            };
+            format!(
+                "**{}.** **{}** by {}\n{}",
+                index + 1,
+                volume.get_title(),
+                volume.get_authors_string(),
+                reason
+            )
+        })
+        .collect();
+
+    let mut description = if lines.is_empty() {
+        "I couldn't find anything new for you right now. Try again after the club finishes a few more books.".to_string()
+    } else {
+        lines.join("\n\n")
+    };
+    if hidden > 0 {
+        description.push_str(&format!(
+            "\n\n_{} mature recommendation{} hidden in this channel._",
+            hidden,
+            if hidden == 1 { "" } else { "s" }
+        ));
+    }
+
+    let embed = CreateEmbed::default()
+        .author(embed_author_with_icon(
+            format!("Recommendations for {}", target_user.name),
+            Some(target_user.face()),
+        ))
+        .description(description)
+        .color(0xB76E79)
+        .footer(CreateEmbedFooter::new(format!(
+            "{} • Based on favorites, reading list and ratings • Powered by Google Books API",
+            get_guild_name(&ctx).await
+        )));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
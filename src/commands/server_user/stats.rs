@@ -3,7 +3,12 @@ use std::collections::HashMap;
 use chrono::{Local, Utc};
 use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
 
-use crate::util::{embed_author_with_icon, get_guild_icon_url, get_guild_name};
+use super::wrapped::{genre_breakdown, primary_genre};
+use crate::charts::{books_per_month, genre_pie, monthly_counts};
+use crate::util::{
+    autocomplete_club, club_participation, embed_author_with_icon, get_guild_icon_url,
+    get_guild_name, resolve_club,
+};
 
 use crate::{types::Context, types::Error};
 
@@ -33,7 +38,12 @@ fn describe_rater(record: Option<(&str, i64, f64, i64)>, default: &str) -> Strin
     description_localized("en-US", "Show overall book club statistics for this server"),
     user_cooldown = 10
 )]
-pub async fn stats(ctx: Context<'_>) -> Result<(), Error> {
+pub async fn stats(
+    ctx: Context<'_>,
+    #[description = "Club to show stats for (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
     ctx.defer().await?;
 
     let pool = &ctx.data().database;
@@ -46,7 +56,10 @@ pub async fn stats(ctx: Context<'_>) -> Result<(), Error> {
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
     };
-    let guild_name = get_guild_name(&ctx).await;
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
+    let guild_name = format!("{}{}", get_guild_name(&ctx).await, club.title_suffix());
     let guild_icon = get_guild_icon_url(&ctx).await;
 
     let summary = sqlx::query!(
@@ -55,9 +68,9 @@ pub async fn stats(ctx: Context<'_>) -> Result<(), Error> {
             COUNT(*)::BIGINT         AS "total_books!",
             MIN(completed_at)        AS first_completed_at
         FROM server_completed_books
-        WHERE server_id = $1
+        WHERE club_id = $1
         "#,
-        guild_id.get() as i64,
+        club.club_id,
     )
     .fetch_one(pool)
     .await?;
@@ -82,9 +95,9 @@ pub async fn stats(ctx: Context<'_>) -> Result<(), Error> {
             COUNT(*)::BIGINT                  AS "rating_count!"
         FROM user_book_ratings ubr
         JOIN server_completed_books scb ON scb.completed_id = ubr.completed_id
-        WHERE scb.server_id = $1
+        WHERE scb.club_id = $1
         "#,
-        guild_id.get() as i64,
+        club.club_id,
     )
     .fetch_one(pool)
     .await?;
@@ -96,13 +109,13 @@ pub async fn stats(ctx: Context<'_>) -> Result<(), Error> {
             average_rating     AS "average_rating!",
             total_ratings      AS "total_ratings!"
         FROM server_completed_books
-        WHERE server_id = $1
+        WHERE club_id = $1
           AND average_rating IS NOT NULL
           AND total_ratings > 0
         ORDER BY average_rating DESC, total_ratings DESC, completed_at DESC
         LIMIT 1
         "#,
-        guild_id.get() as i64,
+        club.club_id,
     )
     .fetch_optional(pool)
     .await?;
@@ -114,13 +127,13 @@ pub async fn stats(ctx: Context<'_>) -> Result<(), Error> {
             average_rating     AS "average_rating!",
             total_ratings      AS "total_ratings!"
         FROM server_completed_books
-        WHERE server_id = $1
+        WHERE club_id = $1
           AND average_rating IS NOT NULL
           AND total_ratings > 0
         ORDER BY average_rating ASC, total_ratings DESC, completed_at DESC
         LIMIT 1
         "#,
-        guild_id.get() as i64,
+        club.club_id,
     )
     .fetch_optional(pool)
     .await?;
@@ -135,13 +148,13 @@ pub async fn stats(ctx: Context<'_>) -> Result<(), Error> {
         FROM user_book_ratings ubr
         JOIN server_completed_books scb ON scb.completed_id = ubr.completed_id
         JOIN discord_users du ON du.user_id = ubr.user_id
-        WHERE scb.server_id = $1
+        WHERE scb.club_id = $1
         GROUP BY du.username, ubr.user_id
         HAVING COUNT(*) > 0
         ORDER BY AVG(ubr.rating) DESC, COUNT(*) DESC, du.username ASC
         LIMIT 1
         "#,
-        guild_id.get() as i64,
+        club.club_id,
     )
     .fetch_optional(pool)
     .await?;
@@ -156,17 +169,52 @@ pub async fn stats(ctx: Context<'_>) -> Result<(), Error> {
         FROM user_book_ratings ubr
         JOIN server_completed_books scb ON scb.completed_id = ubr.completed_id
         JOIN discord_users du ON du.user_id = ubr.user_id
-        WHERE scb.server_id = $1
+        WHERE scb.club_id = $1
         GROUP BY du.username, ubr.user_id
         HAVING COUNT(*) > 0
         ORDER BY AVG(ubr.rating) ASC, COUNT(*) DESC, du.username ASC
         LIMIT 1
         "#,
-        guild_id.get() as i64,
+        club.club_id,
     )
     .fetch_optional(pool)
     .await?;
 
+    let participation = club_participation(pool, club.club_id).await?;
+    let completion_rate_field = match participation.completion_rate() {
+        Some(rate) => format!(
+            "{:.0}% ({} finished, {} DNF)",
+            rate, participation.finished, participation.dnf
+        ),
+        None => "No tracked readers yet.".to_string(),
+    };
+
+    let completed_books = sqlx::query!(
+        "SELECT volume_id, completed_at FROM server_completed_books WHERE club_id = $1",
+        club.club_id,
+    )
+    .fetch_all(pool)
+    .await?;
+    let completion_dates: Vec<_> = completed_books
+        .iter()
+        .filter_map(|book| Some(book.completed_at?.date_naive()))
+        .collect();
+    let completed_ids: Vec<String> = completed_books
+        .iter()
+        .map(|book| book.volume_id.clone())
+        .collect();
+    // Only genres are drawn, so mature books count toward the breakdown
+    let genres = genre_breakdown(
+        google_books
+            .get_volumes_batch(&completed_ids)
+            .await
+            .into_iter()
+            .filter_map(|result| primary_genre(&result.ok()?.get_categories())),
+        6,
+    );
+    let month_chart = books_per_month(&monthly_counts(&completion_dates));
+    let genre_chart = genre_pie(&genres);
+
     let mut volume_ids = Vec::new();
     if let Some(book) = &top_book {
         volume_ids.push(book.volume_id.clone());
@@ -248,6 +296,7 @@ pub async fn stats(ctx: Context<'_>) -> Result<(), Error> {
         .color(0xB76E79)
         .field("Books completed", summary.total_books.to_string(), true)
         .field("Average rating", average_rating_field, true)
+        .field("Completion rate", completion_rate_field, true)
         .field(
             "First book completed",
             if summary.total_books > 0 {
@@ -298,6 +347,20 @@ pub async fn stats(ctx: Context<'_>) -> Result<(), Error> {
             "Ratings sourced from members • Powered by Google Books API",
         ));
 
-    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    let mut reply = poise::CreateReply::default();
+    if let Some(chart) = month_chart.as_ref() {
+        reply = reply
+            .embed(embed.image(chart.url()))
+            .attachment(chart.attachment());
+    } else {
+        reply = reply.embed(embed);
+    }
+    if let Some(chart) = genre_chart.as_ref() {
+        reply = reply
+            .embed(CreateEmbed::default().color(0xB76E79).image(chart.url()))
+            .attachment(chart.attachment());
+    }
+
+    ctx.send(reply).await?;
     Ok(())
 }
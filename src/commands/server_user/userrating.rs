@@ -3,7 +3,9 @@ use crate::maturity_check::{
     server_maturity_enabled,
 };
 use crate::types::QueryMode;
-use crate::util::{detect_query_mode, embed_author_with_icon, normalize_isbn};
+use crate::util::{
+    autocomplete_club, detect_query_mode, embed_author_with_icon, normalize_isbn, resolve_club,
+};
 use crate::{types::Context, types::Error};
 use poise::serenity_prelude::{
     ButtonStyle, CreateActionRow, CreateButton, CreateEmbed, CreateEmbedFooter, User,
@@ -40,18 +42,24 @@ pub async fn userrating(
         String,
     >,
     #[description = "Sort by 'rating' or 'date' (default: rating)"] sort: Option<UserRatingSort>,
+    #[description = "Club whose ratings to show (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
 ) -> Result<(), Error> {
     ctx.defer().await?;
 
     let pool = &ctx.data().database;
     let google_books = &ctx.data().google_books;
-    let Some(guild_id) = ctx.guild_id() else {
+    if ctx.guild_id().is_none() {
         let embed = CreateEmbed::default()
             .title("❌ Error")
             .description("This command must be used in a server.")
             .color(0xB76E79);
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
     };
     let target_user = user;
     let sort = sort.unwrap_or(UserRatingSort::Rating);
@@ -131,11 +139,11 @@ pub async fn userrating(
             r#"
             SELECT completed_id
             FROM server_completed_books
-            WHERE server_id = $1 AND volume_id = $2
+            WHERE club_id = $1 AND volume_id = $2
             ORDER BY completed_at DESC
             LIMIT 1
             "#,
-            guild_id.get() as i64,
+            club.club_id,
             volume_id
         )
         .fetch_optional(pool)
@@ -216,7 +224,7 @@ pub async fn userrating(
         return Ok(());
     }
 
-    // Otherwise, list everything this user has rated in this server
+    // Otherwise, list everything this user has rated in this club
     let rows: Vec<UserRatingRow> = match sort {
         UserRatingSort::Date => {
             sqlx::query_as!(
@@ -224,10 +232,10 @@ pub async fn userrating(
                 r#"
                 SELECT volume_id, rating, rated_at
                 FROM server_book_ratings_view
-                WHERE server_id = $1 AND user_id = $2
+                WHERE club_id = $1 AND user_id = $2
                 ORDER BY rated_at DESC NULLS LAST
                 "#,
-                guild_id.get() as i64,
+                club.club_id,
                 target_user.id.get() as i64
             )
             .fetch_all(pool)
@@ -239,10 +247,10 @@ pub async fn userrating(
                 r#"
                 SELECT volume_id, rating, rated_at
                 FROM server_book_ratings_view
-                WHERE server_id = $1 AND user_id = $2
+                WHERE club_id = $1 AND user_id = $2
                 ORDER BY rating DESC NULLS LAST, rated_at ASC
                 "#,
-                guild_id.get() as i64,
+                club.club_id,
                 target_user.id.get() as i64
             )
             .fetch_all(pool)
@@ -270,7 +278,7 @@ pub async fn userrating(
             ))
             .title("No Ratings Yet")
             .description(format!(
-                "{} hasn't rated any completed books in this server yet.",
+                "{} hasn't rated any completed books in this club yet.",
                 target_user.name
             ))
             .color(0xB76E79)
@@ -313,7 +321,7 @@ pub async fn userrating(
             ))
             .title("No Visible Ratings")
             .description(format!(
-                "{} has rated {} book(s) in this server, but all are marked as mature content.\n\n\
+                "{} has rated {} book(s) in this club, but all are marked as mature content.\n\n\
                  To view mature books, an administrator must enable mature content with `/config mature enable` \
                  and this command must be used in an NSFW channel.",
                 target_user.name, rows.len()
@@ -0,0 +1,526 @@
+use crate::google_books::Volume;
+use crate::google_books_cache::CachedGoogleBooksClient;
+use crate::maturity_check::can_display_mature_content;
+use crate::types::Context;
+use crate::types::Error;
+use crate::util::{Club, autocomplete_club, embed_author_with_icon, get_guild_name, resolve_club};
+use chrono::Datelike;
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter, User};
+use sqlx::PgPool;
+use sqlx::types::chrono::Utc;
+use std::collections::HashMap;
+
+const MATURE_TITLE: &str = "A mature-rated book";
+
+/// The broad genre of a book: the first segment of its first Google Books category, e.g.
+/// "Fiction / Fantasy / Epic" becomes "Fiction".
+pub(crate) fn primary_genre(categories: &[String]) -> Option<String> {
+    let first = categories.first()?;
+    let genre = first.split('/').next().unwrap_or(first).trim();
+    (!genre.is_empty()).then(|| genre.to_string())
+}
+
+/// Counts genres, most common first (ties alphabetical), keeping the top `limit`.
+pub(crate) fn genre_breakdown(
+    genres: impl IntoIterator<Item = String>,
+    limit: usize,
+) -> Vec<(String, usize)> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for genre in genres {
+        *counts.entry(genre).or_default() += 1;
+    }
+    let mut breakdown: Vec<(String, usize)> = counts.into_iter().collect();
+    breakdown.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    breakdown.truncate(limit);
+    breakdown
+}
+
+/// The year an automatic recap posted in `posting_year` covers. Dates in the first half of the
+/// year look back at the previous year; later dates recap the year so far.
+pub(crate) fn recap_year_for_post(post_month: u32, posting_year: i32) -> i32 {
+    if post_month <= 6 {
+        posting_year - 1
+    } else {
+        posting_year
+    }
+}
+
+fn format_genres(breakdown: &[(String, usize)]) -> String {
+    if breakdown.is_empty() {
+        return "No genre data.".to_string();
+    }
+    breakdown
+        .iter()
+        .map(|(genre, count)| format!("{} ({})", genre, count))
+        .collect::<Vec<_>>()
+        .join(" • ")
+}
+
+async fn load_volumes(
+    google_books: &CachedGoogleBooksClient,
+    volume_ids: &[String],
+) -> HashMap<String, Volume> {
+    let mut volumes = HashMap::new();
+    for (id, result) in volume_ids
+        .iter()
+        .zip(google_books.get_volumes_batch(volume_ids).await)
+    {
+        if let Ok(volume) = result {
+            volumes.insert(id.clone(), volume);
+        }
+    }
+    volumes
+}
+
+fn display_title(volumes: &HashMap<String, Volume>, volume_id: &str, allow_mature: bool) -> String {
+    match volumes.get(volume_id) {
+        Some(volume) if volume.is_mature() && !allow_mature => MATURE_TITLE.to_string(),
+        Some(volume) => volume.get_title(),
+        None => format!("Book ({})", volume_id),
+    }
+}
+
+fn plural(count: i64) -> &'static str {
+    if count == 1 { "" } else { "s" }
+}
+
+/// Builds a club's recap of `year`. Returns `None` when the club finished nothing that year.
+pub(crate) async fn build_club_wrapped(
+    pool: &PgPool,
+    google_books: &CachedGoogleBooksClient,
+    club: &Club,
+    server_name: &str,
+    year: i32,
+    allow_mature: bool,
+) -> Result<Option<CreateEmbed>, Error> {
+    let books = sqlx::query!(
+        r#"
+        SELECT
+            scb.volume_id,
+            scb.started_at,
+            scb.completed_at AS "completed_at!",
+            scb.suggested_by_user_id
+        FROM server_completed_books scb
+        WHERE scb.club_id = $1 AND EXTRACT(YEAR FROM scb.completed_at)::INT = $2
+        ORDER BY scb.completed_at
+        "#,
+        club.club_id,
+        year
+    )
+    .fetch_all(pool)
+    .await?;
+
+    if books.is_empty() {
+        return Ok(None);
+    }
+
+    let volume_ids: Vec<String> = books.iter().map(|book| book.volume_id.clone()).collect();
+    let volumes = load_volumes(google_books, &volume_ids).await;
+
+    let total_pages: i64 = volume_ids
+        .iter()
+        .filter_map(|id| volumes.get(id)?.get_page_count())
+        .map(i64::from)
+        .sum();
+    let genres = genre_breakdown(
+        volume_ids
+            .iter()
+            .filter_map(|id| primary_genre(&volumes.get(id)?.get_categories())),
+        5,
+    );
+
+    // Same ordering as /clubread view, limited to the year; a filtered set-returning function
+    // doesn't promise to keep its order, so it's spelled out
+    let rated = sqlx::query!(
+        r#"
+        SELECT
+            volume_id AS "volume_id!",
+            average_rating::FLOAT8 AS "average_rating!",
+            total_ratings AS "total_ratings!"
+        FROM get_club_book_rankings($1)
+        WHERE EXTRACT(YEAR FROM completed_at)::INT = $2
+          AND average_rating IS NOT NULL
+          AND total_ratings > 0
+        ORDER BY average_rating DESC, completed_at DESC
+        "#,
+        club.club_id,
+        year
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let describe_rated = |index: Option<usize>| match index.and_then(|i| rated.get(i)) {
+        Some(book) => format!(
+            "**{}** — {:.2}/5 from {} rating{}",
+            display_title(&volumes, &book.volume_id, allow_mature),
+            book.average_rating,
+            book.total_ratings,
+            plural(book.total_ratings as i64)
+        ),
+        None => "No rated books.".to_string(),
+    };
+    let highest = describe_rated(Some(0));
+    let lowest = describe_rated(rated.len().checked_sub(1));
+
+    let top_suggester = sqlx::query!(
+        r#"
+        SELECT scb.suggested_by_user_id AS "user_id!", COUNT(*) AS "picks!"
+        FROM server_completed_books scb
+        WHERE scb.club_id = $1
+          AND EXTRACT(YEAR FROM scb.completed_at)::INT = $2
+          AND scb.suggested_by_user_id IS NOT NULL
+        GROUP BY scb.suggested_by_user_id
+        ORDER BY COUNT(*) DESC, MIN(scb.completed_at)
+        LIMIT 1
+        "#,
+        club.club_id,
+        year
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let top_reader = sqlx::query!(
+        r#"
+        SELECT cbp.user_id, COUNT(*) AS "books!"
+        FROM completed_book_participants cbp
+        JOIN server_completed_books scb ON scb.completed_id = cbp.completed_id
+        WHERE scb.club_id = $1
+          AND EXTRACT(YEAR FROM scb.completed_at)::INT = $2
+          AND cbp.status <> 'not_started'
+        GROUP BY cbp.user_id
+        ORDER BY COUNT(*) DESC, COUNT(*) FILTER (WHERE cbp.status = 'finished') DESC
+        LIMIT 1
+        "#,
+        club.club_id,
+        year
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let durations: Vec<(&str, i64)> = books
+        .iter()
+        .map(|book| {
+            (
+                book.volume_id.as_str(),
+                (book.completed_at - book.started_at).num_days().max(0),
+            )
+        })
+        .collect();
+    let describe_read = |read: Option<&(&str, i64)>| match read {
+        Some((volume_id, days)) => format!(
+            "**{}** — {} day{}",
+            display_title(&volumes, volume_id, allow_mature),
+            days,
+            plural(*days)
+        ),
+        None => "No reading dates recorded.".to_string(),
+    };
+    let longest = describe_read(durations.iter().max_by_key(|(_, days)| *days));
+    let shortest = describe_read(durations.iter().min_by_key(|(_, days)| *days));
+
+    let embed = CreateEmbed::default()
+        .author(embed_author_with_icon(
+            format!("{}{} — {} Wrapped", server_name, club.title_suffix(), year),
+            None,
+        ))
+        .title(format!("📚 The Club's {} in Books", year))
+        .color(0xB76E79)
+        .field("Books completed", books.len().to_string(), true)
+        .field(
+            "Pages read",
+            if total_pages > 0 {
+                total_pages.to_string()
+            } else {
+                "Unknown".to_string()
+            },
+            true,
+        )
+        .field(
+            "Top suggester",
+            match top_suggester {
+                Some(row) => format!(
+                    "<@{}> — {} pick{}",
+                    row.user_id,
+                    row.picks,
+                    plural(row.picks)
+                ),
+                None => "No suggestions recorded.".to_string(),
+            },
+            true,
+        )
+        .field("Highest rated", highest, false)
+        .field("Lowest rated", lowest, false)
+        .field(
+            "Most active reader",
+            match top_reader {
+                Some(row) => format!(
+                    "<@{}> — tracked progress on {} book{}",
+                    row.user_id,
+                    row.books,
+                    plural(row.books)
+                ),
+                None => "No one tracked their progress.".to_string(),
+            },
+            false,
+        )
+        .field("Top genres", format_genres(&genres), false)
+        .field("Longest read", longest, true)
+        .field("Shortest read", shortest, true)
+        .footer(CreateEmbedFooter::new(
+            "Use /wrapped member:@you for your own year • Powered by Google Books API",
+        ));
+
+    Ok(Some(embed))
+}
+
+/// Builds one member's recap of `year` in a club. Returns `None` when they didn't read or
+/// rate anything with the club that year.
+pub(crate) async fn build_member_wrapped(
+    pool: &PgPool,
+    google_books: &CachedGoogleBooksClient,
+    club: &Club,
+    year: i32,
+    member: &User,
+    allow_mature: bool,
+) -> Result<Option<CreateEmbed>, Error> {
+    let member_id = member.id.get() as i64;
+    let books = sqlx::query!(
+        r#"
+        SELECT
+            scb.volume_id,
+            cbp.status AS "status?",
+            ubr.rating AS "rating?",
+            scb.suggested_by_user_id
+        FROM server_completed_books scb
+        LEFT JOIN completed_book_participants cbp
+            ON cbp.completed_id = scb.completed_id AND cbp.user_id = $3
+        LEFT JOIN user_book_ratings ubr
+            ON ubr.completed_id = scb.completed_id AND ubr.user_id = $3
+        WHERE scb.club_id = $1
+          AND EXTRACT(YEAR FROM scb.completed_at)::INT = $2
+          AND (cbp.user_id IS NOT NULL OR ubr.user_id IS NOT NULL OR scb.suggested_by_user_id = $3)
+        ORDER BY scb.completed_at
+        "#,
+        club.club_id,
+        year,
+        member_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    if books.is_empty() {
+        return Ok(None);
+    }
+
+    let volume_ids: Vec<String> = books.iter().map(|book| book.volume_id.clone()).collect();
+    let volumes = load_volumes(google_books, &volume_ids).await;
+
+    let finished: Vec<&str> = books
+        .iter()
+        .filter(|book| book.status.as_deref() == Some("finished"))
+        .map(|book| book.volume_id.as_str())
+        .collect();
+    let dnf_count = books
+        .iter()
+        .filter(|book| book.status.as_deref() == Some("dnf"))
+        .count();
+    let picks_read = books
+        .iter()
+        .filter(|book| book.suggested_by_user_id == Some(member_id))
+        .count() as i64;
+
+    let pages: i64 = finished
+        .iter()
+        .filter_map(|id| volumes.get(*id)?.get_page_count())
+        .map(i64::from)
+        .sum();
+    let genres = genre_breakdown(
+        finished
+            .iter()
+            .filter_map(|id| primary_genre(&volumes.get(*id)?.get_categories())),
+        3,
+    );
+
+    let ratings: Vec<(&str, i32)> = books
+        .iter()
+        .filter_map(|book| Some((book.volume_id.as_str(), book.rating?)))
+        .collect();
+    let ratings_summary = if ratings.is_empty() {
+        "No ratings given.".to_string()
+    } else {
+        let average = ratings
+            .iter()
+            .map(|(_, rating)| *rating as f64)
+            .sum::<f64>()
+            / ratings.len() as f64;
+        format!(
+            "{} rating{}, averaging {:.2}/5",
+            ratings.len(),
+            plural(ratings.len() as i64),
+            average
+        )
+    };
+    // Earliest wins ties, so the pick reflects the first book that earned the score
+    let favorite = ratings
+        .iter()
+        .rev()
+        .max_by_key(|(_, rating)| *rating)
+        .map(|(id, rating)| {
+            format!(
+                "**{}** — {}/5",
+                display_title(&volumes, id, allow_mature),
+                rating
+            )
+        })
+        .unwrap_or_else(|| "No ratings given.".to_string());
+    let least_favorite = ratings
+        .iter()
+        .min_by_key(|(_, rating)| *rating)
+        .map(|(id, rating)| {
+            format!(
+                "**{}** — {}/5",
+                display_title(&volumes, id, allow_mature),
+                rating
+            )
+        })
+        .unwrap_or_else(|| "No ratings given.".to_string());
+
+    let embed = CreateEmbed::default()
+        .author(embed_author_with_icon(
+            format!("{}'s {} Wrapped{}", member.name, year, club.title_suffix()),
+            Some(member.face()),
+        ))
+        .color(0xB76E79)
+        .field(
+            "Books finished",
+            if dnf_count > 0 {
+                format!("{} ({} DNF)", finished.len(), dnf_count)
+            } else {
+                finished.len().to_string()
+            },
+            true,
+        )
+        .field(
+            "Pages read",
+            if pages > 0 {
+                pages.to_string()
+            } else {
+                "Unknown".to_string()
+            },
+            true,
+        )
+        .field("Picks the club read", picks_read.to_string(), true)
+        .field("Ratings", ratings_summary, false)
+        .field("Favorite", favorite, true)
+        .field("Least favorite", least_favorite, true)
+        .field("Top genres", format_genres(&genres), false)
+        .footer(CreateEmbedFooter::new(
+            "Only counts books the club finished • Powered by Google Books API",
+        ));
+
+    Ok(Some(embed))
+}
+
+#[poise::command(
+    slash_command,
+    guild_only,
+    description_localized("en-US", "Recap the club's reading year, or a member's"),
+    user_cooldown = 10
+)]
+pub async fn wrapped(
+    ctx: Context<'_>,
+    #[description = "Year to recap (defaults to this year)"]
+    #[min = 2000]
+    #[max = 2100]
+    year: Option<i32>,
+    #[description = "Member whose year to recap (leave empty for the whole club)"] member: Option<
+        User,
+    >,
+    #[description = "Club to recap (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    if ctx.guild_id().is_none() {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+    let google_books = &ctx.data().google_books;
+    let year = year.unwrap_or_else(|| Utc::now().year());
+    let allow_mature = can_display_mature_content(&ctx, pool).await?;
+
+    let embed = match member.as_ref() {
+        Some(member) => {
+            build_member_wrapped(pool, google_books, &club, year, member, allow_mature).await?
+        }
+        None => {
+            let guild_name = get_guild_name(&ctx).await;
+            build_club_wrapped(pool, google_books, &club, &guild_name, year, allow_mature).await?
+        }
+    };
+
+    let embed = embed.unwrap_or_else(|| {
+        let description = match member.as_ref() {
+            Some(member) => format!(
+                "**{}** didn't read or rate any club books in {}.",
+                member.name, year
+            ),
+            None => format!("The club didn't finish any books in {}.", year),
+        };
+        CreateEmbed::default()
+            .title(format!("Nothing to Wrap Up{}", club.title_suffix()))
+            .description(description)
+            .color(0xB76E79)
+    });
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{genre_breakdown, primary_genre, recap_year_for_post};
+
+    #[test]
+    fn primary_genre_uses_top_level_category() {
+        let categories = vec![
+            "Fiction / Fantasy / Epic".to_string(),
+            "History".to_string(),
+        ];
+        assert_eq!(primary_genre(&categories), Some("Fiction".to_string()));
+        assert_eq!(primary_genre(&[]), None);
+    }
+
+    #[test]
+    fn genre_breakdown_orders_by_count_then_name() {
+        let genres = [
+            "Fiction",
+            "History",
+            "Fiction",
+            "Biography",
+            "History",
+            "Fiction",
+        ]
+        .map(String::from);
+        assert_eq!(
+            genre_breakdown(genres, 2),
+            vec![("Fiction".to_string(), 3), ("History".to_string(), 2)]
+        );
+    }
+
+    #[test]
+    fn early_posts_recap_the_previous_year() {
+        assert_eq!(recap_year_for_post(1, 2026), 2025);
+        assert_eq!(recap_year_for_post(12, 2026), 2026);
+    }
+}
@@ -1,11 +1,12 @@
+use crate::google_books::Volume;
 use crate::maturity_check::{
     check_volume_maturity, create_mature_content_warning, current_channel_is_nsfw,
     server_maturity_enabled,
 };
 use crate::types::QueryMode;
 use crate::util::{
-    detect_query_mode, embed_author_with_icon, get_guild_name, log_error_with_source,
-    normalize_isbn,
+    LIST_PAGE_SIZE, detect_query_mode, embed_author_with_icon, get_guild_name, list_limits,
+    list_page_count, log_error_with_source, normalize_isbn, send_paginated,
 };
 use crate::*;
 use crate::{types::Context, types::Error};
@@ -13,9 +14,9 @@ use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter, User};
 
 #[poise::command(
     slash_command,
-    subcommands("add", "remove", "view"),
+    subcommands("add", "remove", "view", "author"),
     guild_only,
-    description_localized("en-US", "Manage your favorite books for this server"),
+    description_localized("en-US", "Manage your favorite books and authors for this server"),
     user_cooldown = 10
 )]
 pub async fn favorite(_ctx: Context<'_>) -> Result<(), Error> {
@@ -36,39 +37,14 @@ async fn add(
 ) -> Result<(), Error> {
     ctx.defer().await?;
 
-    let pool = &ctx.data().database;
     let google_books = &ctx.data().google_books;
-    let Some(guild_id) = ctx.guild_id() else {
+    if ctx.guild_id().is_none() {
         let embed = CreateEmbed::default()
             .title("❌ Error")
             .description("This command must be used in a server.")
             .color(0xB76E79);
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
-    };
-    let guild_name = get_guild_name(&ctx).await;
-
-    ensure_user_exists(pool, &ctx.author()).await?;
-    ensure_server_exists(pool, guild_id, &guild_name).await?;
-
-    // Check current count for this server
-    let count = sqlx::query_scalar!(
-        "SELECT COUNT(*) FROM user_favorite_books WHERE user_id = $1 AND server_id = $2",
-        ctx.author().id.get() as i64,
-        guild_id.get() as i64
-    )
-    .fetch_one(pool)
-    .await?
-    .unwrap_or(0);
-
-    if count >= 5 {
-        let embed = CreateEmbed::default()
-            .title("❌ Favorites Limit Reached")
-            .description("You've reached the maximum of 5 favorite books for this server. Please remove some favorites before adding more.")
-            .color(0xB76E79)
-            .footer(CreateEmbedFooter::new("Powered by Google Books API"));
-        ctx.send(poise::CreateReply::default().embed(embed)).await?;
-        return Ok(());
     }
 
     // Search for the book using unified logic
@@ -128,8 +104,50 @@ async fn add(
         }
     };
 
+    add_volume_to_favorites(ctx, &book, result_bool).await
+}
+
+/// Adds a book the member already picked to their favorites, applying the list limit and
+/// maturity check. Shared by `/favorite add` and the "Favorite" book action.
+pub(crate) async fn add_volume_to_favorites(
+    ctx: Context<'_>,
+    book: &Volume,
+    multiple_found: bool,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+    let pool = &ctx.data().database;
+    let guild_name = get_guild_name(&ctx).await;
+
+    ensure_user_exists(pool, &ctx.author()).await?;
+    ensure_server_exists(pool, guild_id, &guild_name).await?;
+
+    // Check current count for this server
+    let count = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM user_favorite_books WHERE user_id = $1 AND server_id = $2",
+        ctx.author().id.get() as i64,
+        guild_id.get() as i64
+    )
+    .fetch_one(pool)
+    .await?
+    .unwrap_or(0);
+
+    let limit = list_limits(pool, guild_id.get() as i64, ctx.data().list_limit_cap)
+        .await?
+        .favorite_books as i64;
+    if count >= limit {
+        let embed = CreateEmbed::default()
+            .title("❌ Favorites Limit Reached")
+            .description(format!("You've reached the maximum of {} favorite books for this server. Please remove some favorites before adding more.", limit))
+            .color(0xB76E79)
+            .footer(CreateEmbedFooter::new("Powered by Google Books API"));
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
     // Check maturity
-    if !check_volume_maturity(&ctx, pool, &book).await? {
+    if !check_volume_maturity(&ctx, pool, book).await? {
         let is_nsfw = current_channel_is_nsfw(&ctx).await?;
         let maturity_enabled = server_maturity_enabled(&ctx, pool).await?;
         let embed =
@@ -161,10 +179,10 @@ async fn add(
                     .field("Title", &book_title, false)
                     .field("Authors", &book_authors, false)
                     .field("Server", guild_name.clone(), true)
-                    .field("Favorites Count", format!("{}/5", count + 1), true)
+                    .field("Favorites Count", format!("{}/{}", count + 1, limit), true)
                     .color(0xB76E79);
 
-                let footer_text = if result_bool {
+                let footer_text = if multiple_found {
                     "Multiple books found. • Powered by Google Books API"
                 } else {
                     "Powered by Google Books API"
@@ -181,7 +199,7 @@ async fn add(
                     ))
                     .color(0xB76E79);
 
-                let footer_text = if result_bool {
+                let footer_text = if multiple_found {
                     "Multiple books found • Powered by Google Books API"
                 } else {
                     "Powered by Google Books API"
@@ -364,7 +382,8 @@ async fn view(
         return Ok(());
     };
     let guild_name = get_guild_name(&ctx).await;
-    let footer_base = format!("{} • Max: 5 per server", guild_name);
+    let limits = list_limits(pool, guild_id.get() as i64, ctx.data().list_limit_cap).await?;
+    let footer_base = format!("{} • Max: {} per server", guild_name, limits.favorite_books);
     let favorites = sqlx::query!(
         r#"
         SELECT 
@@ -374,7 +393,6 @@ async fn view(
         FROM user_favorite_books
         WHERE user_id = $1 AND server_id = $2
         ORDER BY is_number_one DESC, added_at DESC
-        LIMIT 5
         "#,
         target_user.id.get() as i64,
         guild_id.get() as i64
@@ -455,13 +473,7 @@ async fn view(
         return Ok(());
     }
 
-    let mut embed = CreateEmbed::default()
-        .author(embed_author_with_icon(
-            format!("{}'s Favorite Books", target_user.name),
-            Some(target_user.face()),
-        ))
-        .color(0xB76E79);
-
+    let mut notice: Option<String> = None;
     if mature_count > 0 {
         let mut tips: Vec<&str> = Vec::new();
         if !maturity_enabled {
@@ -479,54 +491,442 @@ async fn view(
             message.push('.');
         }
 
-        embed = embed.description(message);
+        notice = Some(message);
     }
 
-    // Check if there's a #1 book and add its thumbnail
-    let mut thumbnail_url: Option<String> = None;
-    for (fav, volume_opt) in &filtered_favorites {
-        if fav.is_number_one {
-            if let Some(volume) = volume_opt {
-                thumbnail_url = volume.get_thumbnail_url();
+    // The #1 book's cover stays on every page
+    let thumbnail_url = filtered_favorites
+        .iter()
+        .find(|(fav, _)| fav.is_number_one)
+        .and_then(|(_, volume_opt)| volume_opt.as_ref())
+        .and_then(|volume| volume.get_thumbnail_url());
+
+    let total_pages = list_page_count(filtered_favorites.len());
+    let mut pages = Vec::new();
+    for (page, chunk) in filtered_favorites.chunks(LIST_PAGE_SIZE).enumerate() {
+        let mut embed = CreateEmbed::default()
+            .author(embed_author_with_icon(
+                format!("{}'s Favorite Books", target_user.name),
+                Some(target_user.face()),
+            ))
+            .color(0xB76E79);
+        if let Some(message) = &notice {
+            embed = embed.description(message);
+        }
+        if let Some(url) = &thumbnail_url {
+            embed = embed.thumbnail(url);
+        }
+
+        for (fav, volume_opt) in chunk {
+            let bullet = if fav.is_number_one { "⭐" } else { "•" };
+
+            match volume_opt {
+                Some(volume) => {
+                    let title = volume.get_title();
+                    let authors = volume.get_authors_string();
+                    embed = embed.field(
+                        format!("{} {}", bullet, title),
+                        format!("by {}", authors),
+                        false,
+                    );
+                }
+                None => {
+                    // Fallback if API fails
+                    embed = embed.field(
+                        format!("{} [Book data unavailable]", bullet),
+                        format!("Volume ID: {}", fav.volume_id),
+                        false,
+                    );
+                }
             }
+        }
+
+        let page_label = if total_pages > 1 {
+            format!(" • Page {}/{}", page + 1, total_pages)
+        } else {
+            String::new()
+        };
+        embed = embed.footer(CreateEmbedFooter::new(format!(
+            "{}{} • Powered by Google Books API",
+            footer_base, page_label
+        )));
+        pages.push(embed);
+    }
+    send_paginated(&ctx, pages).await?;
+
+    Ok(())
+}
+
+/// Google Books results scanned when checking an author name.
+const AUTHOR_SEARCH_RESULTS: u32 = 20;
+
+/// Compares author names ignoring case, spacing and punctuation, so "J.R.R. Tolkien" matches
+/// "J. R. R. Tolkien".
+pub(crate) fn author_key(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Picks the spelling Google Books uses for `query` out of the authors of its search results.
+/// When none match, returns up to three of those authors as suggestions.
+pub(crate) fn resolve_author_name(query: &str, found: &[String]) -> Result<String, Vec<String>> {
+    let key = author_key(query);
+    if let Some(name) = found.iter().find(|name| author_key(name) == key) {
+        return Ok(name.trim().to_string());
+    }
+    let mut suggestions: Vec<String> = Vec::new();
+    for name in found {
+        if suggestions.len() >= 3 {
             break;
         }
+        if !suggestions
+            .iter()
+            .any(|s| author_key(s) == author_key(name))
+        {
+            suggestions.push(name.trim().to_string());
+        }
     }
+    Err(suggestions)
+}
+
+pub(crate) async fn autocomplete_favorite_author(ctx: Context<'_>, partial: &str) -> Vec<String> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Vec::new();
+    };
+    let partial = partial.to_lowercase();
+
+    sqlx::query_scalar!(
+        "SELECT author_name FROM user_favorite_authors
+         WHERE user_id = $1 AND server_id = $2
+         ORDER BY is_number_one DESC, added_at DESC",
+        ctx.author().id.get() as i64,
+        guild_id.get() as i64
+    )
+    .fetch_all(&ctx.data().database)
+    .await
+    .unwrap_or_default()
+    .into_iter()
+    .filter(|name| name.to_lowercase().contains(&partial))
+    .collect()
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("author_add", "author_remove", "author_view"),
+    guild_only,
+    description_localized("en-US", "Manage your favorite authors for this server"),
+    user_cooldown = 10
+)]
+async fn author(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    rename = "add",
+    guild_only,
+    description_localized("en-US", "Add an author to your favorites"),
+    user_cooldown = 10
+)]
+async fn author_add(
+    ctx: Context<'_>,
+    #[description = "Author name"] name: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let pool = &ctx.data().database;
+    let google_books = &ctx.data().google_books;
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+    let guild_name = get_guild_name(&ctx).await;
+
+    ensure_user_exists(pool, ctx.author()).await?;
+    ensure_server_exists(pool, guild_id, &guild_name).await?;
+
+    let count = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM user_favorite_authors WHERE user_id = $1 AND server_id = $2",
+        ctx.author().id.get() as i64,
+        guild_id.get() as i64
+    )
+    .fetch_one(pool)
+    .await?
+    .unwrap_or(0);
 
-    if let Some(url) = thumbnail_url {
-        embed = embed.thumbnail(url);
+    let limit = list_limits(pool, guild_id.get() as i64, ctx.data().list_limit_cap)
+        .await?
+        .favorite_authors as i64;
+    if count >= limit {
+        let embed = CreateEmbed::default()
+            .title("❌ Favorites Limit Reached")
+            .description(format!(
+                "You've reached the maximum of {} favorite authors for this server. Please remove some before adding more.",
+                limit
+            ))
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
     }
 
-    // Add the book fields
-    for (_i, (fav, volume_opt)) in filtered_favorites.iter().enumerate() {
-        let bullet = if fav.is_number_one { "⭐" } else { "•" };
-
-        match volume_opt {
-            Some(volume) => {
-                let title = volume.get_title();
-                let authors = volume.get_authors_string();
-                embed = embed.field(
-                    format!("{} {}", bullet, title),
-                    format!("by {}", authors),
-                    false,
-                );
-            }
-            None => {
-                // Fallback if API fails
-                embed = embed.field(
-                    format!("{} [Book data unavailable]", bullet),
-                    format!("Volume ID: {}", fav.volume_id),
-                    false,
-                );
+    // Only accept names Google Books knows, stored the way it spells them
+    let found: Vec<String> = google_books
+        .search_by_author(name.trim(), Some(AUTHOR_SEARCH_RESULTS))
+        .await?
+        .into_iter()
+        .flat_map(|volume| volume.volume_info.authors.unwrap_or_default())
+        .collect();
+    let author_name = match resolve_author_name(&name, &found) {
+        Ok(author_name) => author_name,
+        Err(suggestions) => {
+            let mut description = format!("Couldn't find an author named **{}**.", name.trim());
+            if !suggestions.is_empty() {
+                description.push_str(&format!(
+                    "\n\nDid you mean: {}?",
+                    suggestions
+                        .iter()
+                        .map(|s| format!("**{}**", s))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
             }
+            let embed = CreateEmbed::default()
+                .title("❌ Author Not Found")
+                .description(description)
+                .color(0xB76E79)
+                .footer(CreateEmbedFooter::new("Searched via Google Books API"));
+            ctx.send(poise::CreateReply::default().embed(embed)).await?;
+            return Ok(());
+        }
+    };
+
+    let result = sqlx::query!(
+        "INSERT INTO user_favorite_authors (user_id, server_id, author_name)
+        VALUES ($1, $2, $3)
+        ON CONFLICT DO NOTHING",
+        ctx.author().id.get() as i64,
+        guild_id.get() as i64,
+        author_name
+    )
+    .execute(pool)
+    .await;
+
+    match result {
+        Ok(res) if res.rows_affected() > 0 => {
+            let embed = CreateEmbed::default()
+                .title("✅ Author Added to Favorites")
+                .field("Author", &author_name, false)
+                .field("Server", guild_name.clone(), true)
+                .field("Favorites Count", format!("{}/{}", count + 1, limit), true)
+                .color(0xB76E79)
+                .footer(CreateEmbedFooter::new("Powered by Google Books API"));
+            ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        }
+        Ok(_) => {
+            let embed = CreateEmbed::default()
+                .title("Already in Favorites")
+                .description(format!(
+                    "{} is already one of your favorite authors for this server!",
+                    author_name
+                ))
+                .color(0xB76E79);
+            ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        }
+        Err(e) => {
+            log_error_with_source("Error adding favorite author", &e);
+            let embed = CreateEmbed::default()
+                .title("❌ Error")
+                .description("An error occurred while adding the author to your favorites.")
+                .color(0xB76E79);
+            ctx.send(poise::CreateReply::default().embed(embed)).await?;
         }
     }
 
-    embed = embed.footer(CreateEmbedFooter::new(format!(
-        "{} • Powered by Google Books API",
-        footer_base
-    )));
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    rename = "remove",
+    guild_only,
+    description_localized("en-US", "Remove an author from your favorites"),
+    user_cooldown = 10
+)]
+async fn author_remove(
+    ctx: Context<'_>,
+    #[description = "Author name"]
+    #[autocomplete = "autocomplete_favorite_author"]
+    name: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let pool = &ctx.data().database;
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+    let guild_name = get_guild_name(&ctx).await;
+    let user_id = ctx.author().id.get() as i64;
+    let server_id = guild_id.get() as i64;
+
+    let favorites = sqlx::query_scalar!(
+        "SELECT author_name FROM user_favorite_authors WHERE user_id = $1 AND server_id = $2",
+        user_id,
+        server_id
+    )
+    .fetch_all(pool)
+    .await?;
+    let Some(author_name) = favorites
+        .into_iter()
+        .find(|favorite| author_key(favorite) == author_key(&name))
+    else {
+        let embed = CreateEmbed::default()
+            .title("❌ Not in Favorites")
+            .description(format!(
+                "{} is not one of your favorite authors for this server.",
+                name.trim()
+            ))
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+
+    sqlx::query!(
+        "DELETE FROM user_favorite_authors
+         WHERE user_id = $1 AND server_id = $2 AND author_name = $3",
+        user_id,
+        server_id,
+        author_name
+    )
+    .execute(pool)
+    .await?;
+
+    let embed = CreateEmbed::default()
+        .title("✅ Author Removed from Favorites")
+        .field("Author", author_name, false)
+        .field("Server", guild_name, true)
+        .color(0xB76E79);
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
 
     Ok(())
 }
+
+#[poise::command(
+    slash_command,
+    rename = "view",
+    guild_only,
+    description_localized("en-US", "View favorite authors in this server"),
+    user_cooldown = 10
+)]
+async fn author_view(
+    ctx: Context<'_>,
+    #[description = "User to check favorites for (defaults to you)"] user: Option<User>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let pool = &ctx.data().database;
+    let target_user = user.as_ref().unwrap_or_else(|| ctx.author());
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+    let guild_name = get_guild_name(&ctx).await;
+
+    let limits = list_limits(pool, guild_id.get() as i64, ctx.data().list_limit_cap).await?;
+    let favorites = sqlx::query!(
+        r#"
+        SELECT author_name, is_number_one
+        FROM user_favorite_authors
+        WHERE user_id = $1 AND server_id = $2
+        ORDER BY is_number_one DESC, added_at DESC
+        "#,
+        target_user.id.get() as i64,
+        guild_id.get() as i64
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let description = if favorites.is_empty() {
+        if target_user.id == ctx.author().id {
+            "You don't have any favorite authors in this server yet!".to_string()
+        } else {
+            format!(
+                "{} doesn't have any favorite authors in this server yet!",
+                target_user.name
+            )
+        }
+    } else {
+        favorites
+            .iter()
+            .map(|favorite| {
+                let bullet = if favorite.is_number_one { "⭐" } else { "•" };
+                format!("{} {}", bullet, favorite.author_name)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let embed = CreateEmbed::default()
+        .author(embed_author_with_icon(
+            format!("{}'s Favorite Authors", target_user.name),
+            Some(target_user.face()),
+        ))
+        .description(description)
+        .color(0xB76E79)
+        .footer(CreateEmbedFooter::new(format!(
+            "{} • Max: {} per server",
+            guild_name, limits.favorite_authors
+        )));
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{author_key, resolve_author_name};
+
+    #[test]
+    fn author_keys_ignore_case_spacing_and_punctuation() {
+        assert_eq!(
+            author_key("J.R.R. Tolkien"),
+            author_key("j. r. r.  tolkien")
+        );
+        assert_ne!(author_key("Ann Leckie"), author_key("Ann Leckey"));
+    }
+
+    #[test]
+    fn resolve_uses_the_google_books_spelling() {
+        let found = vec![
+            "Ursula K. Le Guin".to_string(),
+            "Ursula K. Le Guin".to_string(),
+            "Brian Attebery".to_string(),
+        ];
+        assert_eq!(
+            resolve_author_name("ursula k le guin", &found),
+            Ok("Ursula K. Le Guin".to_string())
+        );
+        assert_eq!(
+            resolve_author_name("Le Guin", &found),
+            Err(vec![
+                "Ursula K. Le Guin".to_string(),
+                "Brian Attebery".to_string()
+            ])
+        );
+        assert_eq!(resolve_author_name("Nobody", &[]), Err(Vec::new()));
+    }
+}
@@ -0,0 +1,428 @@
+use super::readinglist::{DEFAULT_SHELF, MAX_TAGS, normalize_shelf, parse_tags};
+use crate::google_books::Volume;
+use crate::google_books_cache::CachedGoogleBooksClient;
+use crate::maturity_check::can_display_mature_content;
+use crate::reading_import::{ExportFormat, ImportRow, parse_export, search_title};
+use crate::util::{
+    EMBED_DESCRIPTION_LIMIT, ensure_server_exists, ensure_user_exists, get_guild_name,
+    join_lines_capped, list_limits, log_error_with_source,
+};
+use crate::{types::Context, types::Error};
+use futures::future::join_all;
+use poise::serenity_prelude::{Attachment, CreateAttachment, CreateEmbed, CreateEmbedFooter};
+use poise::{CreateReply, ReplyHandle};
+use std::time::Duration;
+
+/// Library exports are small; anything bigger is almost certainly the wrong file.
+const MAX_EXPORT_BYTES: u32 = 5 * 1024 * 1024;
+/// Rows one import may look up on Google Books; later rows are reported as not processed.
+const MAX_IMPORT_ROWS: usize = 300;
+// Same pacing as `get_volumes_batch`, so a big export doesn't exhaust the API quota
+const IMPORT_BATCH_SIZE: usize = 10;
+const IMPORT_BATCH_DELAY: Duration = Duration::from_secs(1);
+/// Unmatched or skipped rows listed in the report; the full list is attached as a file.
+const REPORT_LINES: usize = 10;
+/// Each of the two report sections gets under half of the embed description.
+const REPORT_SECTION_LENGTH: usize = EMBED_DESCRIPTION_LIMIT / 2 - 200;
+const ATTACHED_NOTE: &str = " (see the attached file)";
+
+#[derive(Default)]
+struct ImportReport {
+    reading_list: usize,
+    favorites: usize,
+    ratings: usize,
+    unmatched: Vec<String>,
+    skipped: Vec<String>,
+    not_processed: usize,
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("goodreads", "storygraph"),
+    guild_only,
+    description_localized("en-US", "Import your reading history from another service"),
+    user_cooldown = 10
+)]
+pub async fn import(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    rename = "goodreads",
+    guild_only,
+    description_localized(
+        "en-US",
+        "Import a Goodreads library export into your reading list, favorites and ratings"
+    ),
+    user_cooldown = 10
+)]
+async fn goodreads(
+    ctx: Context<'_>,
+    #[description = "CSV from Goodreads' \"Import and export\" page"] file: Attachment,
+) -> Result<(), Error> {
+    run_import(ctx, file, ExportFormat::Goodreads).await
+}
+
+#[poise::command(
+    slash_command,
+    rename = "storygraph",
+    guild_only,
+    description_localized(
+        "en-US",
+        "Import a StoryGraph library export into your reading list, favorites and ratings"
+    ),
+    user_cooldown = 10
+)]
+async fn storygraph(
+    ctx: Context<'_>,
+    #[description = "CSV from StoryGraph's \"Manage Account\" export"] file: Attachment,
+) -> Result<(), Error> {
+    run_import(ctx, file, ExportFormat::StoryGraph).await
+}
+
+async fn send_error(ctx: &Context<'_>, title: &str, description: String) -> Result<(), Error> {
+    let embed = CreateEmbed::default()
+        .title(title)
+        .description(description)
+        .color(0xB76E79);
+    ctx.send(CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+async fn run_import(ctx: Context<'_>, file: Attachment, format: ExportFormat) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let pool = &ctx.data().database;
+    let Some(guild_id) = ctx.guild_id() else {
+        return send_error(
+            &ctx,
+            "❌ Error",
+            "This command must be used in a server.".to_string(),
+        )
+        .await;
+    };
+    if !file.filename.to_lowercase().ends_with(".csv") || file.size > MAX_EXPORT_BYTES {
+        return send_error(
+            &ctx,
+            "❌ Invalid File",
+            format!(
+                "Please attach the CSV file exported from {} (up to {} MB).",
+                format.name(),
+                MAX_EXPORT_BYTES / 1024 / 1024
+            ),
+        )
+        .await;
+    }
+
+    let bytes = file.download().await?;
+    let rows = match parse_export(format, &String::from_utf8_lossy(&bytes)) {
+        Ok(rows) => rows,
+        Err(missing) => {
+            return send_error(
+                &ctx,
+                "❌ Invalid File",
+                format!(
+                    "This doesn't look like a {} export; it is missing the {} column{}.",
+                    format.name(),
+                    missing.join(", "),
+                    if missing.len() == 1 { "" } else { "s" }
+                ),
+            )
+            .await;
+        }
+    };
+    let mut rows: Vec<ImportRow> = rows.into_iter().filter(ImportRow::has_work).collect();
+    if rows.is_empty() {
+        return send_error(
+            &ctx,
+            "❌ Nothing to Import",
+            "The export has no unread books, favorites or ratings.".to_string(),
+        )
+        .await;
+    }
+
+    let guild_name = get_guild_name(&ctx).await;
+    ensure_user_exists(pool, ctx.author()).await?;
+    ensure_server_exists(pool, guild_id, &guild_name).await?;
+
+    let mut report = ImportReport {
+        not_processed: rows.len().saturating_sub(MAX_IMPORT_ROWS),
+        ..Default::default()
+    };
+    rows.truncate(MAX_IMPORT_ROWS);
+
+    let progress = ctx
+        .send(CreateReply::default().embed(progress_embed(format, 0, rows.len())))
+        .await?;
+
+    let google_books = &ctx.data().google_books;
+    let mut matched: Vec<(ImportRow, Volume)> = Vec::with_capacity(rows.len());
+    let batch_count = rows.len().div_ceil(IMPORT_BATCH_SIZE);
+    for (batch_index, batch) in rows.chunks(IMPORT_BATCH_SIZE).enumerate() {
+        let lookups = join_all(batch.iter().map(|row| resolve_row(google_books, row))).await;
+        for (row, lookup) in batch.iter().zip(lookups) {
+            match lookup {
+                Ok(Some(volume)) => matched.push((row.clone(), volume)),
+                Ok(None) => report
+                    .unmatched
+                    .push(format!("Line {}: {}", row.line, row.label())),
+                Err(e) => {
+                    log_error_with_source(
+                        &format!("Import lookup failed for line {}", row.line),
+                        &e,
+                    );
+                    report.unmatched.push(format!(
+                        "Line {}: {} (lookup failed)",
+                        row.line,
+                        row.label()
+                    ));
+                }
+            }
+        }
+
+        let done = (batch_index * IMPORT_BATCH_SIZE + batch.len()).min(rows.len());
+        progress
+            .edit(
+                ctx,
+                CreateReply::default().embed(progress_embed(format, done, rows.len())),
+            )
+            .await?;
+        if batch_index + 1 < batch_count {
+            tokio::time::sleep(IMPORT_BATCH_DELAY).await;
+        }
+    }
+
+    save_matches(&ctx, guild_id.get() as i64, matched, &mut report).await?;
+    send_report(&ctx, &progress, format, &report).await
+}
+
+fn progress_embed(format: ExportFormat, done: usize, total: usize) -> CreateEmbed {
+    CreateEmbed::default()
+        .title(format!("📥 Importing from {}", format.name()))
+        .description(format!(
+            "Looking up books on Google Books… {}/{}",
+            done, total
+        ))
+        .color(0xB76E79)
+        .footer(CreateEmbedFooter::new("Powered by Google Books API"))
+}
+
+/// Tries each ISBN before falling back to a title and author search.
+async fn resolve_row(
+    google_books: &CachedGoogleBooksClient,
+    row: &ImportRow,
+) -> Result<Option<Volume>, Error> {
+    for isbn in &row.isbns {
+        if let Some(volume) = google_books.search_by_isbn(isbn).await? {
+            return Ok(Some(volume));
+        }
+    }
+    let results = google_books
+        .search_books(&search_title(&row.title), row.author.as_deref(), Some(1))
+        .await?;
+    Ok(results.into_iter().next())
+}
+
+/// Stores matched books, leaving entries the member already has untouched and stopping at
+/// the server's list limits. Ratings overwrite earlier imports of the same book.
+async fn save_matches(
+    ctx: &Context<'_>,
+    server_id: i64,
+    matched: Vec<(ImportRow, Volume)>,
+    report: &mut ImportReport,
+) -> Result<(), Error> {
+    let pool = &ctx.data().database;
+    let user_id = ctx.author().id.get() as i64;
+    let limits = list_limits(pool, server_id, ctx.data().list_limit_cap).await?;
+    let allow_mature = can_display_mature_content(ctx, pool).await?;
+
+    let mut reading_list_count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM user_reading_list WHERE user_id = $1 AND server_id = $2"#,
+        user_id,
+        server_id
+    )
+    .fetch_one(pool)
+    .await?;
+    let mut favorites_count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM user_favorite_books WHERE user_id = $1 AND server_id = $2"#,
+        user_id,
+        server_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    for (row, volume) in matched {
+        if volume.is_mature() && !allow_mature {
+            report.skipped.push(format!(
+                "Line {}: {} (mature content is hidden here)",
+                row.line,
+                row.label()
+            ));
+            continue;
+        }
+
+        if let Some(rating) = row.rating {
+            sqlx::query!(
+                "INSERT INTO user_personal_ratings (user_id, server_id, volume_id, rating)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (user_id, server_id, volume_id)
+                 DO UPDATE SET rating = EXCLUDED.rating, rated_at = CURRENT_TIMESTAMP",
+                user_id,
+                server_id,
+                &volume.id,
+                rating
+            )
+            .execute(pool)
+            .await?;
+            report.ratings += 1;
+        }
+
+        if row.favorite {
+            if favorites_count < limits.favorite_books as i64 {
+                let result = sqlx::query!(
+                    "INSERT INTO user_favorite_books (user_id, server_id, volume_id)
+                     VALUES ($1, $2, $3)
+                     ON CONFLICT DO NOTHING",
+                    user_id,
+                    server_id,
+                    &volume.id
+                )
+                .execute(pool)
+                .await?;
+                if result.rows_affected() > 0 {
+                    favorites_count += 1;
+                    report.favorites += 1;
+                }
+            } else {
+                report.skipped.push(format!(
+                    "Line {}: {} (favorites are full)",
+                    row.line,
+                    row.label()
+                ));
+            }
+        }
+
+        if let Some(shelf) = &row.reading_shelf {
+            if reading_list_count < limits.reading_list as i64 {
+                let shelf = normalize_shelf(shelf).unwrap_or_else(|| DEFAULT_SHELF.to_string());
+                // Tags that don't fit the reading list's rules are dropped rather than failing the row
+                let mut tags: Vec<String> = Vec::new();
+                for tag in row.tags.iter().filter_map(|tag| parse_tags(tag)).flatten() {
+                    if !tags.contains(&tag) && tags.len() < MAX_TAGS {
+                        tags.push(tag);
+                    }
+                }
+                let result = sqlx::query!(
+                    "INSERT INTO user_reading_list (user_id, server_id, volume_id, shelf, tags)
+                     VALUES ($1, $2, $3, $4, $5)
+                     ON CONFLICT DO NOTHING",
+                    user_id,
+                    server_id,
+                    &volume.id,
+                    shelf,
+                    &tags
+                )
+                .execute(pool)
+                .await?;
+                if result.rows_affected() > 0 {
+                    reading_list_count += 1;
+                    report.reading_list += 1;
+                }
+            } else {
+                report.skipped.push(format!(
+                    "Line {}: {} (reading list is full)",
+                    row.line,
+                    row.label()
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The first few lines of a report section, and how many had to be left for the attachment.
+fn report_section(lines: &[String]) -> (String, usize) {
+    let (mut section, hidden) = join_lines_capped(
+        &lines[..lines.len().min(REPORT_LINES)],
+        lines.len(),
+        REPORT_SECTION_LENGTH - ATTACHED_NOTE.len(),
+    );
+    if hidden > 0 {
+        section.push_str(ATTACHED_NOTE);
+    }
+    (section, hidden)
+}
+
+async fn send_report(
+    ctx: &Context<'_>,
+    progress: &ReplyHandle<'_>,
+    format: ExportFormat,
+    report: &ImportReport,
+) -> Result<(), Error> {
+    let mut embed = CreateEmbed::default()
+        .title(format!("✅ {} Import Finished", format.name()))
+        .field(
+            "Reading List",
+            format!("{} added", report.reading_list),
+            true,
+        )
+        .field("Favorites", format!("{} added", report.favorites), true)
+        .field("Ratings", format!("{} saved", report.ratings), true)
+        .color(0xB76E79)
+        .footer(CreateEmbedFooter::new("Powered by Google Books API"));
+
+    let mut description = String::new();
+    let mut needs_attachment = false;
+    if !report.unmatched.is_empty() {
+        let (section, hidden) = report_section(&report.unmatched);
+        needs_attachment |= hidden > 0;
+        description.push_str(&format!(
+            "**Not found on Google Books ({})**\n{}\n\n",
+            report.unmatched.len(),
+            section
+        ));
+    }
+    if !report.skipped.is_empty() {
+        let (section, hidden) = report_section(&report.skipped);
+        needs_attachment |= hidden > 0;
+        description.push_str(&format!(
+            "**Skipped ({})**\n{}\n\n",
+            report.skipped.len(),
+            section
+        ));
+    }
+    if report.not_processed > 0 {
+        description.push_str(&format!(
+            "Only the first {} books with something to import were processed; {} more were left out. Split the file to import the rest.",
+            MAX_IMPORT_ROWS, report.not_processed
+        ));
+    }
+    if !description.is_empty() {
+        embed = embed.description(description.trim_end());
+    }
+
+    let mut reply = CreateReply::default().embed(embed);
+    if needs_attachment {
+        let contents = report
+            .unmatched
+            .iter()
+            .map(|line| format!("Not found: {}", line))
+            .chain(
+                report
+                    .skipped
+                    .iter()
+                    .map(|line| format!("Skipped: {}", line)),
+            )
+            .collect::<Vec<_>>()
+            .join("\n");
+        reply = reply.attachment(CreateAttachment::bytes(
+            contents.replace('*', ""),
+            "import-report.txt",
+        ));
+    }
+    progress.edit(*ctx, reply).await?;
+
+    Ok(())
+}
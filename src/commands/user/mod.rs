@@ -1,7 +1,10 @@
 pub mod favorite;
+pub mod import;
 pub mod numberone;
+pub mod profile;
 pub mod progress;
 pub mod readinglist;
+pub mod remindme;
 
 use crate::{types::Data, types::Error};
 
@@ -13,5 +16,8 @@ pub fn user_commands() -> CommandVec {
         progress::progress(),
         readinglist::readinglist(),
         numberone::numberone(),
+        profile::profile(),
+        remindme::remindme(),
+        import::import(),
     ]
 }
@@ -1,3 +1,4 @@
+use crate::commands::user::favorite::{author_key, autocomplete_favorite_author};
 use crate::types::QueryMode;
 use crate::util::get_guild_name;
 use crate::util::{detect_query_mode, normalize_isbn};
@@ -7,9 +8,9 @@ use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
 
 #[poise::command(
     slash_command,
-    subcommands("set", "remove"),
+    subcommands("set", "remove", "author"),
     guild_only,
-    description_localized("en-US", "Manage your #1 favorite book for this server"),
+    description_localized("en-US", "Manage your #1 favorite book and author for this server"),
     user_cooldown = 10
 )]
 pub async fn numberone(_ctx: Context<'_>) -> Result<(), Error> {
@@ -38,51 +39,12 @@ async fn set(
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
     };
-    let guild_name = get_guild_name(&ctx).await;
-
-    ensure_user_exists(pool, &ctx.author()).await?;
-    ensure_server_exists(pool, guild_id, &guild_name).await?;
-
-    let user_id = ctx.author().id.get() as i64;
-    let server_id = guild_id.get() as i64;
-
-    // Already have a #1 book for this server?
-    if sqlx::query_scalar!(
-        "SELECT volume_id FROM user_favorite_books WHERE user_id = $1 AND server_id = $2 AND is_number_one",
-        user_id,
-        server_id
-    )
-    .fetch_optional(pool)
-    .await?
-    .is_some() {
-        let embed = CreateEmbed::default()
-            .title("❌ Already Have #1 Book")
-            .description("You already have a #1 favorite book for this server. Use `/numberone remove` first.")
-            .color(0xB76E79)
-            .footer(CreateEmbedFooter::new("Powered by Google Books API"));
-        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    if !can_set_number_one(ctx).await? {
         return Ok(());
     }
 
-    // Ensure the user has at least one favorite book in this server
-    let fav_count: i64 = sqlx::query_scalar!(
-        "SELECT COUNT(*) FROM user_favorite_books WHERE user_id = $1 AND server_id = $2",
-        user_id,
-        server_id
-    )
-    .fetch_one(pool)
-    .await?
-    .unwrap_or(0);
-
-    if fav_count == 0 {
-        let embed = CreateEmbed::default()
-            .title("❌ No Favorite Books")
-            .description("You don't have any favorite books in this server yet. Add one with `/favorite add` first.")
-            .color(0xB76E79)
-            .footer(CreateEmbedFooter::new("Powered by Google Books API"));
-        ctx.send(poise::CreateReply::default().embed(embed)).await?;
-        return Ok(());
-    }
+    let user_id = ctx.author().id.get() as i64;
+    let server_id = guild_id.get() as i64;
 
     // Determine which book to set as #1
     let chosen = detect_query_mode(&title_or_isbn);
@@ -152,6 +114,93 @@ async fn set(
         }
     };
 
+    mark_number_one(ctx, &target_volume_id, selected_title).await
+}
+
+/// Marks one of the member's favorite books as their #1 for this server, for the "Set #1" book
+/// action.
+pub(crate) async fn set_number_one_volume(
+    ctx: Context<'_>,
+    target_volume_id: &str,
+    selected_title: Option<String>,
+) -> Result<(), Error> {
+    if !can_set_number_one(ctx).await? {
+        return Ok(());
+    }
+    mark_number_one(ctx, target_volume_id, selected_title).await
+}
+
+/// Checks that the member has no #1 book yet and at least one favorite, explaining why not
+/// otherwise. Runs before any Google Books lookup so refused members cost no API calls.
+async fn can_set_number_one(ctx: Context<'_>) -> Result<bool, Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(false);
+    };
+    let pool = &ctx.data().database;
+    let guild_name = get_guild_name(&ctx).await;
+
+    ensure_user_exists(pool, &ctx.author()).await?;
+    ensure_server_exists(pool, guild_id, &guild_name).await?;
+
+    let user_id = ctx.author().id.get() as i64;
+    let server_id = guild_id.get() as i64;
+
+    // Already have a #1 book for this server?
+    if sqlx::query_scalar!(
+        "SELECT volume_id FROM user_favorite_books WHERE user_id = $1 AND server_id = $2 AND is_number_one",
+        user_id,
+        server_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .is_some() {
+        let embed = CreateEmbed::default()
+            .title("❌ Already Have #1 Book")
+            .description("You already have a #1 favorite book for this server. Use `/numberone remove` first.")
+            .color(0xB76E79)
+            .footer(CreateEmbedFooter::new("Powered by Google Books API"));
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(false);
+    }
+
+    // Ensure the user has at least one favorite book in this server
+    let fav_count: i64 = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM user_favorite_books WHERE user_id = $1 AND server_id = $2",
+        user_id,
+        server_id
+    )
+    .fetch_one(pool)
+    .await?
+    .unwrap_or(0);
+
+    if fav_count == 0 {
+        let embed = CreateEmbed::default()
+            .title("❌ No Favorite Books")
+            .description("You don't have any favorite books in this server yet. Add one with `/favorite add` first.")
+            .color(0xB76E79)
+            .footer(CreateEmbedFooter::new("Powered by Google Books API"));
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(false);
+    }
+
+    Ok(true)
+}
+
+/// Flags the chosen favorite as #1 once `can_set_number_one` has passed.
+async fn mark_number_one(
+    ctx: Context<'_>,
+    target_volume_id: &str,
+    selected_title: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+    let pool = &ctx.data().database;
+    let google = &ctx.data().google_books;
+    let guild_name = get_guild_name(&ctx).await;
+    let user_id = ctx.author().id.get() as i64;
+    let server_id = guild_id.get() as i64;
+
     // Ensure the chosen book is already in favorites for this server
     let affected = sqlx::query!(
         "UPDATE user_favorite_books
@@ -170,7 +219,7 @@ async fn set(
         let book_title = if let Some(t) = selected_title.clone() {
             t
         } else {
-            match google.get_volume(&target_volume_id).await {
+            match google.get_volume(target_volume_id).await {
                 Ok(vol) => vol.get_title(),
                 Err(_) => "That book".to_string(),
             }
@@ -266,3 +315,155 @@ async fn remove(ctx: Context<'_>) -> Result<(), Error> {
     }
     Ok(())
 }
+
+#[poise::command(
+    slash_command,
+    subcommands("author_set", "author_remove"),
+    guild_only,
+    description_localized("en-US", "Manage your #1 favorite author for this server"),
+    user_cooldown = 10
+)]
+async fn author(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Set #1 favorite author for this server
+#[poise::command(
+    slash_command,
+    rename = "set",
+    guild_only,
+    description_localized("en-US", "Set your #1 favorite author for this server")
+)]
+async fn author_set(
+    ctx: Context<'_>,
+    #[description = "One of your favorite authors"]
+    #[autocomplete = "autocomplete_favorite_author"]
+    name: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+    let pool = &ctx.data().database;
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+    let guild_name = get_guild_name(&ctx).await;
+    let user_id = ctx.author().id.get() as i64;
+    let server_id = guild_id.get() as i64;
+
+    let favorites = sqlx::query!(
+        "SELECT author_name, is_number_one FROM user_favorite_authors
+         WHERE user_id = $1 AND server_id = $2",
+        user_id,
+        server_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    if favorites.is_empty() {
+        let embed = CreateEmbed::default()
+            .title("❌ No Favorite Authors")
+            .description("You don't have any favorite authors in this server yet. Add one with `/favorite author add` first.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    if favorites.iter().any(|favorite| favorite.is_number_one) {
+        let embed = CreateEmbed::default()
+            .title("❌ Already Have #1 Author")
+            .description("You already have a #1 favorite author for this server. Use `/numberone author remove` first.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let Some(author_name) = favorites
+        .into_iter()
+        .map(|favorite| favorite.author_name)
+        .find(|favorite| author_key(favorite) == author_key(&name))
+    else {
+        let embed = CreateEmbed::default()
+            .title("❌ Author Not in Favorites")
+            .description(format!(
+                "{} isn't one of your favorite authors for this server. Add them with `/favorite author add` first.",
+                name.trim()
+            ))
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+
+    sqlx::query!(
+        "UPDATE user_favorite_authors
+         SET is_number_one = TRUE
+         WHERE user_id = $1 AND server_id = $2 AND author_name = $3",
+        user_id,
+        server_id,
+        author_name
+    )
+    .execute(pool)
+    .await?;
+
+    let embed = CreateEmbed::default()
+        .title("⭐ #1 Favorite Author Set")
+        .description(format!(
+            "Set {} as your #1 favorite author in {}!",
+            author_name, guild_name
+        ))
+        .color(0xB76E79);
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Remove #1 favorite author for this server
+#[poise::command(
+    slash_command,
+    rename = "remove",
+    guild_only,
+    description_localized("en-US", "Remove your #1 favorite author for this server")
+)]
+async fn author_remove(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.defer().await?;
+    let pool = &ctx.data().database;
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+    let guild_name = get_guild_name(&ctx).await;
+
+    let affected = sqlx::query!(
+        "UPDATE user_favorite_authors
+         SET is_number_one = FALSE
+         WHERE user_id = $1 AND server_id = $2 AND is_number_one",
+        ctx.author().id.get() as i64,
+        guild_id.get() as i64
+    )
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    let embed = if affected == 0 {
+        CreateEmbed::default()
+            .title("❌ No #1 Author Set")
+            .description("You don't have a #1 favorite author set for this server.")
+            .color(0xB76E79)
+    } else {
+        CreateEmbed::default()
+            .title("✅ #1 Author Removed")
+            .description(format!(
+                "Removed your ⭐ #1 favorite author in {}.",
+                guild_name
+            ))
+            .color(0xB76E79)
+    };
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
@@ -0,0 +1,440 @@
+use crate::commands::user::progress::{
+    PROGRESS_HIDDEN_MESSAGE, progress_text_is_allowed_in_channel,
+};
+use crate::google_books::Volume;
+use crate::maturity_check::{
+    can_display_mature_content, check_volume_maturity, current_channel_is_nsfw,
+    server_maturity_enabled,
+};
+use crate::util::{
+    EMBED_FIELD_LIMIT, embed_author_with_icon, get_guild_name, join_lines_capped, list_clubs,
+    truncate_with_ellipsis,
+};
+use crate::{types::Context, types::Error};
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter, User};
+use std::collections::HashMap;
+
+/// How many favorites and reading list books the profile lists before summarizing the rest.
+const PROFILE_LIST_LIMIT: usize = 5;
+/// Progress notes are shortened so one club per line fits in the "Currently Reading" field.
+const PROGRESS_SNIPPET_LENGTH: usize = 120;
+
+/// The member's current and best runs of consecutive club books they finished, given each
+/// book's outcome newest first.
+pub(crate) fn participation_streaks(finished_newest_first: &[bool]) -> (usize, usize) {
+    let current = finished_newest_first
+        .iter()
+        .take_while(|finished| **finished)
+        .count();
+    let mut best = 0;
+    let mut run = 0;
+    for finished in finished_newest_first {
+        run = if *finished { run + 1 } else { 0 };
+        best = best.max(run);
+    }
+    (current, best)
+}
+
+fn book_line(volumes: &HashMap<String, Volume>, volume_id: &str) -> String {
+    match volumes.get(volume_id) {
+        Some(volume) => format!(
+            "**{}** by {}",
+            volume.get_title(),
+            volume.get_authors_string()
+        ),
+        None => format!("Book ({})", volume_id),
+    }
+}
+
+fn list_field(lines: Vec<String>, total: usize, empty: &str) -> String {
+    if lines.is_empty() {
+        return empty.to_string();
+    }
+    join_lines_capped(&lines, total, EMBED_FIELD_LIMIT).0
+}
+
+#[poise::command(
+    slash_command,
+    guild_only,
+    description_localized("en-US", "Show a member's book club profile for this server"),
+    user_cooldown = 10
+)]
+pub async fn profile(
+    ctx: Context<'_>,
+    #[description = "User whose profile to show (defaults to you)"] user: Option<User>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let pool = &ctx.data().database;
+    let google_books = &ctx.data().google_books;
+    let target_user = user.as_ref().unwrap_or_else(|| ctx.author());
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+    let server_id = guild_id.get() as i64;
+    let user_id = target_user.id.get() as i64;
+
+    let favorites = sqlx::query!(
+        r#"
+        SELECT volume_id, is_number_one
+        FROM user_favorite_books
+        WHERE user_id = $1 AND server_id = $2
+        ORDER BY is_number_one DESC, added_at DESC
+        "#,
+        user_id,
+        server_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let reading_list = sqlx::query!(
+        r#"
+        SELECT volume_id
+        FROM user_reading_list
+        WHERE user_id = $1 AND server_id = $2
+        ORDER BY added_at DESC
+        "#,
+        user_id,
+        server_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let favorite_authors = sqlx::query!(
+        r#"
+        SELECT author_name, is_number_one
+        FROM user_favorite_authors
+        WHERE user_id = $1 AND server_id = $2
+        ORDER BY is_number_one DESC, added_at DESC
+        "#,
+        user_id,
+        server_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let current_progress = sqlx::query!(
+        r#"
+        SELECT scb.club_id, scb.volume_id, urp.progress_text, urp.dnf_at IS NOT NULL AS "dnf!"
+        FROM server_current_book scb
+        JOIN server_clubs sc ON sc.club_id = scb.club_id
+        JOIN user_reading_progress urp ON urp.club_id = scb.club_id
+            AND urp.user_id = $1 AND urp.volume_id = scb.volume_id
+        WHERE sc.server_id = $2
+        ORDER BY sc.is_primary DESC, sc.name
+        "#,
+        user_id,
+        server_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let ratings = sqlx::query!(
+        r#"
+        SELECT COUNT(*) AS "count!", AVG(ubr.rating)::FLOAT8 AS average
+        FROM user_book_ratings ubr
+        JOIN server_completed_books scb ON scb.completed_id = ubr.completed_id
+        JOIN server_clubs sc ON sc.club_id = scb.club_id
+        WHERE ubr.user_id = $1 AND sc.server_id = $2
+        "#,
+        user_id,
+        server_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let personal_ratings = sqlx::query!(
+        r#"
+        SELECT COUNT(*) AS "count!", AVG(rating)::FLOAT8 AS average
+        FROM user_personal_ratings
+        WHERE user_id = $1 AND server_id = $2
+        "#,
+        user_id,
+        server_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    // Picks the club finished plus any that are being read right now
+    let picks_selected = sqlx::query_scalar!(
+        r#"
+        SELECT
+            (SELECT COUNT(*) FROM server_completed_books scb
+             JOIN server_clubs sc ON sc.club_id = scb.club_id
+             WHERE sc.server_id = $2 AND scb.suggested_by_user_id = $1)
+          + (SELECT COUNT(*) FROM server_current_book scb
+             JOIN server_clubs sc ON sc.club_id = scb.club_id
+             WHERE sc.server_id = $2 AND scb.suggested_by_user_id = $1)
+            AS "count!"
+        "#,
+        user_id,
+        server_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    // Books finished before participation was tracked can't say who read along, so skip them
+    let finished_history = sqlx::query_scalar!(
+        r#"
+        SELECT COALESCE(cbp.status = 'finished', FALSE) AS "finished!"
+        FROM server_completed_books scb
+        JOIN server_clubs sc ON sc.club_id = scb.club_id
+        LEFT JOIN completed_book_participants cbp
+            ON cbp.completed_id = scb.completed_id AND cbp.user_id = $1
+        WHERE sc.server_id = $2
+          AND EXISTS (
+              SELECT 1 FROM completed_book_participants tracked
+              WHERE tracked.completed_id = scb.completed_id
+          )
+        ORDER BY scb.completed_at DESC
+        "#,
+        user_id,
+        server_id
+    )
+    .fetch_all(pool)
+    .await?;
+    let (current_streak, best_streak) = participation_streaks(&finished_history);
+
+    let mut volume_ids: Vec<String> = Vec::new();
+    for volume_id in favorites
+        .iter()
+        .map(|row| &row.volume_id)
+        .chain(reading_list.iter().map(|row| &row.volume_id))
+        .chain(current_progress.iter().map(|row| &row.volume_id))
+    {
+        if !volume_ids.contains(volume_id) {
+            volume_ids.push(volume_id.clone());
+        }
+    }
+
+    // Books that fail to fetch stay in the lists, like in /favorite view
+    let mut volumes: HashMap<String, Volume> = HashMap::new();
+    let mut hidden: Vec<String> = Vec::new();
+    for (id, result) in volume_ids
+        .iter()
+        .zip(google_books.get_volumes_batch(&volume_ids).await)
+    {
+        if let Ok(volume) = result {
+            if check_volume_maturity(&ctx, pool, &volume).await? {
+                volumes.insert(id.clone(), volume);
+            } else {
+                hidden.push(id.clone());
+            }
+        }
+    }
+    let is_visible = |volume_id: &String| !hidden.contains(volume_id);
+
+    let number_one = favorites
+        .iter()
+        .find(|row| row.is_number_one && is_visible(&row.volume_id));
+    let other_favorites: Vec<&String> = favorites
+        .iter()
+        .filter(|row| !row.is_number_one && is_visible(&row.volume_id))
+        .map(|row| &row.volume_id)
+        .collect();
+    let visible_reading_list: Vec<&String> = reading_list
+        .iter()
+        .map(|row| &row.volume_id)
+        .filter(|volume_id| is_visible(volume_id))
+        .collect();
+
+    let clubs = list_clubs(pool, server_id).await?;
+    let club_suffix = |club_id: i32| {
+        if clubs.len() > 1 {
+            clubs
+                .iter()
+                .find(|club| club.club_id == club_id)
+                .map(|club| format!(" ({})", club.name))
+                .unwrap_or_default()
+        } else {
+            String::new()
+        }
+    };
+    let allow_unrestricted_sexual = can_display_mature_content(&ctx, pool).await?;
+    let progress_lines: Vec<String> = current_progress
+        .iter()
+        .filter(|row| is_visible(&row.volume_id))
+        .map(|row| {
+            let status = if row.dnf {
+                "🚫 Did not finish".to_string()
+            } else {
+                match row.progress_text.as_deref() {
+                    Some(text)
+                        if progress_text_is_allowed_in_channel(text, allow_unrestricted_sexual) =>
+                    {
+                        truncate_with_ellipsis(text, PROGRESS_SNIPPET_LENGTH)
+                    }
+                    Some(_) => PROGRESS_HIDDEN_MESSAGE.to_string(),
+                    None => "No progress shared yet".to_string(),
+                }
+            };
+            format!(
+                "{}{} — {}",
+                book_line(&volumes, &row.volume_id),
+                club_suffix(row.club_id),
+                status
+            )
+        })
+        .collect();
+
+    let mut embed = CreateEmbed::default()
+        .author(embed_author_with_icon(
+            format!("{}'s Book Club Profile", target_user.name),
+            Some(target_user.face()),
+        ))
+        .color(0xB76E79);
+
+    if let Some(url) = number_one
+        .and_then(|row| volumes.get(&row.volume_id))
+        .and_then(|volume| volume.get_thumbnail_url())
+    {
+        embed = embed.thumbnail(url);
+    }
+
+    if !hidden.is_empty() {
+        let mut tips: Vec<&str> = Vec::new();
+        if !server_maturity_enabled(&ctx, pool).await? {
+            tips.push("enable mature content with `/config mature enable`");
+        }
+        if !current_channel_is_nsfw(&ctx).await? {
+            tips.push("use this command in an NSFW channel");
+        }
+        let mut message = String::from("Some mature books are hidden from this profile.");
+        if !tips.is_empty() {
+            message.push_str(" To view them, ");
+            message.push_str(&tips.join(" and "));
+            message.push('.');
+        }
+        embed = embed.description(message);
+    }
+
+    embed = embed
+        .field(
+            "⭐ #1 Book",
+            number_one
+                .map(|row| book_line(&volumes, &row.volume_id))
+                .unwrap_or_else(|| "Not chosen yet".to_string()),
+            false,
+        )
+        .field(
+            "Favorites",
+            list_field(
+                other_favorites
+                    .iter()
+                    .take(PROFILE_LIST_LIMIT)
+                    .map(|volume_id| format!("• {}", book_line(&volumes, volume_id)))
+                    .collect(),
+                other_favorites.len(),
+                "No other favorites yet",
+            ),
+            false,
+        )
+        .field(
+            "Favorite Authors",
+            list_field(
+                favorite_authors
+                    .iter()
+                    .map(|row| {
+                        let bullet = if row.is_number_one { "⭐" } else { "•" };
+                        format!("{} {}", bullet, row.author_name)
+                    })
+                    .collect(),
+                favorite_authors.len(),
+                "No favorite authors yet",
+            ),
+            false,
+        )
+        .field(
+            "Reading List",
+            list_field(
+                visible_reading_list
+                    .iter()
+                    .take(PROFILE_LIST_LIMIT)
+                    .map(|volume_id| format!("• {}", book_line(&volumes, volume_id)))
+                    .collect(),
+                visible_reading_list.len(),
+                "Nothing on their list yet",
+            ),
+            false,
+        )
+        .field(
+            "Currently Reading",
+            list_field(progress_lines, 0, "Not tracking a club book right now"),
+            false,
+        )
+        .field(
+            "Ratings",
+            match ratings.average {
+                Some(average) if ratings.count > 0 => {
+                    format!("{} ({:.2}/5 average)", ratings.count, average)
+                }
+                _ => "None yet".to_string(),
+            },
+            true,
+        )
+        .field(
+            "Personal Ratings",
+            match personal_ratings.average {
+                Some(average) if personal_ratings.count > 0 => {
+                    format!("{} ({:.2}/5 average)", personal_ratings.count, average)
+                }
+                _ => "None yet".to_string(),
+            },
+            true,
+        )
+        .field("Picks Selected", picks_selected.to_string(), true)
+        .field(
+            "Reading Streak",
+            format!(
+                "{} book{} (best: {})",
+                current_streak,
+                if current_streak == 1 { "" } else { "s" },
+                best_streak
+            ),
+            true,
+        )
+        .footer(CreateEmbedFooter::new(format!(
+            "{} • Powered by Google Books API",
+            get_guild_name(&ctx).await
+        )));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{list_field, participation_streaks};
+    use crate::util::EMBED_FIELD_LIMIT;
+
+    #[test]
+    fn streaks_count_consecutive_finished_books() {
+        assert_eq!(
+            participation_streaks(&[true, true, false, true, true, true, false]),
+            (2, 3)
+        );
+        assert_eq!(participation_streaks(&[false, true]), (0, 1));
+        assert_eq!(participation_streaks(&[]), (0, 0));
+    }
+
+    #[test]
+    fn list_fields_fit_in_an_embed_field() {
+        let lines: Vec<String> = (0..8)
+            .map(|club| {
+                format!(
+                    "**Book {club}** by Someone (Club {club}) — {}",
+                    "p".repeat(280)
+                )
+            })
+            .collect();
+        let text = list_field(lines, 0, "empty");
+        assert!(text.len() <= EMBED_FIELD_LIMIT, "{} bytes", text.len());
+        assert!(text.ends_with("more"));
+        assert_eq!(list_field(Vec::new(), 0, "empty"), "empty");
+    }
+}
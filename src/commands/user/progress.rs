@@ -1,5 +1,5 @@
 use crate::maturity_check::can_display_mature_content;
-use crate::util::{embed_author_with_icon, get_guild_name};
+use crate::util::{autocomplete_club, embed_author_with_icon, get_guild_name, resolve_club};
 use crate::*;
 use crate::{types::Context, types::Error};
 use linkify::{LinkFinder, LinkKind};
@@ -18,6 +18,7 @@ struct ProgressEntry {
     username: String,
     progress_text: Option<String>,
     updated_at: Option<DateTime<Utc>>,
+    dnf: bool,
 }
 
 const PROGRESS_PAGE_SIZE: usize = 5;
@@ -44,9 +45,15 @@ fn build_progress_page_embed(
             .map(|dt| dt.format("%Y-%m-%d %H:%M UTC").to_string())
             .unwrap_or_else(|| "Unknown".to_string());
 
+        let dnf = if entry.dnf {
+            " 🚫 Did not finish"
+        } else {
+            ""
+        };
+
         description.push_str(&format!(
-            "**{}** (<@{}>)\n{}\n_Last updated: {}_\n\n",
-            entry.username, entry.user_id, progress_text, updated_at
+            "**{}** (<@{}>){}\n{}\n_Last updated: {}_\n\n",
+            entry.username, entry.user_id, dnf, progress_text, updated_at
         ));
     }
 
@@ -94,7 +101,7 @@ fn pagination_components(current_page: usize, total_pages: usize) -> Vec<CreateA
     vec![CreateActionRow::Buttons(vec![prev_button, next_button])]
 }
 
-async fn is_progress_banned(
+pub(crate) async fn is_progress_banned(
     pool: &sqlx::PgPool,
     server_id: i64,
     user_id: i64,
@@ -165,10 +172,43 @@ pub(crate) fn progress_text_is_allowed_in_channel(
     )
 }
 
-fn validate_progress_text(text: &str, allow_unrestricted_sexual: bool) -> Result<String, String> {
+pub(crate) fn validate_progress_text(
+    text: &str,
+    allow_unrestricted_sexual: bool,
+) -> Result<String, String> {
+    validate_member_text(
+        text,
+        "progress update",
+        "progress updates",
+        280,
+        allow_unrestricted_sexual,
+    )
+}
+
+fn capitalize_first(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Shared checks for free text members post through the bot (progress updates, queue
+/// pitches). `noun` and `noun_plural` only phrase the error messages.
+pub(crate) fn validate_member_text(
+    text: &str,
+    noun: &str,
+    noun_plural: &str,
+    max_len: usize,
+    allow_unrestricted_sexual: bool,
+) -> Result<String, String> {
     // Character limit check
-    if text.len() > 280 {
-        return Err("Progress update must be 280 characters or less.".to_string());
+    if text.len() > max_len {
+        return Err(format!(
+            "{} must be {} characters or less.",
+            capitalize_first(noun),
+            max_len
+        ));
     }
 
     // Check for inappropriate content using rustrict (allow profanity but block slurs and severe content)
@@ -185,15 +225,16 @@ fn validate_progress_text(text: &str, allow_unrestricted_sexual: bool) -> Result
             analysis.is(Type::PROFANE),
         )
     {
-        return Err(
-            "Your progress update contains sexual content that can't be shared in this channel."
-                .to_string(),
-        );
+        return Err(format!(
+            "Your {} contains sexual content that can't be shared in this channel.",
+            noun
+        ));
     }
     if contains_severely_mean || contains_severely_offensive {
-        return Err(
-            "Your progress update contains slurs or other disallowed language.".to_string(),
-        );
+        return Err(format!(
+            "Your {} contains slurs or other disallowed language.",
+            noun
+        ));
     }
 
     // Check for URLs and emails using linkify
@@ -205,10 +246,16 @@ fn validate_progress_text(text: &str, allow_unrestricted_sexual: bool) -> Result
         let has_email = links.iter().any(|link| link.kind() == &LinkKind::Email);
 
         if has_url {
-            return Err("Links and URLs are not allowed in progress updates.".to_string());
+            return Err(format!(
+                "Links and URLs are not allowed in {}.",
+                noun_plural
+            ));
         }
         if has_email {
-            return Err("Email addresses are not allowed in progress updates.".to_string());
+            return Err(format!(
+                "Email addresses are not allowed in {}.",
+                noun_plural
+            ));
         }
     }
 
@@ -220,9 +267,10 @@ fn validate_progress_text(text: &str, allow_unrestricted_sexual: bool) -> Result
         || text.contains("@here")
         || text.contains("<a:")
     {
-        return Err(
-            "Pings, mentions, and custom emojis are not allowed in progress updates.".to_string(),
-        );
+        return Err(format!(
+            "Pings, mentions, and custom emojis are not allowed in {}.",
+            noun_plural
+        ));
     }
 
     // Check for file references or attachments indicators
@@ -235,7 +283,10 @@ fn validate_progress_text(text: &str, allow_unrestricted_sexual: bool) -> Result
         .iter()
         .any(|indicator| text.to_lowercase().contains(indicator))
     {
-        return Err("File references are not allowed in progress updates.".to_string());
+        return Err(format!(
+            "File references are not allowed in {}.",
+            noun_plural
+        ));
     }
 
     // Sanitize input - trim and clean up problematic characters
@@ -256,14 +307,18 @@ fn validate_progress_text(text: &str, allow_unrestricted_sexual: bool) -> Result
 
     // Ensure it's not empty after sanitization
     if sanitized.is_empty() {
-        return Err(
-            "Progress update cannot be empty after removing invalid characters.".to_string(),
-        );
+        return Err(format!(
+            "{} cannot be empty after removing invalid characters.",
+            capitalize_first(noun)
+        ));
     }
 
     // Additional length check after sanitization
-    if sanitized.len() > 280 {
-        return Err("Progress update is too long after processing.".to_string());
+    if sanitized.len() > max_len {
+        return Err(format!(
+            "{} is too long after processing.",
+            capitalize_first(noun)
+        ));
     }
 
     Ok(sanitized)
@@ -278,6 +333,9 @@ async fn update(
     ctx: Context<'_>,
     #[description = "Your progress update (e.g., 'Chapter 5', 'Page 123', '50% done')"]
     progress_text: String,
+    #[description = "Club to update your progress in (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
 ) -> Result<(), Error> {
     ctx.defer().await?;
 
@@ -308,6 +366,9 @@ async fn update(
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
     };
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
     if is_progress_banned(pool, guild_id.get() as i64, ctx.author().id.get() as i64).await? {
         let embed = CreateEmbed::default()
             .title("🚫 Progress Command Disabled")
@@ -327,9 +388,9 @@ async fn update(
         SELECT 
             volume_id
         FROM server_current_book
-        WHERE server_id = $1
+        WHERE club_id = $1
         "#,
-        guild_id.get() as i64
+        club.club_id
     )
     .fetch_optional(pool)
     .await?;
@@ -343,12 +404,12 @@ async fn update(
             };
 
             sqlx::query!(
-                "INSERT INTO user_reading_progress (user_id, server_id, volume_id, progress_text)
+                "INSERT INTO user_reading_progress (user_id, club_id, volume_id, progress_text)
                  VALUES ($1, $2, $3, $4)
-                 ON CONFLICT (user_id, server_id) 
-                 DO UPDATE SET volume_id = $3, progress_text = $4, updated_at = CURRENT_TIMESTAMP",
+                 ON CONFLICT (user_id, club_id) 
+                 DO UPDATE SET volume_id = $3, progress_text = $4, updated_at = CURRENT_TIMESTAMP, dnf_at = NULL",
                 ctx.author().id.get() as i64,
-                guild_id.get() as i64,
+                club.club_id,
                 book.volume_id,
                 sanitized_progress
             )
@@ -356,7 +417,7 @@ async fn update(
             .await?;
 
             let embed = CreateEmbed::default()
-                .title("✅ Progress Updated")
+                .title(format!("✅ Progress Updated{}", club.title_suffix()))
                 .field("Book", book_title, false)
                 .field("Your Progress", &sanitized_progress, false)
                 .color(0xB76E79)
@@ -367,7 +428,7 @@ async fn update(
         None => {
             let embed = CreateEmbed::default()
                 .title("No Current Book")
-                .description("There's no current book being read in this server. Ask an admin to select one from the queue!")
+                .description("There's no current book being read in this club. Ask an admin to select one from the queue!")
                 .color(0xB76E79)
                 .footer(CreateEmbedFooter::new("Powered by Google Books API"));
             ctx.send(poise::CreateReply::default().embed(embed)).await?;
@@ -382,7 +443,12 @@ async fn update(
     description_localized("en-US", "Clear your current reading progress"),
     user_cooldown = 10
 )]
-async fn clear(ctx: Context<'_>) -> Result<(), Error> {
+async fn clear(
+    ctx: Context<'_>,
+    #[description = "Club to clear your progress in (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
     ctx.defer().await?;
 
     let pool = &ctx.data().database;
@@ -395,6 +461,9 @@ async fn clear(ctx: Context<'_>) -> Result<(), Error> {
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
     };
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
     if is_progress_banned(pool, guild_id.get() as i64, ctx.author().id.get() as i64).await? {
         let embed = CreateEmbed::default()
             .title("🚫 Progress Command Disabled")
@@ -414,9 +483,9 @@ async fn clear(ctx: Context<'_>) -> Result<(), Error> {
         SELECT
             volume_id
         FROM server_current_book
-        WHERE server_id = $1
+        WHERE club_id = $1
         "#,
-        guild_id.get() as i64
+        club.club_id
     )
     .fetch_optional(pool)
     .await?;
@@ -425,7 +494,7 @@ async fn clear(ctx: Context<'_>) -> Result<(), Error> {
         let embed = CreateEmbed::default()
             .title("No Current Book")
             .description(
-                "There's no current book being read in this server, so there's no progress to clear.",
+                "There's no current book being read in this club, so there's no progress to clear.",
             )
             .color(0xB76E79)
             .footer(CreateEmbedFooter::new("Powered by Google Books API"));
@@ -440,9 +509,9 @@ async fn clear(ctx: Context<'_>) -> Result<(), Error> {
     };
 
     let result = sqlx::query!(
-        "DELETE FROM user_reading_progress WHERE user_id = $1 AND server_id = $2 AND volume_id = $3",
+        "DELETE FROM user_reading_progress WHERE user_id = $1 AND club_id = $2 AND volume_id = $3",
         ctx.author().id.get() as i64,
-        guild_id.get() as i64,
+        club.club_id,
         volume_id
     )
     .execute(pool)
@@ -481,6 +550,9 @@ async fn clear(ctx: Context<'_>) -> Result<(), Error> {
 async fn view(
     ctx: Context<'_>,
     #[description = "User to check progress for (leave empty to view everyone)"] user: Option<User>,
+    #[description = "Club to view progress for (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
 ) -> Result<(), Error> {
     ctx.defer().await?;
 
@@ -494,6 +566,9 @@ async fn view(
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
     };
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
     if is_progress_banned(pool, guild_id.get() as i64, ctx.author().id.get() as i64).await? {
         let embed = CreateEmbed::default()
             .title("🚫 Progress Command Disabled")
@@ -510,14 +585,15 @@ async fn view(
             SELECT
                 scb.volume_id,
                 urp.progress_text,
-                urp.updated_at
+                urp.updated_at,
+                urp.dnf_at
             FROM server_current_book scb
-            LEFT JOIN user_reading_progress urp ON urp.server_id = scb.server_id
+            LEFT JOIN user_reading_progress urp ON urp.club_id = scb.club_id
                 AND urp.user_id = $1 AND urp.volume_id = scb.volume_id
-            WHERE scb.server_id = $2
+            WHERE scb.club_id = $2
             "#,
             target_user.id.get() as i64,
-            guild_id.get() as i64
+            club.club_id
         )
         .fetch_optional(pool)
         .await?;
@@ -537,6 +613,10 @@ async fn view(
                     .field("Current Book", book_title, false)
                     .color(0xB76E79);
 
+                if record.dnf_at.is_some() {
+                    embed = embed.field("Status", "🚫 Did not finish", false);
+                }
+
                 if let Some(progress) = record.progress_text.as_ref() {
                     if progress_text_is_allowed_in_channel(progress, allow_unrestricted_sexual) {
                         embed = embed.field("Progress", progress.as_str(), false);
@@ -566,7 +646,7 @@ async fn view(
                         Some(target_user.face()),
                     ))
                     .title("No Current Book")
-                    .description("There's no current book being read in this server.")
+                    .description("There's no current book being read in this club.")
                     .color(0xB76E79)
                     .footer(CreateEmbedFooter::new("Powered by Google Books API"));
                 ctx.send(poise::CreateReply::default().embed(embed)).await?;
@@ -581,9 +661,9 @@ async fn view(
         SELECT
             volume_id
         FROM server_current_book
-        WHERE server_id = $1
+        WHERE club_id = $1
         "#,
-        guild_id.get() as i64
+        club.club_id
     )
     .fetch_optional(pool)
     .await?;
@@ -591,7 +671,7 @@ async fn view(
     let Some(book) = current_book else {
         let embed = CreateEmbed::default()
             .title("No Current Book")
-            .description("There's no current book being read in this server.")
+            .description("There's no current book being read in this club.")
             .color(0xB76E79)
             .footer(CreateEmbedFooter::new("Powered by Google Books API"));
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
@@ -609,13 +689,14 @@ async fn view(
             urp.user_id,
             du.username,
             urp.progress_text,
-            urp.updated_at
+            urp.updated_at,
+            urp.dnf_at IS NOT NULL AS "dnf!"
         FROM user_reading_progress urp
         JOIN discord_users du ON du.user_id = urp.user_id
-        WHERE urp.server_id = $1 AND urp.volume_id = $2
+        WHERE urp.club_id = $1 AND urp.volume_id = $2
         ORDER BY urp.updated_at DESC NULLS LAST, urp.user_id
         "#,
-        guild_id.get() as i64,
+        club.club_id,
         book.volume_id
     )
     .fetch_all(pool)
@@ -623,7 +704,7 @@ async fn view(
 
     if progress_rows.is_empty() {
         let embed = CreateEmbed::default()
-            .title(format!("Reading Progress — {}", book_title))
+            .title(format!("Reading Progress — {}{}", book_title, club.title_suffix()))
             .description("No one has shared their progress yet. Use `/progress update` to get things started!")
             .color(0xB76E79)
             .footer(CreateEmbedFooter::new("Powered by Google Books API"));
@@ -644,6 +725,7 @@ async fn view(
                 }
             }),
             updated_at: row.updated_at,
+            dnf: row.dnf,
         })
         .collect();
 
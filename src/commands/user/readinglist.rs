@@ -1,19 +1,140 @@
+use crate::commands::user::progress::validate_member_text;
+use crate::google_books::Volume;
+use crate::list_notifications::ListNotifyMode;
 use crate::maturity_check::{
-    check_volume_maturity, create_mature_content_warning, current_channel_is_nsfw,
-    server_maturity_enabled,
+    can_display_mature_content, check_volume_maturity, create_mature_content_warning,
+    current_channel_is_nsfw, server_maturity_enabled,
 };
 use crate::types::QueryMode;
 use crate::util::{
-    detect_query_mode, embed_author_with_icon, get_guild_name, log_error_with_source,
-    normalize_isbn,
+    LIST_PAGE_SIZE, detect_query_mode, embed_author_with_icon, get_guild_name, list_limits,
+    list_page_count, log_error_with_source, normalize_isbn, send_paginated,
 };
 use crate::*;
 use crate::{types::Context, types::Error};
-use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter, User};
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter, GuildId, User};
+
+/// Shelf books land on when none is given; entries from before shelves existed live here too.
+pub(crate) const DEFAULT_SHELF: &str = "to-read";
+const MAX_SHELF_LENGTH: usize = 32;
+pub(crate) const MAX_TAGS: usize = 5;
+const MAX_TAG_LENGTH: usize = 24;
+
+/// Lowercases a shelf name and joins its words with hyphens, so "Summer Reads" and
+/// "summer-reads" are the same shelf. Returns `None` for empty or overlong names.
+pub(crate) fn normalize_shelf(name: &str) -> Option<String> {
+    let shelf = name
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .to_lowercase();
+    (!shelf.is_empty() && shelf.chars().count() <= MAX_SHELF_LENGTH).then_some(shelf)
+}
+
+/// Splits a comma-separated tag list into lowercase tags without duplicates, dropping a
+/// leading `#`. Returns `None` when there are too many tags or one is too long.
+pub(crate) fn parse_tags(input: &str) -> Option<Vec<String>> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in input.split(',') {
+        let tag = tag.trim().trim_start_matches('#').trim().to_lowercase();
+        if tag.is_empty() || tags.contains(&tag) {
+            continue;
+        }
+        if tag.chars().count() > MAX_TAG_LENGTH {
+            return None;
+        }
+        tags.push(tag);
+    }
+    (tags.len() <= MAX_TAGS).then_some(tags)
+}
+
+/// Runs a shelf name and tags through the same checks as progress updates and pitches, since
+/// other members see them in `/readinglist view`.
+fn validate_shelf_and_tags(
+    shelf: Option<&str>,
+    tags: &[String],
+    allow_unrestricted_sexual: bool,
+) -> Result<(), String> {
+    // Lengths are already limited in characters; these byte bounds never trip first
+    if let Some(shelf) = shelf {
+        validate_member_text(
+            shelf,
+            "shelf name",
+            "shelf names",
+            MAX_SHELF_LENGTH * 4,
+            allow_unrestricted_sexual,
+        )?;
+    }
+    for tag in tags {
+        validate_member_text(
+            tag,
+            "tag",
+            "tags",
+            MAX_TAG_LENGTH * 4,
+            allow_unrestricted_sexual,
+        )?;
+    }
+    Ok(())
+}
+
+fn shelf_and_tags_line(shelf: &str, tags: &[String]) -> String {
+    if tags.is_empty() {
+        format!("📚 {}", shelf)
+    } else {
+        format!("📚 {} • 🏷️ {}", shelf, tags.join(", "))
+    }
+}
+
+async fn invalid_shelf_or_tags(ctx: &Context<'_>) -> Result<(), Error> {
+    let embed = CreateEmbed::default()
+        .title("❌ Invalid Shelf or Tags")
+        .description(format!(
+            "Shelf names can be up to {} characters. Separate tags with commas; you can use up to {} tags of {} characters each.",
+            MAX_SHELF_LENGTH, MAX_TAGS, MAX_TAG_LENGTH
+        ))
+        .color(0xB76E79);
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+async fn rejected_shelf_or_tags(ctx: &Context<'_>, reason: String) -> Result<(), Error> {
+    let embed = CreateEmbed::default()
+        .title("❌ Invalid Shelf or Tags")
+        .description(reason)
+        .color(0xB76E79);
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// The invoking member's shelves in this server, with the default shelf always offered.
+async fn autocomplete_shelf(ctx: Context<'_>, partial: &str) -> Vec<String> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Vec::new();
+    };
+    let partial = partial.to_lowercase();
+
+    let mut shelves = sqlx::query_scalar!(
+        "SELECT DISTINCT shelf FROM user_reading_list
+         WHERE user_id = $1 AND server_id = $2
+         ORDER BY shelf",
+        ctx.author().id.get() as i64,
+        guild_id.get() as i64
+    )
+    .fetch_all(&ctx.data().database)
+    .await
+    .unwrap_or_default();
+    if !shelves.iter().any(|shelf| shelf == DEFAULT_SHELF) {
+        shelves.insert(0, DEFAULT_SHELF.to_string());
+    }
+    shelves
+        .into_iter()
+        .filter(|shelf| shelf.contains(&partial))
+        .collect()
+}
 
 #[poise::command(
     slash_command,
-    subcommands("view", "add", "remove"),
+    subcommands("view", "add", "remove", "move_book", "tag", "notify"),
     guild_only,
     description_localized("en-US", "Manage your reading list for this server"),
     user_cooldown = 10
@@ -32,6 +153,10 @@ pub async fn readinglist(_ctx: Context<'_>) -> Result<(), Error> {
 async fn view(
     ctx: Context<'_>,
     #[description = "User to check reading list for (defaults to you)"] user: Option<User>,
+    #[description = "Only show this shelf"]
+    #[autocomplete = "autocomplete_shelf"]
+    shelf: Option<String>,
+    #[description = "Only show books with this tag"] tag: Option<String>,
 ) -> Result<(), Error> {
     ctx.defer().await?;
 
@@ -48,26 +173,70 @@ async fn view(
     };
     let guild_name = get_guild_name(&ctx).await;
 
+    let shelf = shelf.as_deref().and_then(normalize_shelf);
+    let tag = tag
+        .as_deref()
+        .map(|tag| tag.trim().trim_start_matches('#').trim().to_lowercase())
+        .filter(|tag| !tag.is_empty());
+
     let reading_list = sqlx::query!(
         r#"
         SELECT 
             volume_id,
+            shelf,
+            tags,
             added_at
         FROM user_reading_list
         WHERE user_id = $1 AND server_id = $2
+          AND ($3::TEXT IS NULL OR shelf = $3)
+          AND ($4::TEXT IS NULL OR $4 = ANY(tags))
         ORDER BY added_at DESC
-        LIMIT 5
+        "#,
+        target_user.id.get() as i64,
+        guild_id.get() as i64,
+        shelf,
+        tag
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let shelf_counts = sqlx::query!(
+        r#"
+        SELECT shelf, COUNT(*) AS "count!"
+        FROM user_reading_list
+        WHERE user_id = $1 AND server_id = $2
+        GROUP BY shelf
+        ORDER BY shelf
         "#,
         target_user.id.get() as i64,
         guild_id.get() as i64
     )
     .fetch_all(pool)
     .await?;
+    let shelves_line = format!(
+        "Shelves: {}",
+        shelf_counts
+            .iter()
+            .map(|row| format!("{} ({})", row.shelf, row.count))
+            .collect::<Vec<_>>()
+            .join(" • ")
+    );
+    let filter_label = match (&shelf, &tag) {
+        (Some(shelf), Some(tag)) => format!(" — {} #{}", shelf, tag),
+        (Some(shelf), None) => format!(" — {}", shelf),
+        (None, Some(tag)) => format!(" — #{}", tag),
+        (None, None) => String::new(),
+    };
 
-    let footer_base = format!("{} • Max: 5 per server", guild_name);
+    let limit = list_limits(pool, guild_id.get() as i64, ctx.data().list_limit_cap)
+        .await?
+        .reading_list;
+    let footer_base = format!("{} • Max: {} per server", guild_name, limit);
 
     if reading_list.is_empty() {
-        let msg = if target_user.id == ctx.author().id {
+        let msg = if !filter_label.is_empty() && !shelf_counts.is_empty() {
+            &format!("No books match that filter.\n{}", shelves_line)
+        } else if target_user.id == ctx.author().id {
             "Your reading list in this server is empty! Add books with `/readinglist add`."
         } else {
             &format!(
@@ -78,7 +247,7 @@ async fn view(
 
         let embed = CreateEmbed::default()
             .author(embed_author_with_icon(
-                format!("{}'s Reading List", target_user.name),
+                format!("{}'s Reading List{}", target_user.name, filter_label),
                 Some(target_user.face()),
             ))
             .description(msg)
@@ -120,7 +289,7 @@ async fn view(
     if filtered_list.is_empty() && mature_count > 0 {
         let embed = CreateEmbed::default()
             .author(embed_author_with_icon(
-                format!("{}'s Reading List", target_user.name),
+                format!("{}'s Reading List{}", target_user.name, filter_label),
                 Some(target_user.face()),
             ))
             .description(format!(
@@ -138,13 +307,7 @@ async fn view(
         return Ok(());
     }
 
-    let mut embed = CreateEmbed::default()
-        .author(embed_author_with_icon(
-            format!("{}'s Reading List", target_user.name),
-            Some(target_user.face()),
-        ))
-        .color(0xB76E79);
-
+    let mut notice = shelves_line;
     if mature_count > 0 {
         let mut tips: Vec<&str> = Vec::new();
         if !maturity_enabled {
@@ -162,36 +325,64 @@ async fn view(
             message.push('.');
         }
 
-        embed = embed.description(message);
+        notice.push_str("\n\n");
+        notice.push_str(&message);
     }
 
-    for (i, (book, volume_opt)) in filtered_list.iter().enumerate() {
-        match volume_opt {
-            Some(volume) => {
-                let title = volume.get_title();
-                let authors = volume.get_authors_string();
-                embed = embed.field(
-                    format!("{}. {}", i + 1, title),
-                    format!("by {}", authors),
-                    false,
-                );
-            }
-            None => {
-                // Fallback if API fails
-                embed = embed.field(
-                    format!("{}. [Book data unavailable]", i + 1),
-                    format!("Volume ID: {}", book.volume_id),
-                    false,
-                );
+    let total_pages = list_page_count(filtered_list.len());
+    let mut pages = Vec::new();
+    for (page, chunk) in filtered_list.chunks(LIST_PAGE_SIZE).enumerate() {
+        let mut embed = CreateEmbed::default()
+            .author(embed_author_with_icon(
+                format!("{}'s Reading List{}", target_user.name, filter_label),
+                Some(target_user.face()),
+            ))
+            .description(&notice)
+            .color(0xB76E79);
+
+        for (offset, (book, volume_opt)) in chunk.iter().enumerate() {
+            let number = page * LIST_PAGE_SIZE + offset + 1;
+            match volume_opt {
+                Some(volume) => {
+                    let title = volume.get_title();
+                    let authors = volume.get_authors_string();
+                    embed = embed.field(
+                        format!("{}. {}", number, title),
+                        format!(
+                            "by {}\n{}",
+                            authors,
+                            shelf_and_tags_line(&book.shelf, &book.tags)
+                        ),
+                        false,
+                    );
+                }
+                None => {
+                    // Fallback if API fails
+                    embed = embed.field(
+                        format!("{}. [Book data unavailable]", number),
+                        format!(
+                            "Volume ID: {}\n{}",
+                            book.volume_id,
+                            shelf_and_tags_line(&book.shelf, &book.tags)
+                        ),
+                        false,
+                    );
+                }
             }
         }
-    }
 
-    embed = embed.footer(CreateEmbedFooter::new(format!(
-        "{} • Powered by Google Books API",
-        footer_base
-    )));
-    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        let page_label = if total_pages > 1 {
+            format!(" • Page {}/{}", page + 1, total_pages)
+        } else {
+            String::new()
+        };
+        embed = embed.footer(CreateEmbedFooter::new(format!(
+            "{}{} • Powered by Google Books API",
+            footer_base, page_label
+        )));
+        pages.push(embed);
+    }
+    send_paginated(&ctx, pages).await?;
 
     Ok(())
 }
@@ -206,42 +397,37 @@ async fn add(
     ctx: Context<'_>,
     #[description = "Title or ISBN-10/13"] title_or_isbn: String,
     #[description = "Author name (optional; used when title)"] author: Option<String>,
+    #[description = "Shelf to put it on (defaults to to-read)"]
+    #[autocomplete = "autocomplete_shelf"]
+    shelf: Option<String>,
+    #[description = "Comma-separated tags, e.g. \"cozy, re-read\""] tags: Option<String>,
 ) -> Result<(), Error> {
     ctx.defer().await?;
 
-    let pool = &ctx.data().database;
+    let shelf = match shelf.as_deref() {
+        Some(name) => normalize_shelf(name),
+        None => Some(DEFAULT_SHELF.to_string()),
+    };
+    let tags = match tags.as_deref() {
+        Some(input) => parse_tags(input),
+        None => Some(Vec::new()),
+    };
+    let (Some(shelf), Some(tags)) = (shelf, tags) else {
+        return invalid_shelf_or_tags(&ctx).await;
+    };
+
     let google_books = &ctx.data().google_books;
-    let Some(guild_id) = ctx.guild_id() else {
+    if ctx.guild_id().is_none() {
         let embed = CreateEmbed::default()
             .title("❌ Error")
             .description("This command must be used in a server.")
             .color(0xB76E79);
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
-    };
-    let guild_name = get_guild_name(&ctx).await;
-
-    ensure_user_exists(pool, ctx.author()).await?;
-    ensure_server_exists(pool, guild_id, &guild_name).await?;
-
-    // Check current count for this server
-    let count = sqlx::query_scalar!(
-        "SELECT COUNT(*) FROM user_reading_list WHERE user_id = $1 AND server_id = $2",
-        ctx.author().id.get() as i64,
-        guild_id.get() as i64
-    )
-    .fetch_one(pool)
-    .await?
-    .unwrap_or(0);
-
-    if count >= 5 {
-        let embed = CreateEmbed::default()
-            .title("❌ Reading List Full")
-            .description("You've reached the maximum of 5 books in your reading list for this server. Please remove some books before adding more.")
-            .color(0xB76E79)
-            .footer(CreateEmbedFooter::new("Powered by Google Books API"));
-        ctx.send(poise::CreateReply::default().embed(embed)).await?;
-        return Ok(());
+    }
+    let allow_unrestricted_sexual = can_display_mature_content(&ctx, &ctx.data().database).await?;
+    if let Err(reason) = validate_shelf_and_tags(Some(&shelf), &tags, allow_unrestricted_sexual) {
+        return rejected_shelf_or_tags(&ctx, reason).await;
     }
 
     // Search for the book
@@ -301,7 +487,51 @@ async fn add(
         }
     };
 
-    if !check_volume_maturity(&ctx, pool, &book).await? {
+    add_volume_to_reading_list(ctx, &book, &shelf, &tags, result_bool).await
+}
+
+/// Puts a book the member already picked on their reading list, applying the list limit and
+/// maturity check. Shared by `/readinglist add` and the "Add to reading list" book action.
+pub(crate) async fn add_volume_to_reading_list(
+    ctx: Context<'_>,
+    book: &Volume,
+    shelf: &str,
+    tags: &[String],
+    multiple_found: bool,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+    let pool = &ctx.data().database;
+    let guild_name = get_guild_name(&ctx).await;
+
+    ensure_user_exists(pool, ctx.author()).await?;
+    ensure_server_exists(pool, guild_id, &guild_name).await?;
+
+    // Check current count for this server
+    let count = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM user_reading_list WHERE user_id = $1 AND server_id = $2",
+        ctx.author().id.get() as i64,
+        guild_id.get() as i64
+    )
+    .fetch_one(pool)
+    .await?
+    .unwrap_or(0);
+
+    let limit = list_limits(pool, guild_id.get() as i64, ctx.data().list_limit_cap)
+        .await?
+        .reading_list as i64;
+    if count >= limit {
+        let embed = CreateEmbed::default()
+            .title("❌ Reading List Full")
+            .description(format!("You've reached the maximum of {} books in your reading list for this server. Please remove some books before adding more.", limit))
+            .color(0xB76E79)
+            .footer(CreateEmbedFooter::new("Powered by Google Books API"));
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    if !check_volume_maturity(&ctx, pool, book).await? {
         let is_nsfw = current_channel_is_nsfw(&ctx).await?;
         let maturity_enabled = server_maturity_enabled(&ctx, pool).await?;
         let embed =
@@ -314,12 +544,14 @@ async fn add(
     let book_authors = book.get_authors_string();
 
     let result = sqlx::query!(
-        "INSERT INTO user_reading_list (user_id, server_id, volume_id)
-         VALUES ($1, $2, $3)
+        "INSERT INTO user_reading_list (user_id, server_id, volume_id, shelf, tags)
+         VALUES ($1, $2, $3, $4, $5)
          ON CONFLICT DO NOTHING",
         ctx.author().id.get() as i64,
         guild_id.get() as i64,
-        &book.id
+        &book.id,
+        shelf,
+        tags
     )
     .execute(pool)
     .await;
@@ -332,10 +564,18 @@ async fn add(
                     .field("Title", &book_title, false)
                     .field("Authors", &book_authors, false)
                     .field("Server", guild_name.clone(), true)
-                    .field("Reading List Count", format!("{}/5", count + 1), true)
+                    .field(
+                        "Reading List Count",
+                        format!("{}/{}", count + 1, limit),
+                        true,
+                    )
+                    .field("Shelf", shelf, true)
                     .color(0xB76E79);
+                if !tags.is_empty() {
+                    embed = embed.field("Tags", tags.join(", "), true);
+                }
 
-                let footer_text = if result_bool {
+                let footer_text = if multiple_found {
                     "Multiple books found. • Powered by Google Books API"
                 } else {
                     "Powered by Google Books API"
@@ -347,12 +587,12 @@ async fn add(
                 let mut embed = CreateEmbed::default()
                     .title("Already in Reading List")
                     .description(format!(
-                        "*{}* by {} is already in your reading list for this server!",
+                        "*{}* by {} is already in your reading list for this server! Use `/readinglist move` or `/readinglist tag` to change its shelf or tags.",
                         book_title, book_authors
                     ))
                     .color(0xB76E79);
 
-                let footer_text = if result_bool {
+                let footer_text = if multiple_found {
                     "Multiple books found • Powered by Google Books API"
                 } else {
                     "Powered by Google Books API"
@@ -375,40 +615,22 @@ async fn add(
     Ok(())
 }
 
-#[poise::command(
-    slash_command,
-    rename = "remove",
-    guild_only,
-    description_localized("en-US", "Remove a book from your reading list in this server"),
-    user_cooldown = 10
-)]
-async fn remove(
-    ctx: Context<'_>,
-    #[description = "Title or ISBN-10/13"] title_or_isbn: String,
-    #[description = "Author name (optional; used when title)"] author: Option<String>,
-) -> Result<(), Error> {
-    ctx.defer().await?;
-
+/// Finds the book a member means by title or ISBN, preferring one on their reading list when a
+/// title matches several. Replies with an error embed and returns `None` when there's no match.
+async fn find_listed_book(
+    ctx: &Context<'_>,
+    guild_id: GuildId,
+    title_or_isbn: &str,
+    author: Option<&str>,
+) -> Result<Option<Volume>, Error> {
     let pool = &ctx.data().database;
     let google_books = &ctx.data().google_books;
-    let Some(guild_id) = ctx.guild_id() else {
-        let embed = CreateEmbed::default()
-            .title("❌ Error")
-            .description("This command must be used in a server.")
-            .color(0xB76E79);
-        ctx.send(poise::CreateReply::default().embed(embed)).await?;
-        return Ok(());
-    };
-    let guild_name = get_guild_name(&ctx).await;
-
-    ensure_user_exists(pool, ctx.author()).await?;
-    ensure_server_exists(pool, guild_id, &guild_name).await?;
 
-    let chosen = detect_query_mode(&title_or_isbn);
+    let chosen = detect_query_mode(title_or_isbn);
 
     let book = match chosen {
         QueryMode::Isbn => {
-            let isbn = normalize_isbn(&title_or_isbn);
+            let isbn = normalize_isbn(title_or_isbn);
             if isbn.len() != 10 && isbn.len() != 13 {
                 let embed = CreateEmbed::default()
                     .title("❌ Invalid ISBN")
@@ -419,7 +641,7 @@ async fn remove(
                     .color(0xB76E79)
                     .footer(CreateEmbedFooter::new("Powered by Google Books API"));
                 ctx.send(poise::CreateReply::default().embed(embed)).await?;
-                return Ok(());
+                return Ok(None);
             }
             match google_books.search_by_isbn(&isbn).await? {
                 Some(b) => b,
@@ -430,13 +652,13 @@ async fn remove(
                         .color(0xB76E79)
                         .footer(CreateEmbedFooter::new("Searched via Google Books API"));
                     ctx.send(poise::CreateReply::default().embed(embed)).await?;
-                    return Ok(());
+                    return Ok(None);
                 }
             }
         }
         QueryMode::Title => {
             let mut results = google_books
-                .search_books(&title_or_isbn, author.as_deref(), Some(10))
+                .search_books(title_or_isbn, author, Some(10))
                 .await?;
             if results.is_empty() {
                 let embed = CreateEmbed::default()
@@ -445,7 +667,7 @@ async fn remove(
                     .color(0xB76E79)
                     .footer(CreateEmbedFooter::new("Searched via Google Books API"));
                 ctx.send(poise::CreateReply::default().embed(embed)).await?;
-                return Ok(());
+                return Ok(None);
             }
 
             if results.len() > 1 {
@@ -471,7 +693,7 @@ async fn remove(
                         .color(0xB76E79)
                         .footer(CreateEmbedFooter::new("Powered by Google Books API"));
                     ctx.send(poise::CreateReply::default().embed(embed)).await?;
-                    return Ok(());
+                    return Ok(None);
                 }
             } else {
                 results.remove(0)
@@ -479,6 +701,42 @@ async fn remove(
         }
     };
 
+    Ok(Some(book))
+}
+
+#[poise::command(
+    slash_command,
+    rename = "remove",
+    guild_only,
+    description_localized("en-US", "Remove a book from your reading list in this server"),
+    user_cooldown = 10
+)]
+async fn remove(
+    ctx: Context<'_>,
+    #[description = "Title or ISBN-10/13"] title_or_isbn: String,
+    #[description = "Author name (optional; used when title)"] author: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let pool = &ctx.data().database;
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+    let guild_name = get_guild_name(&ctx).await;
+
+    ensure_user_exists(pool, ctx.author()).await?;
+    ensure_server_exists(pool, guild_id, &guild_name).await?;
+
+    let Some(book) = find_listed_book(&ctx, guild_id, &title_or_isbn, author.as_deref()).await?
+    else {
+        return Ok(());
+    };
+
     let result = sqlx::query!(
         "DELETE FROM user_reading_list 
          WHERE user_id = $1 AND server_id = $2 AND volume_id = $3",
@@ -509,3 +767,284 @@ async fn remove(
 
     Ok(())
 }
+
+#[poise::command(
+    slash_command,
+    rename = "move",
+    guild_only,
+    description_localized("en-US", "Move a book on your reading list to another shelf"),
+    user_cooldown = 10
+)]
+async fn move_book(
+    ctx: Context<'_>,
+    #[description = "Title or ISBN-10/13"] title_or_isbn: String,
+    #[description = "Shelf to move it to"]
+    #[autocomplete = "autocomplete_shelf"]
+    shelf: String,
+    #[description = "Author name (optional; used when title)"] author: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let pool = &ctx.data().database;
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+    let Some(shelf) = normalize_shelf(&shelf) else {
+        return invalid_shelf_or_tags(&ctx).await;
+    };
+    let allow_unrestricted_sexual = can_display_mature_content(&ctx, pool).await?;
+    if let Err(reason) = validate_shelf_and_tags(Some(&shelf), &[], allow_unrestricted_sexual) {
+        return rejected_shelf_or_tags(&ctx, reason).await;
+    }
+
+    let Some(book) = find_listed_book(&ctx, guild_id, &title_or_isbn, author.as_deref()).await?
+    else {
+        return Ok(());
+    };
+
+    let previous = sqlx::query_scalar!(
+        "SELECT shelf FROM user_reading_list
+         WHERE user_id = $1 AND server_id = $2 AND volume_id = $3",
+        ctx.author().id.get() as i64,
+        guild_id.get() as i64,
+        &book.id
+    )
+    .fetch_optional(pool)
+    .await?;
+    let Some(previous) = previous else {
+        let embed = CreateEmbed::default()
+            .title("❌ Book Not in List")
+            .description("This book was not in your reading list for this server.")
+            .color(0xB76E79)
+            .footer(CreateEmbedFooter::new("Powered by Google Books API"));
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+
+    sqlx::query!(
+        "UPDATE user_reading_list SET shelf = $4
+         WHERE user_id = $1 AND server_id = $2 AND volume_id = $3",
+        ctx.author().id.get() as i64,
+        guild_id.get() as i64,
+        &book.id,
+        shelf
+    )
+    .execute(pool)
+    .await?;
+
+    let embed = CreateEmbed::default()
+        .title("✅ Book Moved")
+        .description(format!(
+            "Moved *{}* from **{}** to **{}**.",
+            book.get_title(),
+            previous,
+            shelf
+        ))
+        .color(0xB76E79)
+        .footer(CreateEmbedFooter::new("Powered by Google Books API"));
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    rename = "tag",
+    guild_only,
+    description_localized("en-US", "Set the tags on a book in your reading list"),
+    user_cooldown = 10
+)]
+async fn tag(
+    ctx: Context<'_>,
+    #[description = "Title or ISBN-10/13"] title_or_isbn: String,
+    #[description = "Comma-separated tags; leave empty to clear them"] tags: Option<String>,
+    #[description = "Author name (optional; used when title)"] author: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let pool = &ctx.data().database;
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+    let Some(tags) = parse_tags(tags.as_deref().unwrap_or_default()) else {
+        return invalid_shelf_or_tags(&ctx).await;
+    };
+    let allow_unrestricted_sexual = can_display_mature_content(&ctx, pool).await?;
+    if let Err(reason) = validate_shelf_and_tags(None, &tags, allow_unrestricted_sexual) {
+        return rejected_shelf_or_tags(&ctx, reason).await;
+    }
+
+    let Some(book) = find_listed_book(&ctx, guild_id, &title_or_isbn, author.as_deref()).await?
+    else {
+        return Ok(());
+    };
+
+    let result = sqlx::query!(
+        "UPDATE user_reading_list SET tags = $4
+         WHERE user_id = $1 AND server_id = $2 AND volume_id = $3",
+        ctx.author().id.get() as i64,
+        guild_id.get() as i64,
+        &book.id,
+        &tags
+    )
+    .execute(pool)
+    .await?;
+
+    let embed = if result.rows_affected() == 0 {
+        CreateEmbed::default()
+            .title("❌ Book Not in List")
+            .description("This book was not in your reading list for this server.")
+    } else if tags.is_empty() {
+        CreateEmbed::default()
+            .title("✅ Tags Cleared")
+            .description(format!("Removed all tags from *{}*.", book.get_title()))
+    } else {
+        CreateEmbed::default()
+            .title("✅ Tags Updated")
+            .description(format!(
+                "*{}* is now tagged 🏷️ {}.",
+                book.get_title(),
+                tags.join(", ")
+            ))
+    }
+    .color(0xB76E79)
+    .footer(CreateEmbedFooter::new("Powered by Google Books API"));
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    rename = "notify",
+    guild_only,
+    description_localized(
+        "en-US",
+        "Hear when a book on your reading list or favorites is queued, in a poll or selected",
+    ),
+    user_cooldown = 10
+)]
+async fn notify(
+    ctx: Context<'_>,
+    #[description = "DM me, mention me in the club channel, or turn notifications off"]
+    mode: ListNotifyMode,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let pool = &ctx.data().database;
+    let Some(guild_id) = ctx.guild_id() else {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+    let guild_name = get_guild_name(&ctx).await;
+
+    let embed = match mode.delivery() {
+        Some(delivery) => {
+            ensure_user_exists(pool, ctx.author()).await?;
+            ensure_server_exists(pool, guild_id, &guild_name).await?;
+
+            sqlx::query!(
+                "INSERT INTO user_list_notifications (user_id, server_id, delivery)
+                 VALUES ($1, $2, $3)
+                 ON CONFLICT (user_id, server_id)
+                 DO UPDATE SET delivery = EXCLUDED.delivery, updated_at = CURRENT_TIMESTAMP",
+                ctx.author().id.get() as i64,
+                guild_id.get() as i64,
+                delivery
+            )
+            .execute(pool)
+            .await?;
+
+            let how = if mode == ListNotifyMode::Dm {
+                "I'll DM you"
+            } else {
+                "I'll mention you in the club's announcement channel"
+            };
+            CreateEmbed::default()
+                .title("🔔 List Notifications On")
+                .description(format!(
+                    "{} when a book on your reading list or favorites in **{}** is added to a queue, included in a selection poll or selected. Books you suggest yourself are skipped when queued.",
+                    how, guild_name
+                ))
+        }
+        None => {
+            sqlx::query!(
+                "DELETE FROM user_list_notifications WHERE user_id = $1 AND server_id = $2",
+                ctx.author().id.get() as i64,
+                guild_id.get() as i64
+            )
+            .execute(pool)
+            .await?;
+
+            CreateEmbed::default()
+                .title("🔕 List Notifications Off")
+                .description(format!(
+                    "You won't hear about books from your lists in **{}** anymore.",
+                    guild_name
+                ))
+        }
+    }
+    .color(0xB76E79);
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize_shelf, parse_tags, validate_shelf_and_tags};
+
+    #[test]
+    fn shelf_names_are_normalized() {
+        assert_eq!(
+            normalize_shelf("  Summer   Reads "),
+            Some("summer-reads".to_string())
+        );
+        assert_eq!(normalize_shelf("to-read"), Some("to-read".to_string()));
+        assert_eq!(normalize_shelf("   "), None);
+        assert_eq!(normalize_shelf(&"x".repeat(33)), None);
+    }
+
+    #[test]
+    fn tags_are_split_deduplicated_and_bounded() {
+        assert_eq!(
+            parse_tags("Cozy, #re-read,cozy, ,  Book Club "),
+            Some(vec![
+                "cozy".to_string(),
+                "re-read".to_string(),
+                "book club".to_string()
+            ])
+        );
+        assert_eq!(parse_tags(""), Some(Vec::new()));
+        assert_eq!(parse_tags("a,b,c,d,e,f"), None);
+        assert_eq!(parse_tags(&"x".repeat(25)), None);
+    }
+
+    #[test]
+    fn shelves_and_tags_get_member_text_checks() {
+        let tags = vec!["cozy".to_string(), "re-read".to_string()];
+        assert_eq!(
+            validate_shelf_and_tags(Some("summer-reads"), &tags, false),
+            Ok(())
+        );
+        assert!(validate_shelf_and_tags(Some("<@123456789>"), &[], false).is_err());
+        assert!(
+            validate_shelf_and_tags(None, &["https://example.com".to_string()], false).is_err()
+        );
+        assert!(validate_shelf_and_tags(None, &["@everyone".to_string()], false).is_err());
+    }
+}
@@ -0,0 +1,403 @@
+use crate::util::{autocomplete_club, get_guild_name, resolve_club};
+use crate::*;
+use crate::{types::Context, types::Error};
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
+
+#[derive(poise::ChoiceParameter, Clone, Copy, Debug, PartialEq, Eq)]
+enum ReminderInterval {
+    #[name = "daily"]
+    Daily,
+    #[name = "weekly"]
+    Weekly,
+    #[name = "biweekly"]
+    Biweekly,
+}
+
+impl ReminderInterval {
+    fn days(self) -> i32 {
+        match self {
+            ReminderInterval::Daily => 1,
+            ReminderInterval::Weekly => 7,
+            ReminderInterval::Biweekly => 14,
+        }
+    }
+}
+
+fn describe_interval(days: i32) -> String {
+    match days {
+        1 => "daily".to_string(),
+        7 => "weekly".to_string(),
+        14 => "every two weeks".to_string(),
+        other => format!("every {} days", other),
+    }
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("progress", "stop", "pause", "resume", "quiet", "status"),
+    guild_only,
+    description_localized("en-US", "Get DM reminders to update your reading progress"),
+    user_cooldown = 10
+)]
+pub async fn remindme(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    description_localized(
+        "en-US",
+        "DM me when I haven't updated my progress in this server for a while",
+    ),
+    user_cooldown = 10
+)]
+async fn progress(
+    ctx: Context<'_>,
+    #[description = "How long without an update before I remind you"] interval: ReminderInterval,
+    #[description = "Club to remind you about (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    if ctx.guild_id().is_none() {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+    let guild_name = format!("{}{}", get_guild_name(&ctx).await, club.title_suffix());
+
+    ensure_user_exists(pool, ctx.author()).await?;
+
+    sqlx::query!(
+        "INSERT INTO user_progress_reminders (user_id, club_id, interval_days)
+         VALUES ($1, $2, $3)
+         ON CONFLICT (user_id, club_id)
+         DO UPDATE SET interval_days = $3",
+        ctx.author().id.get() as i64,
+        club.club_id,
+        interval.days()
+    )
+    .execute(pool)
+    .await?;
+
+    let paused = sqlx::query_scalar!(
+        "SELECT reminders_paused FROM user_reminder_settings WHERE user_id = $1",
+        ctx.author().id.get() as i64
+    )
+    .fetch_optional(pool)
+    .await?
+    .unwrap_or(false);
+
+    let mut description = format!(
+        "I'll DM you {} while **{}** is reading a book and you haven't updated your progress.\n\nMake sure your DMs are open so the reminders can reach you!",
+        describe_interval(interval.days()),
+        guild_name
+    );
+    if paused {
+        description.push_str(
+            "\n\n⏸️ Your reminders are currently paused everywhere. Use `/remindme resume` to start receiving them.",
+        );
+    }
+
+    let embed = CreateEmbed::default()
+        .title("⏰ Progress Reminders On")
+        .description(description)
+        .color(0xB76E79)
+        .footer(CreateEmbedFooter::new(
+            "Use /remindme quiet to set hours when I shouldn't DM you",
+        ));
+
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    description_localized("en-US", "Stop progress reminders for this server"),
+    user_cooldown = 10
+)]
+async fn stop(
+    ctx: Context<'_>,
+    #[description = "Club to stop reminders for (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    if ctx.guild_id().is_none() {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+
+    let result = sqlx::query!(
+        "DELETE FROM user_progress_reminders WHERE user_id = $1 AND club_id = $2",
+        ctx.author().id.get() as i64,
+        club.club_id
+    )
+    .execute(pool)
+    .await?;
+
+    let embed = if result.rows_affected() > 0 {
+        CreateEmbed::default()
+            .title("Progress Reminders Off")
+            .description("I won't send you progress reminders for this club anymore.")
+            .color(0xB76E79)
+    } else {
+        CreateEmbed::default()
+            .title("No Reminders Set")
+            .description(format!("You don't have progress reminders set up for this club. Use `/remindme progress{}` to turn them on.", club.command_option()))
+            .color(0xB76E79)
+    };
+
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+    Ok(())
+}
+
+async fn set_reminders_paused(ctx: Context<'_>, paused: bool) -> Result<(), Error> {
+    let pool = &ctx.data().database;
+
+    ensure_user_exists(pool, ctx.author()).await?;
+
+    sqlx::query!(
+        "INSERT INTO user_reminder_settings (user_id, reminders_paused)
+         VALUES ($1, $2)
+         ON CONFLICT (user_id)
+         DO UPDATE SET reminders_paused = $2, updated_at = CURRENT_TIMESTAMP",
+        ctx.author().id.get() as i64,
+        paused
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    description_localized("en-US", "Pause all progress reminders in every server"),
+    user_cooldown = 10
+)]
+async fn pause(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    set_reminders_paused(ctx, true).await?;
+
+    let embed = CreateEmbed::default()
+        .title("⏸️ Reminders Paused")
+        .description("I won't DM you any progress reminders, in any server, until you run `/remindme resume`. Your per-server settings are kept.")
+        .color(0xB76E79);
+
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    description_localized("en-US", "Resume progress reminders after pausing them"),
+    user_cooldown = 10
+)]
+async fn resume(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    set_reminders_paused(ctx, false).await?;
+
+    let embed = CreateEmbed::default()
+        .title("▶️ Reminders Resumed")
+        .description("Progress reminders are back on for every server where you've opted in.")
+        .color(0xB76E79);
+
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    description_localized(
+        "en-US",
+        "Set hours (UTC) when I shouldn't DM you; leave both empty to clear",
+    ),
+    user_cooldown = 10
+)]
+async fn quiet(
+    ctx: Context<'_>,
+    #[description = "Hour quiet time starts (0–23, UTC)"]
+    #[min = 0]
+    #[max = 23]
+    start_hour: Option<u8>,
+    #[description = "Hour quiet time ends (0–23, UTC)"]
+    #[min = 0]
+    #[max = 23]
+    end_hour: Option<u8>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let (start, end) = match (start_hour, end_hour) {
+        (Some(start), Some(end)) if start <= 23 && end <= 23 && start != end => {
+            (Some(start as i16), Some(end as i16))
+        }
+        (None, None) => (None, None),
+        _ => {
+            let embed = CreateEmbed::default()
+                .title("❌ Invalid Quiet Hours")
+                .description("Provide both a start and end hour between 0 and 23 (and not the same hour), or leave both empty to clear quiet hours.")
+                .color(0xB76E79);
+            ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let pool = &ctx.data().database;
+    ensure_user_exists(pool, ctx.author()).await?;
+
+    sqlx::query!(
+        "INSERT INTO user_reminder_settings (user_id, quiet_hours_start, quiet_hours_end)
+         VALUES ($1, $2, $3)
+         ON CONFLICT (user_id)
+         DO UPDATE SET quiet_hours_start = $2, quiet_hours_end = $3, updated_at = CURRENT_TIMESTAMP",
+        ctx.author().id.get() as i64,
+        start,
+        end
+    )
+    .execute(pool)
+    .await?;
+
+    let embed = match (start, end) {
+        (Some(start), Some(end)) => CreateEmbed::default()
+            .title("🌙 Quiet Hours Set")
+            .description(format!(
+                "I won't send reminders between **{:02}:00** and **{:02}:00 UTC**.",
+                start, end
+            ))
+            .color(0xB76E79),
+        _ => CreateEmbed::default()
+            .title("Quiet Hours Cleared")
+            .description("Reminders can arrive at any time of day.")
+            .color(0xB76E79),
+    };
+
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    description_localized("en-US", "View your progress reminder settings"),
+    user_cooldown = 10
+)]
+async fn status(
+    ctx: Context<'_>,
+    #[description = "Club to check (defaults to the main club)"]
+    #[autocomplete = "autocomplete_club"]
+    club: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    if ctx.guild_id().is_none() {
+        let embed = CreateEmbed::default()
+            .title("❌ Error")
+            .description("This command must be used in a server.")
+            .color(0xB76E79);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+    let Some(club) = resolve_club(&ctx, club.as_deref()).await? else {
+        return Ok(());
+    };
+
+    let pool = &ctx.data().database;
+
+    let reminder = sqlx::query!(
+        "SELECT interval_days, last_reminded_at FROM user_progress_reminders
+         WHERE user_id = $1 AND club_id = $2",
+        ctx.author().id.get() as i64,
+        club.club_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let settings = sqlx::query!(
+        "SELECT reminders_paused, quiet_hours_start, quiet_hours_end
+         FROM user_reminder_settings WHERE user_id = $1",
+        ctx.author().id.get() as i64
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let this_server = match reminder.as_ref() {
+        Some(row) => {
+            let mut text = format!("On — {}", describe_interval(row.interval_days));
+            if let Some(last) = row.last_reminded_at {
+                text.push_str(&format!(
+                    "\nLast reminder: {}",
+                    last.format("%Y-%m-%d %H:%M UTC")
+                ));
+            }
+            text
+        }
+        None => format!(
+            "Off — use `/remindme progress{}` to turn on",
+            club.command_option()
+        ),
+    };
+
+    let paused = settings
+        .as_ref()
+        .map(|s| s.reminders_paused)
+        .unwrap_or(false);
+
+    let quiet_hours = match settings
+        .as_ref()
+        .map(|s| (s.quiet_hours_start, s.quiet_hours_end))
+    {
+        Some((Some(start), Some(end))) => format!("{:02}:00 – {:02}:00 UTC", start, end),
+        _ => "None".to_string(),
+    };
+
+    let embed = CreateEmbed::default()
+        .title("⏰ Reminder Settings")
+        .field(
+            if club.is_primary {
+                "This Server".to_string()
+            } else {
+                format!("This Club ({})", club.name)
+            },
+            this_server,
+            false,
+        )
+        .field(
+            "All Servers",
+            if paused { "Paused" } else { "Active" },
+            true,
+        )
+        .field("Quiet Hours", quiet_hours, true)
+        .color(0xB76E79)
+        .footer(CreateEmbedFooter::new("Fumiko Book Club Bot"));
+
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+    Ok(())
+}
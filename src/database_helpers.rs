@@ -5,6 +5,12 @@ pub struct BookSelectionResult {
     pub volume_id: Option<String>,
     pub suggested_by_user_id: Option<i64>,
     pub suggested_by_username: Option<String>,
+    pub pitch: Option<String>,
+    pub success: Option<bool>,
+    pub error_message: Option<String>,
+}
+
+pub struct QueueEditResult {
     pub success: Option<bool>,
     pub error_message: Option<String>,
 }
@@ -19,7 +25,7 @@ pub struct BookCompletionResult {
 
 pub async fn select_book_transactional(
     pool: &PgPool,
-    guild_id: i64,
+    club_id: i32,
     volume_id: &str,
     announcement_channel_id: Option<i64>,
     deadline: Option<sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>>,
@@ -31,11 +37,12 @@ pub async fn select_book_transactional(
             volume_id,
             suggested_by_user_id,
             suggested_by_username,
+            pitch,
             success,
             error_message
         FROM select_book_from_queue_tx($1, $2, $3, $4)
         "#,
-        guild_id,
+        club_id,
         volume_id,
         announcement_channel_id,
         deadline
@@ -55,7 +62,7 @@ pub async fn select_book_transactional(
 
 pub async fn finish_book_transactional(
     pool: &PgPool,
-    guild_id: i64,
+    club_id: i32,
 ) -> Result<BookCompletionResult, Error> {
     let result = sqlx::query_as!(
         BookCompletionResult,
@@ -68,7 +75,7 @@ pub async fn finish_book_transactional(
             f.error_message
         FROM finish_current_book_tx($1) AS f
         "#,
-        guild_id
+        club_id
     )
     .fetch_one(pool)
     .await?;
@@ -82,3 +89,52 @@ pub async fn finish_book_transactional(
 
     Ok(result)
 }
+
+pub async fn move_queue_book_transactional(
+    pool: &PgPool,
+    club_id: i32,
+    from_position: i32,
+    to_position: i32,
+) -> Result<(), Error> {
+    let result = sqlx::query_as!(
+        QueueEditResult,
+        "SELECT success, error_message FROM move_queue_book_tx($1, $2, $3)",
+        club_id,
+        from_position,
+        to_position
+    )
+    .fetch_one(pool)
+    .await?;
+
+    queue_edit_outcome(result)
+}
+
+pub async fn swap_queue_books_transactional(
+    pool: &PgPool,
+    club_id: i32,
+    first_position: i32,
+    second_position: i32,
+) -> Result<(), Error> {
+    let result = sqlx::query_as!(
+        QueueEditResult,
+        "SELECT success, error_message FROM swap_queue_books_tx($1, $2, $3)",
+        club_id,
+        first_position,
+        second_position
+    )
+    .fetch_one(pool)
+    .await?;
+
+    queue_edit_outcome(result)
+}
+
+fn queue_edit_outcome(result: QueueEditResult) -> Result<(), Error> {
+    if !result.success.unwrap_or(false) {
+        return Err(result
+            .error_message
+            .unwrap_or_else(|| "Unknown error".to_string())
+            .into());
+    }
+
+    Ok(())
+}
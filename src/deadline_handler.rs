@@ -4,8 +4,9 @@ use crate::maturity_check::{
     channel_is_nsfw_http, create_mature_content_warning, server_maturity_enabled_by_id,
 };
 use crate::poll_handler;
+use crate::selection_poll_handler;
 use crate::types::Error;
-use crate::util::{format_deadline, log_error, log_error_with_source, pin_polls_enabled};
+use crate::util::{Club, format_deadline, log_error, log_error_with_source, pin_polls_enabled};
 use poise::serenity_prelude as serenity;
 use serenity::{CreateEmbed, CreateEmbedFooter, CreateMessage, CreatePoll, CreatePollAnswer};
 use sqlx::PgPool;
@@ -37,17 +38,20 @@ async fn process_deadlines(
     let rows = sqlx::query!(
         r#"
         SELECT
-            scb.server_id,
+            scb.club_id,
+            sc.server_id,
+            sc.name AS club_name,
+            sc.is_primary,
             scb.volume_id,
             scb.started_at,
             scb.deadline,
             scb.announcement_channel_id,
-            sbc.announcement_channel_id AS config_announcement_channel_id
+            sc.announcement_channel_id AS config_announcement_channel_id
         FROM server_current_book scb
-        JOIN server_bot_config sbc ON sbc.server_id = scb.server_id
+        JOIN server_clubs sc ON sc.club_id = scb.club_id
         WHERE scb.deadline IS NOT NULL
           AND scb.deadline <= NOW()
-          AND sbc.auto_complete_on_deadline
+          AND sc.auto_complete_on_deadline
         "#
     )
     .fetch_all(pool)
@@ -56,8 +60,14 @@ async fn process_deadlines(
     for row in rows {
         let server_id = row.server_id;
         let volume_id = row.volume_id.clone();
-
-        match finish_book_transactional(pool, server_id).await {
+        let club = Club {
+            club_id: row.club_id,
+            server_id,
+            name: row.club_name.clone(),
+            is_primary: row.is_primary,
+        };
+
+        match finish_book_transactional(pool, club.club_id).await {
             Ok(book_info) => {
                 let completed_id = match book_info.completed_id {
                     Some(id) => id,
@@ -75,7 +85,7 @@ async fn process_deadlines(
                 let duration_days = Utc::now().signed_duration_since(started_at).num_days();
 
                 let mut embed = CreateEmbed::default()
-                    .title("Deadline Reached!")
+                    .title(format!("Deadline Reached!{}", club.title_suffix()))
                     .field("Title", &book_title, false)
                     .field("Reading Duration", format!("{} days", duration_days.max(0)), true)
                     .description(
@@ -97,6 +107,16 @@ async fn process_deadlines(
                     .or(row.announcement_channel_id)
                     .map(|id| serenity::ChannelId::new(id as u64));
 
+                if let Err(err) = selection_poll_handler::schedule_auto_poll(
+                    pool,
+                    club.club_id,
+                    target_channel_id.map(|id| id.get() as i64),
+                )
+                .await
+                {
+                    log_error_with_source("Couldn't schedule automatic selection poll", &err);
+                }
+
                 let mut poll_message_id: Option<serenity::MessageId> = None;
                 let mut poll_channel_id: Option<i64> = None;
 
@@ -129,7 +149,7 @@ async fn process_deadlines(
                                     .expect("poll duration fits into std::time::Duration"),
                             );
 
-                        let should_pin_poll = pin_polls_enabled(pool, server_id).await?;
+                        let should_pin_poll = pin_polls_enabled(pool, club.club_id).await?;
 
                         match channel_id
                             .send_message(
@@ -178,7 +198,10 @@ async fn process_deadlines(
                                 .send_message(http, CreateMessage::new().embed(warning))
                                 .await;
 
-                            let notice = "A book deadline passed, but I couldn't create the rating poll because mature content isn't allowed here. Run `/finishbook` in an appropriate channel if you want to create the poll manually.";
+                            let notice = format!(
+                                "A book deadline passed, but I couldn't create the rating poll because mature content isn't allowed here. Run `/finishbook{}` in an appropriate channel if you want to create the poll manually.",
+                                club.command_option()
+                            );
                             let _ = channel_id.say(http, notice).await;
                         }
                     }
@@ -449,18 +449,17 @@ impl Volume {
     }
 
     // Return ISBN_13 or ISBN_10 if present.
-    // pub fn get_isbn(&self) -> Option<String> {
-    //     let ids = self.volume_info.industry_identifiers.as_ref()?;
-    //     ids.iter()
-    //         .find(|id| matches!(id.id_type.as_deref(), Some("ISBN_13")))
-    //         .and_then(|id| id.identifier.clone())
-    //         .or_else(|| {
-    //             ids.iter()
-    //                 .find(|id| matches!(id.id_type.as_deref(), Some("ISBN_10")))
-    //                 .and_then(|id| id.identifier.clone())
-    //         })
-    //         .or_else(|| ids.get(0).and_then(|id| id.identifier.clone()))
-    // }
+    pub fn get_isbn(&self) -> Option<String> {
+        let ids = self.volume_info.industry_identifiers.as_ref()?;
+        ids.iter()
+            .find(|id| matches!(id.id_type.as_deref(), Some("ISBN_13")))
+            .and_then(|id| id.identifier.clone())
+            .or_else(|| {
+                ids.iter()
+                    .find(|id| matches!(id.id_type.as_deref(), Some("ISBN_10")))
+                    .and_then(|id| id.identifier.clone())
+            })
+    }
 
     // info.rs uses `.join(", ")` on this
     pub fn get_categories(&self) -> Vec<String> {
@@ -0,0 +1,396 @@
+use crate::google_books_cache::CachedGoogleBooksClient;
+use crate::maturity_check::can_display_mature_content_event;
+use crate::types::Error;
+use crate::util::{
+    Club, EMBED_DESCRIPTION_LIMIT, MESSAGE_CONTENT_LIMIT, join_lines_capped, log_error_with_source,
+    truncate_with_ellipsis,
+};
+use poise::serenity_prelude as serenity;
+use serenity::{CreateAllowedMentions, CreateEmbed, CreateEmbedFooter, CreateMessage};
+use sqlx::PgPool;
+use std::collections::{BTreeMap, HashMap};
+
+/// Longest book title quoted in a notification.
+const NOTICE_TITLE_LENGTH: usize = 200;
+// "<@" + a 20-digit snowflake + ">" and the separating space
+const MENTION_LENGTH: usize = 24;
+
+/// How a member wants to hear about their listed books, chosen with `/readinglist notify`.
+#[derive(poise::ChoiceParameter, Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ListNotifyMode {
+    #[name = "dm"]
+    Dm,
+    #[name = "mention"]
+    Mention,
+    #[name = "off"]
+    Off,
+}
+
+impl ListNotifyMode {
+    /// Value stored in `user_list_notifications.delivery`; `None` means no row.
+    pub(crate) fn delivery(self) -> Option<&'static str> {
+        match self {
+            ListNotifyMode::Dm => Some("dm"),
+            ListNotifyMode::Mention => Some("mention"),
+            ListNotifyMode::Off => None,
+        }
+    }
+}
+
+/// What just happened to a book in a club.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ListEvent {
+    Queued,
+    InPoll,
+    Selected,
+}
+
+impl ListEvent {
+    fn headline(self) -> &'static str {
+        match self {
+            ListEvent::Queued => "was added to the queue",
+            ListEvent::InPoll => "is up for a vote in the selection poll",
+            ListEvent::Selected => "was selected as the current read",
+        }
+    }
+
+    fn dm_title(self) -> &'static str {
+        match self {
+            ListEvent::Queued => "🔔 A Book From Your List Was Queued",
+            ListEvent::InPoll => "🔔 A Book From Your List Is in the Poll",
+            ListEvent::Selected => "🔔 A Book From Your List Was Selected",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Watcher {
+    user_id: i64,
+    volume_id: String,
+    by_mention: bool,
+}
+
+/// Books each DM watcher should hear about, in the order the volumes were given.
+fn dm_batches(watchers: &[Watcher], volume_ids: &[String]) -> BTreeMap<i64, Vec<String>> {
+    let mut batches: BTreeMap<i64, Vec<String>> = BTreeMap::new();
+    for volume_id in volume_ids {
+        for watcher in watchers
+            .iter()
+            .filter(|w| !w.by_mention && &w.volume_id == volume_id)
+        {
+            batches
+                .entry(watcher.user_id)
+                .or_default()
+                .push(volume_id.clone());
+        }
+    }
+    batches
+}
+
+/// Members to mention for each book, in the order the volumes were given. Books nobody
+/// wants a mention for are left out.
+fn mention_batches(watchers: &[Watcher], volume_ids: &[String]) -> Vec<(String, Vec<i64>)> {
+    volume_ids
+        .iter()
+        .filter_map(|volume_id| {
+            let mut users: Vec<i64> = watchers
+                .iter()
+                .filter(|w| w.by_mention && &w.volume_id == volume_id)
+                .map(|w| w.user_id)
+                .collect();
+            users.sort_unstable();
+            users.dedup();
+            (!users.is_empty()).then(|| (volume_id.clone(), users))
+        })
+        .collect()
+}
+
+fn mention_line(event: ListEvent, title: &str, users: &[i64]) -> String {
+    let mentions = users
+        .iter()
+        .map(|id| format!("<@{}>", id))
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!(
+        "📚 {} — *{}* from your list {}!",
+        mentions,
+        title,
+        event.headline()
+    )
+}
+
+/// Packs mention lines into as few messages as fit Discord's content limit, splitting a book's
+/// members across lines when one line would be too long. Each message carries the members it
+/// mentions.
+fn mention_messages(event: ListEvent, books: &[(String, Vec<i64>)]) -> Vec<(String, Vec<i64>)> {
+    let mut messages = Vec::new();
+    let mut content = String::new();
+    let mut mentioned = Vec::new();
+    for (title, users) in books {
+        let title = truncate_with_ellipsis(title, NOTICE_TITLE_LENGTH);
+        let fixed = mention_line(event, &title, &[]).len();
+        let per_line = (MESSAGE_CONTENT_LIMIT.saturating_sub(fixed) / MENTION_LENGTH).max(1);
+        for chunk in users.chunks(per_line) {
+            let line = mention_line(event, &title, chunk);
+            if !content.is_empty() && content.len() + 1 + line.len() > MESSAGE_CONTENT_LIMIT {
+                messages.push((std::mem::take(&mut content), std::mem::take(&mut mentioned)));
+            }
+            if !content.is_empty() {
+                content.push('\n');
+            }
+            content.push_str(&line);
+            mentioned.extend_from_slice(chunk);
+        }
+    }
+    if !content.is_empty() {
+        messages.push((content, mentioned));
+    }
+    messages
+}
+
+/// A club event to tell watchers about. Mentions are collected into as few messages as fit in
+/// `channel_id`; `skip_user` (usually the suggester) is never notified.
+pub(crate) struct ListNotice<'a> {
+    pub club: &'a Club,
+    pub volume_ids: &'a [String],
+    pub event: ListEvent,
+    pub skip_user: Option<i64>,
+    pub channel_id: serenity::ChannelId,
+}
+
+/// Tells opted-in members that books on their reading list or favorites in this server were
+/// queued, put in a selection poll or selected, one DM per member or one channel message for
+/// everyone who asked for mentions. Failures are logged rather than returned so the
+/// triggering command or poll still completes.
+pub(crate) async fn notify_list_watchers(
+    http: &serenity::Http,
+    pool: &PgPool,
+    google_books: &CachedGoogleBooksClient,
+    notice: ListNotice<'_>,
+) {
+    if let Err(err) = send_notifications(http, pool, google_books, &notice).await {
+        log_error_with_source("Couldn't send list notifications", &err);
+    }
+}
+
+async fn send_notifications(
+    http: &serenity::Http,
+    pool: &PgPool,
+    google_books: &CachedGoogleBooksClient,
+    notice: &ListNotice<'_>,
+) -> Result<(), Error> {
+    let ListNotice {
+        club,
+        volume_ids,
+        event,
+        skip_user,
+        channel_id,
+    } = *notice;
+    if volume_ids.is_empty() {
+        return Ok(());
+    }
+
+    let watchers: Vec<Watcher> = sqlx::query!(
+        r#"
+        SELECT DISTINCT n.user_id, n.delivery, listed.volume_id AS "volume_id!"
+        FROM user_list_notifications n
+        JOIN (
+            SELECT user_id, server_id, volume_id FROM user_reading_list
+            UNION
+            SELECT user_id, server_id, volume_id FROM user_favorite_books
+        ) listed ON listed.user_id = n.user_id AND listed.server_id = n.server_id
+        WHERE n.server_id = $1
+          AND listed.volume_id = ANY($2)
+          AND n.user_id IS DISTINCT FROM $3
+        "#,
+        club.server_id,
+        volume_ids,
+        skip_user
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| Watcher {
+        user_id: row.user_id,
+        volume_id: row.volume_id,
+        by_mention: row.delivery == "mention",
+    })
+    .collect();
+
+    if watchers.is_empty() {
+        return Ok(());
+    }
+
+    let mut listed_ids: Vec<String> = watchers.iter().map(|w| w.volume_id.clone()).collect();
+    listed_ids.sort();
+    listed_ids.dedup();
+    let volumes: HashMap<String, (String, bool)> = listed_ids
+        .iter()
+        .zip(google_books.get_volumes_batch(&listed_ids).await)
+        .map(|(volume_id, volume)| {
+            let details = match volume {
+                Ok(volume) => (volume.get_title(), volume.is_mature()),
+                Err(_) => (format!("Book ({})", volume_id), false),
+            };
+            (volume_id.clone(), details)
+        })
+        .collect();
+    let title = |volume_id: &str| {
+        volumes
+            .get(volume_id)
+            .map(|(title, _)| title.clone())
+            .unwrap_or_else(|| format!("Book ({})", volume_id))
+    };
+
+    let server_name = sqlx::query_scalar!(
+        "SELECT server_name FROM discord_servers WHERE server_id = $1",
+        club.server_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .unwrap_or_else(|| "your server".to_string());
+    let club_label = format!("{}{}", server_name, club.title_suffix());
+
+    for (user_id, books) in dm_batches(&watchers, volume_ids) {
+        let mut description = format!("News from **{}**:\n", club_label);
+        let lines: Vec<String> = books
+            .iter()
+            .map(|volume_id| {
+                format!(
+                    "• *{}* {}",
+                    truncate_with_ellipsis(&title(volume_id), NOTICE_TITLE_LENGTH),
+                    event.headline()
+                )
+            })
+            .collect();
+        let (list, _) = join_lines_capped(
+            &lines,
+            lines.len(),
+            EMBED_DESCRIPTION_LIMIT - description.len(),
+        );
+        description.push_str(&list);
+
+        let embed = CreateEmbed::default()
+            .title(event.dm_title())
+            .description(description)
+            .color(0xB76E79)
+            .footer(CreateEmbedFooter::new(
+                "Use /readinglist notify off to stop these notifications",
+            ));
+
+        let user_id = serenity::UserId::new(user_id as u64);
+        let sent = match user_id.create_dm_channel(http).await {
+            Ok(channel) => channel
+                .send_message(http, CreateMessage::new().embed(embed))
+                .await
+                .map(|_| ()),
+            Err(err) => Err(err),
+        };
+        if let Err(err) = sent {
+            log_error_with_source("Couldn't send list notification DM", &err);
+        }
+    }
+
+    let mentions = mention_batches(&watchers, volume_ids);
+    if mentions.is_empty() {
+        return Ok(());
+    }
+
+    // Mature titles stay out of channels that couldn't show them
+    let allow_mature =
+        can_display_mature_content_event(http, pool, club.server_id, channel_id).await?;
+    let shown: Vec<(String, Vec<i64>)> = mentions
+        .into_iter()
+        .filter(|(volume_id, _)| {
+            allow_mature || !volumes.get(volume_id).is_some_and(|(_, mature)| *mature)
+        })
+        .map(|(volume_id, users)| (title(&volume_id), users))
+        .collect();
+
+    for (content, users) in mention_messages(event, &shown) {
+        let mentioned = users.into_iter().map(|id| serenity::UserId::new(id as u64));
+        if let Err(err) = channel_id
+            .send_message(
+                http,
+                CreateMessage::new()
+                    .content(content)
+                    .allowed_mentions(CreateAllowedMentions::new().users(mentioned)),
+            )
+            .await
+        {
+            log_error_with_source("Couldn't send list notification mentions", &err);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ListEvent, Watcher, dm_batches, mention_batches, mention_line, mention_messages};
+    use crate::util::MESSAGE_CONTENT_LIMIT;
+
+    fn watcher(user_id: i64, volume_id: &str, by_mention: bool) -> Watcher {
+        Watcher {
+            user_id,
+            volume_id: volume_id.to_string(),
+            by_mention,
+        }
+    }
+
+    #[test]
+    fn watchers_are_split_by_delivery_and_grouped() {
+        let volume_ids = vec!["b".to_string(), "a".to_string(), "c".to_string()];
+        let watchers = vec![
+            watcher(1, "a", false),
+            watcher(1, "b", false),
+            watcher(2, "a", true),
+            watcher(3, "a", true),
+            watcher(2, "c", true),
+        ];
+
+        let dms = dm_batches(&watchers, &volume_ids);
+        assert_eq!(dms.len(), 1);
+        assert_eq!(dms[&1], vec!["b".to_string(), "a".to_string()]);
+
+        assert_eq!(
+            mention_batches(&watchers, &volume_ids),
+            vec![("a".to_string(), vec![2, 3]), ("c".to_string(), vec![2]),]
+        );
+    }
+
+    #[test]
+    fn mention_line_names_members_and_event() {
+        assert_eq!(
+            mention_line(ListEvent::Selected, "Piranesi", &[1, 2]),
+            "📚 <@1> <@2> — *Piranesi* from your list was selected as the current read!"
+        );
+    }
+
+    #[test]
+    fn mention_messages_stay_under_the_content_limit() {
+        let books: Vec<(String, Vec<i64>)> = (0..10)
+            .map(|book| {
+                let users = (0..150)
+                    .map(|user| 100_000_000_000_000_000 + user)
+                    .collect();
+                (format!("Book {book} {}", "t".repeat(300)), users)
+            })
+            .collect();
+
+        let messages = mention_messages(ListEvent::InPoll, &books);
+        assert!(messages.len() > 1);
+        assert!(
+            messages
+                .iter()
+                .all(|(content, _)| content.len() <= MESSAGE_CONTENT_LIMIT)
+        );
+        let mentioned: usize = messages.iter().map(|(_, users)| users.len()).sum();
+        assert_eq!(mentioned, 10 * 150);
+        assert!(messages.iter().all(|(content, users)| {
+            users
+                .iter()
+                .all(|id| content.contains(&format!("<@{}>", id)))
+        }));
+    }
+}
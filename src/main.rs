@@ -1,15 +1,25 @@
 mod access_control;
 mod cache_warmer;
+mod charts;
+mod club_export;
 mod commands;
 mod database_helpers;
 mod deadline_handler;
 mod google_books;
 mod google_books_cache;
+mod list_notifications;
 mod maturity_check;
 mod poll_handler;
+mod queue_expiry_handler;
+mod reading_import;
+mod recommendation;
+mod reminder_handler;
 mod selection_poll_handler;
+mod selection_rules;
 mod types;
 mod util;
+mod weighted_selection;
+mod wrapped_handler;
 
 use dotenvy;
 use google_books_cache::CachedGoogleBooksClient;
@@ -25,6 +35,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     dotenvy::dotenv()?;
     let token = env::var("DISCORD_TOKEN").expect("Missing DISCORD_TOKEN in .env");
     let google_api_key = env::var("GOOGLE_BOOKS_API_KEY").ok(); // Optional API key
+    let list_limit_cap = env::var("MAX_LIST_LIMIT")
+        .ok()
+        .and_then(|value| value.parse::<i32>().ok())
+        .filter(|cap| *cap > 0)
+        .unwrap_or(DEFAULT_LIST_LIMIT_CAP);
 
     // Initialize database connection
     let database_url = env::var("DATABASE_URL").expect("missing DATABASE_URL");
@@ -96,7 +111,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             },
             ..Default::default()
         })
-        .setup(|ctx, _ready, framework| {
+        .setup(move |ctx, _ready, framework| {
             Box::pin(async move {
                 poise::builtins::register_globally(ctx, &framework.options().commands).await?;
                 let guild_cache = Arc::new(RwLock::new(
@@ -115,10 +130,29 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     google_books.clone(),
                 );
 
+                reminder_handler::spawn_progress_reminder_watcher(
+                    ctx.http.clone(),
+                    database.clone(),
+                    google_books.clone(),
+                );
+
+                queue_expiry_handler::spawn_queue_expiry_watcher(
+                    ctx.http.clone(),
+                    database.clone(),
+                    google_books.clone(),
+                );
+
+                wrapped_handler::spawn_wrapped_watcher(
+                    ctx.http.clone(),
+                    database.clone(),
+                    google_books.clone(),
+                );
+
                 Ok(types::Data {
                     database,
                     google_books,
                     guild_cache,
+                    list_limit_cap,
                 })
             })
         })
@@ -207,6 +207,19 @@ pub async fn channel_is_nsfw_http(
     }
 }
 
+// Event-side counterpart of can_display_mature_content for a specific channel
+pub async fn can_display_mature_content_event(
+    http: &serenity::Http,
+    pool: &PgPool,
+    server_id: i64,
+    channel_id: ChannelId,
+) -> Result<bool, Error> {
+    if !server_maturity_enabled_by_id(pool, server_id).await? {
+        return Ok(false);
+    }
+    channel_is_nsfw_http(http, channel_id).await
+}
+
 // returns Ok(true) if it's safe to show the volume
 pub async fn check_volume_maturity_event(
     http: &serenity::Http,
@@ -1,12 +1,19 @@
+use crate::commands::server_user::queue::displayable_pitch;
 use crate::database_helpers;
 use crate::ensure_user_exists;
 use crate::google_books_cache::CachedGoogleBooksClient;
+use crate::list_notifications::{ListEvent, ListNotice, notify_list_watchers};
 use crate::maturity_check::{
-    channel_is_nsfw_http, check_volume_maturity_event, create_mature_content_warning,
-    server_maturity_enabled_by_id,
+    can_display_mature_content_event, channel_is_nsfw_http, check_volume_maturity_event,
+    create_mature_content_warning, server_maturity_enabled_by_id,
 };
+use crate::queue_expiry_handler;
+use crate::reminder_handler;
 use crate::types::{Data, Error};
-use crate::util::{format_deadline, log_error, log_error_with_source, pin_polls_enabled};
+use crate::util::{
+    club_announcement_channel, club_by_id, format_deadline, log_error, log_error_with_source,
+    pin_polls_enabled,
+};
 
 use poise::serenity_prelude as serenity;
 use sqlx::PgPool;
@@ -162,6 +169,11 @@ pub async fn handle_event(
                 .await?;
         }
 
+        serenity::FullEvent::InteractionCreate { interaction } => {
+            reminder_handler::handle_interaction(ctx, interaction, data).await?;
+            queue_expiry_handler::handle_interaction(ctx, interaction, data).await?;
+        }
+
         serenity::FullEvent::MessageUpdate {
             old_if_available: _,
             new,
@@ -321,6 +333,31 @@ async fn process_rating_vote_add(
             return Ok(());
         };
 
+        // Clubs can limit ratings to members who tracked progress on the book (a DNF counts)
+        let eligible = sqlx::query_scalar!(
+            r#"
+            SELECT
+                NOT sc.ratings_participants_only
+                OR EXISTS (
+                    SELECT 1 FROM completed_book_participants cbp
+                    WHERE cbp.completed_id = scb.completed_id
+                      AND cbp.user_id = $2
+                      AND cbp.status <> 'not_started'
+                ) AS "eligible!"
+            FROM server_completed_books scb
+            JOIN server_clubs sc ON sc.club_id = scb.club_id
+            WHERE scb.completed_id = $1
+            "#,
+            rating_poll.completed_id,
+            user_id_i64
+        )
+        .fetch_optional(pool)
+        .await?
+        .unwrap_or(false);
+        if !eligible {
+            return Ok(());
+        }
+
         // Ensure user exists, then upsert rating
         if let Ok(user) = user_id.to_user(http).await {
             ensure_user_exists(pool, &user).await?;
@@ -440,7 +477,7 @@ async fn handle_missing_poll_message(
     }
 
     if let Some(selection_poll) = sqlx::query!(
-        "SELECT server_id, processed, selected_volume_id FROM selection_polls WHERE message_id = $1",
+        "SELECT processed, selected_volume_id FROM selection_polls WHERE message_id = $1",
         message_id_i64
     )
     .fetch_optional(pool)
@@ -603,7 +640,7 @@ async fn process_poll_completion(
     // Check if it's a selection poll
     if let Some(selection_poll) = sqlx::query!(
         r#"
-        SELECT server_id, book_options, deadline
+        SELECT club_id, book_options, deadline
         FROM selection_polls
         WHERE message_id = $1 AND NOT processed
         "#,
@@ -612,6 +649,11 @@ async fn process_poll_completion(
     .fetch_optional(pool)
     .await?
     {
+        // The club cascades its polls away when deleted, so this only misses mid-deletion
+        let Some(club) = club_by_id(pool, selection_poll.club_id).await? else {
+            return Ok(());
+        };
+
         if let Some(results) = &poll.results {
             // Find all answers with max votes (handles ties)
             let mut max_votes: u64 = 0;
@@ -709,19 +751,14 @@ async fn process_poll_completion(
 
             // Maturity gate (post warning and close poll if not allowed)
             if let Some(volume) = volume.as_ref() {
-                let can_show = check_volume_maturity_event(
-                    http,
-                    pool,
-                    selection_poll.server_id,
-                    channel_id,
-                    volume,
-                )
-                .await?;
+                let can_show =
+                    check_volume_maturity_event(http, pool, club.server_id, channel_id, volume)
+                        .await?;
 
                 if !can_show {
                     let is_nsfw = channel_is_nsfw_http(http, channel_id).await?;
                     let maturity_enabled =
-                        server_maturity_enabled_by_id(pool, selection_poll.server_id).await?;
+                        server_maturity_enabled_by_id(pool, club.server_id).await?;
                     let embed =
                         create_mature_content_warning(Some(&book_title), is_nsfw, maturity_enabled);
                     let _ = channel_id
@@ -742,19 +779,14 @@ async fn process_poll_completion(
 
             // Try to apply the winner
             //    We update the poll to processed only after a terminal outcome.
-            let config = sqlx::query!(
-                "SELECT announcement_channel_id FROM server_bot_config WHERE server_id = $1",
-                selection_poll.server_id
-            )
-            .fetch_optional(pool)
-            .await?;
-
-            let announcement_channel_id = config.and_then(|c| c.announcement_channel_id);
+            let announcement_channel_id = club_announcement_channel(pool, club.club_id)
+                .await?
+                .map(|channel| channel.get() as i64);
             let poll_deadline = selection_poll.deadline.clone();
 
             match database_helpers::select_book_transactional(
                 pool,
-                selection_poll.server_id,
+                club.club_id,
                 winning_volume_id,
                 announcement_channel_id,
                 poll_deadline.clone(),
@@ -763,8 +795,7 @@ async fn process_poll_completion(
             {
                 Ok(book_info) => {
                     // Success: announce and close the poll
-                    let should_pin_announcements =
-                        pin_polls_enabled(pool, selection_poll.server_id).await?;
+                    let should_pin_announcements = pin_polls_enabled(pool, club.club_id).await?;
 
                     let target_channel = announcement_channel_id
                         .map(|chan| serenity::ChannelId::new(chan as u64))
@@ -772,6 +803,15 @@ async fn process_poll_completion(
                     let suggested_by = book_info
                         .suggested_by_username
                         .unwrap_or_else(|| "Unknown".to_string());
+                    let allow_unrestricted_sexual = can_display_mature_content_event(
+                        http,
+                        pool,
+                        club.server_id,
+                        target_channel,
+                    )
+                    .await?;
+                    let pitch =
+                        displayable_pitch(book_info.pitch.as_deref(), allow_unrestricted_sexual);
 
                     let mut footer_text = String::from("Book data from Google Books API");
                     if volume.is_none() {
@@ -781,14 +821,21 @@ async fn process_poll_completion(
                     }
 
                     let mut embed = serenity::CreateEmbed::default()
-                        .title("New Book Selected!")
+                        .title(format!("New Book Selected!{}", club.title_suffix()))
                         .field("Title", &book_title, false)
                         .field("Authors", &book_authors, false)
                         .field("Suggested by", &suggested_by, false)
-                        .description("Happy reading! Track progress with `/progress`.")
+                        .description(format!(
+                            "Happy reading! Track progress with `/progress{}`.",
+                            club.command_option()
+                        ))
                         .color(0xB76E79)
                         .footer(serenity::CreateEmbedFooter::new(footer_text));
 
+                    if let Some(pitch) = pitch {
+                        embed = embed.field("Pitch", pitch, false);
+                    }
+
                     if let Some(deadline) = poll_deadline {
                         embed = embed.field("Deadline", format_deadline(deadline), true);
                     }
@@ -849,6 +896,20 @@ async fn process_poll_completion(
                     )
                     .execute(pool)
                     .await?;
+
+                    notify_list_watchers(
+                        http,
+                        pool,
+                        google_books,
+                        ListNotice {
+                            club: &club,
+                            volume_ids: &[winning_volume_id.to_string()],
+                            event: ListEvent::Selected,
+                            skip_user: None,
+                            channel_id: target_channel,
+                        },
+                    )
+                    .await;
                 }
                 Err(e) => {
                     let msg = e.to_string();
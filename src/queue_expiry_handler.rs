@@ -0,0 +1,363 @@
+use crate::google_books_cache::CachedGoogleBooksClient;
+use crate::maturity_check::check_volume_maturity_event;
+use crate::types::{Data, Error};
+use crate::util::{
+    Club, EMBED_DESCRIPTION_LIMIT, join_lines_capped, log_error_with_source, member_left_guild,
+    truncate_with_ellipsis,
+};
+use poise::serenity_prelude as serenity;
+use serenity::{
+    ButtonStyle, CreateActionRow, CreateButton, CreateEmbed, CreateEmbedFooter,
+    CreateInteractionResponse, CreateInteractionResponseMessage, CreateMessage,
+};
+use sqlx::PgPool;
+use sqlx::types::chrono::Utc;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::time::{self, Duration};
+
+const RENEW_BUTTON_PREFIX: &str = "queue_renew:";
+/// Removed suggestions listed by title in the cleanup notice; the rest are only counted.
+const CLEANUP_NOTICE_LINES: usize = 25;
+const CLEANUP_TITLE_LENGTH: usize = 150;
+
+pub fn spawn_queue_expiry_watcher(
+    http: Arc<serenity::Http>,
+    pool: PgPool,
+    google_books: CachedGoogleBooksClient,
+) {
+    tokio::spawn(async move {
+        let mut interval = time::interval(Duration::from_secs(3600));
+        loop {
+            interval.tick().await;
+            if let Err(err) = process_queue_expiry(&http, &pool, &google_books).await {
+                log_error_with_source("Queue expiry watcher error", &err);
+            }
+        }
+    });
+}
+
+/// How many days before expiry the suggester is warned. Short TTLs get a proportionally
+/// shorter notice; a TTL of one day gets none.
+pub(crate) fn expiry_warning_lead_days(ttl_days: i32) -> i32 {
+    (ttl_days / 2).clamp(0, 3)
+}
+
+enum RemovalReason {
+    Expired,
+    SuggesterLeft,
+}
+
+async fn process_queue_expiry(
+    http: &Arc<serenity::Http>,
+    pool: &PgPool,
+    google_books: &CachedGoogleBooksClient,
+) -> Result<(), Error> {
+    let clubs = sqlx::query!(
+        r#"
+        SELECT sc.club_id, sc.server_id, sc.name, sc.is_primary, sc.announcement_channel_id,
+               sbc.queue_ttl_days AS "queue_ttl_days!", ds.server_name
+        FROM server_clubs sc
+        JOIN server_bot_config sbc ON sbc.server_id = sc.server_id
+        JOIN discord_servers ds ON ds.server_id = sc.server_id
+        WHERE sbc.queue_ttl_days IS NOT NULL
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for club in clubs {
+        let queue_club = Club {
+            club_id: club.club_id,
+            server_id: club.server_id,
+            name: club.name,
+            is_primary: club.is_primary,
+        };
+        if let Err(err) = process_club_queue(
+            http,
+            pool,
+            google_books,
+            &queue_club,
+            &club.server_name,
+            club.queue_ttl_days,
+            club.announcement_channel_id,
+        )
+        .await
+        {
+            log_error_with_source("Couldn't process queue expiry for club", &err);
+        }
+    }
+
+    Ok(())
+}
+
+async fn process_club_queue(
+    http: &Arc<serenity::Http>,
+    pool: &PgPool,
+    google_books: &CachedGoogleBooksClient,
+    club: &Club,
+    server_name: &str,
+    ttl_days: i32,
+    announcement_channel_id: Option<i64>,
+) -> Result<(), Error> {
+    let entries = sqlx::query!(
+        r#"
+        SELECT
+            sbq.queue_id,
+            sbq.volume_id,
+            sbq.suggested_by_user_id,
+            du.username,
+            COALESCE(sbq.renewed_at, sbq.added_at, NOW()) AS "counted_from!",
+            sbq.expiry_warned_at
+        FROM server_book_queue sbq
+        JOIN discord_users du ON du.user_id = sbq.suggested_by_user_id
+        WHERE sbq.club_id = $1
+        ORDER BY sbq.position
+        "#,
+        club.club_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let server_id = club.server_id;
+    let guild_id = serenity::GuildId::new(server_id as u64);
+    let now = Utc::now();
+    let lead_days = expiry_warning_lead_days(ttl_days);
+    let mut membership: HashMap<i64, bool> = HashMap::new();
+    let mut removed: Vec<(i32, String, String, RemovalReason)> = Vec::new();
+
+    for entry in entries {
+        let expires_at = entry.counted_from + chrono::Duration::days(ttl_days as i64);
+
+        if now >= expires_at {
+            removed.push((
+                entry.queue_id,
+                entry.volume_id,
+                entry.username,
+                RemovalReason::Expired,
+            ));
+            continue;
+        }
+
+        let left = match membership.get(&entry.suggested_by_user_id) {
+            Some(left) => *left,
+            None => {
+                let user_id = serenity::UserId::new(entry.suggested_by_user_id as u64);
+                let left = member_left_guild(http, guild_id, user_id).await?;
+                membership.insert(entry.suggested_by_user_id, left);
+                left
+            }
+        };
+
+        if left {
+            removed.push((
+                entry.queue_id,
+                entry.volume_id,
+                entry.username,
+                RemovalReason::SuggesterLeft,
+            ));
+            continue;
+        }
+
+        if lead_days > 0
+            && entry.expiry_warned_at.is_none()
+            && now >= expires_at - chrono::Duration::days(lead_days as i64)
+        {
+            let book_title = match google_books.get_volume(&entry.volume_id).await {
+                Ok(volume) => volume.get_title(),
+                Err(_) => format!("Book ({})", entry.volume_id),
+            };
+
+            let embed = CreateEmbed::default()
+                .title("⏳ Your Suggestion Is About to Expire")
+                .description(format!(
+                    "**{}** will be removed from the **{}{}** queue <t:{}:R>. Renew it if you'd still like the club to read it.",
+                    book_title,
+                    server_name,
+                    club.title_suffix(),
+                    expires_at.timestamp()
+                ))
+                .color(0xB76E79)
+                .footer(CreateEmbedFooter::new(format!(
+                    "Queue entries in this server expire after {} days",
+                    ttl_days
+                )));
+
+            let components = vec![CreateActionRow::Buttons(vec![
+                CreateButton::new(format!("{}{}", RENEW_BUTTON_PREFIX, entry.queue_id))
+                    .label("Keep It in the Queue")
+                    .style(ButtonStyle::Primary),
+            ])];
+
+            let user_id = serenity::UserId::new(entry.suggested_by_user_id as u64);
+            let sent = match user_id.create_dm_channel(http).await {
+                Ok(channel) => channel
+                    .send_message(
+                        http,
+                        CreateMessage::new().embed(embed).components(components),
+                    )
+                    .await
+                    .map(|_| ()),
+                Err(err) => Err(err),
+            };
+
+            if let Err(err) = sent {
+                log_error_with_source("Couldn't send queue expiry warning DM", &err);
+            }
+
+            // Record the attempt either way so closed DMs don't get retried every tick
+            sqlx::query!(
+                "UPDATE server_book_queue SET expiry_warned_at = NOW() WHERE queue_id = $1",
+                entry.queue_id
+            )
+            .execute(pool)
+            .await?;
+        }
+    }
+
+    if removed.is_empty() {
+        return Ok(());
+    }
+
+    let queue_ids: Vec<i32> = removed.iter().map(|(id, ..)| *id).collect();
+    sqlx::query!(
+        "DELETE FROM server_book_queue WHERE queue_id = ANY($1)",
+        &queue_ids
+    )
+    .execute(pool)
+    .await?;
+
+    let Some(channel_id) = announcement_channel_id else {
+        return Ok(());
+    };
+    let channel_id = serenity::ChannelId::new(channel_id as u64);
+
+    let mut lines: Vec<String> = Vec::new();
+    for (_, volume_id, username, reason) in removed.iter().take(CLEANUP_NOTICE_LINES) {
+        let title = match google_books.get_volume(volume_id).await {
+            Ok(volume) => {
+                if check_volume_maturity_event(http, pool, server_id, channel_id, &volume).await? {
+                    truncate_with_ellipsis(&volume.get_title(), CLEANUP_TITLE_LENGTH)
+                } else {
+                    "A mature-rated book".to_string()
+                }
+            }
+            Err(_) => format!("Book ({})", volume_id),
+        };
+        let why = match reason {
+            RemovalReason::Expired => format!("in the queue for over {} days", ttl_days),
+            RemovalReason::SuggesterLeft => "suggester left the server".to_string(),
+        };
+        lines.push(format!(
+            "• **{}** — suggested by {} ({})",
+            title, username, why
+        ));
+    }
+
+    let mut description = format!(
+        "Removed {} stale suggestion(s) from the queue:\n\n",
+        removed.len()
+    );
+    let (list, _) = join_lines_capped(
+        &lines,
+        removed.len(),
+        EMBED_DESCRIPTION_LIMIT - description.len(),
+    );
+    description.push_str(&list);
+
+    let embed = CreateEmbed::default()
+        .title("🧹 Queue Cleanup")
+        .description(description)
+        .color(0xB76E79)
+        .footer(CreateEmbedFooter::new(
+            "Configured via /config queue expiry",
+        ));
+
+    if let Err(err) = channel_id
+        .send_message(http, CreateMessage::new().embed(embed))
+        .await
+    {
+        log_error_with_source("Couldn't log queue cleanup", &err);
+    }
+
+    Ok(())
+}
+
+/// Handles the "Keep It in the Queue" button on expiry warning DMs.
+pub async fn handle_interaction(
+    ctx: &serenity::Context,
+    interaction: &serenity::Interaction,
+    data: &Data,
+) -> Result<(), Error> {
+    let serenity::Interaction::Component(component) = interaction else {
+        return Ok(());
+    };
+    let Some(queue_id) = component
+        .data
+        .custom_id
+        .strip_prefix(RENEW_BUTTON_PREFIX)
+        .and_then(|id| id.parse::<i32>().ok())
+    else {
+        return Ok(());
+    };
+
+    let renewed = sqlx::query!(
+        "UPDATE server_book_queue SET renewed_at = NOW(), expiry_warned_at = NULL
+         WHERE queue_id = $1 AND suggested_by_user_id = $2
+         RETURNING volume_id",
+        queue_id,
+        component.user.id.get() as i64
+    )
+    .fetch_optional(&data.database)
+    .await?;
+
+    let embed = match renewed {
+        Some(row) => {
+            let book_title = match data.google_books.get_volume(&row.volume_id).await {
+                Ok(volume) => volume.get_title(),
+                Err(_) => format!("Book ({})", row.volume_id),
+            };
+            CreateEmbed::default()
+                .title("✅ Suggestion Renewed")
+                .description(format!(
+                    "**{}** will stay in the queue. I'll check in again before it expires.",
+                    book_title
+                ))
+                .color(0xB76E79)
+        }
+        None => CreateEmbed::default()
+            .title("Suggestion No Longer Queued")
+            .description("This book has already left the queue, so there's nothing to renew.")
+            .color(0xB76E79),
+    };
+
+    component
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .embed(embed)
+                    .components(vec![]),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::expiry_warning_lead_days;
+
+    #[test]
+    fn warning_lead_caps_at_three_days() {
+        assert_eq!(expiry_warning_lead_days(30), 3);
+        assert_eq!(expiry_warning_lead_days(6), 3);
+    }
+
+    #[test]
+    fn warning_lead_shrinks_for_short_ttls() {
+        assert_eq!(expiry_warning_lead_days(4), 2);
+        assert_eq!(expiry_warning_lead_days(1), 0);
+    }
+}
@@ -0,0 +1,362 @@
+use crate::util::truncate_with_ellipsis;
+use std::collections::HashMap;
+
+/// Statuses that mean the member is done with a book, so it doesn't belong on a reading list.
+const FINISHED_STATUSES: [&str; 4] = ["read", "did-not-finish", "dnf", "abandoned"];
+/// Shelf or tag names the services' users conventionally use for favorites.
+const FAVORITE_SHELVES: [&str; 3] = ["favorites", "favourites", "favorite"];
+/// Longest title or author shown for one row in the import report.
+const LABEL_PART_LENGTH: usize = 100;
+
+/// Where an uploaded export came from; each service names its columns differently.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ExportFormat {
+    Goodreads,
+    StoryGraph,
+}
+
+impl ExportFormat {
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            ExportFormat::Goodreads => "Goodreads",
+            ExportFormat::StoryGraph => "StoryGraph",
+        }
+    }
+
+    fn required_columns(self) -> &'static [&'static str] {
+        match self {
+            ExportFormat::Goodreads => &[
+                "Title",
+                "Author",
+                "ISBN",
+                "ISBN13",
+                "My Rating",
+                "Bookshelves",
+                "Exclusive Shelf",
+            ],
+            ExportFormat::StoryGraph => &[
+                "Title",
+                "Authors",
+                "ISBN/UID",
+                "Read Status",
+                "Star Rating",
+                "Tags",
+            ],
+        }
+    }
+}
+
+/// One book from an export, reduced to what the bot can store.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct ImportRow {
+    /// Line of the book in the file, counting the header as line 1.
+    pub line: usize,
+    pub title: String,
+    pub author: Option<String>,
+    /// ISBNs to try before falling back to a title search, most specific first.
+    pub isbns: Vec<String>,
+    /// Set when the book is still unread; holds the service's shelf or status name.
+    pub reading_shelf: Option<String>,
+    pub rating: Option<i32>,
+    pub favorite: bool,
+    pub tags: Vec<String>,
+}
+
+impl ImportRow {
+    /// Rows that are only marked read without a rating have nothing to import.
+    pub(crate) fn has_work(&self) -> bool {
+        self.reading_shelf.is_some() || self.rating.is_some() || self.favorite
+    }
+
+    /// Title and author for the import report, shortened so one long cell can't crowd out the
+    /// rest.
+    pub(crate) fn label(&self) -> String {
+        let title = truncate_with_ellipsis(&self.title, LABEL_PART_LENGTH);
+        match &self.author {
+            Some(author) => format!(
+                "*{}* by {}",
+                title,
+                truncate_with_ellipsis(author, LABEL_PART_LENGTH)
+            ),
+            None => format!("*{}*", title),
+        }
+    }
+}
+
+/// One record of an uploaded CSV file.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct CsvRecord {
+    /// Line the record starts on, so reports point at the right place even after blank lines
+    /// or quoted cells that span several lines.
+    pub line: usize,
+    pub fields: Vec<String>,
+}
+
+/// Splits CSV text into records, honouring quoted fields with embedded commas, doubled quotes
+/// and line breaks. Blank lines are skipped.
+pub(crate) fn parse_csv(text: &str) -> Vec<CsvRecord> {
+    let text = text.strip_prefix('\u{FEFF}').unwrap_or(text);
+    // The reader positions a record where it started reading, before any blank lines or the
+    // '\n' of a "\r\n" it skipped, so count lines from the record's first real byte instead
+    let mut counted_to = 0;
+    let mut line = 1;
+    csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text.as_bytes())
+        .into_records()
+        .filter_map(Result::ok)
+        .map(|record| {
+            let read_from = record
+                .position()
+                .map_or(counted_to, |position| position.byte() as usize)
+                .max(counted_to);
+            let start = read_from
+                + text[read_from..]
+                    .bytes()
+                    .take_while(|byte| matches!(byte, b'\r' | b'\n'))
+                    .count();
+            line += text[counted_to..start].matches('\n').count();
+            counted_to = start;
+            CsvRecord {
+                line,
+                fields: record.iter().map(str::to_string).collect(),
+            }
+        })
+        .filter(|record| record.fields.iter().any(|field| !field.trim().is_empty()))
+        .collect()
+}
+
+/// Goodreads wraps ISBNs as `="0439023483"` so spreadsheets keep the leading zero; StoryGraph
+/// uses the same column for its own IDs. Returns `None` for anything that isn't an ISBN.
+fn clean_isbn(raw: &str) -> Option<String> {
+    let isbn: String = raw
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == 'X' || *c == 'x')
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    let digits_before_check = isbn.get(..isbn.len().saturating_sub(1))?;
+    let valid = (isbn.len() == 10 || isbn.len() == 13)
+        && digits_before_check.chars().all(|c| c.is_ascii_digit())
+        && raw
+            .chars()
+            .all(|c| !c.is_ascii_alphabetic() || c == 'X' || c == 'x');
+    valid.then_some(isbn)
+}
+
+/// Drops series markers like "(The Hunger Games, #1)" that confuse title searches.
+pub(crate) fn search_title(title: &str) -> String {
+    let title = title.trim();
+    match title.rfind(" (") {
+        Some(start) if title.ends_with(')') && title[start..].contains('#') => {
+            title[..start].trim().to_string()
+        }
+        _ => title.to_string(),
+    }
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|item| item.trim().to_lowercase())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// Star ratings of zero mean "not rated"; StoryGraph's quarter stars round to the nearest star.
+fn parse_rating(value: &str) -> Option<i32> {
+    let stars: f64 = value.trim().parse().ok()?;
+    (stars > 0.0).then(|| (stars.round() as i32).clamp(1, 5))
+}
+
+/// Reads a Goodreads or StoryGraph library export. Returns the missing column names when the
+/// header doesn't look like the chosen service's export.
+pub(crate) fn parse_export(
+    format: ExportFormat,
+    text: &str,
+) -> Result<Vec<ImportRow>, Vec<&'static str>> {
+    let mut records = parse_csv(text).into_iter();
+    let header = records
+        .next()
+        .map(|record| record.fields)
+        .unwrap_or_default();
+    let columns: HashMap<&str, usize> = header
+        .iter()
+        .enumerate()
+        .map(|(index, name)| (name.trim(), index))
+        .collect();
+    let missing: Vec<&'static str> = format
+        .required_columns()
+        .iter()
+        .copied()
+        .filter(|name| !columns.contains_key(name))
+        .collect();
+    if !missing.is_empty() {
+        return Err(missing);
+    }
+
+    let rows = records
+        .filter_map(|record| {
+            let cell = |name: &str| {
+                record
+                    .fields
+                    .get(columns[name])
+                    .map(|value| value.trim())
+                    .unwrap_or_default()
+            };
+            let title = cell("Title").to_string();
+            if title.is_empty() {
+                return None;
+            }
+
+            let (author, isbns, status, rating, shelves) = match format {
+                ExportFormat::Goodreads => (
+                    cell("Author").to_string(),
+                    [cell("ISBN13"), cell("ISBN")]
+                        .into_iter()
+                        .filter_map(clean_isbn)
+                        .collect::<Vec<_>>(),
+                    cell("Exclusive Shelf").to_lowercase(),
+                    parse_rating(cell("My Rating")),
+                    split_list(cell("Bookshelves")),
+                ),
+                ExportFormat::StoryGraph => (
+                    cell("Authors")
+                        .split(',')
+                        .next()
+                        .unwrap_or_default()
+                        .trim()
+                        .to_string(),
+                    clean_isbn(cell("ISBN/UID")).into_iter().collect(),
+                    cell("Read Status").to_lowercase(),
+                    parse_rating(cell("Star Rating")),
+                    split_list(cell("Tags")),
+                ),
+            };
+
+            let favorite = shelves
+                .iter()
+                .any(|shelf| FAVORITE_SHELVES.contains(&shelf.as_str()));
+            let reading_shelf = (!status.is_empty()
+                && !FINISHED_STATUSES.contains(&status.as_str()))
+            .then(|| status.clone());
+            let tags = shelves
+                .into_iter()
+                .filter(|shelf| *shelf != status && !FAVORITE_SHELVES.contains(&shelf.as_str()))
+                .collect();
+
+            Some(ImportRow {
+                line: record.line,
+                title,
+                author: (!author.is_empty()).then_some(author),
+                isbns,
+                reading_shelf,
+                rating,
+                favorite,
+                tags,
+            })
+        })
+        .collect();
+
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExportFormat, ImportRow, parse_csv, parse_export, search_title};
+
+    #[test]
+    fn csv_handles_quotes_commas_and_line_breaks() {
+        let text = "\u{FEFF}a,b,c\r\n\"x, y\",\"say \"\"hi\"\"\",\"two\nlines\"\r\n\r\n1,,3";
+        let records = parse_csv(text);
+        assert_eq!(
+            records
+                .iter()
+                .map(|record| record.fields.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                vec!["a", "b", "c"],
+                vec!["x, y", "say \"hi\"", "two\nlines"],
+                vec!["1", "", "3"],
+            ]
+        );
+        // Lines count from the start of each record, past multi-line cells and blank lines
+        assert_eq!(
+            records.iter().map(|record| record.line).collect::<Vec<_>>(),
+            vec![1, 2, 5]
+        );
+    }
+
+    #[test]
+    fn goodreads_rows_map_to_shelves_ratings_and_favorites() {
+        let text = "Book Id,Title,Author,ISBN,ISBN13,My Rating,Bookshelves,Exclusive Shelf\n\
+            1,\"Mockingjay (The Hunger Games, #3)\",Suzanne Collins,\"=\"\"0439023513\"\"\",\"=\"\"9780439023511\"\"\",5,\"favorites, dystopia\",read\n\
+            2,Piranesi,Susanna Clarke,\"=\"\"\"\"\",\"=\"\"\"\"\",0,\"cozy, to-read\",to-read\n\
+            3,Dune,Frank Herbert,,,0,,read\n";
+        let rows = parse_export(ExportFormat::Goodreads, text).unwrap();
+
+        assert_eq!(
+            rows[0],
+            ImportRow {
+                line: 2,
+                title: "Mockingjay (The Hunger Games, #3)".to_string(),
+                author: Some("Suzanne Collins".to_string()),
+                isbns: vec!["9780439023511".to_string(), "0439023513".to_string()],
+                reading_shelf: None,
+                rating: Some(5),
+                favorite: true,
+                tags: vec!["dystopia".to_string()],
+            }
+        );
+        assert_eq!(rows[1].isbns, Vec::<String>::new());
+        assert_eq!(rows[1].reading_shelf.as_deref(), Some("to-read"));
+        assert_eq!(rows[1].tags, vec!["cozy".to_string()]);
+        assert!(!rows[2].has_work());
+        assert_eq!(search_title(&rows[0].title), "Mockingjay");
+    }
+
+    #[test]
+    fn storygraph_rows_round_ratings_and_ignore_uids() {
+        let text = "Title,Authors,Contributors,ISBN/UID,Format,Read Status,Star Rating,Tags\n\
+            Circe,\"Madeline Miller, Someone Else\",,9780316556347,digital,read,4.25,\n\
+            The Hobbit,J.R.R. Tolkien,,24d1b9e6-abcd,paperback,currently-reading,,\"favourites, comfort\"\n";
+        let rows = parse_export(ExportFormat::StoryGraph, text).unwrap();
+
+        assert_eq!(rows[0].author.as_deref(), Some("Madeline Miller"));
+        assert_eq!(rows[0].isbns, vec!["9780316556347".to_string()]);
+        assert_eq!(rows[0].rating, Some(4));
+        assert_eq!(rows[0].reading_shelf, None);
+        assert!(rows[1].isbns.is_empty());
+        assert_eq!(rows[1].reading_shelf.as_deref(), Some("currently-reading"));
+        assert!(rows[1].favorite);
+        assert_eq!(rows[1].tags, vec!["comfort".to_string()]);
+    }
+
+    #[test]
+    fn labels_shorten_long_cells() {
+        let row = ImportRow {
+            title: "T".repeat(500),
+            author: Some("A".repeat(500)),
+            ..Default::default()
+        };
+        assert!(row.label().len() < 250);
+        assert!(row.label().contains("…* by "));
+    }
+
+    #[test]
+    fn wrong_export_reports_missing_columns() {
+        let text =
+            "Title,Authors,ISBN/UID,Read Status,Star Rating,Tags\nCirce,Madeline Miller,,read,4,\n";
+        assert_eq!(
+            parse_export(ExportFormat::Goodreads, text),
+            Err(vec![
+                "Author",
+                "ISBN",
+                "ISBN13",
+                "My Rating",
+                "Bookshelves",
+                "Exclusive Shelf"
+            ])
+        );
+    }
+}
@@ -0,0 +1,326 @@
+use std::collections::{HashMap, HashSet};
+
+/// How much a peer's enthusiasm counts next to the member's own taste.
+const PEER_WEIGHT: f64 = 2.0;
+/// Genres are broad, so a genre match counts for less than an author match.
+const GENRE_WEIGHT: f64 = 0.5;
+
+/// Something a member did that tells us about their taste.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum TasteSignal {
+    NumberOne,
+    Favorite,
+    ReadingList,
+    Rated(i32),
+}
+
+impl TasteSignal {
+    /// Ratings of 3 or lower say nothing about what the member likes.
+    pub(crate) fn weight(self) -> f64 {
+        match self {
+            TasteSignal::NumberOne => 4.0,
+            TasteSignal::Favorite => 3.0,
+            TasteSignal::ReadingList => 1.0,
+            TasteSignal::Rated(rating) => (rating - 3).max(0) as f64,
+        }
+    }
+}
+
+/// The parts of a book recommendations are matched on.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct BookTraits {
+    pub authors: Vec<String>,
+    pub genre: Option<String>,
+}
+
+fn normalize(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+/// A member's weighted favorite authors and genres.
+#[derive(Default)]
+pub(crate) struct TasteProfile {
+    // Keyed by normalized name; the value keeps the first spelling seen for display
+    authors: HashMap<String, (String, f64)>,
+    genres: HashMap<String, (String, f64)>,
+}
+
+impl TasteProfile {
+    pub(crate) fn add(&mut self, signal: TasteSignal, traits: &BookTraits) {
+        let weight = signal.weight();
+        if weight <= 0.0 {
+            return;
+        }
+        for author in &traits.authors {
+            self.authors
+                .entry(normalize(author))
+                .or_insert_with(|| (author.trim().to_string(), 0.0))
+                .1 += weight;
+        }
+        if let Some(genre) = &traits.genre {
+            self.genres
+                .entry(normalize(genre))
+                .or_insert_with(|| (genre.trim().to_string(), 0.0))
+                .1 += weight;
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.authors.is_empty() && self.genres.is_empty()
+    }
+
+    fn top(entries: &HashMap<String, (String, f64)>, limit: usize) -> Vec<String> {
+        let mut ranked: Vec<&(String, f64)> = entries.values().collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        ranked
+            .into_iter()
+            .take(limit)
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Heaviest authors first, ties alphabetical.
+    pub(crate) fn top_authors(&self, limit: usize) -> Vec<String> {
+        Self::top(&self.authors, limit)
+    }
+
+    /// Heaviest genres first, ties alphabetical.
+    pub(crate) fn top_genres(&self, limit: usize) -> Vec<String> {
+        Self::top(&self.genres, limit)
+    }
+
+    /// How well a book matches this profile; 0 means no overlap at all.
+    pub(crate) fn affinity(&self, traits: &BookTraits) -> f64 {
+        let authors: f64 = traits
+            .authors
+            .iter()
+            .filter_map(|author| self.authors.get(&normalize(author)))
+            .map(|(_, weight)| weight)
+            .sum();
+        let genre = traits
+            .genre
+            .as_deref()
+            .and_then(|genre| self.genres.get(&normalize(genre)))
+            .map(|(_, weight)| weight * GENRE_WEIGHT)
+            .unwrap_or(0.0);
+        authors + genre
+    }
+}
+
+/// Where a candidate came from. Variants are in ranking order: every queued book that fits the
+/// member's taste comes before any peer pick, and so on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum CandidateSource {
+    Queue,
+    Peers,
+    Author,
+    Genre,
+}
+
+#[derive(Clone)]
+pub(crate) struct Candidate {
+    pub volume_id: String,
+    pub traits: BookTraits,
+    pub source: CandidateSource,
+    /// Sum of similarity × signal weight from members with similar taste who liked the book.
+    pub peer_support: f64,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct Recommendation {
+    pub volume_id: String,
+    pub source: CandidateSource,
+    pub score: f64,
+}
+
+/// Orders candidates for a member: by source, then score, then volume ID so the same data
+/// always gives the same list. Duplicates are merged under their highest-ranked source,
+/// `exclude` removes books the member already knows, and queue and peer candidates with
+/// nothing in common with the member are dropped.
+pub(crate) fn rank_candidates(
+    profile: &TasteProfile,
+    candidates: Vec<Candidate>,
+    exclude: &HashSet<String>,
+    limit: usize,
+) -> Vec<Recommendation> {
+    let mut merged: HashMap<String, Candidate> = HashMap::new();
+    for candidate in candidates {
+        if exclude.contains(&candidate.volume_id) {
+            continue;
+        }
+        match merged.get_mut(&candidate.volume_id) {
+            Some(existing) => {
+                existing.source = existing.source.min(candidate.source);
+                existing.peer_support += candidate.peer_support;
+            }
+            None => {
+                merged.insert(candidate.volume_id.clone(), candidate);
+            }
+        }
+    }
+
+    let mut ranked: Vec<Recommendation> = merged
+        .into_values()
+        .filter_map(|candidate| {
+            let score = profile.affinity(&candidate.traits) + candidate.peer_support * PEER_WEIGHT;
+            let searched = matches!(
+                candidate.source,
+                CandidateSource::Author | CandidateSource::Genre
+            );
+            (score > 0.0 || searched).then_some(Recommendation {
+                volume_id: candidate.volume_id,
+                source: candidate.source,
+                score,
+            })
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| {
+        a.source
+            .cmp(&b.source)
+            .then_with(|| b.score.total_cmp(&a.score))
+            .then_with(|| a.volume_id.cmp(&b.volume_id))
+    });
+    ranked.truncate(limit);
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BookTraits, Candidate, CandidateSource, TasteProfile, TasteSignal, rank_candidates,
+    };
+    use std::collections::HashSet;
+
+    fn traits(authors: &[&str], genre: Option<&str>) -> BookTraits {
+        BookTraits {
+            authors: authors.iter().map(|author| author.to_string()).collect(),
+            genre: genre.map(String::from),
+        }
+    }
+
+    fn candidate(id: &str, source: CandidateSource, book: BookTraits, peers: f64) -> Candidate {
+        Candidate {
+            volume_id: id.to_string(),
+            traits: book,
+            source,
+            peer_support: peers,
+        }
+    }
+
+    fn fixture_profile() -> TasteProfile {
+        let mut profile = TasteProfile::default();
+        profile.add(
+            TasteSignal::NumberOne,
+            &traits(&["Ursula K. Le Guin"], Some("Fiction")),
+        );
+        profile.add(
+            TasteSignal::Rated(5),
+            &traits(&["N. K. Jemisin"], Some("Fiction")),
+        );
+        profile.add(
+            TasteSignal::Rated(2),
+            &traits(&["Disliked Author"], Some("History")),
+        );
+        profile.add(
+            TasteSignal::ReadingList,
+            &traits(&["Ann Leckie"], Some("Science")),
+        );
+        profile
+    }
+
+    #[test]
+    fn profile_weights_signals_and_ignores_low_ratings() {
+        let profile = fixture_profile();
+        assert_eq!(
+            profile.top_authors(3),
+            vec!["Ursula K. Le Guin", "N. K. Jemisin", "Ann Leckie"]
+        );
+        assert_eq!(profile.top_genres(5), vec!["Fiction", "Science"]);
+        assert_eq!(
+            profile.affinity(&traits(&["ursula k. le guin "], Some("fiction"))),
+            4.0 + 6.0 * 0.5
+        );
+        assert_eq!(
+            profile.affinity(&traits(&["Disliked Author"], Some("History"))),
+            0.0
+        );
+    }
+
+    #[test]
+    fn queued_matches_rank_first_and_unrelated_ones_are_dropped() {
+        let profile = fixture_profile();
+        let candidates = vec![
+            candidate(
+                "author-hit",
+                CandidateSource::Author,
+                traits(&["Ursula K. Le Guin"], Some("Fiction")),
+                0.0,
+            ),
+            candidate(
+                "queue-weak",
+                CandidateSource::Queue,
+                traits(&["Someone"], Some("Science")),
+                0.0,
+            ),
+            candidate(
+                "queue-unrelated",
+                CandidateSource::Queue,
+                traits(&["Someone"], Some("Cooking")),
+                0.0,
+            ),
+            candidate(
+                "peer-pick",
+                CandidateSource::Peers,
+                traits(&["Someone Else"], None),
+                0.8,
+            ),
+            candidate(
+                "queue-strong",
+                CandidateSource::Queue,
+                traits(&["N. K. Jemisin"], None),
+                0.0,
+            ),
+        ];
+
+        let ranked: Vec<String> = rank_candidates(&profile, candidates, &HashSet::new(), 10)
+            .into_iter()
+            .map(|rec| rec.volume_id)
+            .collect();
+        assert_eq!(
+            ranked,
+            vec!["queue-strong", "queue-weak", "peer-pick", "author-hit"]
+        );
+    }
+
+    #[test]
+    fn duplicates_merge_and_known_books_are_excluded() {
+        let profile = fixture_profile();
+        let book = || traits(&["Ann Leckie"], None);
+        let candidates = vec![
+            candidate("dup", CandidateSource::Genre, book(), 0.0),
+            candidate("dup", CandidateSource::Peers, book(), 0.5),
+            candidate("known", CandidateSource::Queue, book(), 0.0),
+        ];
+        let exclude: HashSet<String> = ["known".to_string()].into();
+
+        let ranked = rank_candidates(&profile, candidates, &exclude, 10);
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].source, CandidateSource::Peers);
+        assert_eq!(ranked[0].score, 1.0 + 0.5 * 2.0);
+    }
+
+    #[test]
+    fn ties_break_on_volume_id() {
+        let profile = fixture_profile();
+        let candidates = ["b", "c", "a"]
+            .into_iter()
+            .map(|id| candidate(id, CandidateSource::Genre, BookTraits::default(), 0.0))
+            .collect();
+        let ranked: Vec<String> = rank_candidates(&profile, candidates, &HashSet::new(), 2)
+            .into_iter()
+            .map(|rec| rec.volume_id)
+            .collect();
+        assert_eq!(ranked, vec!["a", "b"]);
+    }
+}
@@ -0,0 +1,356 @@
+use crate::commands::user::progress::{is_progress_banned, validate_progress_text};
+use crate::google_books_cache::CachedGoogleBooksClient;
+use crate::maturity_check::server_maturity_enabled_by_id;
+use crate::types::{Data, Error};
+use crate::util::{club_by_id, format_deadline, log_error_with_source};
+use chrono::Timelike;
+use poise::serenity_prelude as serenity;
+use serenity::{
+    ActionRowComponent, ButtonStyle, CreateActionRow, CreateButton, CreateEmbed, CreateEmbedFooter,
+    CreateInputText, CreateInteractionResponse, CreateInteractionResponseMessage, CreateMessage,
+    CreateModal, InputTextStyle,
+};
+use sqlx::PgPool;
+use sqlx::types::chrono::Utc;
+use std::sync::Arc;
+use tokio::time::{self, Duration};
+
+const REMINDER_BUTTON_PREFIX: &str = "progress_reminder_update:";
+const REMINDER_MODAL_PREFIX: &str = "progress_reminder_modal:";
+const REMINDER_INPUT_ID: &str = "progress_text";
+
+pub fn spawn_progress_reminder_watcher(
+    http: Arc<serenity::Http>,
+    pool: PgPool,
+    google_books: CachedGoogleBooksClient,
+) {
+    tokio::spawn(async move {
+        // Check every 30 minutes so quiet hours are respected with reasonable precision
+        let mut interval = time::interval(Duration::from_secs(1800));
+        loop {
+            interval.tick().await;
+            if let Err(err) = send_due_reminders(&http, &pool, &google_books).await {
+                log_error_with_source("Progress reminder watcher error", &err);
+            }
+        }
+    });
+}
+
+/// True if `hour` (UTC) falls inside the quiet window. Windows may wrap past midnight,
+/// e.g. 22 → 7. Equal start and end hours are treated as an empty window.
+pub(crate) fn within_quiet_hours(hour: u32, start: Option<i16>, end: Option<i16>) -> bool {
+    let (Some(start), Some(end)) = (start, end) else {
+        return false;
+    };
+    let (start, end) = (start as u32, end as u32);
+
+    if start == end {
+        false
+    } else if start < end {
+        hour >= start && hour < end
+    } else {
+        hour >= start || hour < end
+    }
+}
+
+async fn send_due_reminders(
+    http: &Arc<serenity::Http>,
+    pool: &PgPool,
+    google_books: &CachedGoogleBooksClient,
+) -> Result<(), Error> {
+    // A member is due when their last progress update (or the start of the read, if they
+    // never posted one) and their last reminder are both older than their interval.
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            upr.user_id,
+            upr.club_id,
+            sc.name AS club_name,
+            sc.is_primary,
+            ds.server_name,
+            scb.volume_id,
+            scb.deadline,
+            urp.progress_text AS "progress_text?",
+            urs.quiet_hours_start AS "quiet_hours_start?",
+            urs.quiet_hours_end AS "quiet_hours_end?"
+        FROM user_progress_reminders upr
+        JOIN server_current_book scb ON scb.club_id = upr.club_id
+        JOIN server_clubs sc ON sc.club_id = upr.club_id
+        JOIN discord_servers ds ON ds.server_id = sc.server_id
+        LEFT JOIN user_reading_progress urp ON urp.user_id = upr.user_id
+            AND urp.club_id = upr.club_id
+            AND urp.volume_id = scb.volume_id
+        LEFT JOIN user_reminder_settings urs ON urs.user_id = upr.user_id
+        LEFT JOIN progress_command_bans pcb ON pcb.user_id = upr.user_id
+            AND pcb.server_id = sc.server_id
+        WHERE NOT COALESCE(urs.reminders_paused, FALSE)
+          AND pcb.user_id IS NULL
+          AND urp.dnf_at IS NULL
+          AND COALESCE(urp.updated_at, scb.started_at)
+              <= NOW() - make_interval(days => upr.interval_days)
+          AND (
+              upr.last_reminded_at IS NULL
+              OR upr.last_reminded_at <= NOW() - make_interval(days => upr.interval_days)
+          )
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let current_hour = Utc::now().hour();
+
+    for row in rows {
+        if within_quiet_hours(current_hour, row.quiet_hours_start, row.quiet_hours_end) {
+            continue;
+        }
+
+        let book_title = match google_books.get_volume(&row.volume_id).await {
+            Ok(volume) => volume.get_title(),
+            Err(_) => format!("Book ({})", row.volume_id),
+        };
+
+        let club_label = if row.is_primary {
+            row.server_name.clone()
+        } else {
+            format!("{} — {}", row.server_name, row.club_name)
+        };
+
+        let mut embed = CreateEmbed::default()
+            .title("📖 Reading Progress Reminder")
+            .description(format!(
+                "You haven't updated your progress in **{}** for a while. How's the book going?",
+                club_label
+            ))
+            .field("Current Book", &book_title, false)
+            .color(0xB76E79)
+            .footer(CreateEmbedFooter::new(
+                "Use /remindme stop or /remindme pause to stop these reminders",
+            ));
+
+        if let Some(deadline) = row.deadline {
+            embed = embed.field("Deadline", format_deadline(deadline), true);
+        }
+
+        if let Some(progress) = row.progress_text.as_ref() {
+            embed = embed.field("Your Last Update", progress, false);
+        }
+
+        let components = vec![CreateActionRow::Buttons(vec![
+            CreateButton::new(format!("{}{}", REMINDER_BUTTON_PREFIX, row.club_id))
+                .label("Update Progress")
+                .style(ButtonStyle::Primary),
+        ])];
+
+        let user_id = serenity::UserId::new(row.user_id as u64);
+        let sent = match user_id.create_dm_channel(http).await {
+            Ok(channel) => channel
+                .send_message(
+                    http,
+                    CreateMessage::new().embed(embed).components(components),
+                )
+                .await
+                .map(|_| ()),
+            Err(err) => Err(err),
+        };
+
+        if let Err(err) = sent {
+            log_error_with_source("Couldn't send progress reminder DM", &err);
+        }
+
+        // Record the attempt either way so closed DMs don't get retried every tick
+        sqlx::query!(
+            "UPDATE user_progress_reminders SET last_reminded_at = NOW()
+             WHERE user_id = $1 AND club_id = $2",
+            row.user_id,
+            row.club_id
+        )
+        .execute(pool)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Handles the "Update Progress" button on reminder DMs and the modal it opens.
+pub async fn handle_interaction(
+    ctx: &serenity::Context,
+    interaction: &serenity::Interaction,
+    data: &Data,
+) -> Result<(), Error> {
+    match interaction {
+        serenity::Interaction::Component(component) => {
+            let Some(club_id) = component
+                .data
+                .custom_id
+                .strip_prefix(REMINDER_BUTTON_PREFIX)
+            else {
+                return Ok(());
+            };
+
+            let modal = CreateModal::new(
+                format!("{}{}", REMINDER_MODAL_PREFIX, club_id),
+                "Update Reading Progress",
+            )
+            .components(vec![CreateActionRow::InputText(
+                CreateInputText::new(InputTextStyle::Short, "Your progress", REMINDER_INPUT_ID)
+                    .placeholder("e.g. 'Chapter 5', 'Page 123', '50% done'")
+                    .max_length(280)
+                    .required(true),
+            )]);
+
+            component
+                .create_response(&ctx.http, CreateInteractionResponse::Modal(modal))
+                .await?;
+        }
+        serenity::Interaction::Modal(modal) => {
+            let Some(club_id) = modal
+                .data
+                .custom_id
+                .strip_prefix(REMINDER_MODAL_PREFIX)
+                .and_then(|id| id.parse::<i32>().ok())
+            else {
+                return Ok(());
+            };
+
+            let progress_text = modal
+                .data
+                .components
+                .iter()
+                .flat_map(|row| row.components.iter())
+                .find_map(|component| match component {
+                    ActionRowComponent::InputText(input)
+                        if input.custom_id == REMINDER_INPUT_ID =>
+                    {
+                        input.value.clone()
+                    }
+                    _ => None,
+                })
+                .unwrap_or_default();
+
+            let embed = submit_reminder_progress(
+                &data.database,
+                &data.google_books,
+                club_id,
+                modal.user.id.get() as i64,
+                &progress_text,
+            )
+            .await?;
+
+            modal
+                .create_response(
+                    &ctx.http,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new().embed(embed),
+                    ),
+                )
+                .await?;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+async fn submit_reminder_progress(
+    pool: &PgPool,
+    google_books: &CachedGoogleBooksClient,
+    club_id: i32,
+    user_id: i64,
+    progress_text: &str,
+) -> Result<CreateEmbed, Error> {
+    let Some(club) = club_by_id(pool, club_id).await? else {
+        return Ok(CreateEmbed::default()
+            .title("❌ Club Not Found")
+            .description("That book club no longer exists, so there's nothing to update.")
+            .color(0xB76E79));
+    };
+
+    if is_progress_banned(pool, club.server_id, user_id).await? {
+        return Ok(CreateEmbed::default()
+            .title("🚫 Progress Command Disabled")
+            .description("You are banned from using /progress commands in this server.")
+            .color(0xB76E79));
+    }
+
+    let current_book = sqlx::query!(
+        "SELECT volume_id FROM server_current_book WHERE club_id = $1",
+        club.club_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let Some(book) = current_book else {
+        return Ok(CreateEmbed::default()
+            .title("No Current Book")
+            .description("That club isn't reading a book right now, so there's nothing to update.")
+            .color(0xB76E79)
+            .footer(CreateEmbedFooter::new("Powered by Google Books API")));
+    };
+
+    // DMs have no channel gate; stored text is still filtered wherever it is displayed.
+    let allow_unrestricted_sexual = server_maturity_enabled_by_id(pool, club.server_id).await?;
+    let sanitized_progress = match validate_progress_text(progress_text, allow_unrestricted_sexual)
+    {
+        Ok(text) => text,
+        Err(error_msg) => {
+            return Ok(CreateEmbed::default()
+                .title("❌ Invalid Progress Update")
+                .description(error_msg)
+                .color(0xB76E79)
+                .footer(CreateEmbedFooter::new("Powered by Google Books API")));
+        }
+    };
+
+    sqlx::query!(
+        "INSERT INTO user_reading_progress (user_id, club_id, volume_id, progress_text)
+         VALUES ($1, $2, $3, $4)
+         ON CONFLICT (user_id, club_id)
+         DO UPDATE SET volume_id = $3, progress_text = $4, updated_at = CURRENT_TIMESTAMP, dnf_at = NULL",
+        user_id,
+        club.club_id,
+        book.volume_id,
+        sanitized_progress
+    )
+    .execute(pool)
+    .await?;
+
+    let book_title = match google_books.get_volume(&book.volume_id).await {
+        Ok(volume) => volume.get_title(),
+        Err(_) => format!("Book ({})", book.volume_id),
+    };
+
+    Ok(CreateEmbed::default()
+        .title("✅ Progress Updated")
+        .field("Book", book_title, false)
+        .field("Your Progress", &sanitized_progress, false)
+        .color(0xB76E79)
+        .footer(CreateEmbedFooter::new("Powered by Google Books API")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::within_quiet_hours;
+
+    #[test]
+    fn quiet_hours_disabled_without_both_bounds() {
+        assert!(!within_quiet_hours(3, None, None));
+        assert!(!within_quiet_hours(3, Some(1), None));
+    }
+
+    #[test]
+    fn quiet_hours_same_day_window() {
+        assert!(within_quiet_hours(9, Some(9), Some(17)));
+        assert!(within_quiet_hours(16, Some(9), Some(17)));
+        assert!(!within_quiet_hours(17, Some(9), Some(17)));
+        assert!(!within_quiet_hours(8, Some(9), Some(17)));
+    }
+
+    #[test]
+    fn quiet_hours_wrap_past_midnight() {
+        assert!(within_quiet_hours(23, Some(22), Some(7)));
+        assert!(within_quiet_hours(0, Some(22), Some(7)));
+        assert!(within_quiet_hours(6, Some(22), Some(7)));
+        assert!(!within_quiet_hours(7, Some(22), Some(7)));
+        assert!(!within_quiet_hours(12, Some(22), Some(7)));
+    }
+}
@@ -1,9 +1,11 @@
+use crate::commands::server_admin::select::{PollOpenOutcome, PollSettings, open_selection_poll};
 use crate::google_books_cache::CachedGoogleBooksClient;
 use crate::poll_handler;
 use crate::types::Error;
-use crate::util::log_error_with_source;
+use crate::util::{auto_poll_config, club_by_id, log_error, log_error_with_source};
 use poise::serenity_prelude as serenity;
 use sqlx::PgPool;
+use sqlx::types::chrono::Utc;
 use std::sync::Arc;
 use tokio::time::{self, Duration};
 
@@ -20,6 +22,9 @@ pub fn spawn_selection_poll_watcher(
             if let Err(err) = check_expired_polls(&http, &pool, &google_books).await {
                 log_error_with_source("Selection poll watcher error", &err);
             }
+            if let Err(err) = open_scheduled_polls(&http, &pool, &google_books).await {
+                log_error_with_source("Scheduled selection poll error", &err);
+            }
         }
     });
 }
@@ -61,3 +66,105 @@ async fn check_expired_polls(
 
     Ok(())
 }
+
+/// Queues an automatic selection poll after a book finishes, if the server opted in with
+/// `/config autopoll`. Replaces any poll already waiting for this club.
+pub async fn schedule_auto_poll(
+    pool: &PgPool,
+    club_id: i32,
+    fallback_channel_id: Option<i64>,
+) -> Result<(), Error> {
+    sqlx::query!(
+        "INSERT INTO scheduled_selection_polls (club_id, fallback_channel_id, run_at)
+         SELECT sc.club_id, $2, NOW() + make_interval(hours => sbc.auto_poll_delay_hours)
+         FROM server_clubs sc
+         JOIN server_bot_config sbc ON sbc.server_id = sc.server_id
+         WHERE sc.club_id = $1 AND sbc.auto_poll_enabled
+         ON CONFLICT (club_id)
+         DO UPDATE SET fallback_channel_id = EXCLUDED.fallback_channel_id, run_at = EXCLUDED.run_at",
+        club_id,
+        fallback_channel_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+async fn open_scheduled_polls(
+    http: &Arc<serenity::Http>,
+    pool: &PgPool,
+    google_books: &CachedGoogleBooksClient,
+) -> Result<(), Error> {
+    let due = sqlx::query!(
+        "SELECT club_id, fallback_channel_id FROM scheduled_selection_polls WHERE run_at <= NOW()"
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for row in due {
+        // Each schedule fires once, whatever the outcome
+        sqlx::query!(
+            "DELETE FROM scheduled_selection_polls WHERE club_id = $1",
+            row.club_id
+        )
+        .execute(pool)
+        .await?;
+
+        let Some(club) = club_by_id(pool, row.club_id).await? else {
+            continue;
+        };
+        let config = auto_poll_config(pool, club.server_id).await?;
+        if !config.enabled {
+            continue;
+        }
+
+        let duration_hours = config.duration_hours.clamp(1, 167) as u64;
+        let deadline = config.reading_days.map(|days| {
+            Utc::now()
+                + chrono::Duration::hours(duration_hours as i64)
+                + chrono::Duration::days(days as i64)
+        });
+        let settings = PollSettings {
+            poll_size: config.poll_size.clamp(2, 10),
+            duration_hours,
+            deadline,
+            by_votes: false,
+        };
+        let fallback_channel = row
+            .fallback_channel_id
+            .map(|id| serenity::ChannelId::new(id as u64));
+
+        match open_selection_poll(http, pool, google_books, &club, fallback_channel, &settings)
+            .await
+        {
+            Ok(PollOpenOutcome::Posted { .. }) => {}
+            Ok(PollOpenOutcome::InsufficientBooks) => {
+                if let Some(channel) = fallback_channel {
+                    let command_option = club.command_option();
+                    let _ = channel
+                        .say(
+                            http,
+                            format!(
+                                "📚 I tried to open the next selection poll automatically, but the queue needs at least 2 books. Add some with `/queue add{command_option}`, then run `/select poll{command_option}`."
+                            ),
+                        )
+                        .await;
+                }
+            }
+            // An admin already picked a book or opened a poll themselves
+            Ok(PollOpenOutcome::BookAlreadySelected | PollOpenOutcome::PollAlreadyActive) => {}
+            Ok(PollOpenOutcome::NoChannel) => {
+                log_error(format!(
+                    "No channel to post the automatic selection poll for club {} in server {}",
+                    club.club_id, club.server_id
+                ));
+            }
+            Err(err) => {
+                log_error_with_source("Couldn't open automatic selection poll", &err);
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,330 @@
+use crate::google_books_cache::CachedGoogleBooksClient;
+use crate::types::Error;
+use crate::util::{Club, SelectionRules, selection_rules};
+use sqlx::PgPool;
+
+// Embed field values are capped at 1024 characters
+const SUMMARY_MAX_LENGTH: usize = 1000;
+
+/// Why a queued book was held back from selection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ExclusionReason {
+    RecentSuggester { window: i32 },
+    RepeatCategory(String),
+    TooLong { pages: i32, max_pages: i32 },
+}
+
+impl ExclusionReason {
+    pub(crate) fn describe(&self) -> String {
+        match self {
+            ExclusionReason::RecentSuggester { window: 1 } => {
+                "their pick was the last book read".to_string()
+            }
+            ExclusionReason::RecentSuggester { window } => {
+                format!("their pick was one of the last {} books read", window)
+            }
+            ExclusionReason::RepeatCategory(category) => {
+                format!("same category as the last book ({})", category)
+            }
+            ExclusionReason::TooLong { pages, max_pages } => {
+                format!("{} pages, over the {}-page limit", pages, max_pages)
+            }
+        }
+    }
+}
+
+pub(crate) struct ExcludedBook {
+    pub volume_id: String,
+    pub title: String,
+    pub suggested_by: String,
+    pub mature: bool,
+    pub reason: ExclusionReason,
+}
+
+/// Queue books the server's selection rules hold back from the next pick.
+#[derive(Default)]
+pub(crate) struct RuleCheck {
+    pub excluded: Vec<ExcludedBook>,
+}
+
+impl RuleCheck {
+    pub(crate) fn excluded_ids(&self) -> Vec<String> {
+        self.excluded
+            .iter()
+            .map(|book| book.volume_id.clone())
+            .collect()
+    }
+
+    /// One line per excluded book, trimmed to fit an embed field. Mature titles are hidden
+    /// unless the destination channel allows them.
+    pub(crate) fn summary(&self, allow_mature: bool) -> Option<String> {
+        if self.excluded.is_empty() {
+            return None;
+        }
+
+        let mut summary = String::new();
+        for (shown, book) in self.excluded.iter().enumerate() {
+            let title = if book.mature && !allow_mature {
+                "A mature-rated book"
+            } else {
+                &book.title
+            };
+            let line = format!(
+                "• **{}** ({}) — {}\n",
+                title,
+                book.suggested_by,
+                book.reason.describe()
+            );
+            if summary.len() + line.len() > SUMMARY_MAX_LENGTH {
+                summary.push_str(&format!("…and {} more", self.excluded.len() - shown));
+                break;
+            }
+            summary.push_str(&line);
+        }
+
+        Some(summary.trim_end().to_string())
+    }
+}
+
+/// The category a book is compared by: the first one Google Books lists.
+pub(crate) fn primary_category(categories: &[String]) -> Option<&str> {
+    categories
+        .first()
+        .map(|category| category.trim())
+        .filter(|category| !category.is_empty())
+}
+
+/// Returns the first rule a candidate breaks, if any. Books with unknown metadata pass the
+/// rules that depend on it.
+pub(crate) fn exclusion_reason(
+    rules: &SelectionRules,
+    suggester_id: i64,
+    categories: &[String],
+    page_count: Option<i32>,
+    recent_suggesters: &[i64],
+    last_category: Option<&str>,
+) -> Option<ExclusionReason> {
+    if rules.skip_recent_suggesters > 0 && recent_suggesters.contains(&suggester_id) {
+        return Some(ExclusionReason::RecentSuggester {
+            window: rules.skip_recent_suggesters,
+        });
+    }
+
+    if rules.avoid_repeat_category
+        && let (Some(last), Some(category)) = (last_category, primary_category(categories))
+        && last.eq_ignore_ascii_case(category)
+    {
+        return Some(ExclusionReason::RepeatCategory(category.to_string()));
+    }
+
+    if let (Some(max_pages), Some(pages)) = (rules.max_pages, page_count)
+        && pages > max_pages
+    {
+        return Some(ExclusionReason::TooLong { pages, max_pages });
+    }
+
+    None
+}
+
+/// Evaluates the server's selection rules against a club's queue and completed book history.
+/// Pinned books are left alone, since pinning is an explicit admin choice.
+pub(crate) async fn check_queue(
+    pool: &PgPool,
+    google_books: &CachedGoogleBooksClient,
+    club: &Club,
+) -> Result<RuleCheck, Error> {
+    let rules = selection_rules(pool, club.server_id).await?;
+    if !rules.any_active() {
+        return Ok(RuleCheck::default());
+    }
+
+    let entries = sqlx::query!(
+        "SELECT sbq.volume_id, sbq.suggested_by_user_id, du.username
+         FROM server_book_queue sbq
+         JOIN discord_users du ON du.user_id = sbq.suggested_by_user_id
+         WHERE sbq.club_id = $1 AND NOT sbq.pinned
+         ORDER BY sbq.position",
+        club.club_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    if entries.is_empty() {
+        return Ok(RuleCheck::default());
+    }
+
+    let recent_suggesters: Vec<i64> = if rules.skip_recent_suggesters > 0 {
+        sqlx::query_scalar!(
+            "SELECT suggested_by_user_id FROM server_completed_books
+             WHERE club_id = $1
+             ORDER BY completed_at DESC
+             LIMIT $2",
+            club.club_id,
+            rules.skip_recent_suggesters as i64
+        )
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .flatten()
+        .collect()
+    } else {
+        Vec::new()
+    };
+
+    let last_category = if rules.avoid_repeat_category {
+        let last_volume_id = sqlx::query_scalar!(
+            "SELECT volume_id FROM server_completed_books
+             WHERE club_id = $1
+             ORDER BY completed_at DESC
+             LIMIT 1",
+            club.club_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        match last_volume_id {
+            Some(volume_id) => google_books
+                .get_volume(&volume_id)
+                .await
+                .ok()
+                .and_then(|volume| primary_category(&volume.get_categories()).map(str::to_string)),
+            None => None,
+        }
+    } else {
+        None
+    };
+
+    let volume_ids: Vec<String> = entries.iter().map(|e| e.volume_id.clone()).collect();
+    let volumes = google_books.get_volumes_batch(&volume_ids).await;
+
+    let mut check = RuleCheck::default();
+    for (entry, volume) in entries.into_iter().zip(volumes) {
+        let (categories, page_count) = match &volume {
+            Ok(volume) => (volume.get_categories(), volume.get_page_count()),
+            Err(_) => (Vec::new(), None),
+        };
+
+        let Some(reason) = exclusion_reason(
+            &rules,
+            entry.suggested_by_user_id,
+            &categories,
+            page_count,
+            &recent_suggesters,
+            last_category.as_deref(),
+        ) else {
+            continue;
+        };
+
+        let (title, mature) = match &volume {
+            Ok(volume) => (volume.get_title(), volume.is_mature()),
+            Err(_) => (format!("Book ({})", entry.volume_id), false),
+        };
+
+        check.excluded.push(ExcludedBook {
+            volume_id: entry.volume_id,
+            title,
+            suggested_by: entry.username,
+            mature,
+            reason,
+        });
+    }
+
+    Ok(check)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExclusionReason, exclusion_reason, primary_category};
+    use crate::util::SelectionRules;
+
+    fn categories(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn no_rules_exclude_nothing() {
+        let rules = SelectionRules::default();
+        let reason = exclusion_reason(
+            &rules,
+            1,
+            &categories(&["Fiction"]),
+            Some(900),
+            &[1],
+            Some("Fiction"),
+        );
+        assert_eq!(reason, None);
+    }
+
+    #[test]
+    fn recent_suggesters_are_skipped() {
+        let rules = SelectionRules {
+            skip_recent_suggesters: 2,
+            ..Default::default()
+        };
+        assert_eq!(
+            exclusion_reason(&rules, 7, &[], None, &[3, 7], None),
+            Some(ExclusionReason::RecentSuggester { window: 2 })
+        );
+        assert_eq!(exclusion_reason(&rules, 8, &[], None, &[3, 7], None), None);
+    }
+
+    #[test]
+    fn repeat_category_matches_primary_category_only() {
+        let rules = SelectionRules {
+            avoid_repeat_category: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            exclusion_reason(
+                &rules,
+                1,
+                &categories(&["fiction", "Fantasy"]),
+                None,
+                &[],
+                Some("Fiction")
+            ),
+            Some(ExclusionReason::RepeatCategory("fiction".to_string()))
+        );
+        assert_eq!(
+            exclusion_reason(
+                &rules,
+                1,
+                &categories(&["History", "Fiction"]),
+                None,
+                &[],
+                Some("Fiction")
+            ),
+            None
+        );
+        assert_eq!(
+            exclusion_reason(&rules, 1, &[], None, &[], Some("Fiction")),
+            None
+        );
+    }
+
+    #[test]
+    fn page_limit_ignores_unknown_lengths() {
+        let rules = SelectionRules {
+            max_pages: Some(400),
+            ..Default::default()
+        };
+        assert_eq!(
+            exclusion_reason(&rules, 1, &[], Some(401), &[], None),
+            Some(ExclusionReason::TooLong {
+                pages: 401,
+                max_pages: 400
+            })
+        );
+        assert_eq!(exclusion_reason(&rules, 1, &[], Some(400), &[], None), None);
+        assert_eq!(exclusion_reason(&rules, 1, &[], None, &[], None), None);
+    }
+
+    #[test]
+    fn blank_primary_category_is_ignored() {
+        assert_eq!(primary_category(&categories(&["  "])), None);
+        assert_eq!(
+            primary_category(&categories(&[" Poetry ", "Fiction"])),
+            Some("Poetry")
+        );
+    }
+}
@@ -13,6 +13,8 @@ pub struct Data {
     // pub google_books: GoogleBooksClient,
     pub google_books: CachedGoogleBooksClient,
     pub guild_cache: Arc<RwLock<HashSet<serenity::GuildId>>>,
+    /// Highest favorites or reading list limit a server can configure (`MAX_LIST_LIMIT`).
+    pub list_limit_cap: i32,
 }
 
 #[derive(poise::ChoiceParameter, Clone, Copy, Debug, PartialEq, Eq)]
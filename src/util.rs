@@ -86,6 +86,26 @@ pub fn detect_query_mode(query: &str) -> QueryMode {
     }
 }
 
+// Discord's "Unknown Member" JSON error code
+const UNKNOWN_MEMBER_CODE: isize = 10007;
+
+/// Whether the user is no longer in the guild, including accounts that were deleted.
+pub async fn member_left_guild(
+    http: &serenity::Http,
+    guild_id: serenity::GuildId,
+    user_id: serenity::UserId,
+) -> Result<bool, types::Error> {
+    match http.get_member(guild_id, user_id).await {
+        Ok(_) => Ok(false),
+        Err(serenity::Error::Http(serenity::HttpError::UnsuccessfulRequest(response)))
+            if response.error.code == UNKNOWN_MEMBER_CODE =>
+        {
+            Ok(true)
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
 pub async fn get_guild_name(ctx: &PoiseContext<'_>) -> String {
     if let Some(guild) = ctx.guild() {
         return guild.name.clone();
@@ -199,35 +219,516 @@ pub async fn queue_commands_enabled(pool: &PgPool, server_id: i64) -> Result<boo
         .unwrap_or(true))
 }
 
-pub async fn pin_polls_enabled(pool: &PgPool, server_id: i64) -> Result<bool, types::Error> {
+pub async fn pin_polls_enabled(pool: &PgPool, club_id: i32) -> Result<bool, types::Error> {
     let record = sqlx::query!(
-        "SELECT COALESCE(pin_polls, TRUE) AS pin_polls FROM server_bot_config WHERE server_id = $1",
-        server_id
+        "SELECT pin_polls FROM server_clubs WHERE club_id = $1",
+        club_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(record.map(|row| row.pin_polls).unwrap_or(true))
+}
+
+pub async fn ratings_participants_only(pool: &PgPool, club_id: i32) -> Result<bool, types::Error> {
+    let record = sqlx::query!(
+        "SELECT ratings_participants_only FROM server_clubs WHERE club_id = $1",
+        club_id
     )
     .fetch_optional(pool)
     .await?;
 
     Ok(record
-        .map(|row| row.pin_polls.unwrap_or(true))
-        .unwrap_or(true))
+        .map(|row| row.ratings_participants_only)
+        .unwrap_or(false))
 }
 
 pub async fn auto_complete_on_deadline_enabled(
     pool: &PgPool,
-    server_id: i64,
+    club_id: i32,
 ) -> Result<bool, types::Error> {
     let record = sqlx::query!(
-        "SELECT COALESCE(auto_complete_on_deadline, FALSE) AS auto_complete_on_deadline FROM server_bot_config WHERE server_id = $1",
-        server_id
+        "SELECT auto_complete_on_deadline FROM server_clubs WHERE club_id = $1",
+        club_id
     )
     .fetch_optional(pool)
     .await?;
 
     Ok(record
-        .map(|row| row.auto_complete_on_deadline.unwrap_or(false))
+        .map(|row| row.auto_complete_on_deadline)
         .unwrap_or(false))
 }
 
+pub async fn club_announcement_channel(
+    pool: &PgPool,
+    club_id: i32,
+) -> Result<Option<serenity::ChannelId>, types::Error> {
+    let record = sqlx::query!(
+        "SELECT announcement_channel_id FROM server_clubs WHERE club_id = $1",
+        club_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(record
+        .and_then(|row| row.announcement_channel_id)
+        .map(|id| serenity::ChannelId::new(id as u64)))
+}
+
+pub const PRIMARY_CLUB_NAME: &str = "Main";
+
+/// A named book club within a server. Commands act on the server's primary club unless
+/// another one is named.
+#[derive(Clone, Debug)]
+pub struct Club {
+    pub club_id: i32,
+    pub server_id: i64,
+    pub name: String,
+    pub is_primary: bool,
+}
+
+impl Club {
+    /// Appended to embed titles so members can tell clubs apart. Empty for the primary club,
+    /// so servers with a single club see no difference.
+    pub fn title_suffix(&self) -> String {
+        if self.is_primary {
+            String::new()
+        } else {
+            format!(" — {}", self.name)
+        }
+    }
+
+    /// The `club:` option to repeat in command hints, e.g. `/queue add club:Nonfiction`.
+    pub fn command_option(&self) -> String {
+        if self.is_primary {
+            String::new()
+        } else {
+            format!(" club:{}", self.name)
+        }
+    }
+}
+
+pub async fn club_by_id(pool: &PgPool, club_id: i32) -> Result<Option<Club>, types::Error> {
+    let club = sqlx::query_as!(
+        Club,
+        "SELECT club_id, server_id, name, is_primary FROM server_clubs WHERE club_id = $1",
+        club_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(club)
+}
+
+pub async fn find_club(
+    pool: &PgPool,
+    server_id: i64,
+    name: &str,
+) -> Result<Option<Club>, types::Error> {
+    let club = sqlx::query_as!(
+        Club,
+        "SELECT club_id, server_id, name, is_primary FROM server_clubs
+         WHERE server_id = $1 AND LOWER(name) = LOWER($2)",
+        server_id,
+        name.trim()
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(club)
+}
+
+pub async fn list_clubs(pool: &PgPool, server_id: i64) -> Result<Vec<Club>, types::Error> {
+    let clubs = sqlx::query_as!(
+        Club,
+        "SELECT club_id, server_id, name, is_primary FROM server_clubs
+         WHERE server_id = $1
+         ORDER BY is_primary DESC, LOWER(name)",
+        server_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(clubs)
+}
+
+/// Returns the server's primary club, creating it the first time a server needs one. The
+/// server must already exist in `discord_servers`.
+pub async fn primary_club(pool: &PgPool, server_id: i64) -> Result<Club, types::Error> {
+    sqlx::query!(
+        "INSERT INTO server_clubs (server_id, name, is_primary)
+         VALUES ($1, $2, TRUE)
+         ON CONFLICT DO NOTHING",
+        server_id,
+        PRIMARY_CLUB_NAME
+    )
+    .execute(pool)
+    .await?;
+
+    let club = sqlx::query_as!(
+        Club,
+        "SELECT club_id, server_id, name, is_primary FROM server_clubs
+         WHERE server_id = $1 AND is_primary",
+        server_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(club)
+}
+
+/// Resolves a command's optional `club` argument, defaulting to the primary club. Replies with
+/// an error and returns `None` when the server has no club by that name.
+pub async fn resolve_club(
+    ctx: &PoiseContext<'_>,
+    club: Option<&str>,
+) -> Result<Option<Club>, types::Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(None);
+    };
+    let pool = &ctx.data().database;
+    let server_id = guild_id.get() as i64;
+
+    let name = club.map(str::trim).filter(|name| !name.is_empty());
+    let Some(name) = name else {
+        let guild_name = get_guild_name(ctx).await;
+        ensure_server_exists(pool, guild_id, &guild_name).await?;
+        return Ok(Some(primary_club(pool, server_id).await?));
+    };
+
+    if let Some(club) = find_club(pool, server_id, name).await? {
+        return Ok(Some(club));
+    }
+
+    let clubs = list_clubs(pool, server_id).await?;
+    let available = if clubs.is_empty() {
+        PRIMARY_CLUB_NAME.to_string()
+    } else {
+        clubs
+            .iter()
+            .map(|club| club.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let embed = serenity::CreateEmbed::default()
+        .title("❌ Club Not Found")
+        .description(format!(
+            "There's no club called **{}** in this server.\n\nClubs: {}",
+            name, available
+        ))
+        .color(0xB76E79);
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(None)
+}
+
+/// Autocompletes the `club` option with the server's club names.
+pub async fn autocomplete_club(ctx: PoiseContext<'_>, partial: &str) -> Vec<String> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Vec::new();
+    };
+    let partial = partial.to_lowercase();
+
+    list_clubs(&ctx.data().database, guild_id.get() as i64)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|club| club.name)
+        .filter(|name| name.to_lowercase().contains(&partial))
+        .collect()
+}
+
+/// Per-server limits applied to `/queue add` (admins bypass them with `/adminqueue add`).
+pub struct QueueRules {
+    pub max_per_user: i32,
+    pub cooldown_days: i32,
+    pub block_completed: bool,
+}
+
+impl Default for QueueRules {
+    fn default() -> Self {
+        Self {
+            max_per_user: 1,
+            cooldown_days: 0,
+            block_completed: false,
+        }
+    }
+}
+
+pub async fn queue_rules(pool: &PgPool, server_id: i64) -> Result<QueueRules, types::Error> {
+    let record = sqlx::query!(
+        "SELECT queue_max_per_user, queue_cooldown_days, queue_block_completed
+         FROM server_bot_config WHERE server_id = $1",
+        server_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(record
+        .map(|row| QueueRules {
+            max_per_user: row.queue_max_per_user,
+            cooldown_days: row.queue_cooldown_days,
+            block_completed: row.queue_block_completed,
+        })
+        .unwrap_or_default())
+}
+
+/// Highest list limit servers can configure when the operator doesn't set `MAX_LIST_LIMIT`.
+pub const DEFAULT_LIST_LIMIT_CAP: i32 = 25;
+/// Entries shown per page by `/favorite view` and `/readinglist view`.
+pub const LIST_PAGE_SIZE: usize = 5;
+
+/// Per-server caps on how many favorites and reading list books each member can keep.
+pub struct ListLimits {
+    pub favorite_books: i32,
+    pub favorite_authors: i32,
+    pub reading_list: i32,
+}
+
+impl ListLimits {
+    fn capped(self, cap: i32) -> Self {
+        Self {
+            favorite_books: self.favorite_books.min(cap),
+            favorite_authors: self.favorite_authors.min(cap),
+            reading_list: self.reading_list.min(cap),
+        }
+    }
+}
+
+impl Default for ListLimits {
+    fn default() -> Self {
+        Self {
+            favorite_books: 5,
+            favorite_authors: 5,
+            reading_list: 5,
+        }
+    }
+}
+
+/// The server's list limits, held to `cap` so lowering `MAX_LIST_LIMIT` applies to servers
+/// that configured more.
+pub async fn list_limits(
+    pool: &PgPool,
+    server_id: i64,
+    cap: i32,
+) -> Result<ListLimits, types::Error> {
+    let record = sqlx::query!(
+        "SELECT favorite_books_limit, favorite_authors_limit, reading_list_limit
+         FROM server_bot_config WHERE server_id = $1",
+        server_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(record
+        .map(|row| ListLimits {
+            favorite_books: row.favorite_books_limit,
+            favorite_authors: row.favorite_authors_limit,
+            reading_list: row.reading_list_limit,
+        })
+        .unwrap_or_default()
+        .capped(cap))
+}
+
+/// How many pages `total` entries fill at `LIST_PAGE_SIZE` per page (at least one).
+pub fn list_page_count(total: usize) -> usize {
+    total.div_ceil(LIST_PAGE_SIZE).max(1)
+}
+
+/// Sends `pages` with Previous/Next buttons that only the invoking member can use. A single
+/// page goes out without buttons.
+pub async fn send_paginated(
+    ctx: &PoiseContext<'_>,
+    pages: Vec<serenity::CreateEmbed>,
+) -> Result<(), types::Error> {
+    use futures::StreamExt;
+    use serenity::{
+        ButtonStyle, CreateActionRow, CreateButton, CreateInteractionResponse,
+        CreateInteractionResponseMessage,
+    };
+
+    let buttons = |page: usize| {
+        vec![CreateActionRow::Buttons(vec![
+            CreateButton::new("list_prev_page")
+                .label("Previous")
+                .style(ButtonStyle::Secondary)
+                .disabled(page == 0),
+            CreateButton::new("list_next_page")
+                .label("Next")
+                .style(ButtonStyle::Secondary)
+                .disabled(page + 1 >= pages.len()),
+        ])]
+    };
+
+    let Some(first) = pages.first() else {
+        return Ok(());
+    };
+    let mut reply = poise::CreateReply::default().embed(first.clone());
+    if pages.len() > 1 {
+        reply = reply.components(buttons(0));
+    }
+    let message = ctx.send(reply).await?.into_message().await?;
+    if pages.len() <= 1 {
+        return Ok(());
+    }
+
+    let mut page = 0usize;
+    let mut interactions = message
+        .await_component_interactions(ctx)
+        .timeout(std::time::Duration::from_secs(120))
+        .author_id(ctx.author().id)
+        .stream();
+    while let Some(mci) = interactions.next().await {
+        page = match mci.data.custom_id.as_str() {
+            "list_prev_page" => page.saturating_sub(1),
+            "list_next_page" => (page + 1).min(pages.len() - 1),
+            _ => continue,
+        };
+        mci.create_response(
+            ctx.serenity_context(),
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .embeds(vec![pages[page].clone()])
+                    .components(buttons(page)),
+            ),
+        )
+        .await
+        .ok();
+    }
+
+    Ok(())
+}
+
+/// Days a queue entry may sit before the expiry watcher removes it; `None` keeps entries forever.
+pub async fn queue_ttl_days(pool: &PgPool, server_id: i64) -> Result<Option<i32>, types::Error> {
+    let record = sqlx::query!(
+        "SELECT queue_ttl_days FROM server_bot_config WHERE server_id = $1",
+        server_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(record.and_then(|row| row.queue_ttl_days))
+}
+
+/// The month and day the yearly `/wrapped` recap is posted, or `None` when automatic posting
+/// is off.
+pub async fn wrapped_post_date(
+    pool: &PgPool,
+    server_id: i64,
+) -> Result<Option<(u32, u32)>, types::Error> {
+    let record = sqlx::query!(
+        "SELECT wrapped_post_month, wrapped_post_day FROM server_bot_config WHERE server_id = $1",
+        server_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(record.and_then(|row| {
+        Some((
+            row.wrapped_post_month? as u32,
+            row.wrapped_post_day.unwrap_or(1) as u32,
+        ))
+    }))
+}
+
+/// Per-server constraints applied before `/select` commands and selection polls choose
+/// candidates. Pinned queue books bypass them.
+#[derive(Default)]
+pub struct SelectionRules {
+    /// Skip members whose suggestion was one of the last N books read; 0 turns this off.
+    pub skip_recent_suggesters: i32,
+    pub avoid_repeat_category: bool,
+    pub max_pages: Option<i32>,
+}
+
+impl SelectionRules {
+    pub fn any_active(&self) -> bool {
+        self.skip_recent_suggesters > 0 || self.avoid_repeat_category || self.max_pages.is_some()
+    }
+}
+
+pub async fn selection_rules(
+    pool: &PgPool,
+    server_id: i64,
+) -> Result<SelectionRules, types::Error> {
+    let record = sqlx::query!(
+        "SELECT selection_skip_recent_suggesters, selection_avoid_repeat_category, selection_max_pages
+         FROM server_bot_config WHERE server_id = $1",
+        server_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(record
+        .map(|row| SelectionRules {
+            skip_recent_suggesters: row.selection_skip_recent_suggesters,
+            avoid_repeat_category: row.selection_avoid_repeat_category,
+            max_pages: row.selection_max_pages,
+        })
+        .unwrap_or_default())
+}
+
+/// Settings for the selection poll opened automatically after a book finishes.
+pub struct AutoPollConfig {
+    pub enabled: bool,
+    pub delay_hours: i32,
+    pub poll_size: i32,
+    pub duration_hours: i32,
+    pub reading_days: Option<i32>,
+}
+
+impl Default for AutoPollConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            delay_hours: 0,
+            poll_size: 5,
+            duration_hours: 24,
+            reading_days: None,
+        }
+    }
+}
+
+pub async fn auto_poll_config(
+    pool: &PgPool,
+    server_id: i64,
+) -> Result<AutoPollConfig, types::Error> {
+    let record = sqlx::query!(
+        "SELECT auto_poll_enabled, auto_poll_delay_hours, auto_poll_size,
+                auto_poll_duration_hours, auto_poll_reading_days
+         FROM server_bot_config WHERE server_id = $1",
+        server_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(record
+        .map(|row| AutoPollConfig {
+            enabled: row.auto_poll_enabled,
+            delay_hours: row.auto_poll_delay_hours,
+            poll_size: row.auto_poll_size,
+            duration_hours: row.auto_poll_duration_hours,
+            reading_days: row.auto_poll_reading_days,
+        })
+        .unwrap_or_default())
+}
+
+/// Returns when a member may suggest again if they're still cooling down after one of their
+/// picks was read, or `None` if they're free to suggest.
+pub fn suggestion_cooldown_ends(
+    last_completed_at: Option<DateTime<Utc>>,
+    cooldown_days: i32,
+    now: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    if cooldown_days <= 0 {
+        return None;
+    }
+    let ends = last_completed_at? + chrono::Duration::days(cooldown_days as i64);
+    (ends > now).then_some(ends)
+}
+
 pub fn truncate_on_char_boundary(s: &str, max_bytes: usize) -> (&str, usize) {
     if s.len() <= max_bytes {
         return (s, 0);
@@ -241,6 +742,139 @@ pub fn truncate_on_char_boundary(s: &str, max_bytes: usize) -> (&str, usize) {
     (&s[..end], s.len() - end)
 }
 
+/// Discord's limits on embed descriptions, embed field values and message content.
+pub const EMBED_DESCRIPTION_LIMIT: usize = 4096;
+pub const EMBED_FIELD_LIMIT: usize = 1024;
+pub const MESSAGE_CONTENT_LIMIT: usize = 2000;
+
+// Room for a trailing "…and N more" line
+const MORE_LINE_RESERVE: usize = 32;
+
+/// Shortens text to at most `max_bytes`, marking the cut with an ellipsis.
+pub fn truncate_with_ellipsis(s: &str, max_bytes: usize) -> String {
+    if s.len() <= max_bytes {
+        return s.to_string();
+    }
+    let (prefix, _) = truncate_on_char_boundary(s, max_bytes.saturating_sub('…'.len_utf8()));
+    format!("{prefix}…")
+}
+
+/// Joins lines without going over `max_len` bytes. `total` counts every entry the lines were
+/// taken from, and any that aren't shown are summed up as "…and N more". Returns the text and
+/// how many entries were left out.
+pub fn join_lines_capped(lines: &[String], total: usize, max_len: usize) -> (String, usize) {
+    let total = total.max(lines.len());
+    let mut text = String::new();
+    for (shown, line) in lines.iter().enumerate() {
+        let remaining = total - shown;
+        let reserve = if remaining > 1 { MORE_LINE_RESERVE } else { 0 };
+        let separator = usize::from(!text.is_empty());
+        if text.len() + separator + line.len() + reserve > max_len {
+            push_more_line(&mut text, remaining);
+            return (text, remaining);
+        }
+        if separator == 1 {
+            text.push('\n');
+        }
+        text.push_str(line);
+    }
+    let hidden = total - lines.len();
+    if hidden > 0 {
+        push_more_line(&mut text, hidden);
+    }
+    (text, hidden)
+}
+
+fn push_more_line(text: &mut String, count: usize) {
+    if !text.is_empty() {
+        text.push('\n');
+    }
+    text.push_str(&format!("…and {} more", count));
+}
+
+/// How members fared with completed books, from the snapshot taken when each book finished.
+#[derive(Debug, Default, PartialEq)]
+pub struct Participation {
+    pub finished: i64,
+    pub dnf: i64,
+    pub not_started: i64,
+}
+
+impl Participation {
+    /// Percentage of members who started a book and went on to finish it. `None` when nobody
+    /// started, so books with no tracked progress don't drag the rate down.
+    pub fn completion_rate(&self) -> Option<f64> {
+        let started = self.finished + self.dnf;
+        (started > 0).then(|| self.finished as f64 * 100.0 / started as f64)
+    }
+
+    pub fn summary(&self) -> String {
+        if self.finished + self.dnf + self.not_started == 0 {
+            return "No one tracked their progress.".to_string();
+        }
+
+        let mut text = format!(
+            "{} finished • {} did not finish • {} didn't start",
+            self.finished, self.dnf, self.not_started
+        );
+        if let Some(rate) = self.completion_rate() {
+            text.push_str(&format!("\n{:.0}% completion rate", rate));
+        }
+        text
+    }
+}
+
+pub async fn book_participation(
+    pool: &PgPool,
+    completed_id: i32,
+) -> Result<Participation, types::Error> {
+    let row = sqlx::query!(
+        r#"
+        SELECT
+            COUNT(*) FILTER (WHERE status = 'finished') AS "finished!",
+            COUNT(*) FILTER (WHERE status = 'dnf') AS "dnf!",
+            COUNT(*) FILTER (WHERE status = 'not_started') AS "not_started!"
+        FROM completed_book_participants
+        WHERE completed_id = $1
+        "#,
+        completed_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(Participation {
+        finished: row.finished,
+        dnf: row.dnf,
+        not_started: row.not_started,
+    })
+}
+
+pub async fn club_participation(
+    pool: &PgPool,
+    club_id: i32,
+) -> Result<Participation, types::Error> {
+    let row = sqlx::query!(
+        r#"
+        SELECT
+            COUNT(*) FILTER (WHERE cbp.status = 'finished') AS "finished!",
+            COUNT(*) FILTER (WHERE cbp.status = 'dnf') AS "dnf!",
+            COUNT(*) FILTER (WHERE cbp.status = 'not_started') AS "not_started!"
+        FROM completed_book_participants cbp
+        JOIN server_completed_books scb ON scb.completed_id = cbp.completed_id
+        WHERE scb.club_id = $1
+        "#,
+        club_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(Participation {
+        finished: row.finished,
+        dnf: row.dnf,
+        not_started: row.not_started,
+    })
+}
+
 pub fn format_deadline(deadline: DateTime<Utc>) -> String {
     let now = Utc::now();
     let date_label = deadline.date_naive().to_string();
@@ -276,7 +910,11 @@ pub fn format_deadline(deadline: DateTime<Utc>) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::truncate_on_char_boundary;
+    use super::{
+        ListLimits, Participation, join_lines_capped, list_page_count, suggestion_cooldown_ends,
+        truncate_on_char_boundary, truncate_with_ellipsis,
+    };
+    use sqlx::types::chrono::{TimeZone, Utc};
 
     #[test]
     fn truncate_ascii_boundary() {
@@ -301,4 +939,86 @@ mod tests {
         assert_eq!(prefix, "");
         assert_eq!(truncated, input.len());
     }
+
+    #[test]
+    fn ellipsis_marks_truncated_text() {
+        assert_eq!(truncate_with_ellipsis("short", 10), "short");
+        let cut = truncate_with_ellipsis("aé😊bcdef", 8);
+        assert_eq!(cut, "aé…");
+        assert!(cut.len() <= 8);
+    }
+
+    #[test]
+    fn capped_lines_stay_within_limits() {
+        let lines: Vec<String> = (0..50).map(|i| format!("line number {i}")).collect();
+
+        let (text, hidden) = join_lines_capped(&lines[..3], lines.len(), 4096);
+        assert_eq!(
+            text,
+            "line number 0\nline number 1\nline number 2\n…and 47 more"
+        );
+        assert_eq!(hidden, 47);
+
+        let (text, hidden) = join_lines_capped(&lines, lines.len(), 100);
+        assert!(text.len() <= 100, "{} bytes", text.len());
+        assert!(text.ends_with(&format!("…and {hidden} more")));
+
+        let (text, hidden) = join_lines_capped(&lines[..2], 2, 4096);
+        assert_eq!(text, "line number 0\nline number 1");
+        assert_eq!(hidden, 0);
+    }
+
+    #[test]
+    fn cooldown_disabled_or_never_read() {
+        let now = Utc.with_ymd_and_hms(2025, 3, 10, 12, 0, 0).unwrap();
+        assert_eq!(suggestion_cooldown_ends(Some(now), 0, now), None);
+        assert_eq!(suggestion_cooldown_ends(None, 14, now), None);
+    }
+
+    #[test]
+    fn cooldown_active_until_days_elapse() {
+        let read_at = Utc.with_ymd_and_hms(2025, 3, 1, 12, 0, 0).unwrap();
+        let now = Utc.with_ymd_and_hms(2025, 3, 10, 12, 0, 0).unwrap();
+        assert_eq!(
+            suggestion_cooldown_ends(Some(read_at), 14, now),
+            Some(Utc.with_ymd_and_hms(2025, 3, 15, 12, 0, 0).unwrap())
+        );
+        assert_eq!(suggestion_cooldown_ends(Some(read_at), 9, now), None);
+    }
+
+    #[test]
+    fn completion_rate_ignores_members_who_never_started() {
+        let participation = Participation {
+            finished: 3,
+            dnf: 1,
+            not_started: 4,
+        };
+        assert_eq!(participation.completion_rate(), Some(75.0));
+        assert_eq!(Participation::default().completion_rate(), None);
+    }
+
+    #[test]
+    fn list_limits_respect_the_operator_cap() {
+        let limits = ListLimits {
+            favorite_books: 40,
+            favorite_authors: 5,
+            reading_list: 25,
+        }
+        .capped(25);
+        assert_eq!(
+            (
+                limits.favorite_books,
+                limits.favorite_authors,
+                limits.reading_list
+            ),
+            (25, 5, 25)
+        );
+    }
+
+    #[test]
+    fn list_pages_round_up() {
+        assert_eq!(list_page_count(0), 1);
+        assert_eq!(list_page_count(5), 1);
+        assert_eq!(list_page_count(6), 2);
+    }
 }
@@ -0,0 +1,249 @@
+use rand::Rng;
+use sqlx::types::chrono::{DateTime, Utc};
+
+/// How `/select random` weights each eligible queue book.
+#[derive(poise::ChoiceParameter, Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum RandomWeighting {
+    #[name = "uniform"]
+    Uniform,
+    #[name = "time in queue"]
+    QueueAge,
+    #[name = "upvotes"]
+    Upvotes,
+    #[name = "suggester hasn't had a pick recently"]
+    SuggesterDrought,
+}
+
+impl RandomWeighting {
+    pub(crate) fn describe(self) -> &'static str {
+        match self {
+            RandomWeighting::Uniform => "every book had the same chance",
+            RandomWeighting::QueueAge => "weighted by time in the queue",
+            RandomWeighting::Upvotes => "weighted by upvotes",
+            RandomWeighting::SuggesterDrought => {
+                "weighted toward members who haven't had a pick recently"
+            }
+        }
+    }
+}
+
+/// What a candidate's weight is computed from.
+pub(crate) struct CandidateStats {
+    pub added_at: Option<DateTime<Utc>>,
+    pub vote_count: i64,
+    /// Books the club has read since this suggester's last pick; `None` if they've never had one.
+    pub books_since_pick: Option<i64>,
+}
+
+/// Every weight starts at 1 so new, unvoted, or recently picked books keep some chance.
+pub(crate) fn candidate_weight(
+    mode: RandomWeighting,
+    stats: &CandidateStats,
+    books_read: i64,
+    now: DateTime<Utc>,
+) -> f64 {
+    match mode {
+        RandomWeighting::Uniform => 1.0,
+        RandomWeighting::QueueAge => {
+            let days = stats
+                .added_at
+                .map(|added| (now - added).num_minutes().max(0) as f64 / 1440.0)
+                .unwrap_or(0.0);
+            1.0 + days
+        }
+        RandomWeighting::Upvotes => 1.0 + stats.vote_count.max(0) as f64,
+        RandomWeighting::SuggesterDrought => {
+            // Never having a pick counts as waiting since before the club's first book
+            let waited = stats.books_since_pick.unwrap_or(books_read + 1).max(0);
+            1.0 + waited as f64
+        }
+    }
+}
+
+/// Each weight's share of the total, as a probability between 0 and 1.
+pub(crate) fn probabilities(weights: &[f64]) -> Vec<f64> {
+    let total: f64 = weights.iter().sum();
+    if total <= 0.0 {
+        return vec![0.0; weights.len()];
+    }
+    weights.iter().map(|weight| weight / total).collect()
+}
+
+/// Picks an index with probability proportional to its weight, or `None` if nothing can win.
+pub(crate) fn pick_weighted<R: Rng + ?Sized>(weights: &[f64], rng: &mut R) -> Option<usize> {
+    let total: f64 = weights.iter().filter(|weight| **weight > 0.0).sum();
+    if total <= 0.0 {
+        return None;
+    }
+
+    let mut target = rng.gen_range(0.0..total);
+    let mut last_eligible = None;
+    for (index, weight) in weights.iter().enumerate() {
+        if *weight <= 0.0 {
+            continue;
+        }
+        if target < *weight {
+            return Some(index);
+        }
+        target -= weight;
+        last_eligible = Some(index);
+    }
+
+    // Floating point rounding can leave a sliver past the last weight
+    last_eligible
+}
+
+pub(crate) fn format_probability(probability: f64) -> String {
+    format!("{:.1}%", probability * 100.0)
+}
+
+/// Where a queue book stands for `/select top`.
+pub(crate) struct VoteStanding {
+    pub position: i32,
+    pub vote_count: i64,
+}
+
+/// The most upvoted book, with ties going to the earlier queue position.
+/// Pinning only guarantees a poll slot, so it plays no part here.
+pub(crate) fn most_upvoted(books: &[VoteStanding]) -> Option<usize> {
+    books
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, book)| (std::cmp::Reverse(book.vote_count), book.position))
+        .map(|(index, _)| index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CandidateStats, RandomWeighting, VoteStanding, candidate_weight, format_probability,
+        most_upvoted, pick_weighted, probabilities,
+    };
+    use chrono::Duration;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use sqlx::types::chrono::{TimeZone, Utc};
+
+    fn stats(days_ago: i64, votes: i64, since_pick: Option<i64>) -> CandidateStats {
+        let now = Utc.with_ymd_and_hms(2025, 6, 1, 12, 0, 0).unwrap();
+        CandidateStats {
+            added_at: Some(now - Duration::days(days_ago)),
+            vote_count: votes,
+            books_since_pick: since_pick,
+        }
+    }
+
+    #[test]
+    fn weights_follow_the_chosen_mode() {
+        let now = Utc.with_ymd_and_hms(2025, 6, 1, 12, 0, 0).unwrap();
+        let book = stats(30, 4, Some(2));
+
+        assert_eq!(
+            candidate_weight(RandomWeighting::Uniform, &book, 5, now),
+            1.0
+        );
+        assert_eq!(
+            candidate_weight(RandomWeighting::QueueAge, &book, 5, now),
+            31.0
+        );
+        assert_eq!(
+            candidate_weight(RandomWeighting::Upvotes, &book, 5, now),
+            5.0
+        );
+        assert_eq!(
+            candidate_weight(RandomWeighting::SuggesterDrought, &book, 5, now),
+            3.0
+        );
+    }
+
+    #[test]
+    fn never_picked_suggesters_outrank_everyone() {
+        let now = Utc.with_ymd_and_hms(2025, 6, 1, 12, 0, 0).unwrap();
+        let never = candidate_weight(
+            RandomWeighting::SuggesterDrought,
+            &stats(0, 0, None),
+            5,
+            now,
+        );
+        let oldest = candidate_weight(
+            RandomWeighting::SuggesterDrought,
+            &stats(0, 0, Some(4)),
+            5,
+            now,
+        );
+        assert!(never > oldest);
+    }
+
+    #[test]
+    fn probabilities_sum_to_one() {
+        let odds = probabilities(&[1.0, 3.0]);
+        assert_eq!(odds, vec![0.25, 0.75]);
+        assert_eq!(format_probability(odds[0]), "25.0%");
+        assert_eq!(probabilities(&[0.0, 0.0]), vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn seeded_picks_are_reproducible() {
+        let weights = [1.0, 2.0, 3.0, 4.0];
+        let picks = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..20)
+                .map(|_| pick_weighted(&weights, &mut rng).unwrap())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(picks(42), picks(42));
+    }
+
+    #[test]
+    fn picks_follow_the_weights() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let weights = [1.0, 3.0, 0.0];
+        let mut counts = [0u32; 3];
+        for _ in 0..10_000 {
+            counts[pick_weighted(&weights, &mut rng).unwrap()] += 1;
+        }
+
+        assert_eq!(counts[2], 0);
+        let heavy_share = counts[1] as f64 / 10_000.0;
+        assert!((heavy_share - 0.75).abs() < 0.02, "share was {heavy_share}");
+    }
+
+    #[test]
+    fn nothing_to_pick_without_positive_weights() {
+        let mut rng = StdRng::seed_from_u64(1);
+        assert_eq!(pick_weighted(&[], &mut rng), None);
+        assert_eq!(pick_weighted(&[0.0, 0.0], &mut rng), None);
+    }
+
+    #[test]
+    fn pinned_books_do_not_outrank_votes() {
+        // Pinned books sit at the front of the queue
+        let books = [
+            VoteStanding {
+                position: 1,
+                vote_count: 0,
+            },
+            VoteStanding {
+                position: 2,
+                vote_count: 3,
+            },
+        ];
+        assert_eq!(most_upvoted(&books), Some(1));
+    }
+
+    #[test]
+    fn vote_ties_go_to_the_earlier_position() {
+        let books = [
+            VoteStanding {
+                position: 4,
+                vote_count: 2,
+            },
+            VoteStanding {
+                position: 2,
+                vote_count: 2,
+            },
+        ];
+        assert_eq!(most_upvoted(&books), Some(1));
+        assert_eq!(most_upvoted(&[]), None);
+    }
+}
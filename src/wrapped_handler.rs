@@ -0,0 +1,126 @@
+use crate::commands::server_user::wrapped::{build_club_wrapped, recap_year_for_post};
+use crate::google_books_cache::CachedGoogleBooksClient;
+use crate::maturity_check::can_display_mature_content_event;
+use crate::types::Error;
+use crate::util::{Club, log_error_with_source};
+use chrono::Datelike;
+use poise::serenity_prelude as serenity;
+use serenity::CreateMessage;
+use sqlx::PgPool;
+use sqlx::types::chrono::Utc;
+use std::sync::Arc;
+use tokio::time::{self, Duration};
+
+pub fn spawn_wrapped_watcher(
+    http: Arc<serenity::Http>,
+    pool: PgPool,
+    google_books: CachedGoogleBooksClient,
+) {
+    tokio::spawn(async move {
+        let mut interval = time::interval(Duration::from_secs(3600));
+        loop {
+            interval.tick().await;
+            if let Err(err) = process_wrapped_posts(&http, &pool, &google_books).await {
+                log_error_with_source("Wrapped watcher error", &err);
+            }
+        }
+    });
+}
+
+async fn process_wrapped_posts(
+    http: &Arc<serenity::Http>,
+    pool: &PgPool,
+    google_books: &CachedGoogleBooksClient,
+) -> Result<(), Error> {
+    let today = Utc::now().date_naive();
+    let posting_year = today.year();
+
+    let clubs = sqlx::query!(
+        r#"
+        SELECT sc.club_id, sc.server_id, sc.name, sc.is_primary,
+               sc.announcement_channel_id AS "announcement_channel_id!",
+               sbc.wrapped_post_month AS "wrapped_post_month!", ds.server_name
+        FROM server_clubs sc
+        JOIN server_bot_config sbc ON sbc.server_id = sc.server_id
+        JOIN discord_servers ds ON ds.server_id = sc.server_id
+        WHERE sc.announcement_channel_id IS NOT NULL
+          AND sbc.wrapped_post_month IS NOT NULL
+          AND make_date($1, sbc.wrapped_post_month, COALESCE(sbc.wrapped_post_day, 1)) <= $2
+          AND COALESCE(sc.wrapped_posted_year, 0) < $1
+        "#,
+        posting_year,
+        today
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for row in clubs {
+        let club = Club {
+            club_id: row.club_id,
+            server_id: row.server_id,
+            name: row.name,
+            is_primary: row.is_primary,
+        };
+        let recap_year = recap_year_for_post(row.wrapped_post_month as u32, posting_year);
+        if let Err(err) = post_club_wrapped(
+            http,
+            pool,
+            google_books,
+            &club,
+            &row.server_name,
+            recap_year,
+            serenity::ChannelId::new(row.announcement_channel_id as u64),
+        )
+        .await
+        {
+            log_error_with_source("Couldn't post wrapped recap for club", &err);
+        }
+
+        // Marked even when posting failed so a missing channel doesn't retry every hour
+        sqlx::query!(
+            "UPDATE server_clubs SET wrapped_posted_year = $2 WHERE club_id = $1",
+            club.club_id,
+            posting_year
+        )
+        .execute(pool)
+        .await?;
+    }
+
+    Ok(())
+}
+
+async fn post_club_wrapped(
+    http: &Arc<serenity::Http>,
+    pool: &PgPool,
+    google_books: &CachedGoogleBooksClient,
+    club: &Club,
+    server_name: &str,
+    recap_year: i32,
+    channel_id: serenity::ChannelId,
+) -> Result<(), Error> {
+    let allow_mature =
+        can_display_mature_content_event(http, pool, club.server_id, channel_id).await?;
+    let Some(embed) = build_club_wrapped(
+        pool,
+        google_books,
+        club,
+        server_name,
+        recap_year,
+        allow_mature,
+    )
+    .await?
+    else {
+        // Nothing finished that year, so there's nothing to celebrate
+        return Ok(());
+    };
+
+    channel_id
+        .send_message(
+            http,
+            CreateMessage::new()
+                .content(format!("🎉 Here's the club's {} wrapped!", recap_year))
+                .embed(embed),
+        )
+        .await?;
+    Ok(())
+}